@@ -0,0 +1,109 @@
+// Park-to-Park and Summit-to-Summit contacts
+// Reference: POTA and SOTA award rules
+//
+// A POTA QSO where both stations are activating a park (POTA_REF and
+// MY_POTA_REF both set) is a park-to-park (P2P) contact; the SOTA equivalent
+// between two summits is summit-to-summit (S2S). Both programs count these
+// in their own award category, so they are flagged in the log and tagged in
+// the exported COMMENT, where most logging and spotting tools look for them.
+
+use std::collections::HashMap;
+
+/// An activation program with a "both ends activating" category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationProgram {
+    Pota,
+    Sota,
+}
+
+impl ActivationProgram {
+    /// Database columns holding the other station's and my reference
+    pub fn columns(self) -> (&'static str, &'static str) {
+        match self {
+            ActivationProgram::Pota => ("pota_ref", "my_pota_ref"),
+            ActivationProgram::Sota => ("sota_ref", "my_sota_ref"),
+        }
+    }
+
+    /// Tag for a contact with both ends activating
+    pub fn tag(self) -> &'static str {
+        match self {
+            ActivationProgram::Pota => "P2P",
+            ActivationProgram::Sota => "S2S",
+        }
+    }
+}
+
+/// True when both the other station's and my reference are set
+pub fn both_activating(their_ref: Option<&str>, my_ref: Option<&str>) -> bool {
+    let is_set = |r: Option<&str>| r.is_some_and(|r| !r.trim().is_empty());
+    is_set(their_ref) && is_set(my_ref)
+}
+
+/// True when `comment` already carries `tag` as a word, even with punctuation
+/// around it ("P2P.", "#p2p") as other loggers and operators write it
+fn has_tag(comment: &str, tag: &str) -> bool {
+    comment
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|word| word.eq_ignore_ascii_case(tag))
+}
+
+/// Append P2P / S2S to an ADIF record's COMMENT (once) when both ends were activating
+pub fn tag_comment(record: &mut HashMap<String, String>) {
+    for program in [ActivationProgram::Pota, ActivationProgram::Sota] {
+        let (theirs, mine) = program.columns();
+        let theirs = record.get(&theirs.to_uppercase()).map(String::as_str);
+        let mine = record.get(&mine.to_uppercase()).map(String::as_str);
+        if !both_activating(theirs, mine) {
+            continue;
+        }
+        let comment = record.entry("COMMENT".to_string()).or_default();
+        if !has_tag(comment, program.tag()) {
+            if !comment.is_empty() {
+                comment.push(' ');
+            }
+            comment.push_str(program.tag());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_p2p_detection() {
+        assert!(both_activating(Some("US-0001"), Some("K-1234")));
+        assert!(!both_activating(Some("US-0001"), None));
+        assert!(!both_activating(None, Some("K-1234")));
+        assert!(!both_activating(Some("US-0001"), Some("  ")));
+    }
+
+    #[test]
+    fn test_tag_comment() {
+        let mut record = HashMap::from([
+            ("POTA_REF".to_string(), "US-0001".to_string()),
+            ("MY_POTA_REF".to_string(), "US-4567".to_string()),
+            ("COMMENT".to_string(), "tnx".to_string()),
+        ]);
+        tag_comment(&mut record);
+        assert_eq!(record["COMMENT"], "tnx P2P");
+        // Tagging twice doesn't repeat it
+        tag_comment(&mut record);
+        assert_eq!(record["COMMENT"], "tnx P2P");
+        // Already tagged by hand, with punctuation
+        for comment in ["tnx P2P.", "#p2p tnx", "P2P!"] {
+            record.insert("COMMENT".to_string(), comment.to_string());
+            tag_comment(&mut record);
+            assert_eq!(record["COMMENT"], comment);
+        }
+
+        let mut record = HashMap::from([
+            ("SOTA_REF".to_string(), "W7W/LC-001".to_string()),
+            ("MY_SOTA_REF".to_string(), "W7W/KG-042".to_string()),
+            ("POTA_REF".to_string(), "US-0001".to_string()),
+        ]);
+        tag_comment(&mut record);
+        assert_eq!(record["COMMENT"], "S2S");
+    }
+}
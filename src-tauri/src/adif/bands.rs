@@ -8,41 +8,97 @@
 
 // This module provides frequency-to-band conversion and band metadata.
 
-/// HF and VHF/UHF amateur radio bands
+/// LF/MF, HF and VHF/UHF amateur radio band edges in Hz (inclusive).
+/// Kept in integer Hz so band edges and 60m channels compare exactly.
+const BAND_EDGES_HZ: &[(u64, u64, &str)] = &[
+    // LF/MF Bands
+    (135_700, 137_800, "2200m"),
+    (472_000, 479_000, "630m"),
+    // HF Bands
+    (1_800_000, 2_000_000, "160m"),
+    (3_500_000, 4_000_000, "80m"),
+    (5_000_000, 5_500_000, "60m"), // Channelized in US
+    (7_000_000, 7_300_000, "40m"),
+    (10_100_000, 10_150_000, "30m"),
+    (14_000_000, 14_350_000, "20m"),
+    (18_068_000, 18_168_000, "17m"),
+    (21_000_000, 21_450_000, "15m"),
+    (24_890_000, 24_990_000, "12m"),
+    (28_000_000, 29_700_000, "10m"),
+    // VHF/UHF Bands
+    (50_000_000, 54_000_000, "6m"),
+    (144_000_000, 148_000_000, "2m"),
+    (222_000_000, 225_000_000, "1.25m"),
+    (420_000_000, 450_000_000, "70cm"),
+    (902_000_000, 928_000_000, "33cm"),
+    (1_240_000_000, 1_300_000_000, "23cm"),
+];
+
 /// Returns the ADIF band name for a given frequency in MHz
 pub fn freq_to_band(freq_mhz: f64) -> Option<&'static str> {
-    match freq_mhz {
-        // HF Bands
-        f if (1.8..=2.0).contains(&f) => Some("160m"),
-        f if (3.5..=4.0).contains(&f) => Some("80m"),
-        f if (5.0..=5.5).contains(&f) => Some("60m"),   // Channelized in US
-        f if (7.0..=7.3).contains(&f) => Some("40m"),
-        f if (10.1..=10.15).contains(&f) => Some("30m"),
-        f if (14.0..=14.35).contains(&f) => Some("20m"),
-        f if (18.068..=18.168).contains(&f) => Some("17m"),
-        f if (21.0..=21.45).contains(&f) => Some("15m"),
-        f if (24.89..=24.99).contains(&f) => Some("12m"),
-        f if (28.0..=29.7).contains(&f) => Some("10m"),
-        // VHF/UHF Bands
-        f if (50.0..=54.0).contains(&f) => Some("6m"),
-        f if (144.0..=148.0).contains(&f) => Some("2m"),
-        f if (222.0..=225.0).contains(&f) => Some("1.25m"),
-        f if (420.0..=450.0).contains(&f) => Some("70cm"),
-        f if (902.0..=928.0).contains(&f) => Some("33cm"),
-        f if (1240.0..=1300.0).contains(&f) => Some("23cm"),
-        _ => None,
+    freq_hz_to_band(mhz_to_hz(freq_mhz)?)
+}
+
+/// Band name for a frequency in Hz, or the frequency itself (e.g. "0.300MHz")
+/// when it's outside every band, so a logged QSO never gets a blank band
+pub fn band_label_for_hz(freq_hz: u64) -> String {
+    freq_hz_to_band(freq_hz)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{:.3}MHz", freq_hz as f64 / 1_000_000.0))
+}
+
+/// MHz to the nearest Hz; None for zero, negative or non-finite values
+pub fn mhz_to_hz(freq_mhz: f64) -> Option<u64> {
+    (freq_mhz.is_finite() && freq_mhz > 0.0).then(|| (freq_mhz * 1_000_000.0).round() as u64)
+}
+
+/// Parse an ADIF FREQ (MHz as decimal text) to Hz without going through f64,
+/// so "14.074123" is exactly 14074123. Digits past the Hz place are rounded.
+pub fn parse_mhz_to_hz(freq: &str) -> Option<u64> {
+    let freq = freq.trim();
+    let (whole, frac) = freq.split_once('.').unwrap_or((freq, ""));
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && frac.is_empty()) || !is_digits(whole) || !is_digits(frac) {
+        return None;
+    }
+    if frac.len() > 6 {
+        return mhz_to_hz(freq.parse().ok()?);
     }
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let frac: u64 = format!("{:0<6}", frac).parse().ok()?;
+    Some(whole * 1_000_000 + frac).filter(|hz| *hz > 0)
+}
+
+/// Hz as an ADIF FREQ in MHz with all six decimals, e.g. "5.351500"
+pub fn format_hz_as_mhz(freq_hz: u64) -> String {
+    format!("{}.{:06}", freq_hz / 1_000_000, freq_hz % 1_000_000)
+}
+
+/// Bands in frequency order, used to sort exports and reports by band
+pub const BAND_ORDER: &[&str] = &[
+    "2200m", "630m", "160m", "80m", "60m", "40m", "30m", "20m", "17m", "15m", "12m", "10m",
+    "6m", "2m", "1.25m", "70cm", "33cm", "23cm",
+];
+
+/// Position of a band in BAND_ORDER (case insensitive); unknown bands sort last
+pub fn band_sort_index(band: &str) -> usize {
+    let band = band.trim().to_lowercase();
+    BAND_ORDER.iter().position(|b| *b == band).unwrap_or(BAND_ORDER.len())
 }
 
 /// Convert frequency in Hz to band
 pub fn freq_hz_to_band(freq_hz: u64) -> Option<&'static str> {
-    freq_to_band(freq_hz as f64 / 1_000_000.0)
+    BAND_EDGES_HZ
+        .iter()
+        .find(|(low, high, _)| (*low..=*high).contains(&freq_hz))
+        .map(|(_, _, band)| *band)
 }
 
 /// Get the typical FT8 frequency for a band (in Hz)
 /// These are the most common FT8 frequencies per band
 pub fn get_ft8_freq(band: &str) -> Option<u64> {
     match band.to_lowercase().as_str() {
+        "630m" => Some(474_200),
         "160m" => Some(1_840_000),
         "80m" => Some(3_573_000),
         "60m" => Some(5_357_000),
@@ -60,6 +116,66 @@ pub fn get_ft8_freq(band: &str) -> Option<u64> {
     }
 }
 
+/// Get the typical FT4 frequency for a band (in Hz)
+pub fn get_ft4_freq(band: &str) -> Option<u64> {
+    match band.to_lowercase().as_str() {
+        "80m" => Some(3_575_000),
+        "40m" => Some(7_047_500),
+        "30m" => Some(10_140_000),
+        "20m" => Some(14_080_000),
+        "17m" => Some(18_104_000),
+        "15m" => Some(21_140_000),
+        "12m" => Some(24_919_000),
+        "10m" => Some(28_180_000),
+        "6m" => Some(50_318_000),
+        "2m" => Some(144_170_000),
+        _ => None,
+    }
+}
+
+/// Standard dial frequency (in MHz) for a mode on a band, used when a QSO has a
+/// band but no frequency. Only FT8 and FT4 have one; phone, CW and other modes
+/// can be anywhere in the band, so their frequency is left unknown.
+pub fn band_to_default_freq(band: &str, mode: &str) -> Option<f64> {
+    let hz = match mode.trim().to_uppercase().as_str() {
+        "FT8" => get_ft8_freq(band.trim()),
+        "FT4" => get_ft4_freq(band.trim()),
+        _ => None,
+    }?;
+    Some(hz as f64 / 1_000_000.0)
+}
+
+/// Result of reconciling a QSO's stored band against its stored frequency
+#[derive(Debug, Clone, PartialEq)]
+pub enum BandFreqFix {
+    /// Band and frequency agree, or the frequency is ambiguous - leave alone
+    NoChange,
+    /// Frequency maps cleanly to a different band - correct the band
+    CorrectBand(&'static str),
+    /// Frequency missing - fill with the mode's standard dial frequency (MHz)
+    FillFreq(f64),
+}
+
+/// Decide how to repair a QSO whose band and frequency may disagree.
+///
+/// The frequency wins when it maps cleanly to a band. Frequencies outside any
+/// known band are treated as ambiguous and never override the stored band.
+/// A missing frequency is only filled for modes with a standard dial frequency.
+pub fn reconcile_band_freq(band: Option<&str>, freq_mhz: Option<f64>, mode: Option<&str>) -> BandFreqFix {
+    match freq_mhz.filter(|f| *f > 0.0) {
+        Some(freq) => match freq_to_band(freq) {
+            Some(freq_band) if !band.is_some_and(|b| b.trim().eq_ignore_ascii_case(freq_band)) => {
+                BandFreqFix::CorrectBand(freq_band)
+            }
+            _ => BandFreqFix::NoChange,
+        },
+        None => match band.zip(mode).and_then(|(band, mode)| band_to_default_freq(band, mode)) {
+            Some(mhz) => BandFreqFix::FillFreq(mhz),
+            None => BandFreqFix::NoChange,
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,16 +190,118 @@ mod tests {
         assert_eq!(freq_to_band(999.0), None);
     }
 
+    #[test]
+    fn test_lf_mf_bands() {
+        assert_eq!(freq_to_band(0.1357), Some("2200m"));
+        assert_eq!(freq_to_band(0.137), Some("2200m"));
+        assert_eq!(freq_to_band(0.4742), Some("630m"));
+        assert_eq!(freq_to_band(0.479), Some("630m"));
+        assert_eq!(freq_to_band(0.5), None);
+        assert_eq!(freq_hz_to_band(136_000), Some("2200m"));
+        assert_eq!(freq_hz_to_band(475_700), Some("630m"));
+        assert!(band_sort_index("2200m") < band_sort_index("630M"));
+        assert!(band_sort_index("630m") < band_sort_index("160m"));
+        assert_eq!(band_to_default_freq("630m", "FT8"), Some(0.4742));
+    }
+
     #[test]
     fn test_freq_hz_to_band() {
         assert_eq!(freq_hz_to_band(7_074_000), Some("40m"));
         assert_eq!(freq_hz_to_band(14_074_000), Some("20m"));
     }
 
+    #[test]
+    fn test_band_label_for_hz() {
+        assert_eq!(band_label_for_hz(136_500), "2200m");
+        assert_eq!(band_label_for_hz(474_200), "630m");
+        assert_eq!(band_label_for_hz(300_000), "0.300MHz");
+        // Band edges are inclusive, including bands above 70cm
+        assert_eq!(band_label_for_hz(14_350_000), "20m");
+        assert_eq!(band_label_for_hz(14_350_001), "14.350MHz");
+        assert_eq!(band_label_for_hz(222_100_000), "1.25m");
+        assert_eq!(band_label_for_hz(1_296_100_000), "23cm");
+    }
+
+    #[test]
+    fn test_hz_round_trip() {
+        for (text, hz) in [("14.074123", 14_074_123), ("5.3515", 5_351_500), ("0.4742", 474_200), ("1296.1", 1_296_100_000)] {
+            assert_eq!(parse_mhz_to_hz(text), Some(hz));
+            assert_eq!(parse_mhz_to_hz(&format_hz_as_mhz(hz)), Some(hz));
+        }
+        assert_eq!(format_hz_as_mhz(5_351_500), "5.351500");
+        assert_eq!(parse_mhz_to_hz("14.0741235"), Some(14_074_124));
+        assert_eq!(parse_mhz_to_hz("14"), Some(14_000_000));
+        for bad in ["", ".", "0", "abc", "14,074", "-7.074"] {
+            assert_eq!(parse_mhz_to_hz(bad), None, "{}", bad);
+        }
+        // 14.35 * 1e6 is 14350000.000000002 in f64; band edges still hold
+        assert_eq!(freq_to_band(14.35), Some("20m"));
+        assert_eq!(freq_to_band(10.15), Some("30m"));
+        assert_eq!(freq_hz_to_band(14_350_001), None);
+        assert_eq!(mhz_to_hz(0.0), None);
+    }
+
+    #[test]
+    fn test_band_sort_index() {
+        assert!(band_sort_index("160m") < band_sort_index("20M"));
+        assert!(band_sort_index("10m") < band_sort_index("6m"));
+        assert_eq!(band_sort_index("11m"), BAND_ORDER.len());
+    }
+
     #[test]
     fn test_get_ft8_freq() {
         assert_eq!(get_ft8_freq("40m"), Some(7_074_000));
         assert_eq!(get_ft8_freq("20M"), Some(14_074_000)); // case insensitive
         assert_eq!(get_ft8_freq("unknown"), None);
     }
+
+    #[test]
+    fn test_band_to_default_freq() {
+        assert_eq!(band_to_default_freq("20M", "FT8"), Some(14.074));
+        assert_eq!(band_to_default_freq("20m", "ft4"), Some(14.080));
+        assert_eq!(band_to_default_freq("13cm", "FT8"), None);
+        // No made-up frequency for modes used anywhere in the band
+        for mode in ["SSB", "CW", "RTTY", ""] {
+            assert_eq!(band_to_default_freq("20m", mode), None, "{}", mode);
+        }
+        // Every default maps back to its own band
+        for band in BAND_ORDER {
+            for mode in ["FT8", "FT4"] {
+                if let Some(mhz) = band_to_default_freq(band, mode) {
+                    assert_eq!(freq_to_band(mhz), Some(*band));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reconcile_band_mismatch() {
+        // Band says 20m but the frequency is clearly 40m
+        assert_eq!(reconcile_band_freq(Some("20M"), Some(7.074), None), BandFreqFix::CorrectBand("40m"));
+        // Missing band is filled from frequency
+        assert_eq!(reconcile_band_freq(None, Some(14.074), None), BandFreqFix::CorrectBand("20m"));
+    }
+
+    #[test]
+    fn test_reconcile_band_matches() {
+        assert_eq!(reconcile_band_freq(Some("20M"), Some(14.074), None), BandFreqFix::NoChange);
+        assert_eq!(reconcile_band_freq(Some("40m"), Some(7.074), None), BandFreqFix::NoChange);
+    }
+
+    #[test]
+    fn test_reconcile_ambiguous_freq_untouched() {
+        // Out-of-band / garbage frequency - don't trust it over the band
+        assert_eq!(reconcile_band_freq(Some("20m"), Some(15.5), None), BandFreqFix::NoChange);
+        assert_eq!(reconcile_band_freq(Some("20m"), Some(14074.0), None), BandFreqFix::NoChange);
+    }
+
+    #[test]
+    fn test_reconcile_fill_missing_freq() {
+        assert_eq!(reconcile_band_freq(Some("20m"), None, Some("FT8")), BandFreqFix::FillFreq(14.074));
+        assert_eq!(reconcile_band_freq(Some("40M"), Some(0.0), Some("FT8")), BandFreqFix::FillFreq(7.074));
+        // Unknown band, or a mode without a standard frequency - nothing to fill
+        assert_eq!(reconcile_band_freq(Some("13cm"), None, Some("FT8")), BandFreqFix::NoChange);
+        assert_eq!(reconcile_band_freq(Some("20m"), None, Some("SSB")), BandFreqFix::NoChange);
+        assert_eq!(reconcile_band_freq(Some("20m"), None, None), BandFreqFix::NoChange);
+    }
 }
@@ -0,0 +1,229 @@
+// Cabrillo Parser
+// Reads contest logs in Cabrillo 3.0 format into ADIF records
+// Reference: https://wwrof.org/cabrillo/
+//
+// QSO lines look like:
+//   QSO: 14025 CW 2026-01-10 1200 W1AW          599 05     JA1ABC        599 25
+//   QSO: 7150  PH 2026-03-01 0100 W1AW          59  CT     K5ABC         59  TX
+//   QSO: 50    DG 2026-01-09 1800 W1AW          FN31       K2XYZ         FN20
+//
+// Exchanges differ per contest, so the sent and received halves are found by
+// splitting the fields after the time in two: my call + sent exchange, then
+// their call + received exchange (and an optional trailing transmitter ID).
+
+use super::parser::AdifRecord;
+use super::bands::freq_to_band;
+
+/// Map a Cabrillo mode code to an ADIF mode.
+/// DG is used almost exclusively by FT8/FT4 contests (WW Digi, ARRL Digital).
+pub fn cabrillo_mode_to_adif(mode: &str) -> Option<&'static str> {
+    match mode.trim().to_uppercase().as_str() {
+        "CW" => Some("CW"),
+        "PH" => Some("SSB"),
+        "FM" => Some("FM"),
+        "RY" => Some("RTTY"),
+        "DG" => Some("FT8"),
+        _ => None,
+    }
+}
+
+/// Decode the Cabrillo frequency field into (band, freq in MHz).
+/// HF logs give kHz ("14025"); VHF+ logs may give only the band ("50", "144", "1.2G"),
+/// in which case no frequency is returned.
+pub fn cabrillo_freq_to_band(freq: &str) -> Option<(&'static str, Option<f64>)> {
+    let freq = freq.trim().to_uppercase();
+    // Microwave designators; only 23cm is in our band table
+    if freq == "1.2G" {
+        return Some(("23cm", None));
+    }
+
+    let value: f64 = freq.parse().ok()?;
+    if value >= 1800.0 {
+        let mhz = value / 1000.0;
+        freq_to_band(mhz).map(|b| (b, Some(mhz)))
+    } else {
+        // VHF band designator in MHz ("50", "144", "432")
+        freq_to_band(value).map(|b| (b, None))
+    }
+}
+
+/// Signal report at the start of an exchange: 59, 579, 5NN.
+/// Contest reports are readability 5, which keeps serials like "12" out.
+fn is_rst(token: &str) -> bool {
+    let t = token.to_uppercase();
+    (t.len() == 2 || t.len() == 3)
+        && t.starts_with('5')
+        && t[1..].chars().all(|c| c.is_ascii_digit() || c == 'N')
+}
+
+/// Split one exchange half into (rst, remaining exchange)
+fn split_exchange(tokens: &[&str]) -> (Option<String>, String) {
+    match tokens.split_first() {
+        Some((first, rest)) if is_rst(first) && !rest.is_empty() => {
+            (Some(first.to_uppercase().replace('N', "9")), rest.join(" "))
+        }
+        _ => (None, tokens.join(" ")),
+    }
+}
+
+/// Parse a single "QSO:" line
+fn parse_qso_line(line: &str, contest: Option<&str>) -> Result<AdifRecord, String> {
+    let tokens: Vec<&str> = line.split_whitespace().skip(1).collect();
+    if tokens.len() < 6 {
+        return Err(format!("Too few fields: {}", line));
+    }
+
+    let (band, freq) = cabrillo_freq_to_band(tokens[0])
+        .ok_or_else(|| format!("Unknown frequency '{}': {}", tokens[0], line))?;
+    let mode = cabrillo_mode_to_adif(tokens[1])
+        .ok_or_else(|| format!("Unknown mode '{}': {}", tokens[1], line))?;
+    let date = tokens[2].replace('-', "");
+    if date.len() != 8 || !date.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid date '{}': {}", tokens[2], line));
+    }
+    let time = tokens[3];
+    if time.len() != 4 || !time.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid time '{}': {}", time, line));
+    }
+
+    // Calls and exchanges; an odd count means a trailing transmitter ID
+    let mut rest = &tokens[4..];
+    if rest.len() % 2 == 1 {
+        rest = &rest[..rest.len() - 1];
+    }
+    let half = rest.len() / 2;
+    if half < 1 {
+        return Err(format!("Missing callsigns: {}", line));
+    }
+    let (sent, rcvd) = rest.split_at(half);
+
+    let mut record = AdifRecord::new();
+    let mut set = |key: &str, value: String| {
+        if !value.is_empty() {
+            record.fields.insert(key.to_string(), value);
+        }
+    };
+
+    set("CALL", rcvd[0].to_uppercase());
+    set("STATION_CALLSIGN", sent[0].to_uppercase());
+    set("BAND", band.to_string());
+    if let Some(mhz) = freq {
+        set("FREQ", format!("{:.3}", mhz));
+    }
+    set("MODE", mode.to_string());
+    set("QSO_DATE", date);
+    set("TIME_ON", format!("{}00", time));
+
+    let (rst_sent, stx) = split_exchange(&sent[1..]);
+    let (rst_rcvd, srx) = split_exchange(&rcvd[1..]);
+    set("RST_SENT", rst_sent.unwrap_or_default());
+    set("RST_RCVD", rst_rcvd.unwrap_or_default());
+    set("STX_STRING", stx);
+    set("SRX_STRING", srx);
+    set("CONTEST_ID", contest.unwrap_or_default().to_string());
+
+    Ok(record)
+}
+
+/// Parse a Cabrillo log into ADIF records.
+/// Fails if the file has no QSO lines or any QSO line is malformed.
+pub fn parse_cabrillo(content: &str) -> Result<Vec<AdifRecord>, String> {
+    let mut contest: Option<String> = None;
+    let mut records = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let Some((tag, value)) = line.split_once(':') else {
+            continue;
+        };
+        match tag.trim().to_uppercase().as_str() {
+            "CONTEST" => contest = Some(value.trim().to_uppercase()).filter(|c| !c.is_empty()),
+            "QSO" => {
+                let record = parse_qso_line(line, contest.as_deref())
+                    .map_err(|e| format!("Line {}: {}", index + 1, e))?;
+                records.push(record);
+            }
+            _ => {}
+        }
+    }
+
+    if records.is_empty() {
+        return Err("No QSO: lines found in Cabrillo log".to_string());
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field<'a>(record: &'a AdifRecord, key: &str) -> Option<&'a str> {
+        record.get(key).map(String::as_str)
+    }
+
+    #[test]
+    fn test_cq_ww_cw() {
+        let log = "START-OF-LOG: 3.0\n\
+                   CONTEST: CQ-WW-CW\n\
+                   CALLSIGN: W1AW\n\
+                   QSO: 14025 CW 2026-11-28 1200 W1AW          599 05     JA1ABC        5NN 25\n\
+                   END-OF-LOG:\n";
+        let records = parse_cabrillo(log).unwrap();
+        assert_eq!(records.len(), 1);
+        let r = &records[0];
+        assert_eq!(field(r, "CALL"), Some("JA1ABC"));
+        assert_eq!(field(r, "STATION_CALLSIGN"), Some("W1AW"));
+        assert_eq!(field(r, "BAND"), Some("20m"));
+        assert_eq!(field(r, "FREQ"), Some("14.025"));
+        assert_eq!(field(r, "MODE"), Some("CW"));
+        assert_eq!(field(r, "QSO_DATE"), Some("20261128"));
+        assert_eq!(field(r, "TIME_ON"), Some("120000"));
+        assert_eq!(field(r, "RST_SENT"), Some("599"));
+        assert_eq!(field(r, "RST_RCVD"), Some("599"));
+        assert_eq!(field(r, "STX_STRING"), Some("05"));
+        assert_eq!(field(r, "SRX_STRING"), Some("25"));
+        assert_eq!(field(r, "CONTEST_ID"), Some("CQ-WW-CW"));
+    }
+
+    #[test]
+    fn test_sweepstakes_style_exchange_with_transmitter_id() {
+        // ARRL SS: serial, precedence, check, section; trailing transmitter ID
+        let log = "CONTEST: ARRL-SS-SSB\n\
+                   QSO: 7200 PH 2026-11-21 2101 W1AW 1 A 72 CT K5ABC 12 B 88 STX 0\n";
+        let r = &parse_cabrillo(log).unwrap()[0];
+        assert_eq!(field(r, "CALL"), Some("K5ABC"));
+        assert_eq!(field(r, "MODE"), Some("SSB"));
+        assert_eq!(field(r, "BAND"), Some("40m"));
+        assert_eq!(field(r, "RST_SENT"), None);
+        assert_eq!(field(r, "STX_STRING"), Some("1 A 72 CT"));
+        assert_eq!(field(r, "SRX_STRING"), Some("12 B 88 STX"));
+    }
+
+    #[test]
+    fn test_vhf_digital_grid_exchange() {
+        let log = "CONTEST: ARRL-VHF-JAN\n\
+                   QSO: 50 DG 2026-01-17 1900 W1AW FN31 K2XYZ FN20\n\
+                   QSO: 1.2G FM 2026-01-17 2000 W1AW FN31 K2XYZ FN20\n";
+        let records = parse_cabrillo(log).unwrap();
+        assert_eq!(field(&records[0], "BAND"), Some("6m"));
+        assert_eq!(field(&records[0], "MODE"), Some("FT8"));
+        assert_eq!(field(&records[0], "FREQ"), None);
+        assert_eq!(field(&records[0], "SRX_STRING"), Some("FN20"));
+        assert_eq!(field(&records[1], "BAND"), Some("23cm"));
+        assert_eq!(field(&records[1], "MODE"), Some("FM"));
+    }
+
+    #[test]
+    fn test_rtty_mode() {
+        let r = &parse_cabrillo("QSO: 21080 RY 2026-09-26 0000 W1AW 599 CT G0ABC 599 001\n").unwrap()[0];
+        assert_eq!(field(r, "MODE"), Some("RTTY"));
+        assert_eq!(field(r, "SRX_STRING"), Some("001"));
+    }
+
+    #[test]
+    fn test_malformed_logs() {
+        assert!(parse_cabrillo("START-OF-LOG: 3.0\nEND-OF-LOG:\n").is_err());
+        assert!(parse_cabrillo("QSO: 14025 XX 2026-01-01 1200 W1AW 599 CT K1ABC 599 MA\n").is_err());
+        assert!(parse_cabrillo("QSO: 14025 CW 01/01/2026 1200 W1AW 599 CT K1ABC 599 MA\n").is_err());
+    }
+}
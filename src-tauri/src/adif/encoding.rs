@@ -0,0 +1,69 @@
+// Text decoding for imported files
+//
+// ADIF exports and the FCC EN.dat file are nominally ASCII, but names and
+// QTHs often carry accented characters. Older loggers and the FCC write them
+// as latin-1 (ISO-8859-1), newer ones as UTF-8. Bytes are decoded as UTF-8
+// when valid and as latin-1 otherwise; latin-1 maps every byte to a character,
+// so decoding never fails and nothing is dropped.
+//
+// Some Windows tools save with a byte order mark. A UTF-8 BOM is dropped; a
+// UTF-16 file (little or big endian BOM) is transcoded to UTF-8 first, since
+// ADIF field lengths can only be followed over single-byte-per-ASCII text.
+
+use std::borrow::Cow;
+
+/// Decode bytes as UTF-8, falling back to latin-1
+pub fn decode_text(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Strip a leading byte order mark, transcoding UTF-16 input to UTF-8
+pub fn strip_bom(bytes: &[u8]) -> Cow<'_, [u8]> {
+    let utf16 = |little_endian: bool| {
+        let units: Vec<u16> = bytes[2..]
+            .chunks_exact(2)
+            .map(|c| if little_endian { u16::from_le_bytes([c[0], c[1]]) } else { u16::from_be_bytes([c[0], c[1]]) })
+            .collect();
+        Cow::Owned(String::from_utf16_lossy(&units).into_bytes())
+    };
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => Cow::Borrowed(rest),
+        [0xFF, 0xFE, ..] => utf16(true),
+        [0xFE, 0xFF, ..] => utf16(false),
+        _ => Cow::Borrowed(bytes),
+    }
+}
+
+/// CRLF and lone CR line breaks as LF
+pub fn normalize_line_endings(text: &str) -> Cow<'_, str> {
+    if text.contains('\r') {
+        Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_utf8_and_latin1() {
+        assert_eq!(decode_text("José".as_bytes()), "José");
+        assert_eq!(decode_text(b"Jos\xe9"), "José");
+        assert_eq!(decode_text(b"M\xfcller"), "Müller");
+        assert_eq!(decode_text(b"W1AW"), "W1AW");
+    }
+
+    #[test]
+    fn test_strip_bom() {
+        assert_eq!(&*strip_bom(b"\xEF\xBB\xBF<CALL:4>W1AW"), b"<CALL:4>W1AW");
+        assert_eq!(&*strip_bom(b"<CALL:4>W1AW"), b"<CALL:4>W1AW");
+        assert_eq!(&*strip_bom(b"\xFF\xFE<\x00A\x00"), b"<A");
+        assert_eq!(&*strip_bom(b"\xFE\xFF\x00<\x00A"), b"<A");
+        assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\nc\n");
+    }
+}
@@ -5,6 +5,10 @@ pub mod parser;
 pub mod writer;
 pub mod modes;
 pub mod bands;
+pub mod cabrillo;
+pub mod encoding;
+pub mod propagation;
+pub mod activation;
 
 pub use parser::parse_adif;
 pub use writer::write_adif;
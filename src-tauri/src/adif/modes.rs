@@ -27,31 +27,66 @@ impl ModeGroup {
     }
 }
 
-/// Normalize a mode string to standard ADIF format
+/// Normalize a mode string to standard ADIF format.
+///
+/// Some loggers store the radio's mode setting instead of an ADIF mode;
+/// those are mapped to the ADIF mode they stand for. Data sideband modes
+/// (PKTUSB, DATA-U, ...) don't say which digital mode was used and become
+/// DATA; see `combine_mode` for picking the real one up from SUBMODE.
 pub fn normalize_mode(mode: &str) -> String {
-    mode.to_uppercase().trim().to_string()
+    let upper = mode.to_uppercase().trim().to_string();
+    let adif = match upper.as_str() {
+        "CWR" | "CW-R" | "CW-U" | "CW-L" | "CWU" | "CWL" => "CW",
+        "RTTYR" | "RTTY-R" | "FSK" | "FSK-R" => "RTTY",
+        "NFM" | "FM-N" | "WFM" | "FMN" => "FM",
+        "AM-N" | "SAM" => "AM",
+        "PKTUSB" | "PKTLSB" | "PKT-U" | "PKT-L" | "PKTFM" | "DATA-U" | "DATA-L" | "DATA-FM"
+        | "DATA-USB" | "DATA-LSB" | "USB-D" | "LSB-D" | "DIGU" | "DIGL" | "DIG" => "DATA",
+        _ => return upper,
+    };
+    adif.to_string()
 }
 
+/// Voice modes
+const PHONE_MODES: &[&str] = &["SSB", "LSB", "USB", "FM", "AM", "C4FM", "DMR", "DSTAR", "M17", "FREEDV", "DIGVOICE"];
+
+/// Image modes
+const IMAGE_MODES: &[&str] = &["SSTV", "FAX", "ATV"];
+
 /// Get the mode group for a given mode
 pub fn get_mode_group(mode: &str) -> ModeGroup {
     let mode_upper = mode.to_uppercase();
-    
-    match mode_upper.as_str() {
-        // CW
-        "CW" => ModeGroup::CW,
-        
-        // Phone modes
-        "SSB" | "LSB" | "USB" | "FM" | "AM" | "C4FM" | "DMR" | "DSTAR" | "M17" 
-        | "FREEDV" | "DIGVOICE" => ModeGroup::Phone,
-        
-        // Image modes
-        "SSTV" | "FAX" | "ATV" => ModeGroup::Image,
-        
+    let mode_upper = mode_upper.as_str();
+
+    if mode_upper == "CW" {
+        ModeGroup::CW
+    } else if PHONE_MODES.contains(&mode_upper) {
+        ModeGroup::Phone
+    } else if IMAGE_MODES.contains(&mode_upper) {
+        ModeGroup::Image
+    } else {
         // Everything else is Data
-        _ => ModeGroup::Data,
+        ModeGroup::Data
     }
 }
 
+/// SQL expression giving `ModeGroup::as_str` for a mode column, so queries
+/// can group by it instead of classifying each row in Rust
+pub fn mode_group_sql(column: &str) -> String {
+    let list = |modes: &[&str]| modes.iter().map(|m| format!("'{}'", m)).collect::<Vec<_>>().join(", ");
+    format!(
+        "CASE WHEN UPPER({col}) = 'CW' THEN '{cw}' WHEN UPPER({col}) IN ({phone}) THEN '{ph}' \
+         WHEN UPPER({col}) IN ({image}) THEN '{im}' ELSE '{data}' END",
+        col = column,
+        cw = ModeGroup::CW.as_str(),
+        phone = list(PHONE_MODES),
+        ph = ModeGroup::Phone.as_str(),
+        image = list(IMAGE_MODES),
+        im = ModeGroup::Image.as_str(),
+        data = ModeGroup::Data.as_str(),
+    )
+}
+
 /// All valid ADIF modes (from spec + common submodes)
 /// This list is used for validation during import
 pub const VALID_MODES: &[&str] = &[
@@ -65,9 +100,9 @@ pub const VALID_MODES: &[&str] = &[
     
     // Common submodes that might appear as MODE
     "LSB", "USB", "RTTY45", "RTTY50", "RTTY75", 
-    "PSK31", "PSK63", "PSK125", "PSK250",
+    "PSK10", "PSK31", "PSK63", "PSK63F", "PSK125", "PSK250", "PSK500", "PSK1000", "PSKFEC31",
     "BPSK31", "BPSK63", "BPSK125", "BPSK250",
-    "QPSK31", "QPSK63", "QPSK125", "QPSK250",
+    "QPSK31", "QPSK63", "QPSK125", "QPSK250", "QPSK500", "ASCI",
     "8PSK125", "8PSK250", "8PSK500", "8PSK1000",
     "JT65A", "JT65B", "JT65C",
     "MFSK4", "MFSK8", "MFSK11", "MFSK16", "MFSK22", "MFSK31", "MFSK32", "MFSK64", "MFSK128",
@@ -98,6 +133,71 @@ pub fn is_valid_mode(mode: &str) -> bool {
     VALID_MODES.iter().any(|m| m.to_uppercase() == mode_upper)
 }
 
+/// Split a logged mode into ADIF MODE and SUBMODE.
+///
+/// Modes that ADIF defines as submodes (FT4 under MFSK, USB under SSB,
+/// PSK31 under PSK, ...) are mapped to their parent; anything else is
+/// returned unchanged with no submode.
+pub fn split_mode(mode: &str) -> (String, Option<String>) {
+    let upper = normalize_mode(mode);
+
+    let parent = match upper.as_str() {
+        "FT4" | "FST4" | "FST4W" | "JS8" | "Q65" | "MFSK4" | "MFSK8" | "MFSK11" | "MFSK16"
+        | "MFSK22" | "MFSK31" | "MFSK32" | "MFSK64" | "MFSK128" => "MFSK",
+        "USB" | "LSB" => "SSB",
+        "PSK10" | "PSK31" | "PSK63" | "PSK63F" | "PSK125" | "PSK250" | "PSK500" | "PSK1000"
+        | "PSKFEC31" | "BPSK31" | "BPSK63" | "BPSK125" | "BPSK250" | "QPSK31" | "QPSK63"
+        | "QPSK125" | "QPSK250" | "QPSK500" | "8PSK125" | "8PSK250" | "8PSK500" | "8PSK1000" => "PSK",
+        // RTTY itself has no rate submodes in ADIF; ASCI is its only one
+        "ASCI" => "RTTY",
+        "JT65A" | "JT65B" | "JT65C" => "JT65",
+        "THOR4" | "THOR5" | "THOR8" | "THOR11" | "THOR16" | "THOR22" | "THOR25" | "THOR50"
+        | "THOR100" => "THOR",
+        "DOM4" | "DOM5" | "DOM8" | "DOM11" | "DOM16" | "DOM22" | "DOM44" | "DOM88" | "DOMINOEX"
+        | "DOMINOF" => "DOMINO",
+        "FMHELL" | "HELL80" | "FSKHELL" | "PSKHELL" | "SLOWHELL" => "HELL",
+        _ => return (upper, None),
+    };
+
+    (parent.to_string(), Some(upper))
+}
+
+/// The mode a QSO is stored and shown under: the specific submode when it
+/// belongs to the mode (PSK + PSK31 -> PSK31, MFSK + FT4 -> FT4), else the mode.
+/// Sideband is not a mode for awards, so USB and LSB are stored as SSB; a
+/// rig data mode takes the digital mode named in SUBMODE if there is one.
+pub fn combine_mode(mode: &str, submode: Option<&str>) -> String {
+    let mode = normalize_mode(mode);
+    let submode = submode.map(normalize_mode).filter(|s| !s.is_empty());
+    if split_mode(&mode).0 == "SSB" {
+        return "SSB".to_string();
+    }
+    match submode {
+        Some(sub) if mode == "DATA" && is_valid_mode(&sub) => combine_mode(&sub, None),
+        Some(sub) if mode != "SSB" && split_mode(&sub).0 == mode => sub,
+        _ => mode,
+    }
+}
+
+/// Mode and submode columns for an imported MODE/SUBMODE pair: the stored
+/// mode from `combine_mode`, with the sideband of a bare USB/LSB kept as
+/// the submode.
+pub fn import_mode(mode: &str, submode: Option<&str>) -> (String, Option<String>) {
+    let stored = combine_mode(mode, submode);
+    let submode = submode.filter(|_| normalize_mode(mode) != "DATA");
+    let sideband = split_mode(mode).1.filter(|_| stored == "SSB");
+    let submode = export_mode(&stored, submode).1.or(sideband);
+    (stored, submode)
+}
+
+/// ADIF MODE and SUBMODE to write for a stored mode; a stored submode wins
+/// over the one derived from the mode.
+pub fn export_mode(mode: &str, submode: Option<&str>) -> (String, Option<String>) {
+    let (parent, derived) = split_mode(mode);
+    let stored = submode.map(normalize_mode).filter(|s| !s.is_empty());
+    (parent, stored.or(derived))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +210,7 @@ mod tests {
         assert_eq!(get_mode_group("SSTV"), ModeGroup::Image);
         assert_eq!(get_mode_group("RTTY"), ModeGroup::Data);
         assert_eq!(get_mode_group("FM"), ModeGroup::Phone);
+        assert_eq!(get_mode_group("usb"), ModeGroup::Phone);
     }
     
     #[test]
@@ -120,4 +221,50 @@ mod tests {
         assert!(is_valid_mode("CW"));
         assert!(is_valid_mode("PSK31"));
     }
+    
+    #[test]
+    fn test_split_mode() {
+        assert_eq!(split_mode("FT4"), ("MFSK".to_string(), Some("FT4".to_string())));
+        assert_eq!(split_mode("usb"), ("SSB".to_string(), Some("USB".to_string())));
+        assert_eq!(split_mode("PSK31"), ("PSK".to_string(), Some("PSK31".to_string())));
+        assert_eq!(split_mode("FT8"), ("FT8".to_string(), None));
+        assert_eq!(split_mode("CW"), ("CW".to_string(), None));
+    }
+
+    #[test]
+    fn test_psk_and_rtty_submodes() {
+        for sub in ["PSK31", "PSK63", "PSK125"] {
+            assert_eq!(split_mode(sub), ("PSK".to_string(), Some(sub.to_string())));
+            assert_eq!(combine_mode("PSK", Some(sub)), sub);
+            assert_eq!(export_mode(sub, None), ("PSK".to_string(), Some(sub.to_string())));
+            assert_eq!(export_mode("PSK", Some(&sub.to_lowercase())), ("PSK".to_string(), Some(sub.to_string())));
+        }
+        assert_eq!(split_mode("RTTY"), ("RTTY".to_string(), None));
+        assert_eq!(combine_mode("rtty", None), "RTTY");
+        assert_eq!(export_mode("RTTY", Some("")), ("RTTY".to_string(), None));
+        // A submode that doesn't belong to the mode is not merged into it
+        assert_eq!(combine_mode("RTTY", Some("PSK31")), "RTTY");
+    }
+
+    #[test]
+    fn test_rig_modes() {
+        // Sideband designations
+        assert_eq!(import_mode("USB", None), ("SSB".to_string(), Some("USB".to_string())));
+        assert_eq!(import_mode("lsb", None), ("SSB".to_string(), Some("LSB".to_string())));
+        assert_eq!(import_mode("SSB", Some("USB")), ("SSB".to_string(), Some("USB".to_string())));
+        assert_eq!(import_mode("SSB", None), ("SSB".to_string(), None));
+        // Data sideband: the digital mode when SUBMODE names one, else DATA
+        assert_eq!(import_mode("PKTUSB", Some("FT8")), ("FT8".to_string(), None));
+        assert_eq!(import_mode("DATA-U", Some("PSK31")), ("PSK31".to_string(), Some("PSK31".to_string())));
+        assert_eq!(import_mode("DATA-U", None), ("DATA".to_string(), None));
+        assert_eq!(import_mode("PKTLSB", Some("junk")), ("DATA".to_string(), None));
+        // Other rig modes
+        assert_eq!(normalize_mode("CW-R"), "CW");
+        assert_eq!(normalize_mode("RTTYR"), "RTTY");
+        assert_eq!(normalize_mode("NFM"), "FM");
+        assert_eq!(normalize_mode("AM-N"), "AM");
+        // ADIF modes are untouched
+        assert_eq!(normalize_mode("pkt"), "PKT");
+        assert_eq!(import_mode("FT4", None), ("FT4".to_string(), Some("FT4".to_string())));
+    }
 }
@@ -46,6 +46,10 @@ impl AdifRecord {
     pub fn freq(&self) -> Option<f64> {
         self.get("FREQ").and_then(|s| s.parse().ok())
     }
+    /// FREQ in Hz, parsed exactly from the decimal text
+    pub fn freq_hz(&self) -> Option<u64> {
+        self.get("FREQ").and_then(|s| super::bands::parse_mhz_to_hz(s))
+    }
     pub fn dxcc(&self) -> Option<i64> {
         self.get("DXCC").and_then(|s| s.parse().ok())
     }
@@ -53,6 +57,10 @@ impl AdifRecord {
     pub fn cnty(&self) -> Option<&String> { self.get("CNTY") }
     pub fn gridsquare(&self) -> Option<&String> { self.get("GRIDSQUARE") }
     pub fn country(&self) -> Option<&String> { self.get("COUNTRY") }
+    /// SWL=Y: a shortwave listener report, not a two-way QSO
+    pub fn is_swl(&self) -> bool {
+        self.get("SWL").is_some_and(|v| v.trim().eq_ignore_ascii_case("Y"))
+    }
     pub fn cqz(&self) -> Option<i64> {
         self.get("CQZ").and_then(|s| s.parse().ok())
     }
@@ -88,51 +96,67 @@ impl AdifFile {
 
 /// Parse an ADIF string into records
 pub fn parse_adif(content: &str) -> Result<AdifFile, String> {
+    parse_adif_bytes(content.as_bytes())
+}
+
+/// Parse raw ADIF file bytes into records.
+/// Field lengths count bytes; values are decoded as UTF-8 or latin-1
+/// (see `encoding::decode_text`), so accented names survive either way.
+/// A leading BOM is stripped (UTF-16 is transcoded), and line breaks inside
+/// values come back as LF whether the file used CRLF, CR or LF. Records are
+/// found by their tags, so files without any line breaks parse the same.
+pub fn parse_adif_bytes(content: &[u8]) -> Result<AdifFile, String> {
     let mut file = AdifFile::new();
-    
+    let content = crate::adif::encoding::strip_bom(content);
+    let content = &content[..];
+
+    // ASCII-only uppercasing keeps byte offsets aligned with `content`
+    let content_upper = content.to_ascii_uppercase();
+
     // Find end of header
-    let content_upper = content.to_uppercase();
-    let body_start = if let Some(eoh_pos) = content_upper.find("<EOH>") {
+    let body_start = if let Some(eoh_pos) = find_tag(&content_upper, b"<EOH>") {
         // Parse header fields
-        let header_section = &content[..eoh_pos];
-        parse_fields_into(header_section, &mut file.header);
+        parse_fields_into(&content[..eoh_pos], &mut file.header);
         eoh_pos + 5 // Skip past <EOH>
     } else {
         // No header, start from beginning
         0
     };
-    
+
     let body = &content[body_start..];
-    
+    let body_upper = &content_upper[body_start..];
+
     // Split by <EOR> case-insensitively
     let mut current_pos = 0;
-    let body_upper = body.to_uppercase();
-    
-    while let Some(eor_offset) = body_upper[current_pos..].find("<EOR>") {
+    while let Some(eor_offset) = find_tag(&body_upper[current_pos..], b"<EOR>") {
         let record_end = current_pos + eor_offset;
-        let record_str = &body[current_pos..record_end];
-        
-        if !record_str.trim().is_empty() {
+        let record_bytes = &body[current_pos..record_end];
+
+        if !record_bytes.iter().all(u8::is_ascii_whitespace) {
             let mut record = AdifRecord::new();
-            parse_fields_into(record_str, &mut record.fields);
-            
+            parse_fields_into(record_bytes, &mut record.fields);
+
             // Only add if it has at least a CALL field
             if record.has("CALL") {
                 file.records.push(record);
             }
         }
-        
+
         current_pos = record_end + 5; // Skip past <EOR>
     }
-    
+
     Ok(file)
 }
 
-/// Parse ADIF fields from a string section into a HashMap
-fn parse_fields_into(content: &str, map: &mut HashMap<String, String>) {
+/// Byte offset of an (already uppercased) tag
+fn find_tag(haystack: &[u8], tag: &[u8]) -> Option<usize> {
+    haystack.windows(tag.len()).position(|w| w == tag)
+}
+
+/// Parse ADIF fields from a section of the file into a HashMap
+fn parse_fields_into(bytes: &[u8], map: &mut HashMap<String, String>) {
     let mut pos = 0;
-    let bytes = content.as_bytes();
-    
+
     while pos < bytes.len() {
         // Find next '<'
         match bytes[pos..].iter().position(|&b| b == b'<') {
@@ -141,44 +165,46 @@ fn parse_fields_into(content: &str, map: &mut HashMap<String, String>) {
             }
             None => break,
         }
-        
+
         // Find matching '>'
         let field_end = match bytes[pos..].iter().position(|&b| b == b'>') {
             Some(offset) => pos + offset,
             None => break,
         };
-        
-        let field_spec = &content[pos..field_end];
+
+        let field_spec = String::from_utf8_lossy(&bytes[pos..field_end]);
         pos = field_end + 1; // Move past '>'
-        
+
         // Parse field spec: NAME:LENGTH or NAME:LENGTH:TYPE
         // Also handle NAME only (for <EOH>, <EOR>)
         let parts: Vec<&str> = field_spec.split(':').collect();
         if parts.is_empty() {
             continue;
         }
-        
+
         let field_name = parts[0].to_uppercase();
-        
+
         // Skip control tags
         if field_name == "EOH" || field_name == "EOR" {
             continue;
         }
-        
+
         // Get length if specified
         let length: usize = if parts.len() > 1 {
             parts[1].parse().unwrap_or(0)
         } else {
             0
         };
-        
+
         // Extract value
         if length > 0 && pos + length <= bytes.len() {
-            let value = &content[pos..pos + length];
+            let end = complete_utf8_end(bytes, pos, pos + length);
+            let value = crate::adif::encoding::decode_text(&bytes[pos..end]);
+            let value = crate::adif::encoding::normalize_line_endings(&value);
             // Strip comments (text after //)
-            let clean_value = value.split("//").next().unwrap_or(value).trim();
+            let clean_value = value.split("//").next().unwrap_or(&value).trim();
             map.insert(field_name, clean_value.to_string());
-            pos += length;
+            pos = end;
         } else if length == 0 {
             // Boolean/empty field
             map.insert(field_name, String::new());
@@ -186,6 +212,18 @@ fn parse_fields_into(content: &str, map: &mut HashMap<String, String>) {
     }
 }
 
+/// Some writers count characters rather than bytes, which cuts a UTF-8
+/// value mid-character. Extend `end` over the missing continuation bytes.
+fn complete_utf8_end(bytes: &[u8], start: usize, mut end: usize) -> usize {
+    while end < bytes.len()
+        && (0x80..=0xBF).contains(&bytes[end])
+        && std::str::from_utf8(&bytes[start..end]).is_err_and(|e| e.error_len().is_none())
+    {
+        end += 1;
+    }
+    end
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,4 +298,57 @@ mod tests {
         assert_eq!(file.records[1].call(), Some(&"N2XYZ".to_string()));
         assert_eq!(file.records[2].call(), Some(&"K3AB".to_string()));
     }
+
+    #[test]
+    fn test_parse_latin1_name() {
+        let mut adif = b"<CALL:5>EA1AB<NAME:4>Jos".to_vec();
+        adif.push(0xE9); // latin-1 e-acute
+        adif.extend_from_slice(b"<QTH:6>Madrid<EOR>");
+        let file = parse_adif_bytes(&adif).unwrap();
+        assert_eq!(file.records.len(), 1);
+        assert_eq!(file.records[0].get("NAME").map(String::as_str), Some("José"));
+        assert_eq!(file.records[0].get("QTH").map(String::as_str), Some("Madrid"));
+    }
+
+    #[test]
+    fn test_parse_utf8_name_byte_or_char_length() {
+        // Byte count (5) and character count (4) both keep the whole name
+        for adif in ["<CALL:5>EA1AB<NAME:5>José<EOR>", "<CALL:5>EA1AB<NAME:4>José<EOR>"] {
+            let file = parse_adif(adif).unwrap();
+            assert_eq!(file.records[0].get("NAME").map(String::as_str), Some("José"), "{}", adif);
+        }
+    }
+
+    #[test]
+    fn test_parse_bom_crlf_and_single_line_alike() {
+        let lf = "ADIF export\n<ADIF_VER:5>3.1.4\n<EOH>\n\
+                  <CALL:4>W1AW<BAND:3>20m<MODE:3>FT8<QSO_DATE:8>20260110\n<EOR>\n\
+                  <CALL:6>JA1ABC<BAND:3>40m<MODE:2>CW<COMMENT:7>tnx\nfer<EOR>\n";
+        let crlf = lf.replace('\n', "\r\n").replace("<COMMENT:7>", "<COMMENT:8>");
+        let single_line = lf.replace("\n<", "<").replace(">\n", ">").replace("export", "export ");
+        let mut utf8_bom = vec![0xEF, 0xBB, 0xBF];
+        utf8_bom.extend_from_slice(lf.as_bytes());
+        let mut utf16_bom = vec![0xFF, 0xFE];
+        utf16_bom.extend(lf.encode_utf16().flat_map(u16::to_le_bytes));
+
+        let fields = |file: AdifFile| -> Vec<Vec<(String, String)>> {
+            file.records
+                .into_iter()
+                .map(|r| {
+                    let mut f: Vec<_> = r.fields.into_iter().collect();
+                    f.sort();
+                    f
+                })
+                .collect()
+        };
+        let expected = fields(parse_adif(lf).unwrap());
+        assert_eq!(expected.len(), 2);
+        assert_eq!(expected[1][0], ("BAND".to_string(), "40m".to_string()));
+        assert!(expected[1].contains(&("COMMENT".to_string(), "tnx\nfer".to_string())));
+
+        assert_eq!(fields(parse_adif(&crlf).unwrap()), expected);
+        assert_eq!(fields(parse_adif(&single_line).unwrap()), expected);
+        assert_eq!(fields(parse_adif_bytes(&utf8_bom).unwrap()), expected);
+        assert_eq!(fields(parse_adif_bytes(&utf16_bom).unwrap()), expected);
+    }
 }
@@ -0,0 +1,113 @@
+// Propagation Mode Inference
+// Reference: ADIF 3.1.4 Propagation_Mode enumeration
+//
+// On 6m and up the propagation mode matters for awards (VUCC and WAZ have
+// EME and satellite endorsements), and WSJT-X rarely fills it in. Many
+// VHF/UHF modes and sub-bands are used for one kind of propagation only:
+// MSK144 is meteor scatter, JT65/Q65 in the EME windows is moonbounce,
+// anything in the satellite sub-bands is via satellite.
+//
+// Rules are checked in order and the first match wins. The default table can
+// be replaced by a JSON array in the `prop_mode_rules` setting.
+
+use serde::{Deserialize, Serialize};
+
+/// One inference rule: a mode and/or frequency range that implies a PROP_MODE
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropModeRule {
+    /// Logged mode or its ADIF parent mode ("JT65" matches "JT65B"); any mode if None
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub freq_min_mhz: Option<f64>,
+    #[serde(default)]
+    pub freq_max_mhz: Option<f64>,
+    /// ADIF PROP_MODE value (MS, EME, SAT, TR, ES, ...)
+    pub prop_mode: String,
+}
+
+impl PropModeRule {
+    fn new(mode: Option<&str>, range: Option<(f64, f64)>, prop_mode: &str) -> Self {
+        Self {
+            mode: mode.map(str::to_string),
+            freq_min_mhz: range.map(|r| r.0),
+            freq_max_mhz: range.map(|r| r.1),
+            prop_mode: prop_mode.to_string(),
+        }
+    }
+
+    fn matches(&self, mode: &str, freq_mhz: f64) -> bool {
+        let mode_ok = self.mode.as_deref().is_none_or(|rule_mode| {
+            let (parent, _) = super::modes::split_mode(mode);
+            rule_mode.eq_ignore_ascii_case(mode) || rule_mode.eq_ignore_ascii_case(&parent)
+        });
+        mode_ok
+            && self.freq_min_mhz.is_none_or(|min| freq_mhz >= min)
+            && self.freq_max_mhz.is_none_or(|max| freq_mhz <= max)
+    }
+}
+
+/// Built-in rules for the common VHF/UHF cases
+pub fn default_prop_mode_rules() -> Vec<PropModeRule> {
+    vec![
+        // Meteor scatter modes, on any band
+        PropModeRule::new(Some("MSK144"), None, "MS"),
+        PropModeRule::new(Some("FSK441"), None, "MS"),
+        PropModeRule::new(Some("ISCAT"), None, "MS"),
+        // Amateur satellite sub-bands
+        PropModeRule::new(None, Some((145.8, 146.0)), "SAT"),
+        PropModeRule::new(None, Some((435.0, 438.0)), "SAT"),
+        // Digital EME windows
+        PropModeRule::new(Some("JT65"), Some((144.100, 144.160)), "EME"),
+        PropModeRule::new(Some("Q65"), Some((144.100, 144.160)), "EME"),
+        PropModeRule::new(Some("JT65"), Some((432.000, 432.100)), "EME"),
+        PropModeRule::new(Some("Q65"), Some((432.000, 432.100)), "EME"),
+        PropModeRule::new(Some("JT65"), Some((1296.000, 1296.100)), "EME"),
+        PropModeRule::new(Some("Q65"), Some((1296.000, 1296.100)), "EME"),
+    ]
+}
+
+/// Parse a user rule table (JSON array); None if it isn't valid
+pub fn parse_prop_mode_rules(json: &str) -> Option<Vec<PropModeRule>> {
+    serde_json::from_str(json).ok()
+}
+
+/// PROP_MODE implied by the mode and frequency, if any rule matches
+pub fn infer_prop_mode(rules: &[PropModeRule], mode: &str, freq_mhz: f64) -> Option<String> {
+    rules
+        .iter()
+        .find(|rule| rule.matches(mode, freq_mhz))
+        .map(|rule| rule.prop_mode.to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_msk144_is_meteor_scatter() {
+        let rules = default_prop_mode_rules();
+        assert_eq!(infer_prop_mode(&rules, "MSK144", 50.260).as_deref(), Some("MS"));
+        assert_eq!(infer_prop_mode(&rules, "MSK144", 144.150).as_deref(), Some("MS"));
+    }
+
+    #[test]
+    fn test_eme_and_satellite_windows() {
+        let rules = default_prop_mode_rules();
+        assert_eq!(infer_prop_mode(&rules, "Q65", 144.120).as_deref(), Some("EME"));
+        assert_eq!(infer_prop_mode(&rules, "JT65B", 432.065).as_deref(), Some("EME"));
+        assert_eq!(infer_prop_mode(&rules, "FM", 145.900).as_deref(), Some("SAT"));
+        // Terrestrial FT8 and HF are left alone
+        assert_eq!(infer_prop_mode(&rules, "FT8", 144.174), None);
+        assert_eq!(infer_prop_mode(&rules, "Q65", 50.275), None);
+    }
+
+    #[test]
+    fn test_user_rules() {
+        let rules = parse_prop_mode_rules(r#"[{"mode": "FT8", "freq_min_mhz": 50.0, "freq_max_mhz": 54.0, "prop_mode": "es"}]"#)
+            .unwrap();
+        assert_eq!(infer_prop_mode(&rules, "FT8", 50.313).as_deref(), Some("ES"));
+        assert_eq!(infer_prop_mode(&rules, "MSK144", 50.260), None);
+        assert!(parse_prop_mode_rules("not json").is_none());
+    }
+}
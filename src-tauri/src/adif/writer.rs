@@ -7,28 +7,50 @@
 #![allow(dead_code)]
 
 use std::collections::HashMap;
+use super::bands::{band_to_default_freq, freq_to_band};
 
 /// Write an ADIF file from records
 pub fn write_adif(records: &[HashMap<String, String>], program_id: &str) -> String {
+    let mut output = adif_header(program_id);
+    for record in records {
+        push_record(&mut output, record);
+    }
+    output
+}
+
+/// Write an ADIF file with records in labelled groups.
+///
+/// Each group is preceded by a plain-text comment line. ADIF readers ignore
+/// text outside tags, so the separators only help people editing by hand.
+pub fn write_adif_grouped(groups: &[(String, Vec<HashMap<String, String>>)], program_id: &str) -> String {
+    let mut output = adif_header(program_id);
+    for (label, records) in groups {
+        // '<' would start a tag; keep the comment inert
+        let label = label.replace('<', "(").replace('>', ")");
+        output.push_str(&format!("# {} ({} QSOs)\n\n", label, records.len()));
+        for record in records {
+            push_record(&mut output, record);
+        }
+    }
+    output
+}
+
+fn adif_header(program_id: &str) -> String {
     let mut output = String::new();
-    
-    // Header
     output.push_str(&format!("Generated by {} on {}\n", program_id, chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")));
     output.push_str(&format!("<PROGRAMID:{}>{}\n", program_id.len(), program_id));
     output.push_str("<ADIF_VER:5>3.1.4\n");
     output.push_str("<EOH>\n\n");
-    
-    // Records
-    for record in records {
-        for (key, value) in record {
-            if !value.is_empty() {
-                output.push_str(&format!("<{}:{}>{}", key.to_uppercase(), value.len(), value));
-            }
+    output
+}
+
+fn push_record(output: &mut String, record: &HashMap<String, String>) {
+    for (key, value) in record {
+        if !value.is_empty() {
+            output.push_str(&format!("<{}:{}>{}", key.to_uppercase(), value.len(), value));
         }
-        output.push_str("<EOR>\n\n");
     }
-    
-    output
+    output.push_str("<EOR>\n\n");
 }
 
 /// Standard ADIF fields we export (in order)
@@ -56,6 +78,9 @@ pub const EXPORT_FIELDS: &[&str] = &[
     "RIG", "ANT", "ANT_AZ", "ANT_EL",
 ];
 
+/// grid_source values for grids we filled in rather than received
+const INFERRED_GRID_SOURCES: &[&str] = &["prior_qso", "fcc"];
+
 /// Convert a QSO from our database format to ADIF HashMap
 pub fn qso_to_adif(qso: &serde_json::Value) -> HashMap<String, String> {
     let mut map = HashMap::new();
@@ -111,9 +136,22 @@ pub fn qso_to_adif(qso: &serde_json::Value) -> HashMap<String, String> {
             }
         }
     }
+
+    // Stored modes like PSK31 or FT4 are ADIF submodes of PSK / MFSK
+    if let Some(mode) = map.get("MODE").cloned() {
+        let (mode, submode) = crate::adif::modes::export_mode(&mode, map.get("SUBMODE").map(String::as_str));
+        map.insert("MODE".to_string(), mode);
+        match submode {
+            Some(submode) => map.insert("SUBMODE".to_string(), submode),
+            None => map.remove("SUBMODE"),
+        };
+    }
     
     // Numeric fields
-    if let Some(freq) = qso.get("freq").and_then(|v| v.as_f64()) {
+    // The exact Hz value wins over the MHz display column
+    if let Some(hz) = qso.get("freq_hz").and_then(|v| v.as_u64()).filter(|hz| *hz > 0) {
+        map.insert("FREQ".to_string(), super::bands::format_hz_as_mhz(hz));
+    } else if let Some(freq) = qso.get("freq").and_then(|v| v.as_f64()) {
         map.insert("FREQ".to_string(), format!("{:.6}", freq));
     }
     if let Some(dxcc) = qso.get("dxcc").and_then(|v| v.as_i64()) {
@@ -128,6 +166,13 @@ pub fn qso_to_adif(qso: &serde_json::Value) -> HashMap<String, String> {
     if let Some(pwr) = qso.get("tx_pwr").and_then(|v| v.as_f64()) {
         map.insert("TX_PWR".to_string(), pwr.to_string());
     }
+
+    // Inferred grids are marked so a re-import keeps their provenance
+    if let Some(source) = qso.get("grid_source").and_then(|v| v.as_str()) {
+        if INFERRED_GRID_SOURCES.contains(&source) && map.contains_key("GRIDSQUARE") {
+            map.insert("APP_GOQSO_GRID_SOURCE".to_string(), source.to_string());
+        }
+    }
     
     // Extended fields from adif_fields JSON blob
     if let Some(adif_json) = qso.get("adif_fields").and_then(|v| v.as_str()) {
@@ -143,6 +188,162 @@ pub fn qso_to_adif(qso: &serde_json::Value) -> HashMap<String, String> {
             }
         }
     }
-    
+
+    super::activation::tag_comment(&mut map);
+    fill_band_freq(&mut map);
     map
 }
+
+/// Never write a zero or unparseable FREQ. A missing frequency is filled with
+/// the standard dial frequency for FT8/FT4 on the band and dropped otherwise,
+/// and a missing band is derived from the frequency.
+fn fill_band_freq(map: &mut HashMap<String, String>) {
+    let freq = map
+        .get("FREQ")
+        .and_then(|f| f.trim().parse::<f64>().ok())
+        .filter(|f| *f > 0.0);
+    match freq {
+        Some(mhz) => {
+            if !map.contains_key("BAND") {
+                if let Some(band) = freq_to_band(mhz) {
+                    map.insert("BAND".to_string(), band.to_string());
+                }
+            }
+        }
+        None => {
+            // MODE/SUBMODE are already split per ADIF here (MFSK + FT4)
+            let mode = map.get("SUBMODE").or_else(|| map.get("MODE")).cloned().unwrap_or_default();
+            match map.get("BAND").and_then(|b| band_to_default_freq(b, &mode)) {
+                Some(mhz) => {
+                    map.insert("FREQ".to_string(), format!("{:.6}", mhz));
+                }
+                None => {
+                    map.remove("FREQ");
+                }
+            }
+        }
+    }
+}
+
+/// Fields LoTW accepts; everything else is dropped to avoid TQSL rejections
+pub const LOTW_FIELDS: &[&str] = &[
+    "CALL", "QSO_DATE", "TIME_ON", "BAND", "MODE", "SUBMODE", "FREQ", "PROP_MODE", "SAT_NAME",
+];
+
+/// Convert a QSO to a minimal LoTW record; qso_to_adif has already split MODE/SUBMODE per ADIF
+pub fn qso_to_lotw_adif(qso: &serde_json::Value) -> HashMap<String, String> {
+    qso_to_adif(qso)
+        .into_iter()
+        .filter(|(k, _)| LOTW_FIELDS.contains(&k.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test: Column-backed fields come from their columns, and inferred grids are marked
+    #[test]
+    fn test_export_prop_mode_and_grid_source() {
+        let qso = serde_json::json!({
+            "call": "K5ABC",
+            "qso_date": "20260115",
+            "time_on": "123000",
+            "band": "6m",
+            "mode": "FT8",
+            "gridsquare": "EM12",
+            "grid_source": "fcc",
+            "prop_mode": "ES",
+            "adif_fields": "{\"contest_id\":\"TEST\"}",
+        });
+        let record = qso_to_adif(&qso);
+        assert_eq!(record.get("PROP_MODE").map(String::as_str), Some("ES"));
+        assert_eq!(record.get("APP_GOQSO_GRID_SOURCE").map(String::as_str), Some("fcc"));
+        assert_eq!(record.get("CONTEST_ID").map(String::as_str), Some("TEST"));
+
+        let sent = serde_json::json!({ "call": "K5ABC", "gridsquare": "EM12", "grid_source": "station" });
+        assert!(!qso_to_adif(&sent).contains_key("APP_GOQSO_GRID_SOURCE"));
+    }
+
+    #[test]
+    fn test_lotw_export_ft4_as_mfsk() {
+        let qso = serde_json::json!({
+            "call": "JA1ABC",
+            "qso_date": "20260115",
+            "time_on": "123000",
+            "band": "20m",
+            "mode": "FT4",
+            "freq": 14.080,
+            "country": "Japan",
+            "rst_sent": "-10",
+            "gridsquare": "PM95",
+            "adif_fields": "{\"prop_mode\":\"F2\",\"comment\":\"tnx\"}",
+        });
+
+        let record = qso_to_lotw_adif(&qso);
+        assert_eq!(record.get("MODE").map(String::as_str), Some("MFSK"));
+        assert_eq!(record.get("SUBMODE").map(String::as_str), Some("FT4"));
+        assert_eq!(record.get("PROP_MODE").map(String::as_str), Some("F2"));
+        assert_eq!(record.get("FREQ").map(String::as_str), Some("14.080000"));
+        // Internal/non-LoTW fields are stripped
+        for field in ["COUNTRY", "RST_SENT", "GRIDSQUARE", "COMMENT"] {
+            assert!(!record.contains_key(field), "{} should be stripped", field);
+        }
+    }
+
+    #[test]
+    fn test_lotw_export_keeps_plain_mode() {
+        let qso = serde_json::json!({"call": "W1AW", "mode": "FT8"});
+        let record = qso_to_lotw_adif(&qso);
+        assert_eq!(record.get("MODE").map(String::as_str), Some("FT8"));
+        assert!(!record.contains_key("SUBMODE"));
+    }
+
+    #[test]
+    fn test_grouped_export_separators_parse_cleanly() {
+        let record = |call: &str| HashMap::from([("CALL".to_string(), call.to_string())]);
+        let groups = vec![
+            ("Band 40m".to_string(), vec![record("K1ABC")]),
+            ("DXCC <339>".to_string(), vec![record("JA1ABC"), record("JA2ABC")]),
+        ];
+
+        let output = write_adif_grouped(&groups, "GoQSO");
+        assert!(output.contains("# Band 40m (1 QSOs)"));
+        assert!(output.contains("# DXCC (339) (2 QSOs)"));
+
+        let parsed = crate::adif::parse_adif(&output).unwrap();
+        assert_eq!(parsed.records.len(), 3);
+    }
+
+    #[test]
+    fn test_band_only_qso_exports_default_freq() {
+        let qso = serde_json::json!({"call": "W1AW", "band": "40m", "mode": "FT8", "freq": 0.0});
+        let record = qso_to_adif(&qso);
+        assert_eq!(record.get("FREQ").map(String::as_str), Some("7.074000"));
+
+        let qso = serde_json::json!({"call": "W1AW", "band": "20m", "mode": "FT8", "freq": null});
+        let record = qso_to_lotw_adif(&qso);
+        assert_eq!(record.get("FREQ").map(String::as_str), Some("14.074000"));
+
+        let qso = serde_json::json!({"call": "W1AW", "band": "20m", "mode": "FT4"});
+        assert_eq!(qso_to_adif(&qso).get("FREQ").map(String::as_str), Some("14.080000"));
+    }
+
+    #[test]
+    fn test_band_only_phone_qso_has_no_freq() {
+        for mode in ["SSB", "CW"] {
+            let qso = serde_json::json!({"call": "W1AW", "band": "20m", "mode": mode, "freq": 0.0});
+            assert!(!qso_to_adif(&qso).contains_key("FREQ"), "{}", mode);
+        }
+    }
+
+    #[test]
+    fn test_no_zero_freq_without_band() {
+        let qso = serde_json::json!({"call": "W1AW", "band": "13cm", "freq": 0.0});
+        assert!(!qso_to_adif(&qso).contains_key("FREQ"));
+
+        // Frequency-only QSO gets its band filled
+        let qso = serde_json::json!({"call": "W1AW", "freq": 21.074});
+        assert_eq!(qso_to_adif(&qso).get("BAND").map(String::as_str), Some("15m"));
+    }
+}
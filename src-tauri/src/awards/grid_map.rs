@@ -0,0 +1,173 @@
+// Grid-square arithmetic for the "grid chase" map
+//
+// Grids are converted to integer cell coordinates at 4-char (square) or
+// 6-char (subsquare) precision so a region can be walked cell by cell.
+// Longitude wraps from field R back to field A; latitude stops at the poles.
+
+/// Grid precision for the map: 4-char squares or 6-char subsquares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridPrecision {
+    Square,
+    Subsquare,
+}
+
+impl GridPrecision {
+    /// Parse 4 or 6 (anything else is rejected)
+    pub fn from_chars(chars: u8) -> Option<Self> {
+        match chars {
+            4 => Some(GridPrecision::Square),
+            6 => Some(GridPrecision::Subsquare),
+            _ => None,
+        }
+    }
+
+    pub fn chars(self) -> usize {
+        match self {
+            GridPrecision::Square => 4,
+            GridPrecision::Subsquare => 6,
+        }
+    }
+
+    /// Number of cells around the globe in each axis (18 fields x 10 squares [x 24 subsquares])
+    fn cells_per_axis(self) -> i32 {
+        match self {
+            GridPrecision::Square => 180,
+            GridPrecision::Subsquare => 180 * 24,
+        }
+    }
+}
+
+/// Convert a grid to (x, y) cell coordinates at the given precision.
+/// x counts eastward from 180°W, y counts northward from 90°S.
+/// A 4-char grid used at subsquare precision resolves to its center subsquare.
+pub fn grid_to_cell(grid: &str, precision: GridPrecision) -> Option<(i32, i32)> {
+    let grid = grid.trim().to_uppercase();
+    let b = grid.as_bytes();
+    if b.len() < 4 {
+        return None;
+    }
+    if !(b'A'..=b'R').contains(&b[0]) || !(b'A'..=b'R').contains(&b[1]) {
+        return None;
+    }
+    if !b[2].is_ascii_digit() || !b[3].is_ascii_digit() {
+        return None;
+    }
+
+    let x4 = (b[0] - b'A') as i32 * 10 + (b[2] - b'0') as i32;
+    let y4 = (b[1] - b'A') as i32 * 10 + (b[3] - b'0') as i32;
+
+    match precision {
+        GridPrecision::Square => Some((x4, y4)),
+        GridPrecision::Subsquare => {
+            let (sx, sy) = if b.len() >= 6 {
+                if !(b'A'..=b'X').contains(&b[4]) || !(b'A'..=b'X').contains(&b[5]) {
+                    return None;
+                }
+                ((b[4] - b'A') as i32, (b[5] - b'A') as i32)
+            } else {
+                (11, 11)
+            };
+            Some((x4 * 24 + sx, y4 * 24 + sy))
+        }
+    }
+}
+
+/// Convert cell coordinates back to a grid string. Longitude wraps; latitude
+/// outside the poles returns None.
+pub fn cell_to_grid(x: i32, y: i32, precision: GridPrecision) -> Option<String> {
+    let n = precision.cells_per_axis();
+    if !(0..n).contains(&y) {
+        return None;
+    }
+    let x = x.rem_euclid(n);
+
+    let (x4, y4, sub) = match precision {
+        GridPrecision::Square => (x, y, None),
+        GridPrecision::Subsquare => (x / 24, y / 24, Some((x % 24, y % 24))),
+    };
+
+    let mut grid = String::with_capacity(6);
+    grid.push((b'A' + (x4 / 10) as u8) as char);
+    grid.push((b'A' + (y4 / 10) as u8) as char);
+    grid.push((b'0' + (x4 % 10) as u8) as char);
+    grid.push((b'0' + (y4 % 10) as u8) as char);
+    if let Some((sx, sy)) = sub {
+        grid.push((b'a' + sx as u8) as char);
+        grid.push((b'a' + sy as u8) as char);
+    }
+    Some(grid)
+}
+
+/// All grids in a square region around `center`, `radius` cells in each
+/// direction. Rows run north to south, columns west to east; cells beyond the
+/// poles are None so every row keeps the same width.
+pub fn region_around(center: &str, radius: i32, precision: GridPrecision) -> Option<Vec<Vec<Option<String>>>> {
+    let (cx, cy) = grid_to_cell(center, precision)?;
+    let radius = radius.max(0);
+
+    let rows = ((cy - radius)..=(cy + radius))
+        .rev()
+        .map(|y| {
+            ((cx - radius)..=(cx + radius))
+                .map(|x| cell_to_grid(x, y, precision))
+                .collect()
+        })
+        .collect();
+    Some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_cell_round_trip() {
+        for grid in ["FN31", "AA00", "RR99", "JO01"] {
+            let (x, y) = grid_to_cell(grid, GridPrecision::Square).unwrap();
+            assert_eq!(cell_to_grid(x, y, GridPrecision::Square).unwrap(), grid);
+        }
+        for grid in ["FN31pr", "EM12ab", "RR99xx"] {
+            let (x, y) = grid_to_cell(grid, GridPrecision::Subsquare).unwrap();
+            assert_eq!(cell_to_grid(x, y, GridPrecision::Subsquare).unwrap(), grid);
+        }
+    }
+
+    #[test]
+    fn test_four_char_center_at_subsquare_precision() {
+        let (x, y) = grid_to_cell("FN31", GridPrecision::Subsquare).unwrap();
+        assert_eq!(cell_to_grid(x, y, GridPrecision::Subsquare).unwrap(), "FN31ll");
+    }
+
+    #[test]
+    fn test_invalid_grids() {
+        assert!(grid_to_cell("ZZ00", GridPrecision::Square).is_none());
+        assert!(grid_to_cell("FN", GridPrecision::Square).is_none());
+        assert!(grid_to_cell("FN31zz", GridPrecision::Subsquare).is_none());
+    }
+
+    #[test]
+    fn test_region_size_and_order() {
+        let region = region_around("FN31", 2, GridPrecision::Square).unwrap();
+        assert_eq!(region.len(), 5);
+        assert!(region.iter().all(|row| row.len() == 5));
+        assert_eq!(region[2][2].as_deref(), Some("FN31"));
+        assert_eq!(region[0][2].as_deref(), Some("FN33")); // north
+        assert_eq!(region[2][0].as_deref(), Some("FN11")); // west
+    }
+
+    #[test]
+    fn test_region_wraps_longitude() {
+        // AA-field west edge wraps to the R field
+        let region = region_around("AJ05", 1, GridPrecision::Square).unwrap();
+        assert_eq!(region[1][0].as_deref(), Some("RJ95"));
+        let region = region_around("RJ95", 1, GridPrecision::Square).unwrap();
+        assert_eq!(region[1][2].as_deref(), Some("AJ05"));
+    }
+
+    #[test]
+    fn test_region_stops_at_poles() {
+        let region = region_around("JR09", 1, GridPrecision::Square).unwrap();
+        assert!(region[0].iter().all(|c| c.is_none()));
+        assert_eq!(region[1][1].as_deref(), Some("JR09"));
+    }
+}
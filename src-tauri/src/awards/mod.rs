@@ -1,3 +1,6 @@
 pub mod dxcc;
+pub mod grid_map;
+pub mod subdivisions;
 pub mod was;
 pub mod vucc;
+pub mod wwff;
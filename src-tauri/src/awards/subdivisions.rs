@@ -0,0 +1,38 @@
+// Sub-entity award tracking (JA prefectures, DOKs, oblasts)
+//
+// Subdivisions for each DXCC entity live in the `subdivisions` table and are
+// matched against the QSO's STATE field (ADIF primary administrative
+// subdivision). Numeric codes are compared by value, so "1" and "01" both
+// match JA prefecture 01 (Hokkaido).
+
+/// Canonical form of a subdivision code for matching: trimmed, uppercase,
+/// and leading zeros dropped from purely numeric codes
+pub fn subdivision_key(code: &str) -> Option<String> {
+    let code = code.trim().to_uppercase();
+    if code.is_empty() {
+        return None;
+    }
+    if code.chars().all(|c| c.is_ascii_digit()) {
+        let trimmed = code.trim_start_matches('0');
+        return Some(if trimmed.is_empty() { "0".to_string() } else { trimmed.to_string() });
+    }
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_codes_match_by_value() {
+        assert_eq!(subdivision_key("01"), subdivision_key("1"));
+        assert_eq!(subdivision_key(" 47 "), Some("47".to_string()));
+        assert_eq!(subdivision_key("00"), Some("0".to_string()));
+    }
+
+    #[test]
+    fn test_alpha_codes_uppercased() {
+        assert_eq!(subdivision_key("f05"), Some("F05".to_string()));
+        assert_eq!(subdivision_key("  "), None);
+    }
+}
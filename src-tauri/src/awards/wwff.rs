@@ -0,0 +1,102 @@
+// WWFF (World Wide Flora & Fauna) reference tracking
+//
+// WWFF references use the format "XXFF-NNNN": a national program prefix
+// (e.g. K, VK, DL), the literal "FF", a dash, and a 4-digit number.
+
+use std::collections::BTreeMap;
+
+/// Normalize a WWFF reference (trim + uppercase)
+pub fn normalize_wwff_ref(reference: &str) -> String {
+    reference.trim().to_uppercase()
+}
+
+/// Validate a WWFF reference against the "XXFF-NNNN" format
+pub fn is_valid_wwff_ref(reference: &str) -> bool {
+    let r = normalize_wwff_ref(reference);
+    let Some((program, number)) = r.split_once('-') else {
+        return false;
+    };
+
+    let Some(prefix) = program.strip_suffix("FF") else {
+        return false;
+    };
+
+    !prefix.is_empty()
+        && prefix.len() <= 4
+        && prefix.chars().all(|c| c.is_ascii_alphanumeric())
+        && number.len() == 4
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Get the country prefix of a WWFF reference ("KFF-0001" -> "K")
+pub fn wwff_country_prefix(reference: &str) -> Option<String> {
+    if !is_valid_wwff_ref(reference) {
+        return None;
+    }
+    let r = normalize_wwff_ref(reference);
+    r.split_once("FF-").map(|(prefix, _)| prefix.to_string())
+}
+
+/// Deduplicate valid references and group them by country prefix.
+/// Invalid references are ignored.
+pub fn group_by_prefix<I, S>(references: I) -> BTreeMap<String, Vec<String>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for reference in references {
+        let Some(prefix) = wwff_country_prefix(reference.as_ref()) else {
+            continue;
+        };
+        let normalized = normalize_wwff_ref(reference.as_ref());
+        let refs = groups.entry(prefix).or_default();
+        if !refs.contains(&normalized) {
+            refs.push(normalized);
+        }
+    }
+    for refs in groups.values_mut() {
+        refs.sort();
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_wwff_refs() {
+        assert!(is_valid_wwff_ref("KFF-0001"));
+        assert!(is_valid_wwff_ref("VKFF-1234"));
+        assert!(is_valid_wwff_ref("DLFF-0099"));
+        assert!(is_valid_wwff_ref(" kff-0001 "));
+    }
+
+    #[test]
+    fn test_invalid_wwff_refs() {
+        assert!(!is_valid_wwff_ref(""));
+        assert!(!is_valid_wwff_ref("K-0001"));       // POTA format
+        assert!(!is_valid_wwff_ref("FF-0001"));      // missing prefix
+        assert!(!is_valid_wwff_ref("KFF-001"));      // 3 digits
+        assert!(!is_valid_wwff_ref("KFF-00001"));    // 5 digits
+        assert!(!is_valid_wwff_ref("KFF0001"));      // missing dash
+        assert!(!is_valid_wwff_ref("KFF-00A1"));     // non-digit
+        assert!(!is_valid_wwff_ref("W4C/CM-001"));   // SOTA format
+    }
+
+    #[test]
+    fn test_wwff_country_prefix() {
+        assert_eq!(wwff_country_prefix("KFF-0001"), Some("K".to_string()));
+        assert_eq!(wwff_country_prefix("vkff-0100"), Some("VK".to_string()));
+        assert_eq!(wwff_country_prefix("K-0001"), None);
+    }
+
+    #[test]
+    fn test_group_by_prefix() {
+        let groups = group_by_prefix(["KFF-0002", "kff-0001", "KFF-0001", "VKFF-0100", "BAD"]);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["K"], vec!["KFF-0001", "KFF-0002"]);
+        assert_eq!(groups["VK"], vec!["VKFF-0100"]);
+    }
+}
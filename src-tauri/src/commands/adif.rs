@@ -2,16 +2,27 @@
 //!
 //! This module handles:
 //! - import_adif: Import QSOs from ADIF file content
+//! - import_adif_multi: Import several overlapping ADIF files as one merged log
+//! - import_cabrillo: Import QSOs from a Cabrillo contest log
 //! - export_adif: Export QSOs to ADIF format
+//! - export_adif_with_manifest: Export QSOs plus a JSON summary of what's in the file
+//! - export_adif_for_lotw: Export pending QSOs with only the fields LoTW accepts
 //! - import_lotw_confirmations: Import LoTW confirmation data
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use tauri::command;
 
+use super::jobs::JOB_IMPORT;
+use super::lotw::{find_lotw_match, lotw_match_tolerance};
+use super::qso::{GRID_SOURCE_ADIF, GRID_SOURCE_LOTW, SOURCE_SWL};
+use super::settings::{
+    counted_qso_filter, read_bool_setting, PRESERVE_APP_FIELDS_SETTING, SANITIZE_RST_SETTING, SKIP_SWL_IMPORT_SETTING,
+};
 use super::state::AppState;
 use super::time_utils::{extract_hhmm, normalize_time_to_hhmmss, time_to_seconds};
-use crate::udp::wsjtx::{is_valid_grid, normalize_rst};
+use crate::adif::modes::{combine_mode, import_mode};
+use crate::udp::wsjtx::{is_valid_grid, normalize_rst, sanitize_rst};
 
 // ============================================================================
 // Data Types
@@ -22,6 +33,8 @@ pub struct ImportResult {
     pub total_records: usize,
     pub imported: usize,
     pub skipped: usize,
+    /// Existing QSOs merged with incoming data under DuplicatePolicy::Replace
+    pub updated: usize,
     pub errors: usize,
     pub error_messages: Vec<String>,
 }
@@ -35,12 +48,81 @@ pub struct LotwImportResult {
     pub errors: usize,
 }
 
+/// What an import does with a record that matches a logged QSO (same call, date,
+/// band and mode, TIME_ON within 2 minutes)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// Leave the logged QSO alone and drop the record
+    #[default]
+    Skip,
+    /// Merge the record into the logged QSO; its non-empty fields win
+    Replace,
+    /// Import the record as a separate QSO
+    KeepBoth,
+}
+
+impl DuplicatePolicy {
+    /// Policy for the older `skip_duplicates` flag
+    pub fn from_skip_duplicates(skip_duplicates: bool) -> Self {
+        if skip_duplicates {
+            DuplicatePolicy::Skip
+        } else {
+            DuplicatePolicy::KeepBoth
+        }
+    }
+}
+
+/// Record order for ADIF export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportSort {
+    /// Newest first
+    #[default]
+    DateDesc,
+    /// Oldest first (chronological)
+    DateAsc,
+}
+
+/// Summary of an ADIF export, written alongside it so recipients can see
+/// what's inside and check the file arrived intact
+#[derive(Debug, Serialize)]
+pub struct ExportManifest {
+    pub record_count: usize,
+    /// QSO_DATE of the earliest and latest records (YYYYMMDD)
+    pub first_qso_date: Option<String>,
+    pub last_qso_date: Option<String>,
+    pub bands: Vec<String>,
+    pub modes: Vec<String>,
+    pub dxcc_count: usize,
+    pub state_count: usize,
+    /// SHA-256 of the ADIF text, lowercase hex
+    pub sha256: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdifExportWithManifest {
+    pub adif: String,
+    /// ExportManifest as pretty-printed JSON
+    pub manifest: String,
+}
+
+/// Optional grouping for ADIF export; records within a group keep the sort order
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportGroupBy {
+    /// Band order, 160m upward
+    Band,
+    /// DXCC entity number; QSOs without one go last
+    Dxcc,
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
 pub fn row_to_json(row: &sqlx::sqlite::SqliteRow) -> serde_json::Value {
-    serde_json::json!({
+    let mut qso = serde_json::json!({
         "id": row.get::<i64, _>("id"),
         "uuid": row.get::<String, _>("uuid"),
         "call": row.get::<String, _>("call"),
@@ -50,6 +132,7 @@ pub fn row_to_json(row: &sqlx::sqlite::SqliteRow) -> serde_json::Value {
         "band": row.get::<String, _>("band"),
         "mode": row.get::<String, _>("mode"),
         "freq": row.try_get::<f64, _>("freq").ok(),
+        "freq_hz": row.try_get::<i64, _>("freq_hz").ok(),
         "dxcc": row.try_get::<i64, _>("dxcc").ok(),
         "country": row.try_get::<String, _>("country").ok(),
         "state": row.try_get::<String, _>("state").ok(),
@@ -62,38 +145,390 @@ pub fn row_to_json(row: &sqlx::sqlite::SqliteRow) -> serde_json::Value {
         "rst_rcvd": row.try_get::<String, _>("rst_rcvd").ok(),
         "station_callsign": row.try_get::<String, _>("station_callsign").ok(),
         "my_gridsquare": row.try_get::<String, _>("my_gridsquare").ok(),
+        "pota_ref": row.try_get::<String, _>("pota_ref").ok(),
+        "sota_ref": row.try_get::<String, _>("sota_ref").ok(),
+        "my_pota_ref": row.try_get::<String, _>("my_pota_ref").ok(),
+        "my_sota_ref": row.try_get::<String, _>("my_sota_ref").ok(),
         "tx_pwr": row.try_get::<f64, _>("tx_pwr").ok(),
         "adif_fields": row.try_get::<String, _>("adif_fields").ok(),
         "source": row.try_get::<String, _>("source").ok(),
-    })
+    });
+    // Other text columns the writer maps to ADIF fields
+    for column in [
+        "qso_date_off", "submode", "grid_source", "operator", "prop_mode", "sat_name", "iota", "wwff_ref", "pfx",
+        "name", "qth", "comment", "arrl_sect", "my_cnty", "my_arrl_sect",
+    ] {
+        qso[column] = row.try_get::<String, _>(column).ok().into();
+    }
+    qso
 }
 
-// ============================================================================
-// Commands
-// ============================================================================
+/// Load the QSOs to export. `qso_ids` limits the export to those QSOs;
+/// `unconfirmed_source` keeps only QSOs without a received confirmation from
+/// that source (`Some(None)` = from any source), e.g. for an OQRS request list.
+/// SWL reports, and test QSOs when `exclude_test_data` is on, are left out.
+pub async fn fetch_export_qsos(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    qso_ids: Option<&[i64]>,
+    unconfirmed_source: Option<Option<&str>>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut conditions: Vec<String> = Vec::new();
+    if let Some(ids) = qso_ids {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        conditions.push(format!("id IN ({})", placeholders));
+    }
+    if let Some(source) = unconfirmed_source {
+        conditions.push(format!(
+            "NOT EXISTS (SELECT 1 FROM confirmations c WHERE c.qso_id = qsos.id AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0{})",
+            if source.is_some() { " AND UPPER(c.source) = UPPER(?)" } else { "" }
+        ));
+    }
 
-#[command]
-pub async fn import_adif(
-    state: tauri::State<'_, AppState>,
-    content: String,
-    skip_duplicates: bool,
-) -> Result<ImportResult, String> {
-    use crate::adif::parse_adif;
+    let mut query = format!("SELECT * FROM qsos WHERE 1 = 1{}", counted_qso_filter(pool, "qsos").await);
+    for condition in &conditions {
+        query.push_str(" AND ");
+        query.push_str(condition);
+    }
 
-    let db_guard = state.db.lock().await;
-    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+    let mut q = sqlx::query(&query);
+    for id in qso_ids.unwrap_or_default() {
+        q = q.bind(id);
+    }
+    if let Some(Some(source)) = unconfirmed_source {
+        q = q.bind(source);
+    }
+    let rows = q.fetch_all(pool).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(row_to_json).collect())
+}
+
+/// Sort exported QSOs by date/time and split them into labelled groups.
+/// Without grouping a single unlabelled group is returned.
+pub fn arrange_export(
+    mut qsos: Vec<serde_json::Value>,
+    sort: ExportSort,
+    group_by: Option<ExportGroupBy>,
+) -> Vec<(Option<String>, Vec<serde_json::Value>)> {
+    let field = |q: &serde_json::Value, name: &str| q.get(name).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let timestamp = |q: &serde_json::Value| (field(q, "qso_date"), normalize_time_to_hhmmss(&field(q, "time_on")));
+
+    qsos.sort_by(|a, b| match sort {
+        ExportSort::DateAsc => timestamp(a).cmp(&timestamp(b)),
+        ExportSort::DateDesc => timestamp(b).cmp(&timestamp(a)),
+    });
+
+    let Some(group_by) = group_by else {
+        return vec![(None, qsos)];
+    };
+
+    // (sort key, label); the sort is stable so date order survives within a group
+    let group_of = |q: &serde_json::Value| -> ((usize, i64), String) {
+        match group_by {
+            ExportGroupBy::Band => {
+                let band = field(q, "band").to_lowercase();
+                let label = if band.is_empty() { "Band unknown".to_string() } else { format!("Band {}", band) };
+                ((crate::adif::bands::band_sort_index(&band), 0), label)
+            }
+            ExportGroupBy::Dxcc => match q.get("dxcc").and_then(|v| v.as_i64()) {
+                Some(dxcc) => {
+                    let country = field(q, "country");
+                    let label = if country.is_empty() {
+                        format!("DXCC {}", dxcc)
+                    } else {
+                        format!("DXCC {} {}", dxcc, country)
+                    };
+                    ((0, dxcc), label)
+                }
+                None => ((1, 0), "DXCC unknown".to_string()),
+            },
+        }
+    };
+
+    let mut keyed: Vec<((usize, i64), String, serde_json::Value)> = qsos
+        .into_iter()
+        .map(|q| {
+            let (key, label) = group_of(&q);
+            (key, label, q)
+        })
+        .collect();
+    keyed.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut groups: Vec<(Option<String>, Vec<serde_json::Value>)> = Vec::new();
+    for (_, label, qso) in keyed {
+        match groups.last_mut() {
+            Some((Some(last), members)) if *last == label => members.push(qso),
+            _ => groups.push((Some(label), vec![qso])),
+        }
+    }
+    groups
+}
+
+/// Summarize exported QSOs (as loaded by fetch_export_qsos) and the ADIF written for them
+pub fn export_manifest(qsos: &[serde_json::Value], adif: &str) -> ExportManifest {
+    use sha2::{Digest, Sha256};
+    use std::collections::BTreeSet;
+
+    let text = |q: &serde_json::Value, name: &str| {
+        q.get(name).and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+    };
+    let dates: BTreeSet<String> = qsos.iter().filter_map(|q| text(q, "qso_date")).collect();
+    let mut bands: Vec<String> = qsos
+        .iter()
+        .filter_map(|q| text(q, "band").map(|b| b.to_lowercase()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    bands.sort_by_key(|b| crate::adif::bands::band_sort_index(b));
+    let modes: BTreeSet<String> = qsos.iter().filter_map(|q| text(q, "mode").map(|m| m.to_uppercase())).collect();
+    let dxccs: BTreeSet<i64> = qsos.iter().filter_map(|q| q.get("dxcc").and_then(|v| v.as_i64())).collect();
+    let states: BTreeSet<String> = qsos.iter().filter_map(|q| text(q, "state").map(|s| s.to_uppercase())).collect();
+
+    let sha256 = Sha256::digest(adif.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    ExportManifest {
+        record_count: qsos.len(),
+        first_qso_date: dates.first().cloned(),
+        last_qso_date: dates.last().cloned(),
+        bands,
+        modes: modes.into_iter().collect(),
+        dxcc_count: dxccs.len(),
+        state_count: states.len(),
+        sha256,
+    }
+}
+
+/// ADIF text for exported QSOs in the requested order and grouping
+fn render_export(
+    qsos: Vec<serde_json::Value>,
+    sort: ExportSort,
+    group_by: Option<ExportGroupBy>,
+) -> String {
+    let groups = arrange_export(qsos, sort, group_by);
+    let to_records = |qsos: &[serde_json::Value]| -> Vec<std::collections::HashMap<String, String>> {
+        qsos.iter().map(crate::adif::writer::qso_to_adif).collect()
+    };
 
-    let adif_file = parse_adif(&content)?;
+    if group_by.is_none() {
+        let records = groups.first().map(|(_, q)| to_records(q)).unwrap_or_default();
+        return crate::adif::write_adif(&records, "GoQSO");
+    }
+
+    let grouped: Vec<(String, Vec<std::collections::HashMap<String, String>>)> = groups
+        .iter()
+        .map(|(label, qsos)| (label.clone().unwrap_or_default(), to_records(qsos)))
+        .collect();
+    crate::adif::writer::write_adif_grouped(&grouped, "GoQSO")
+}
+
+/// Fetch QSOs not yet marked as sent to a confirmation source ('LOTW', 'EQSL').
+/// SWL reports are never uploaded: they aren't two-way QSOs.
+pub async fn fetch_pending_uploads(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    source: &str,
+) -> Result<Vec<sqlx::sqlite::SqliteRow>, String> {
+    sqlx::query(
+        r#"
+        SELECT q.* FROM qsos q
+        LEFT JOIN confirmations c ON q.id = c.qso_id AND c.source = ?
+        WHERE (c.id IS NULL OR c.qsl_sent IS NULL OR c.qsl_sent != 'Y')
+          AND q.source IS NOT ?
+        ORDER BY q.qso_date DESC, q.time_on DESC
+        "#,
+    )
+    .bind(source)
+    .bind(SOURCE_SWL)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query pending QSOs: {}", e))
+}
+
+/// Convert QSO rows to ADIF records for upload/export
+pub fn rows_to_adif_records(
+    rows: &[sqlx::sqlite::SqliteRow],
+) -> Vec<std::collections::HashMap<String, String>> {
+    rows.iter()
+        .map(|r| crate::adif::writer::qso_to_adif(&row_to_json(r)))
+        .collect()
+}
+
+/// Convert QSO rows to minimal LoTW records (LoTW fields only, MODE/SUBMODE split)
+pub fn rows_to_lotw_records(
+    rows: &[sqlx::sqlite::SqliteRow],
+) -> Vec<std::collections::HashMap<String, String>> {
+    rows.iter()
+        .map(|r| crate::adif::writer::qso_to_lotw_adif(&row_to_json(r)))
+        .collect()
+}
 
+/// Mark QSOs as sent to a confirmation source
+pub async fn mark_qsos_sent(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    qso_ids: &[i64],
+    source: &str,
+) -> Result<(), String> {
+    let today = chrono::Utc::now().format("%Y%m%d").to_string();
+    for qso_id in qso_ids {
+        sqlx::query(
+            r#"
+            INSERT INTO confirmations (qso_id, source, qsl_sent, qsl_sent_date)
+            VALUES (?, ?, 'Y', ?)
+            ON CONFLICT(qso_id, source) DO UPDATE SET
+                qsl_sent = 'Y',
+                qsl_sent_date = COALESCE(confirmations.qsl_sent_date, excluded.qsl_sent_date)
+            "#,
+        )
+        .bind(qso_id)
+        .bind(source)
+        .bind(&today)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to mark QSO {} as sent: {}", qso_id, e))?;
+    }
+    Ok(())
+}
+
+/// Columns a Replace import merges into the logged QSO. The match key, uuid,
+/// source and created_at stay as logged.
+const MERGE_COLUMNS: &[&str] = &[
+    "qso_date_off", "time_off", "submode", "freq", "freq_hz", "dxcc", "country", "state", "cnty",
+    "gridsquare", "grid_source", "continent", "cqz", "ituz", "rst_sent", "rst_rcvd", "station_callsign",
+    "operator", "my_gridsquare", "tx_pwr", "prop_mode", "sat_name", "iota", "pota_ref",
+    "sota_ref", "wwff_ref", "pfx", "name", "qth", "comment", "arrl_sect", "my_cnty",
+    "my_arrl_sect", "my_sota_ref", "my_pota_ref",
+];
+
+/// ON CONFLICT clause merging non-empty incoming columns over the logged QSO;
+/// a missing or blank incoming field never clears a logged value
+fn merge_clause() -> String {
+    let mut sets: Vec<String> = MERGE_COLUMNS
+        .iter()
+        .map(|c| format!("{c} = CASE WHEN TRIM(COALESCE(excluded.{c}, '')) = '' THEN {c} ELSE excluded.{c} END"))
+        .collect();
+    sets.push("adif_fields = json_patch(COALESCE(adif_fields, '{}'), excluded.adif_fields)".to_string());
+    sets.push("updated_at = excluded.updated_at".to_string());
+    format!(
+        "\n            ON CONFLICT(call, qso_date, time_on, band, mode) DO UPDATE SET {}",
+        sets.join(", ")
+    )
+}
+
+/// Whether two TIME_ON values are the same QSO: within 120 seconds (across
+/// midnight), or the same HHMM when either can't be parsed
+fn same_time_on(a: &str, b: &str) -> bool {
+    match (time_to_seconds(&normalize_time_to_hhmmss(a)), time_to_seconds(&normalize_time_to_hhmmss(b))) {
+        (Some(a), Some(b)) => {
+            let diff = (a as i32 - b as i32).abs();
+            diff.min(86400 - diff) <= 120
+        }
+        _ => extract_hhmm(a) == extract_hhmm(b),
+    }
+}
+
+/// Key (time_on, band, mode) of a logged QSO matching the record: same call and date,
+/// band and mode ignoring case, TIME_ON within 120 seconds
+async fn find_duplicate(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    call: &str,
+    qso_date: &str,
+    band: &str,
+    mode: &str,
+    time_on: &str,
+) -> Option<(String, String, String)> {
+    let existing: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT time_on, band, mode FROM qsos WHERE call = ? AND qso_date = ? AND LOWER(band) = LOWER(?) AND UPPER(mode) = UPPER(?)",
+    )
+    .bind(call)
+    .bind(qso_date)
+    .bind(band)
+    .bind(mode)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    existing.into_iter().find(|(existing_time, _, _)| same_time_on(time_on, existing_time))
+}
+
+/// Merge records from several files into one list with cross-file duplicates
+/// removed, using the same match as the database check. The first copy is
+/// kept and picks up any fields only the later copies have.
+/// Returns the merged records and how many duplicates were dropped.
+pub fn merge_records(
+    files: Vec<Vec<crate::adif::parser::AdifRecord>>,
+) -> (Vec<crate::adif::parser::AdifRecord>, usize) {
+    let slot = |r: &crate::adif::parser::AdifRecord| {
+        (
+            r.get_or("CALL", "").to_uppercase(),
+            r.get_or("QSO_DATE", ""),
+            r.get_or("BAND", "").to_lowercase(),
+            combine_mode(&r.get_or("MODE", ""), r.get("SUBMODE").map(String::as_str)),
+        )
+    };
+
+    let mut merged: Vec<crate::adif::parser::AdifRecord> = Vec::new();
+    let mut by_slot: std::collections::HashMap<(String, String, String, String), Vec<usize>> =
+        std::collections::HashMap::new();
+    let mut dropped = 0;
+
+    for record in files.into_iter().flatten() {
+        let key = slot(&record);
+        let time_on = record.get_or("TIME_ON", "");
+        let indexes = by_slot.entry(key).or_default();
+        match indexes.iter().find(|&&i| same_time_on(&merged[i].get_or("TIME_ON", ""), &time_on)) {
+            Some(&i) => {
+                for (field, value) in record.fields {
+                    if !value.is_empty() {
+                        merged[i].fields.entry(field).or_insert(value);
+                    }
+                }
+                dropped += 1;
+            }
+            None => {
+                indexes.push(merged.len());
+                merged.push(record);
+            }
+        }
+    }
+    (merged, dropped)
+}
+
+/// Whether an imported non-core field goes into adif_fields. APP_ fields are
+/// program-specific: dropped unless `preserve_app_fields`, and never our own
+/// APP_GOQSO_ ones, which map to columns. Kept APP_ fields stay namespaced under
+/// their full name (app_qrzlog_foo) and are written back out on export.
+fn keep_extended_field(key: &str, preserve_app_fields: bool) -> bool {
+    if !key.starts_with("APP_") {
+        return true;
+    }
+    preserve_app_fields && !key.starts_with("APP_GOQSO_")
+}
+
+/// Insert parsed records into the log, shared by the ADIF and Cabrillo imports.
+/// `source` is stored in the qsos.source column; SWL reports (SWL=Y) get source
+/// 'SWL' instead, or are skipped when `skip_swl_import` is on.
+pub async fn import_records(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    records: &[crate::adif::parser::AdifRecord],
+    policy: DuplicatePolicy,
+    source: &str,
+) -> ImportResult {
     let mut result = ImportResult {
-        total_records: adif_file.records.len(),
+        total_records: records.len(),
         imported: 0,
         skipped: 0,
+        updated: 0,
         errors: 0,
         error_messages: Vec::new(),
     };
+    let sanitize = read_bool_setting(pool, SANITIZE_RST_SETTING, true).await;
+    let preserve_app_fields = read_bool_setting(pool, PRESERVE_APP_FIELDS_SETTING, false).await;
+    let skip_swl = read_bool_setting(pool, SKIP_SWL_IMPORT_SETTING, false).await;
 
-    for record in &adif_file.records {
+    for record in records {
         let call = match record.call() {
             Some(c) => c.to_uppercase(),
             None => {
@@ -104,7 +539,8 @@ pub async fn import_adif(
         };
 
         let band = record.get_or("BAND", "").to_uppercase();
-        let mode = record.get_or("MODE", "").to_uppercase();
+        // PSK + PSK31 is stored as PSK31, the way the log shows it
+        let (mode, submode) = import_mode(&record.get_or("MODE", ""), record.get("SUBMODE").map(String::as_str));
         let qso_date = record.get_or("QSO_DATE", "");
         let time_on = record.get_or("TIME_ON", "");
 
@@ -116,6 +552,12 @@ pub async fn import_adif(
             continue;
         }
 
+        let swl = record.is_swl();
+        if swl && skip_swl {
+            result.skipped += 1;
+            continue;
+        }
+
         let time_on_normalized = normalize_time_to_hhmmss(&time_on);
 
         // Validate grid (reject FT8 messages like "RR73")
@@ -123,45 +565,42 @@ pub async fn import_adif(
             .gridsquare()
             .filter(|g| is_valid_grid(g))
             .map(|g| g.to_uppercase());
+        // Our own exports carry the provenance; anything else came with the file
+        let grid_source = gridsquare.as_ref().map(|_| {
+            record
+                .get("APP_GOQSO_GRID_SOURCE")
+                .map(|s| s.to_lowercase())
+                .unwrap_or_else(|| GRID_SOURCE_ADIF.to_string())
+        });
 
-        // Normalize RST values
-        let rst_sent = record.get("RST_SENT").map(|r| normalize_rst(r));
-        let rst_rcvd = record.get("RST_RCVD").map(|r| normalize_rst(r));
+        // Normalize RST values, dropping "RR73"-style acknowledgments if enabled
+        let clean_rst = |field: &str| {
+            let value = record.get(field)?;
+            if sanitize {
+                sanitize_rst(value, &mode, None)
+            } else {
+                Some(normalize_rst(value))
+            }
+        };
+        let rst_sent = clean_rst("RST_SENT");
+        let rst_rcvd = clean_rst("RST_RCVD");
 
         // Check for duplicate using ±120 second window
-        if skip_duplicates {
-            let incoming_seconds = time_to_seconds(&time_on_normalized);
-
-            let existing_times: Vec<String> = sqlx::query_scalar(
-                "SELECT time_on FROM qsos WHERE call = ? AND qso_date = ? AND LOWER(band) = LOWER(?) AND UPPER(mode) = UPPER(?)",
-            )
-            .bind(&call)
-            .bind(&qso_date)
-            .bind(&band)
-            .bind(&mode)
-            .fetch_all(pool)
-            .await
-            .unwrap_or_default();
-
-            let is_duplicate = existing_times.iter().any(|existing_time| {
-                if let (Some(incoming), Some(existing)) =
-                    (incoming_seconds, time_to_seconds(existing_time))
-                {
-                    let diff = (incoming as i32 - existing as i32).abs();
-                    let diff = diff.min(86400 - diff);
-                    diff <= 120
-                } else {
-                    extract_hhmm(&time_on) == extract_hhmm(existing_time)
-                }
-            });
-
-            if is_duplicate {
-                result.skipped += 1;
-                continue;
-            }
+        let duplicate = match policy {
+            DuplicatePolicy::KeepBoth => None,
+            _ => find_duplicate(pool, &call, &qso_date, &band, &mode, &time_on).await,
+        };
+        if duplicate.is_some() && policy == DuplicatePolicy::Skip {
+            result.skipped += 1;
+            continue;
         }
 
-        // Build adif_fields JSON for extended fields
+        // Replace writes onto the logged QSO's key so the insert becomes a merge
+        let (time_on_key, band_key, mode_key) =
+            duplicate.clone().unwrap_or((time_on_normalized, band, mode));
+
+        // Build adif_fields JSON for extended fields; anything stored in its own
+        // column stays out so export writes it once
         let mut adif_fields = serde_json::Map::new();
         for (key, value) in &record.fields {
             let core_fields = [
@@ -187,73 +626,100 @@ pub async fn import_adif(
                 "MY_GRIDSQUARE",
                 "TX_PWR",
                 "OPERATOR",
+                "SUBMODE",
+                "PROP_MODE",
+                "SAT_NAME",
+                "IOTA",
+                "POTA_REF",
+                "SOTA_REF",
+                "WWFF_REF",
+                "PFX",
+                "NAME",
+                "QTH",
+                "COMMENT",
+                "ARRL_SECT",
+                "MY_CNTY",
+                "MY_ARRL_SECT",
+                "MY_SOTA_REF",
+                "MY_POTA_REF",
             ];
-            if !core_fields.contains(&key.as_str()) && !key.starts_with("APP_") {
-                adif_fields.insert(key.to_lowercase(), serde_json::Value::String(value.clone()));
+            if core_fields.contains(&key.as_str()) || !keep_extended_field(key, preserve_app_fields) {
+                continue;
             }
+            // A blank field in a Replace merge would overwrite the logged one
+            if duplicate.is_some() && value.trim().is_empty() {
+                continue;
+            }
+            adif_fields.insert(key.to_lowercase(), serde_json::Value::String(value.clone()));
         }
 
         let uuid = uuid::Uuid::new_v4().to_string();
         let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-        let insert_result = sqlx::query(
+        let upsert = if duplicate.is_some() { merge_clause() } else { String::new() };
+        let insert_sql = format!(
             r#"INSERT INTO qsos (
-                uuid, call, qso_date, qso_date_off, time_on, time_off, band, mode, submode, freq,
-                dxcc, country, state, cnty, gridsquare, continent, cqz, ituz,
+                uuid, call, qso_date, qso_date_off, time_on, time_off, band, mode, submode, freq, freq_hz,
+                dxcc, country, state, cnty, gridsquare, grid_source, continent, cqz, ituz,
                 rst_sent, rst_rcvd, station_callsign, operator, my_gridsquare, tx_pwr,
                 prop_mode, sat_name, iota, pota_ref, sota_ref, wwff_ref, pfx,
                 name, qth, comment, arrl_sect,
                 my_cnty, my_arrl_sect, my_sota_ref, my_pota_ref,
                 adif_fields, source, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
-        )
-        .bind(&uuid)
-        .bind(&call)
-        .bind(qso_date)
-        .bind(record.get("QSO_DATE_OFF"))
-        .bind(&time_on_normalized)
-        .bind(record.get("TIME_OFF"))
-        .bind(&band)
-        .bind(&mode)
-        .bind(record.get("SUBMODE"))
-        .bind(record.freq())
-        .bind(record.dxcc())
-        .bind(record.country())
-        .bind(record.state())
-        .bind(record.cnty())
-        .bind(&gridsquare)
-        .bind(record.get("CONT"))
-        .bind(record.cqz())
-        .bind(record.ituz())
-        .bind(&rst_sent)
-        .bind(&rst_rcvd)
-        .bind(record.get("STATION_CALLSIGN"))
-        .bind(record.get("OPERATOR"))
-        .bind(record.get("MY_GRIDSQUARE"))
-        .bind(record.get("TX_PWR").and_then(|s| s.parse::<f64>().ok()))
-        .bind(record.get("PROP_MODE"))
-        .bind(record.get("SAT_NAME"))
-        .bind(record.get("IOTA"))
-        .bind(record.get("POTA_REF"))
-        .bind(record.get("SOTA_REF"))
-        .bind(record.get("WWFF_REF"))
-        .bind(record.get("PFX"))
-        .bind(record.get("NAME"))
-        .bind(record.get("QTH"))
-        .bind(record.get("COMMENT"))
-        .bind(record.get("ARRL_SECT"))
-        .bind(record.get("MY_CNTY"))
-        .bind(record.get("MY_ARRL_SECT"))
-        .bind(record.get("MY_SOTA_REF"))
-        .bind(record.get("MY_POTA_REF"))
-        .bind(serde_json::to_string(&adif_fields).unwrap_or_default())
-        .bind("ADIF")
-        .bind(&now)
-        .bind(&now)
-        .execute(pool)
-        .await;
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?){}"#,
+            upsert
+        );
+        let insert_result = sqlx::query(&insert_sql)
+            .bind(&uuid)
+            .bind(&call)
+            .bind(qso_date)
+            .bind(record.get("QSO_DATE_OFF"))
+            .bind(&time_on_key)
+            .bind(record.get("TIME_OFF"))
+            .bind(&band_key)
+            .bind(&mode_key)
+            .bind(&submode)
+            .bind(record.freq())
+            .bind(record.freq_hz().map(|hz| hz as i64))
+            .bind(record.dxcc())
+            .bind(record.country())
+            .bind(record.state())
+            .bind(record.cnty())
+            .bind(&gridsquare)
+            .bind(&grid_source)
+            .bind(record.get("CONT"))
+            .bind(record.cqz())
+            .bind(record.ituz())
+            .bind(&rst_sent)
+            .bind(&rst_rcvd)
+            .bind(record.get("STATION_CALLSIGN"))
+            .bind(record.get("OPERATOR"))
+            .bind(record.get("MY_GRIDSQUARE"))
+            .bind(record.get("TX_PWR").and_then(|s| s.parse::<f64>().ok()))
+            .bind(record.get("PROP_MODE"))
+            .bind(record.get("SAT_NAME"))
+            .bind(record.get("IOTA"))
+            .bind(record.get("POTA_REF"))
+            .bind(record.get("SOTA_REF"))
+            .bind(record.get("WWFF_REF"))
+            .bind(record.get("PFX"))
+            .bind(record.get("NAME"))
+            .bind(record.get("QTH"))
+            .bind(record.get("COMMENT"))
+            .bind(record.get("ARRL_SECT"))
+            .bind(record.get("MY_CNTY"))
+            .bind(record.get("MY_ARRL_SECT"))
+            .bind(record.get("MY_SOTA_REF"))
+            .bind(record.get("MY_POTA_REF"))
+            .bind(serde_json::to_string(&adif_fields).unwrap_or_default())
+            .bind(if swl { SOURCE_SWL } else { source })
+            .bind(&now)
+            .bind(&now)
+            .execute(pool)
+            .await;
 
         match insert_result {
+            Ok(_) if duplicate.is_some() => result.updated += 1,
             Ok(_) => result.imported += 1,
             Err(e) => {
                 result.errors += 1;
@@ -265,48 +731,188 @@ pub async fn import_adif(
     }
 
     log::info!(
-        "ADIF import: {} imported, {} skipped, {} errors",
+        "{} import: {} imported, {} updated, {} skipped, {} errors",
+        source,
         result.imported,
+        result.updated,
         result.skipped,
         result.errors
     );
 
-    Ok(result)
+    result
+}
+
+/// Fill DXCC, country, continent and zones from the callsign where a record has none
+fn fill_location_from_call(record: &mut crate::adif::parser::AdifRecord) {
+    if record.dxcc().is_some() {
+        return;
+    }
+    let Some(call) = record.call().cloned() else {
+        return;
+    };
+    let lookup = crate::reference::lookup_call_full(&call);
+    let fields = [
+        ("DXCC", lookup.dxcc_as_i32().map(|d| d.to_string())),
+        ("COUNTRY", lookup.country),
+        ("CONT", lookup.continent),
+        ("CQZ", lookup.cqz.map(|z| z.to_string())),
+        ("ITUZ", lookup.ituz.map(|z| z.to_string())),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            record.fields.entry(key.to_string()).or_insert(value);
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn import_adif(
+    state: tauri::State<'_, AppState>,
+    content: String,
+    skip_duplicates: bool,
+    duplicate_policy: Option<DuplicatePolicy>,
+) -> Result<ImportResult, String> {
+    use crate::adif::parse_adif;
+
+    let import = async {
+        let db_guard = state.db.lock().await;
+        let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+        let adif_file = parse_adif(&content)?;
+        let policy = duplicate_policy.unwrap_or_else(|| DuplicatePolicy::from_skip_duplicates(skip_duplicates));
+        let result = import_records(pool, &adif_file.records, policy, "ADIF").await;
+        state.worked.rebuild_or_log(pool).await;
+        Ok(result)
+    };
+    state.jobs.run(JOB_IMPORT, "ADIF import", import).await
+}
+
+/// Import several ADIF files at once, dropping QSOs that appear in more
+/// than one file before anything is written
+#[command]
+pub async fn import_adif_multi(
+    state: tauri::State<'_, AppState>,
+    contents: Vec<String>,
+    duplicate_policy: Option<DuplicatePolicy>,
+) -> Result<ImportResult, String> {
+    let import = async {
+        let db_guard = state.db.lock().await;
+        let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+        let files = contents
+            .iter()
+            .enumerate()
+            .map(|(i, content)| {
+                crate::adif::parse_adif(content)
+                    .map(|f| f.records)
+                    .map_err(|e| format!("File {}: {}", i + 1, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let total_records = files.iter().map(Vec::len).sum();
+
+        let (records, dropped) = merge_records(files);
+        let mut result = import_records(pool, &records, duplicate_policy.unwrap_or_default(), "ADIF").await;
+        result.total_records = total_records;
+        result.skipped += dropped;
+        state.worked.rebuild_or_log(pool).await;
+
+        log::info!("Merged {} ADIF files: {} cross-file duplicates dropped", contents.len(), dropped);
+        Ok(result)
+    };
+    let label = format!("ADIF import ({} files)", contents.len());
+    state.jobs.run(JOB_IMPORT, &label, import).await
+}
+
+/// Import a Cabrillo contest log
+#[command]
+pub async fn import_cabrillo(
+    state: tauri::State<'_, AppState>,
+    content: String,
+    skip_duplicates: bool,
+    duplicate_policy: Option<DuplicatePolicy>,
+) -> Result<ImportResult, String> {
+    let import = async {
+        let db_guard = state.db.lock().await;
+        let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+        // Cabrillo carries no location data, so look it up from the callsign
+        let mut records = crate::adif::cabrillo::parse_cabrillo(&content)?;
+        for record in &mut records {
+            fill_location_from_call(record);
+        }
+
+        let policy = duplicate_policy.unwrap_or_else(|| DuplicatePolicy::from_skip_duplicates(skip_duplicates));
+        let result = import_records(pool, &records, policy, "CABRILLO").await;
+        state.worked.rebuild_or_log(pool).await;
+        Ok(result)
+    };
+    state.jobs.run(JOB_IMPORT, "Cabrillo import", import).await
 }
 
 #[command]
 pub async fn export_adif(
     state: tauri::State<'_, AppState>,
     qso_ids: Option<Vec<i64>>,
+    sort: Option<ExportSort>,
+    group_by: Option<ExportGroupBy>,
+    unconfirmed_only: Option<bool>,
+    confirmation_source: Option<String>,
 ) -> Result<String, String> {
     let db_guard = state.db.lock().await;
     let pool = db_guard.as_ref().ok_or("Database not initialized")?;
 
-    let qsos: Vec<serde_json::Value> = if let Some(ids) = qso_ids {
-        let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-        let query = format!(
-            "SELECT * FROM qsos WHERE id IN ({}) ORDER BY qso_date DESC, time_on DESC",
-            placeholders
-        );
-        let mut q = sqlx::query(&query);
-        for id in &ids {
-            q = q.bind(id);
-        }
-        let rows = q.fetch_all(pool).await.map_err(|e| e.to_string())?;
-        rows.iter().map(|r| row_to_json(r)).collect()
-    } else {
-        let rows = sqlx::query("SELECT * FROM qsos ORDER BY qso_date DESC, time_on DESC")
-            .fetch_all(pool)
-            .await
-            .map_err(|e| e.to_string())?;
-        rows.iter().map(|r| row_to_json(r)).collect()
-    };
+    let unconfirmed_source = unconfirmed_only
+        .unwrap_or(false)
+        .then_some(confirmation_source.as_deref().filter(|s| !s.trim().is_empty()));
+    let qsos = fetch_export_qsos(pool, qso_ids.as_deref(), unconfirmed_source).await?;
 
-    let records: Vec<std::collections::HashMap<String, String>> = qsos
-        .iter()
-        .map(|q| crate::adif::writer::qso_to_adif(q))
-        .collect();
+    Ok(render_export(qsos, sort.unwrap_or_default(), group_by))
+}
+
+/// Same as export_adif, plus a manifest describing the exported file
+#[command]
+pub async fn export_adif_with_manifest(
+    state: tauri::State<'_, AppState>,
+    qso_ids: Option<Vec<i64>>,
+    sort: Option<ExportSort>,
+    group_by: Option<ExportGroupBy>,
+    unconfirmed_only: Option<bool>,
+    confirmation_source: Option<String>,
+) -> Result<AdifExportWithManifest, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let unconfirmed_source = unconfirmed_only
+        .unwrap_or(false)
+        .then_some(confirmation_source.as_deref().filter(|s| !s.trim().is_empty()));
+    let qsos = fetch_export_qsos(pool, qso_ids.as_deref(), unconfirmed_source).await?;
 
+    let adif = render_export(qsos.clone(), sort.unwrap_or_default(), group_by);
+    let manifest = export_manifest(&qsos, &adif);
+    let manifest = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    Ok(AdifExportWithManifest { adif, manifest })
+}
+
+/// Export QSOs pending LoTW upload (or the given QSOs) as LoTW-ready ADIF
+#[command]
+pub async fn export_adif_for_lotw(
+    state: tauri::State<'_, AppState>,
+    qso_ids: Option<Vec<i64>>,
+) -> Result<String, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let mut rows = fetch_pending_uploads(pool, "LOTW").await?;
+    if let Some(ids) = qso_ids {
+        rows.retain(|r| ids.contains(&r.get::<i64, _>("id")));
+    }
+
+    let records = rows_to_lotw_records(&rows);
+    log::info!("Exported {} QSOs for LoTW", records.len());
     Ok(crate::adif::write_adif(&records, "GoQSO"))
 }
 
@@ -317,20 +923,32 @@ pub async fn import_lotw_confirmations(
 ) -> Result<LotwImportResult, String> {
     use crate::adif::parse_adif;
 
-    let db_guard = state.db.lock().await;
-    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+    let import = async {
+        let db_guard = state.db.lock().await;
+        let pool = db_guard.as_ref().ok_or("Database not initialized")?;
 
-    let adif_file = parse_adif(&content)?;
+        let adif_file = parse_adif(&content)?;
+        import_lotw_records(pool, &adif_file.records).await
+    };
+    state.jobs.run(JOB_IMPORT, "LoTW confirmations import", import).await
+}
 
+/// Apply LoTW confirmations from a lotwreport.adi file. QSOs are matched the
+/// same way as the LoTW download (`find_lotw_match`).
+pub async fn import_lotw_records(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    records: &[crate::adif::parser::AdifRecord],
+) -> Result<LotwImportResult, String> {
     let mut result = LotwImportResult {
-        total_records: adif_file.records.len(),
+        total_records: records.len(),
         matched: 0,
         not_found: 0,
         already_confirmed: 0,
         errors: 0,
     };
+    let tolerance = lotw_match_tolerance(pool).await;
 
-    for record in &adif_file.records {
+    for record in records {
         if !record.is_lotw_confirmed() {
             continue;
         }
@@ -340,32 +958,12 @@ pub async fn import_lotw_confirmations(
             None => continue,
         };
 
-        let band = record.get_or("BAND", "").to_uppercase();
-        let mode = record.get_or("MODE", "").to_uppercase();
+        let band = record.get_or("BAND", "");
         let qso_date = record.get_or("QSO_DATE", "");
         let time_on = record.get_or("TIME_ON", "");
 
-        let matching_qso: Option<(i64,)> = sqlx::query_as(
-            r#"SELECT id FROM qsos 
-               WHERE call = ? AND band = ? AND mode = ? AND qso_date = ?
-               ORDER BY ABS(
-                   CAST(SUBSTR(time_on, 1, 2) AS INTEGER) * 60 + CAST(SUBSTR(time_on, 3, 2) AS INTEGER) -
-                   CAST(SUBSTR(?, 1, 2) AS INTEGER) * 60 - CAST(SUBSTR(?, 3, 2) AS INTEGER)
-               )
-               LIMIT 1"#,
-        )
-        .bind(&call)
-        .bind(&band)
-        .bind(&mode)
-        .bind(qso_date)
-        .bind(&time_on)
-        .bind(&time_on)
-        .fetch_optional(pool)
-        .await
-        .map_err(|e| e.to_string())?;
-
-        let qso_id = match matching_qso {
-            Some((id,)) => id,
+        let qso_id = match find_lotw_match(pool, &call, &band, &qso_date, &time_on, tolerance).await? {
+            Some(id) => id,
             None => {
                 result.not_found += 1;
                 continue;
@@ -394,7 +992,8 @@ pub async fn import_lotw_confirmations(
                ON CONFLICT(qso_id, source) DO UPDATE SET 
                    qsl_rcvd = 'Y', 
                    qsl_rcvd_date = excluded.qsl_rcvd_date,
-                   verified_at = excluded.verified_at"#,
+                   verified_at = excluded.verified_at,
+                   needs_reverify = 0"#,
         )
         .bind(qso_id)
         .bind(qslrdate)
@@ -423,8 +1022,9 @@ pub async fn import_lotw_confirmations(
                 .await;
         }
         if let Some(grid) = record.gridsquare() {
-            let _ = sqlx::query("UPDATE qsos SET gridsquare = COALESCE(gridsquare, ?) WHERE id = ?")
+            let _ = sqlx::query("UPDATE qsos SET gridsquare = ?, grid_source = ? WHERE id = ? AND gridsquare IS NULL")
                 .bind(grid)
+                .bind(GRID_SOURCE_LOTW)
                 .bind(qso_id)
                 .execute(pool)
                 .await;
@@ -440,3 +1040,531 @@ pub async fn import_lotw_confirmations(
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    fn qso(call: &str, date: &str, time: &str, band: &str, dxcc: Option<i64>) -> serde_json::Value {
+        serde_json::json!({
+            "call": call, "qso_date": date, "time_on": time, "band": band, "dxcc": dxcc,
+        })
+    }
+
+    fn calls(qsos: &[serde_json::Value]) -> Vec<&str> {
+        qsos.iter().map(|q| q["call"].as_str().unwrap()).collect()
+    }
+
+    fn sample() -> Vec<serde_json::Value> {
+        vec![
+            qso("B", "20260102", "0900", "20m", Some(339)),
+            qso("A", "20260101", "235900", "40m", Some(291)),
+            qso("D", "20260103", "1200", "20m", None),
+            qso("C", "20260102", "1000", "160m", Some(339)),
+        ]
+    }
+
+    #[test]
+    fn test_export_sort_date_desc_default() {
+        let groups = arrange_export(sample(), ExportSort::default(), None);
+        assert_eq!(groups.len(), 1);
+        assert!(groups[0].0.is_none());
+        assert_eq!(calls(&groups[0].1), ["D", "C", "B", "A"]);
+    }
+
+    #[test]
+    fn test_export_sort_date_asc() {
+        let groups = arrange_export(sample(), ExportSort::DateAsc, None);
+        assert_eq!(calls(&groups[0].1), ["A", "B", "C", "D"]);
+    }
+
+    #[test]
+    fn test_export_group_by_band() {
+        let groups = arrange_export(sample(), ExportSort::DateAsc, Some(ExportGroupBy::Band));
+        let labels: Vec<_> = groups.iter().map(|(l, _)| l.clone().unwrap()).collect();
+        assert_eq!(labels, ["Band 160m", "Band 40m", "Band 20m"]);
+        assert_eq!(calls(&groups[2].1), ["B", "D"]);
+    }
+
+    #[test]
+    fn test_export_group_by_dxcc() {
+        let groups = arrange_export(sample(), ExportSort::DateDesc, Some(ExportGroupBy::Dxcc));
+        let labels: Vec<_> = groups.iter().map(|(l, _)| l.clone().unwrap()).collect();
+        assert_eq!(labels, ["DXCC 291", "DXCC 339", "DXCC unknown"]);
+        assert_eq!(calls(&groups[1].1), ["C", "B"]);
+    }
+
+    /// Test: Cabrillo records go through the ADIF insert path with exchange kept in adif_fields
+    #[tokio::test]
+    async fn test_import_cabrillo_records() {
+        let pool = test_pool().await;
+
+        let log = "CONTEST: CQ-WW-CW\n\
+                   QSO: 14025 CW 2026-11-28 1200 W1AW 599 05 JA1ABC 599 25\n\
+                   QSO: 14025 CW 2026-11-28 1200 W1AW 599 05 JA1ABC 599 25\n";
+        let mut records = crate::adif::cabrillo::parse_cabrillo(log).unwrap();
+        for record in &mut records {
+            fill_location_from_call(record);
+        }
+
+        let result = import_records(&pool, &records, DuplicatePolicy::Skip, "CABRILLO").await;
+        assert_eq!((result.imported, result.skipped, result.errors), (1, 1, 0));
+
+        let (dxcc, source, adif_fields): (Option<i64>, String, String) =
+            sqlx::query_as("SELECT dxcc, source, adif_fields FROM qsos WHERE call = 'JA1ABC'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(dxcc, Some(339));
+        assert_eq!(source, "CABRILLO");
+        let fields: serde_json::Value = serde_json::from_str(&adif_fields).unwrap();
+        assert_eq!(fields["srx_string"], "25");
+        assert_eq!(fields["contest_id"], "CQ-WW-CW");
+    }
+
+    /// Test: A QSO present in two files is inserted once, with fields from both
+    #[tokio::test]
+    async fn test_import_multi_merges_overlap() {
+        let pool = test_pool().await;
+
+        let file_a = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:6>120000<BAND:3>20m<MODE:3>FT8<EOR>\
+             <CALL:5>G0ABC<QSO_DATE:8>20260110<TIME_ON:4>1300<BAND:3>40m<MODE:3>FT8<EOR>",
+        )
+        .unwrap();
+        // Same JA1ABC QSO from another tool: upper-case band, seconds differ, has a grid
+        let file_b = crate::adif::parse_adif(
+            "<CALL:6>ja1abc<QSO_DATE:8>20260110<TIME_ON:6>120015<BAND:3>20M<MODE:3>FT8<GRIDSQUARE:4>PM95<EOR>\
+             <CALL:5>K1ABC<QSO_DATE:8>20260110<TIME_ON:4>1400<BAND:3>20m<MODE:2>CW<EOR>",
+        )
+        .unwrap();
+
+        let (records, dropped) = merge_records(vec![file_a.records, file_b.records]);
+        assert_eq!((records.len(), dropped), (3, 1));
+        assert_eq!(records[0].get("GRIDSQUARE").map(String::as_str), Some("PM95"));
+
+        let result = import_records(&pool, &records, DuplicatePolicy::KeepBoth, "ADIF").await;
+        assert_eq!(result.imported, 3);
+        let ja: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM qsos WHERE call = 'JA1ABC'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(ja, 1);
+    }
+
+    /// Test: Imported grids are marked as from the file unless our own export says otherwise
+    #[tokio::test]
+    async fn test_import_grid_source() {
+        let pool = test_pool().await;
+
+        let file = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:4>1200<BAND:3>20m<MODE:3>FT8<GRIDSQUARE:4>PM95<EOR>\
+             <CALL:5>K5ABC<QSO_DATE:8>20260110<TIME_ON:4>1300<BAND:3>20m<MODE:3>FT8<GRIDSQUARE:4>EM12\
+             <APP_GOQSO_GRID_SOURCE:9>prior_qso<EOR>\
+             <CALL:5>G0XYZ<QSO_DATE:8>20260110<TIME_ON:4>1400<BAND:3>20m<MODE:3>FT8<EOR>",
+        )
+        .unwrap();
+        let result = import_records(&pool, &file.records, DuplicatePolicy::Skip, "ADIF").await;
+        assert_eq!(result.imported, 3);
+
+        let sources: Vec<(String, Option<String>)> =
+            sqlx::query_as("SELECT call, grid_source FROM qsos ORDER BY call")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            sources,
+            vec![
+                ("G0XYZ".to_string(), None),
+                ("JA1ABC".to_string(), Some("adif".to_string())),
+                ("K5ABC".to_string(), Some("prior_qso".to_string())),
+            ]
+        );
+    }
+
+    /// Test: APP_ fields from other loggers are dropped by default and kept,
+    /// namespaced, with preserve_app_fields on
+    #[tokio::test]
+    async fn test_import_preserves_app_fields() {
+        let pool = test_pool().await;
+
+        let adif = "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:4>1200<BAND:3>20m<MODE:3>FT8\
+                    <APP_FOO:3>bar<APP_GOQSO_GRID_SOURCE:3>fcc<CONTEST_ID:4>TEST<EOR>";
+        let fields_after_import = |pool: sqlx::Pool<sqlx::Sqlite>| async move {
+            let records = crate::adif::parse_adif(adif).unwrap().records;
+            sqlx::query("DELETE FROM qsos").execute(&pool).await.unwrap();
+            assert_eq!(import_records(&pool, &records, DuplicatePolicy::Skip, "ADIF").await.imported, 1);
+            let json: String = sqlx::query_scalar("SELECT adif_fields FROM qsos")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            serde_json::from_str::<serde_json::Value>(&json).unwrap()
+        };
+
+        let fields = fields_after_import(pool.clone()).await;
+        assert_eq!(fields["contest_id"], "TEST");
+        assert!(fields.get("app_foo").is_none());
+
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'true', datetime('now'))")
+            .bind(PRESERVE_APP_FIELDS_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let fields = fields_after_import(pool.clone()).await;
+        assert_eq!(fields["app_foo"], "bar");
+        assert!(fields.get("app_goqso_grid_source").is_none());
+
+        // Round trip: the kept field is written back out under its own name
+        let qso = &fetch_export_qsos(&pool, None, None).await.unwrap()[0];
+        assert_eq!(crate::adif::writer::qso_to_adif(qso)["APP_FOO"], "bar");
+    }
+
+    /// Test: exporting, re-importing and exporting a P2P QSO tags its COMMENT once
+    #[tokio::test]
+    async fn test_p2p_comment_round_trip() {
+        let mut adif = "<CALL:5>K1ABC<QSO_DATE:8>20260110<TIME_ON:6>120000<BAND:3>20m<MODE:2>CW\
+                        <POTA_REF:7>US-0001<MY_POTA_REF:7>US-4567<COMMENT:4>tnx!<EOR>"
+            .to_string();
+        let mut comments = Vec::new();
+        for _ in 0..3 {
+            let pool = test_pool().await;
+            let file = crate::adif::parse_adif(&adif).unwrap();
+            assert_eq!(import_records(&pool, &file.records, DuplicatePolicy::Skip, "ADIF").await.imported, 1);
+            let qsos = fetch_export_qsos(&pool, None, None).await.unwrap();
+            let record = crate::adif::writer::qso_to_adif(&qsos[0]);
+            comments.push(record["COMMENT"].clone());
+            adif = crate::adif::writer::write_adif(&[record], "GoQSO");
+        }
+        assert_eq!(comments, ["tnx! P2P"; 3]);
+    }
+
+    /// Import JA1ABC once, then the same QSO 30 seconds later with a grid and comment
+    async fn import_twice(policy: DuplicatePolicy) -> (sqlx::Pool<sqlx::Sqlite>, ImportResult) {
+        let pool = test_pool().await;
+
+        let original = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:6>120000<BAND:3>20m<MODE:3>FT8<RST_SENT:3>-10<EOR>",
+        )
+        .unwrap();
+        import_records(&pool, &original.records, DuplicatePolicy::Skip, "ADIF").await;
+
+        let corrected = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:6>120030<BAND:3>20M<MODE:3>FT8\
+             <GRIDSQUARE:4>PM95<COMMENT:5>fixed<EOR>",
+        )
+        .unwrap();
+        let result = import_records(&pool, &corrected.records, policy, "ADIF").await;
+        (pool, result)
+    }
+
+    #[tokio::test]
+    async fn test_import_duplicate_skip() {
+        let (pool, result) = import_twice(DuplicatePolicy::Skip).await;
+        assert_eq!((result.imported, result.updated, result.skipped), (0, 0, 1));
+
+        let grid: Option<String> = sqlx::query_scalar("SELECT gridsquare FROM qsos").fetch_one(&pool).await.unwrap();
+        assert_eq!(grid, None);
+    }
+
+    #[tokio::test]
+    async fn test_import_duplicate_replace_merges_fields() {
+        let (pool, result) = import_twice(DuplicatePolicy::Replace).await;
+        assert_eq!((result.imported, result.updated, result.skipped, result.errors), (0, 1, 0, 0));
+
+        // Logged key and RST kept, incoming grid and comment merged in
+        let rows: Vec<(String, String, Option<String>, Option<String>, Option<String>, Option<String>)> =
+            sqlx::query_as("SELECT time_on, band, gridsquare, grid_source, comment, rst_sent FROM qsos")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(rows.len(), 1);
+        let (time_on, band, grid, grid_source, comment, rst_sent) = &rows[0];
+        assert_eq!((time_on.as_str(), band.as_str()), ("120000", "20M"));
+        assert_eq!(grid.as_deref(), Some("PM95"));
+        assert_eq!(grid_source.as_deref(), Some("adif"));
+        assert_eq!(comment.as_deref(), Some("fixed"));
+        assert_eq!(rst_sent.as_deref(), Some("-10"));
+    }
+
+    /// Test: Blank fields in a Replace import leave the logged values alone
+    #[tokio::test]
+    async fn test_import_duplicate_replace_keeps_values_over_blanks() {
+        let (pool, _) = import_twice(DuplicatePolicy::Replace).await;
+        sqlx::query("UPDATE qsos SET adif_fields = '{\"contest_id\":\"CQ-WW\"}'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let blank = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:6>120000<BAND:3>20m<MODE:3>FT8\
+             <GRIDSQUARE:0><COMMENT:1> <RST_SENT:2>-5<CONTEST_ID:0><EOR>",
+        )
+        .unwrap();
+        let result = import_records(&pool, &blank.records, DuplicatePolicy::Replace, "ADIF").await;
+        assert_eq!(result.updated, 1);
+
+        let (grid, comment, rst_sent, adif_fields): (Option<String>, Option<String>, Option<String>, String) =
+            sqlx::query_as("SELECT gridsquare, comment, rst_sent, adif_fields FROM qsos")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(grid.as_deref(), Some("PM95"));
+        assert_eq!(comment.as_deref(), Some("fixed"));
+        assert_eq!(rst_sent.as_deref(), Some("-05"));
+        assert_eq!(adif_fields, r#"{"contest_id":"CQ-WW"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_import_drops_acknowledgment_rst() {
+        let pool = test_pool().await;
+
+        let adif = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:6>120000<BAND:3>20m<MODE:3>FT8\
+             <RST_SENT:4>RR73<RST_RCVD:2>-9<EOR>",
+        )
+        .unwrap();
+        import_records(&pool, &adif.records, DuplicatePolicy::Skip, "ADIF").await;
+
+        let (rst_sent, rst_rcvd): (Option<String>, Option<String>) =
+            sqlx::query_as("SELECT rst_sent, rst_rcvd FROM qsos").fetch_one(&pool).await.unwrap();
+        assert_eq!(rst_sent, None);
+        assert_eq!(rst_rcvd.as_deref(), Some("-09"));
+    }
+
+    #[tokio::test]
+    async fn test_import_duplicate_keep_both() {
+        let (pool, result) = import_twice(DuplicatePolicy::KeepBoth).await;
+        assert_eq!((result.imported, result.updated, result.skipped), (1, 0, 0));
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM qsos").fetch_one(&pool).await.unwrap();
+        assert_eq!(count, 2);
+    }
+
+    /// Test: The manifest matches the records actually written
+    #[tokio::test]
+    async fn test_export_manifest_counts() {
+        let pool = test_pool().await;
+
+        let file = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:4>1200<BAND:3>20m<MODE:3>FT8<DXCC:3>339<EOR>\
+             <CALL:4>W1AW<QSO_DATE:8>20260301<TIME_ON:4>1300<BAND:3>40m<MODE:2>CW<DXCC:3>291<STATE:2>CT<EOR>\
+             <CALL:5>K5ABC<QSO_DATE:8>20260205<TIME_ON:4>1400<BAND:3>20m<MODE:3>FT8<DXCC:3>291<STATE:2>TX<EOR>",
+        )
+        .unwrap();
+        import_records(&pool, &file.records, DuplicatePolicy::Skip, "ADIF").await;
+
+        let qsos = fetch_export_qsos(&pool, None, None).await.unwrap();
+        let adif = render_export(qsos.clone(), ExportSort::DateAsc, None);
+        let manifest = export_manifest(&qsos, &adif);
+
+        assert_eq!(manifest.record_count, crate::adif::parse_adif(&adif).unwrap().records.len());
+        assert_eq!(manifest.record_count, 3);
+        assert_eq!(manifest.first_qso_date.as_deref(), Some("20260110"));
+        assert_eq!(manifest.last_qso_date.as_deref(), Some("20260301"));
+        assert_eq!(manifest.bands, vec!["40m", "20m"]);
+        assert_eq!(manifest.modes, vec!["CW", "FT8"]);
+        assert_eq!((manifest.dxcc_count, manifest.state_count), (2, 2));
+        assert_eq!(manifest.sha256.len(), 64);
+        assert_eq!(manifest.sha256, export_manifest(&qsos, &adif).sha256);
+        assert_ne!(manifest.sha256, export_manifest(&qsos, &adif.replace("JA1ABC", "JA1ABD")).sha256);
+    }
+
+    /// Test: PSK submodes survive an import/export round trip, RTTY stays RTTY
+    #[tokio::test]
+    async fn test_submode_round_trip() {
+        let pool = test_pool().await;
+
+        let adif = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:4>1200<BAND:3>20m<MODE:3>PSK<SUBMODE:5>PSK63<EOR>\
+             <CALL:5>G0ABC<QSO_DATE:8>20260110<TIME_ON:4>1300<BAND:3>20m<MODE:5>PSK31<EOR>\
+             <CALL:5>K1ABC<QSO_DATE:8>20260110<TIME_ON:4>1400<BAND:3>20m<MODE:4>RTTY<EOR>",
+        )
+        .unwrap();
+        let result = import_records(&pool, &adif.records, DuplicatePolicy::Skip, "ADIF").await;
+        assert_eq!(result.imported, 3);
+
+        let rows = sqlx::query("SELECT * FROM qsos ORDER BY time_on").fetch_all(&pool).await.unwrap();
+        let stored: Vec<(String, Option<String>)> = rows.iter().map(|r| (r.get("mode"), r.get("submode"))).collect();
+        assert_eq!(
+            stored,
+            [
+                ("PSK63".to_string(), Some("PSK63".to_string())),
+                ("PSK31".to_string(), Some("PSK31".to_string())),
+                ("RTTY".to_string(), None),
+            ]
+        );
+
+        let exported: Vec<(String, Option<String>)> = rows_to_adif_records(&rows)
+            .into_iter()
+            .map(|r| (r["MODE"].clone(), r.get("SUBMODE").cloned()))
+            .collect();
+        assert_eq!(
+            exported,
+            [
+                ("PSK".to_string(), Some("PSK63".to_string())),
+                ("PSK".to_string(), Some("PSK31".to_string())),
+                ("RTTY".to_string(), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_unconfirmed_only() {
+        let pool = test_pool().await;
+
+        for (id, call) in [(1, "JA1ABC"), (2, "G0XYZ"), (3, "VK2ABC")] {
+            insert_qso(call).id(id).insert(&pool).await;
+        }
+        // JA confirmed on LoTW, VK only by card
+        for (qso_id, source) in [(1, "LOTW"), (3, "CARD")] {
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, ?, 'Y')")
+                .bind(qso_id)
+                .bind(source)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let all = fetch_export_qsos(&pool, None, None).await.unwrap();
+        assert_eq!(all.len(), 3);
+
+        let unconfirmed = fetch_export_qsos(&pool, None, Some(None)).await.unwrap();
+        assert_eq!(calls(&unconfirmed), ["G0XYZ"]);
+
+        let mut no_lotw = fetch_export_qsos(&pool, None, Some(Some("lotw"))).await.unwrap();
+        no_lotw.sort_by_key(|q| q["id"].as_i64());
+        assert_eq!(calls(&no_lotw), ["G0XYZ", "VK2ABC"]);
+
+        // Combines with an explicit selection
+        let selected = fetch_export_qsos(&pool, Some(&[1, 3]), Some(Some("LOTW"))).await.unwrap();
+        assert_eq!(calls(&selected), ["VK2ABC"]);
+    }
+
+    /// Test: test QSOs are exported unless exclude_test_data is on
+    #[tokio::test]
+    async fn test_export_excludes_test_data() {
+        let pool = test_pool().await;
+
+        for (id, call, source) in [(1, "JA1ABC", "WSJTX"), (2, "G0XYZ", "TEST")] {
+            insert_qso(call).id(id).source(source).insert(&pool).await;
+        }
+        assert_eq!(fetch_export_qsos(&pool, None, None).await.unwrap().len(), 2);
+
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'true', datetime('now'))")
+            .bind(crate::commands::settings::EXCLUDE_TEST_DATA_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(calls(&fetch_export_qsos(&pool, None, None).await.unwrap()), ["JA1ABC"]);
+        // Also applies to an explicit selection
+        assert!(fetch_export_qsos(&pool, Some(&[2]), None).await.unwrap().is_empty());
+    }
+
+    /// Test: SWL reports are logged as source 'SWL', left out of DXCC, or skipped by setting
+    #[tokio::test]
+    async fn test_import_swl_report() {
+        let pool = test_pool().await;
+
+        let file = crate::adif::parse_adif(
+            "<EOH>\n\
+             <CALL:6>JA1ABC<QSO_DATE:8>20260101<TIME_ON:4>1200<BAND:3>20m<MODE:3>FT8<DXCC:3>339<EOR>\n\
+             <CALL:6>VK2XYZ<QSO_DATE:8>20260101<TIME_ON:4>1300<BAND:3>20m<MODE:3>SSB<DXCC:3>150<SWL:1>Y<RST_RCVD:2>55<EOR>\n",
+        )
+        .unwrap();
+        let result = import_records(&pool, &file.records, DuplicatePolicy::Skip, "ADIF").await;
+        assert_eq!((result.imported, result.skipped), (2, 0));
+
+        let sources: Vec<(String, String)> = sqlx::query_as("SELECT call, source FROM qsos ORDER BY call")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(sources, [("JA1ABC".to_string(), "ADIF".to_string()), ("VK2XYZ".to_string(), "SWL".to_string())]);
+
+        let reports = crate::commands::qso::swl_reports(&pool).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!((reports[0].call.as_str(), reports[0].rst_rcvd.as_deref()), ("VK2XYZ", Some("55")));
+        // Only the two-way QSO counts toward DXCC
+        assert_eq!(crate::commands::awards::dxcc_progress(&pool, None, None).await.worked, 1);
+
+        sqlx::query("DELETE FROM qsos").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'true', datetime('now'))")
+            .bind(SKIP_SWL_IMPORT_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let result = import_records(&pool, &file.records, DuplicatePolicy::Skip, "ADIF").await;
+        assert_eq!((result.imported, result.skipped), (1, 1));
+    }
+
+    /// Test: SWL reports are held back from LoTW/eQSL uploads and exports
+    #[tokio::test]
+    async fn test_swl_report_not_uploaded() {
+        let pool = test_pool().await;
+
+        let file = crate::adif::parse_adif(
+            "<EOH>\n\
+             <CALL:6>JA1ABC<QSO_DATE:8>20260101<TIME_ON:4>1200<BAND:3>20m<MODE:3>FT8<EOR>\n\
+             <CALL:6>VK2XYZ<QSO_DATE:8>20260101<TIME_ON:4>1300<BAND:3>20m<MODE:3>SSB<SWL:1>Y<EOR>\n",
+        )
+        .unwrap();
+        import_records(&pool, &file.records, DuplicatePolicy::Skip, "ADIF").await;
+
+        for source in ["LOTW", "EQSL"] {
+            let pending = fetch_pending_uploads(&pool, source).await.unwrap();
+            let pending: Vec<String> = pending.iter().map(|r| r.get("call")).collect();
+            assert_eq!(pending, ["JA1ABC"], "{}", source);
+        }
+        assert_eq!(calls(&fetch_export_qsos(&pool, None, None).await.unwrap()), ["JA1ABC"]);
+    }
+
+    /// Test: the lotwreport.adi import matches 4- and 6-char times like the LoTW download
+    #[tokio::test]
+    async fn test_lotw_file_import_matches_hhmm_times() {
+        let pool = test_pool().await;
+        for (call, time_on) in [("JA1ABC", "123045"), ("G0XYZ", "1400"), ("K5ABC", "150000")] {
+            insert_qso(call).date("20260115").time(time_on).insert(&pool).await;
+        }
+
+        let adif = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC <BAND:3>20M <MODE:4>MFSK <SUBMODE:3>FT4 <QSO_DATE:8>20260115 <TIME_ON:4>1230 <QSL_RCVD:1>Y <EOR>\n\
+             <CALL:5>G0XYZ <BAND:3>20M <MODE:3>FT8 <QSO_DATE:8>20260115 <TIME_ON:6>140012 <QSL_RCVD:1>Y <EOR>\n\
+             <CALL:5>K5ABC <BAND:3>20M <MODE:3>FT8 <QSO_DATE:8>20260115 <TIME_ON:4>1510 <QSL_RCVD:1>Y <EOR>\n",
+        )
+        .unwrap();
+        let result = import_lotw_records(&pool, &adif.records).await.unwrap();
+        assert_eq!((result.matched, result.not_found), (2, 1));
+    }
+
+    /// Test: an imported FREQ keeps its exact Hz value through the log and back out
+    #[tokio::test]
+    async fn test_freq_hz_round_trip() {
+        let pool = test_pool().await;
+
+        let file = crate::adif::parse_adif(
+            "<CALL:6>JA1ABC<QSO_DATE:8>20260110<TIME_ON:4>1200<BAND:3>20m<MODE:3>FT8<FREQ:9>14.074123<EOR>\
+             <CALL:5>K5ABC<QSO_DATE:8>20260110<TIME_ON:4>1300<BAND:3>60m<MODE:3>USB<FREQ:6>5.3515<EOR>",
+        )
+        .unwrap();
+        assert_eq!(import_records(&pool, &file.records, DuplicatePolicy::Skip, "ADIF").await.imported, 2);
+
+        let stored: Vec<(String, i64)> = sqlx::query_as("SELECT call, freq_hz FROM qsos ORDER BY call")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, [("JA1ABC".to_string(), 14_074_123), ("K5ABC".to_string(), 5_351_500)]);
+
+        let mut exported: Vec<(String, String)> = fetch_export_qsos(&pool, None, None)
+            .await
+            .unwrap()
+            .iter()
+            .map(crate::adif::writer::qso_to_adif)
+            .map(|r| (r["CALL"].clone(), r["FREQ"].clone()))
+            .collect();
+        exported.sort();
+        assert_eq!(
+            exported,
+            [("JA1ABC".to_string(), "14.074123".to_string()), ("K5ABC".to_string(), "5.351500".to_string())]
+        );
+    }
+}
@@ -3,11 +3,37 @@
 //! This module handles:
 //! - get_dxcc_progress: DXCC worked/confirmed counts
 //! - get_was_progress: WAS (Worked All States) progress
+//! - get_dxcc_by_mode_group: DXCC Phone, CW and Digital counted separately
+//! - get_wwff_progress: WWFF (World Wide Flora & Fauna) references worked/activated
+//! - get_vucc_progress: VUCC grids worked/confirmed on 6m and up, or on one band
+//! - get_all_award_progress: Every award enabled in `enabled_awards`, in one call
+//! - take_award_snapshot: Record today's worked/confirmed counts for every award
+//! - get_award_history: Recorded snapshots of one award, oldest first
+//! - get_next_milestones: Confirmations still needed for each enabled award's next level
+//! - get_entity_band_mode_matrix: Band/mode slots worked for a single DXCC entity
+//! - get_grid_map_data: Worked/confirmed status of every grid around a center grid
+//! - get_subdivision_progress: Prefectures/DOKs/oblasts worked and confirmed for one DXCC entity
+//! - get_dxcc_application: One confirmed proof QSO per DXCC entity, for the award application
+//! - get_was_application: One confirmed proof QSO per state, for the award application
+//! - export_adif_for_award: The application's proof QSOs as ADIF, with their confirmations
+//! - get_granted_credits: Awards LoTW has officially granted credit for, next to our own counts
+//! - get_confirmation_reconciliation: DXCC entities we count as confirmed vs those LoTW has granted
+//! - get_last_contact_per_entity / get_last_contact_for_entity: Most recent QSO with each DXCC entity
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+use std::collections::{BTreeMap, BTreeSet};
 use tauri::command;
 
+use super::settings::{read_bool_setting, read_setting, counted_qso_filter};
 use super::state::AppState;
+use crate::adif::bands::{band_sort_index, BAND_ORDER};
+use crate::adif::modes::{mode_group_sql, ModeGroup};
+use crate::awards::grid_map::{self, GridPrecision};
+use crate::awards::subdivisions::subdivision_key;
+use crate::awards::wwff;
+use crate::reference::dxcc::get_current_entity_count;
+use crate::reference::states::{get_us_state, US_STATES};
 
 // ============================================================================
 // Data Types
@@ -29,115 +55,2238 @@ pub struct WasProgress {
     pub confirmed_states: Vec<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct WwffPrefixGroup {
+    pub prefix: String,
+    pub references: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WwffProgress {
+    pub worked: i64,
+    pub activated: i64,
+    pub worked_by_prefix: Vec<WwffPrefixGroup>,
+    pub activated_by_prefix: Vec<WwffPrefixGroup>,
+}
+
+/// DXCC progress within one mode award (DXCC Phone, CW or Digital)
+#[derive(Debug, Serialize)]
+pub struct DxccModeGroupProgress {
+    /// PHONE, CW or DIGITAL
+    pub mode_group: String,
+    pub worked: i64,
+    pub confirmed: i64,
+    pub total: i64,
+}
+
+/// Worked/confirmed counts toward a fixed total (WAC continents, WAZ zones)
+#[derive(Debug, Serialize)]
+pub struct CountProgress {
+    pub worked: i64,
+    pub confirmed: i64,
+    pub total: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VuccProgress {
+    pub worked: i64,
+    pub confirmed: i64,
+    pub target: i64,
+    pub band: Option<String>,
+}
+
+/// One award's entry in get_all_award_progress, serialized as that award's own shape
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum AwardProgress {
+    Dxcc(DxccProgress),
+    Was(WasProgress),
+    Count(CountProgress),
+    Vucc(VuccProgress),
+    Wwff(WwffProgress),
+}
+
+impl AwardProgress {
+    /// Worked and confirmed counts, None for WWFF (worked/activated, no confirmations)
+    pub fn worked_confirmed(&self) -> Option<(i64, i64)> {
+        match self {
+            AwardProgress::Dxcc(p) => Some((p.worked, p.confirmed)),
+            AwardProgress::Was(p) => Some((p.worked, p.confirmed)),
+            AwardProgress::Count(p) => Some((p.worked, p.confirmed)),
+            AwardProgress::Vucc(p) => Some((p.worked, p.confirmed)),
+            AwardProgress::Wwff(_) => None,
+        }
+    }
+}
+
+/// An award's worked/confirmed counts at one point in time
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AwardSnapshot {
+    pub award: String,
+    pub worked: i64,
+    pub confirmed: i64,
+    pub taken_at: String,
+}
+
+/// The next level of an award and how far away it is, e.g. 7 more states for WAS
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NextMilestone {
+    pub award: String,
+    /// Level name, e.g. "DXCC 150" or "Honor Roll"
+    pub level: String,
+    pub confirmed: i64,
+    pub threshold: i64,
+    pub remaining: i64,
+}
+
+/// One band/mode slot worked for a DXCC entity
+#[derive(Debug, Serialize)]
+pub struct EntitySlot {
+    pub band: String,
+    pub mode: String,
+    pub qso_count: i64,
+    pub confirmed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntityBandModeMatrix {
+    pub dxcc: i32,
+    pub entity_name: Option<String>,
+    pub slots: Vec<EntitySlot>,
+    pub bands_worked: Vec<String>,
+    pub bands_confirmed: Vec<String>,
+}
+
+/// One cell of the grid chase map
+#[derive(Debug, Serialize)]
+pub struct GridMapCell {
+    pub grid: String,
+    pub row: usize,
+    pub col: usize,
+    pub worked: bool,
+    pub confirmed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GridMapData {
+    pub center: String,
+    pub precision: usize,
+    pub radius: i32,
+    pub rows: usize,
+    pub cols: usize,
+    pub worked: usize,
+    pub confirmed: usize,
+    /// Cells north-to-south, west-to-east; cells beyond the poles are omitted
+    pub cells: Vec<GridMapCell>,
+}
+
+/// Worked/confirmed status of one subdivision (prefecture, DOK, oblast)
+#[derive(Debug, Serialize)]
+pub struct SubdivisionStatus {
+    pub code: String,
+    pub name: String,
+    pub worked: bool,
+    pub confirmed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubdivisionProgress {
+    pub dxcc: i32,
+    pub entity_name: Option<String>,
+    pub total: i64,
+    pub worked: i64,
+    pub confirmed: i64,
+    pub subdivisions: Vec<SubdivisionStatus>,
+}
+
+/// The QSO submitted as proof for one award slot (an entity or a state)
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AwardProofQso {
+    /// DXCC entity ID or state abbreviation
+    pub slot: String,
+    /// Entity or state name as printed on the record sheet
+    #[sqlx(skip)]
+    pub slot_name: String,
+    pub qso_id: i64,
+    pub call: String,
+    pub qso_date: String,
+    pub time_on: String,
+    pub band: String,
+    pub mode: String,
+    pub confirmation_source: String,
+    pub confirmation_date: Option<String>,
+}
+
+/// Award with an application record sheet
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AwardType {
+    Dxcc,
+    Was,
+}
+
+/// LoTW's official credit for one award alongside what its confirmations earn locally
+#[derive(Debug, Serialize, PartialEq)]
+pub struct GrantedCredit {
+    /// Award code from APP_LOTW_CREDIT_GRANTED, e.g. "DXCC_BAND"
+    pub award: String,
+    /// QSOs LoTW has applied to this award
+    pub granted_qsos: i64,
+    /// Distinct slots granted: entities, entity/band pairs, states, ...
+    pub granted_slots: i64,
+    /// Slots our LoTW confirmations earn by local rules; None for awards we don't compute
+    pub confirmed_slots: Option<i64>,
+}
+
+/// A DXCC entity on one side of the reconciliation only
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReconciledEntity {
+    pub dxcc: i32,
+    pub name: String,
+}
+
+/// Our confirmed DXCC count against LoTW's granted DXCC credit
+#[derive(Debug, Serialize)]
+pub struct ConfirmationReconciliation {
+    /// Entities confirmed by accepted sources (the get_dxcc_progress count)
+    pub confirmed: i64,
+    /// Entities with DXCC in APP_LOTW_CREDIT_GRANTED
+    pub granted: i64,
+    /// Confirmed here but not (yet) granted by LoTW: pending credit, card or eQSL only
+    pub confirmed_not_granted: Vec<ReconciledEntity>,
+    /// Granted by LoTW but not counted here: missing entity on the QSO, source not accepted
+    pub granted_not_confirmed: Vec<ReconciledEntity>,
+}
+
+/// The most recent QSO with one DXCC entity (for QSL routing)
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct EntityLastContact {
+    pub dxcc: i32,
+    #[sqlx(skip)]
+    pub entity_name: String,
+    pub qso_id: i64,
+    pub call: String,
+    pub qso_date: String,
+    pub time_on: String,
+    pub band: String,
+    pub mode: String,
+}
+
+/// Award credits confirmed at one point in time, used to diff before/after a sync
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConfirmedCredits {
+    pub dxcc: BTreeSet<i32>,
+    pub states: BTreeSet<String>,
+    pub grids: BTreeSet<String>,
+    pub cq_zones: BTreeSet<i32>,
+    /// Confirmed count of each AWARD_MILESTONES award, as award progress reports it
+    pub award_counts: BTreeMap<String, usize>,
+}
+
+/// Credits confirmed by a sync that weren't confirmed before it
+#[derive(Debug, Default, Clone, Serialize, PartialEq)]
+pub struct ConfirmationDelta {
+    /// Entity names, e.g. "Japan"
+    pub new_dxcc_confirmed: Vec<String>,
+    pub new_states_confirmed: Vec<String>,
+    /// 4-character grid squares
+    pub new_grids_confirmed: Vec<String>,
+    pub new_zones_confirmed: Vec<String>,
+    /// Award levels the confirmed counts crossed
+    pub milestones_reached: Vec<AwardMilestone>,
+}
+
+/// An award level reached, e.g. DXCC 100
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AwardMilestone {
+    pub award: String,
+    pub threshold: usize,
+    pub confirmed: usize,
+}
+
+/// Confirmed counts worth announcing: the base awards and the DXCC endorsements
+pub const AWARD_MILESTONES: &[(&str, &[usize])] = &[
+    ("DXCC", &[100, 150, 200, 250, 300, 325]),
+    ("WAS", &[50]),
+    ("WAZ", &[40]),
+];
+
+impl ConfirmedCredits {
+    fn award_count(&self, award: &str) -> usize {
+        self.award_counts.get(award).copied().unwrap_or(0)
+    }
+
+    /// Everything confirmed in `after` that isn't confirmed in `self`
+    pub fn newly_confirmed(&self, after: &ConfirmedCredits) -> ConfirmationDelta {
+        let mut milestones_reached = Vec::new();
+        for (award, thresholds) in AWARD_MILESTONES {
+            let (before, confirmed) = (self.award_count(award), after.award_count(award));
+            for &threshold in thresholds.iter().filter(|&&t| before < t && confirmed >= t) {
+                milestones_reached.push(AwardMilestone {
+                    award: award.to_string(),
+                    threshold,
+                    confirmed,
+                });
+            }
+        }
+
+        ConfirmationDelta {
+            new_dxcc_confirmed: after.dxcc.difference(&self.dxcc).map(|dxcc| entity_name(*dxcc)).collect(),
+            new_states_confirmed: after.states.difference(&self.states).cloned().collect(),
+            new_grids_confirmed: after.grids.difference(&self.grids).cloned().collect(),
+            new_zones_confirmed: after.cq_zones.difference(&self.cq_zones).map(|z| z.to_string()).collect(),
+            milestones_reached,
+        }
+    }
+}
+
 // ============================================================================
-// Award Commands
+// Helper Functions
 // ============================================================================
 
-#[command]
-pub async fn get_dxcc_progress(
-    state: tauri::State<'_, AppState>,
-    band: Option<String>,
-    mode: Option<String>,
-) -> Result<DxccProgress, String> {
-    let db_guard = state.db.lock().await;
-    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+/// Entity name for a DXCC number, "DXCC n" if unknown
+fn entity_name(dxcc: i32) -> String {
+    crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc))
+        .map(|e| e.name.to_string())
+        .unwrap_or_else(|| format!("DXCC {}", dxcc))
+}
 
-    // Count unique worked DXCC entities
-    let worked: i64 = if let (Some(b), Some(m)) = (&band, &mode) {
-        sqlx::query_scalar(
-            "SELECT COUNT(DISTINCT dxcc) FROM qsos WHERE dxcc IS NOT NULL AND band = ? AND mode = ?",
-        )
-        .bind(b)
-        .bind(m)
-        .fetch_one(pool)
-        .await
-        .unwrap_or(0)
-    } else if let Some(b) = &band {
-        sqlx::query_scalar(
-            "SELECT COUNT(DISTINCT dxcc) FROM qsos WHERE dxcc IS NOT NULL AND band = ?",
-        )
-        .bind(b)
-        .fetch_one(pool)
-        .await
-        .unwrap_or(0)
-    } else if let Some(m) = &mode {
-        sqlx::query_scalar(
-            "SELECT COUNT(DISTINCT dxcc) FROM qsos WHERE dxcc IS NOT NULL AND mode = ?",
-        )
-        .bind(m)
-        .fetch_one(pool)
-        .await
-        .unwrap_or(0)
-    } else {
-        sqlx::query_scalar("SELECT COUNT(DISTINCT dxcc) FROM qsos WHERE dxcc IS NOT NULL")
-            .fetch_one(pool)
-            .await
-            .unwrap_or(0)
+/// DXCC entities whose states count for WAS (Worked All States):
+/// - 291: United States of America (continental)
+/// - 6: Alaska (separate DXCC entity, but state AK counts for WAS)
+/// - 110: Hawaii (separate DXCC entity, but state HI counts for WAS)
+pub const WAS_DXCC: &[i32] = &[291, 6, 110];
+
+/// Conditions on `alias` for a QSO that counts for WAS: a WAS entity and one
+/// of the 50 states (DC doesn't count)
+pub(crate) fn was_qso_sql(alias: &str) -> String {
+    let entities = WAS_DXCC.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", ");
+    let states = US_STATES.iter().map(|s| format!("'{}'", s.code)).collect::<Vec<_>>().join(",");
+    format!("{alias}.dxcc IN ({entities}) AND UPPER(TRIM({alias}.state)) IN ({states})")
+}
+
+/// The WAS state a QSO counts for, uppercased, None if it counts for none
+fn was_state(dxcc: Option<i32>, state: Option<&str>) -> Option<String> {
+    let state = state?.trim();
+    let counts = dxcc.is_some_and(|d| WAS_DXCC.contains(&d)) && get_us_state(state).is_some();
+    counts.then(|| state.to_uppercase())
+}
+
+/// Largest radius the grid map will render (61 x 61 cells)
+const MAX_GRID_MAP_RADIUS: i32 = 30;
+
+/// Setting holding the comma-separated confirmation sources that count for awards
+pub const CONFIRMATION_SOURCES_SETTING: &str = "award_confirmation_sources";
+
+/// Setting holding comma-separated DXCC entity numbers that don't count for DXCC
+pub const EXCLUDED_ENTITIES_SETTING: &str = "award_excluded_entities";
+
+/// Setting: count only the first-ever QSO with each entity (ATNO) for DXCC
+pub const ATNO_ONLY_SETTING: &str = "award_atno_only";
+
+/// Setting holding the comma-separated awards get_all_award_progress computes
+pub const ENABLED_AWARDS_SETTING: &str = "enabled_awards";
+
+/// Awards get_all_award_progress can compute, in display order
+pub const PROGRESS_AWARDS: &[&str] = &["DXCC", "WAS", "WAC", "WAZ", "VUCC", "WWFF"];
+
+/// Setting: take an award snapshot after each LoTW download/resync
+pub const SNAPSHOT_AFTER_SYNC_SETTING: &str = "award_snapshot_after_sync";
+
+/// Continents counted for WAC (Antarctica doesn't count)
+const WAC_CONTINENTS: &[&str] = &["NA", "SA", "EU", "AF", "AS", "OC"];
+
+/// Confirmation sources the award queries understand
+const KNOWN_CONFIRMATION_SOURCES: &[&str] = &["LOTW", "EQSL", "QRZ", "CLUBLOG", "CARD"];
+
+/// Parse the accepted-sources setting, defaulting to LoTW only.
+/// Unknown sources are dropped so the result is safe to inline into SQL.
+pub fn parse_confirmation_sources(value: Option<&str>) -> Vec<&'static str> {
+    let mut sources: Vec<&'static str> = Vec::new();
+    for part in value.unwrap_or("").split(',') {
+        let part = part.trim().to_uppercase();
+        if let Some(known) = KNOWN_CONFIRMATION_SOURCES.iter().find(|s| **s == part) {
+            if !sources.contains(known) {
+                sources.push(known);
+            }
+        }
+    }
+    if sources.is_empty() {
+        sources.push("LOTW");
+    }
+    sources
+}
+
+/// Confirmation sources currently accepted for award credit
+pub async fn accepted_confirmation_sources(pool: &Pool<Sqlite>) -> Vec<&'static str> {
+    let value = read_setting(pool, CONFIRMATION_SOURCES_SETTING).await;
+    parse_confirmation_sources(value.as_deref())
+}
+
+/// Render sources as a SQL IN list body: 'LOTW','CARD'
+pub(crate) fn sources_sql(sources: &[&str]) -> String {
+    sources.iter().map(|s| format!("'{}'", s)).collect::<Vec<_>>().join(",")
+}
+
+/// Parse the excluded-entities setting; entries that aren't numbers are dropped
+pub fn parse_excluded_entities(value: Option<&str>) -> Vec<i32> {
+    let mut entities: Vec<i32> = Vec::new();
+    for part in value.unwrap_or("").split(',') {
+        if let Ok(dxcc) = part.trim().parse::<i32>() {
+            if !entities.contains(&dxcc) {
+                entities.push(dxcc);
+            }
+        }
+    }
+    entities
+}
+
+/// Conditions on `q` for DXCC counting: excluded entities dropped, and with
+/// ATNO only on, only each entity's first QSO kept
+async fn dxcc_counting_filter(pool: &Pool<Sqlite>) -> String {
+    let mut sql = String::new();
+    let excluded = parse_excluded_entities(read_setting(pool, EXCLUDED_ENTITIES_SETTING).await.as_deref());
+    if !excluded.is_empty() {
+        let list = excluded.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(",");
+        sql.push_str(&format!(" AND q.dxcc NOT IN ({})", list));
+    }
+    if read_bool_setting(pool, ATNO_ONLY_SETTING, false).await {
+        sql.push_str(&format!(
+            r#" AND q.id = (SELECT f.id FROM qsos f WHERE f.dxcc = q.dxcc{}
+                            ORDER BY f.qso_date, f.time_on, f.id LIMIT 1)"#,
+            counted_qso_filter(pool, "f").await
+        ));
+    }
+    sql
+}
+
+/// Snapshot the DXCC entities, WAS states, grids and CQ zones confirmed by accepted sources
+pub async fn confirmed_credits(pool: &Pool<Sqlite>) -> Result<ConfirmedCredits, String> {
+    // (dxcc, state, 4-char grid, cq zone)
+    type CreditRow = (Option<i32>, Option<String>, Option<String>, Option<i32>);
+
+    let sources = accepted_confirmation_sources(pool).await;
+    let rows: Vec<CreditRow> = sqlx::query_as(&format!(
+        r#"SELECT DISTINCT q.dxcc, q.state, UPPER(SUBSTR(q.gridsquare, 1, 4)), q.cqz
+           FROM qsos q
+           JOIN confirmations c ON c.qso_id = q.id
+           WHERE c.source IN ({}) AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0{}"#,
+        sources_sql(&sources),
+        counted_qso_filter(pool, "q").await
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query confirmed credits: {}", e))?;
+
+    let mut credits = ConfirmedCredits::default();
+    for (dxcc, state, grid, cqz) in rows {
+        if let Some(dxcc) = dxcc {
+            credits.dxcc.insert(dxcc);
+        }
+        if let Some(state) = was_state(dxcc, state.as_deref()) {
+            credits.states.insert(state);
+        }
+        if let Some(grid) = grid.filter(|g| g.len() == 4) {
+            credits.grids.insert(grid);
+        }
+        if let Some(cqz) = cqz {
+            credits.cq_zones.insert(cqz);
+        }
+    }
+
+    // Milestones follow the award pages, exclusions and ATNO included
+    for (award, _) in AWARD_MILESTONES {
+        if let Some((_, confirmed)) = award_progress(pool, award).await?.and_then(|p| p.worked_confirmed()) {
+            credits.award_counts.insert(award.to_string(), confirmed as usize);
+        }
+    }
+    Ok(credits)
+}
+
+/// Parse APP_LOTW_CREDIT_GRANTED ("DXCC,DXCC_BAND" or ADIF-style "DXCC:LOTW,WAS:CARD")
+/// into uppercase award codes, dropping any medium suffix and duplicates
+pub fn parse_credit_granted(value: &str) -> Vec<String> {
+    let mut awards: Vec<String> = Vec::new();
+    for part in value.split(',') {
+        let award = part.split(':').next().unwrap_or("").trim().to_uppercase().replace('-', "_");
+        if !award.is_empty() && !awards.contains(&award) {
+            awards.push(award);
+        }
+    }
+    awards
+}
+
+/// Slot a QSO fills for an award we compute locally, None if it fills none.
+/// Awards outside LOCAL_AWARDS always return None.
+fn award_slot(award: &str, dxcc: Option<i32>, state: Option<&str>, band: &str, mode: &str) -> Option<String> {
+    let was_state = || was_state(dxcc, state);
+    let mode_group = || crate::adif::modes::get_mode_group(mode).as_str();
+    match award {
+        "DXCC" => dxcc.map(|d| d.to_string()),
+        "DXCC_BAND" => dxcc.map(|d| format!("{}/{}", d, band.to_lowercase())),
+        "DXCC_MODE" => dxcc.map(|d| format!("{}/{}", d, mode_group())),
+        "WAS" => was_state(),
+        "WAS_BAND" => was_state().map(|s| format!("{}/{}", s, band.to_lowercase())),
+        "WAS_MODE" => was_state().map(|s| format!("{}/{}", s, mode_group())),
+        _ => None,
+    }
+}
+
+/// Awards whose slots award_slot knows how to compute
+const LOCAL_AWARDS: &[&str] = &["DXCC", "DXCC_BAND", "DXCC_MODE", "WAS", "WAS_BAND", "WAS_MODE"];
+
+/// Summarize LoTW-granted credit per award and compare with our LoTW-confirmed slots
+pub async fn granted_credits(pool: &Pool<Sqlite>) -> Result<Vec<GrantedCredit>, String> {
+    // (qso id, dxcc, state, band, mode, credit_granted)
+    type GrantRow = (i64, Option<i32>, Option<String>, String, String, Option<String>);
+
+    let rows: Vec<GrantRow> = sqlx::query_as(&format!(
+        r#"SELECT q.id, q.dxcc, q.state, q.band, q.mode, c.credit_granted
+           FROM qsos q
+           JOIN confirmations c ON c.qso_id = q.id
+           WHERE c.source = 'LOTW' AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0{}"#,
+        counted_qso_filter(pool, "q").await
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query LoTW credits: {}", e))?;
+
+    // award -> (granted qso count, granted slots)
+    let mut granted: BTreeMap<String, (i64, BTreeSet<String>)> = BTreeMap::new();
+    let mut confirmed: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for (id, dxcc, state, band, mode, credit) in &rows {
+        for award in LOCAL_AWARDS {
+            if let Some(slot) = award_slot(award, *dxcc, state.as_deref(), band, mode) {
+                confirmed.entry(award).or_default().insert(slot);
+            }
+        }
+        for award in parse_credit_granted(credit.as_deref().unwrap_or("")) {
+            let slot = award_slot(&award, *dxcc, state.as_deref(), band, mode).unwrap_or_else(|| id.to_string());
+            let entry = granted.entry(award).or_default();
+            entry.0 += 1;
+            entry.1.insert(slot);
+        }
+    }
+
+    Ok(granted
+        .into_iter()
+        .map(|(award, (qsos, slots))| GrantedCredit {
+            confirmed_slots: LOCAL_AWARDS
+                .contains(&award.as_str())
+                .then(|| confirmed.get(award.as_str()).map_or(0, |s| s.len() as i64)),
+            award,
+            granted_qsos: qsos,
+            granted_slots: slots.len() as i64,
+        })
+        .collect())
+}
+
+/// Compare the entities we count as confirmed with those LoTW has granted DXCC credit
+pub async fn confirmation_reconciliation(pool: &Pool<Sqlite>) -> Result<ConfirmationReconciliation, String> {
+    let confirmed = confirmed_credits(pool).await?.dxcc;
+
+    let rows: Vec<(i32, String)> = sqlx::query_as(&format!(
+        r#"SELECT q.dxcc, c.credit_granted
+           FROM qsos q
+           JOIN confirmations c ON c.qso_id = q.id
+           WHERE c.source = 'LOTW' AND q.dxcc IS NOT NULL AND c.credit_granted IS NOT NULL{}"#,
+        counted_qso_filter(pool, "q").await
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query LoTW credits: {}", e))?;
+    let granted: BTreeSet<i32> = rows
+        .into_iter()
+        .filter(|(_, credit)| parse_credit_granted(credit).iter().any(|a| a == "DXCC"))
+        .map(|(dxcc, _)| dxcc)
+        .collect();
+
+    let to_entities = |ids: Vec<&i32>| {
+        let mut entities: Vec<ReconciledEntity> = ids
+            .into_iter()
+            .map(|&dxcc| ReconciledEntity { dxcc, name: entity_name(dxcc) })
+            .collect();
+        entities.sort_by(|a, b| a.name.cmp(&b.name));
+        entities
     };
 
+    Ok(ConfirmationReconciliation {
+        confirmed: confirmed.len() as i64,
+        granted: granted.len() as i64,
+        confirmed_not_granted: to_entities(confirmed.difference(&granted).collect()),
+        granted_not_confirmed: to_entities(granted.difference(&confirmed).collect()),
+    })
+}
+
+/// " AND q.band = ? AND q.mode = ?" for whichever of band/mode is given
+fn band_mode_sql(band: Option<&str>, mode: Option<&str>) -> String {
+    let mut sql = String::new();
+    if band.is_some() {
+        sql.push_str(" AND q.band = ?");
+    }
+    if mode.is_some() {
+        sql.push_str(" AND q.mode = ?");
+    }
+    sql
+}
+
+/// DXCC entities worked (optionally on one band and/or mode) and confirmed
+pub async fn dxcc_progress(pool: &Pool<Sqlite>, band: Option<&str>, mode: Option<&str>) -> DxccProgress {
+    let counted = counted_qso_filter(pool, "q").await;
+    let counting = dxcc_counting_filter(pool).await;
+
+    // Count unique worked DXCC entities
+    let worked_sql = format!(
+        "SELECT COUNT(DISTINCT q.dxcc) FROM qsos q WHERE q.dxcc IS NOT NULL{}{}{}",
+        band_mode_sql(band, mode),
+        counted,
+        counting
+    );
+    let mut query = sqlx::query_scalar(&worked_sql);
+    for value in [band, mode].into_iter().flatten() {
+        query = query.bind(value);
+    }
+    let worked: i64 = query.fetch_one(pool).await.unwrap_or(0);
+
     // Count confirmed DXCC entities
-    let confirmed: i64 = sqlx::query_scalar(
+    let sources = accepted_confirmation_sources(pool).await;
+    let confirmed: i64 = sqlx::query_scalar(&format!(
         r#"SELECT COUNT(DISTINCT q.dxcc) FROM qsos q
            JOIN confirmations c ON c.qso_id = q.id
-           WHERE q.dxcc IS NOT NULL AND c.source = 'LOTW' AND c.qsl_rcvd = 'Y'"#,
-    )
+           WHERE q.dxcc IS NOT NULL AND c.source IN ({}) AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0{}{}"#,
+        sources_sql(&sources),
+        counted,
+        counting
+    ))
     .fetch_one(pool)
     .await
     .unwrap_or(0);
 
-    Ok(DxccProgress {
+    DxccProgress {
         worked,
         confirmed,
-        total: 340, // Current active DXCC entities
-    })
+        total: get_current_entity_count() as i64,
+    }
 }
 
-#[command]
-pub async fn get_was_progress(
-    state: tauri::State<'_, AppState>,
-    band: Option<String>,
-    mode: Option<String>,
-) -> Result<WasProgress, String> {
-    let db_guard = state.db.lock().await;
-    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+/// The DXCC mode awards with the mode group each counts; image modes
+/// (SSTV, FAX, ATV) count for none of them
+const DXCC_MODE_AWARDS: [(&str, ModeGroup); 3] =
+    [("PHONE", ModeGroup::Phone), ("CW", ModeGroup::CW), ("DIGITAL", ModeGroup::Data)];
+
+/// DXCC entities worked and confirmed for each of the three mode awards,
+/// in the order PHONE, CW, DIGITAL
+pub async fn dxcc_by_mode_group(pool: &Pool<Sqlite>) -> Result<Vec<DxccModeGroupProgress>, String> {
+    let sources = accepted_confirmation_sources(pool).await;
+    // (mode group, worked, confirmed)
+    let rows: Vec<(String, i64, i64)> = sqlx::query_as(&format!(
+        r#"SELECT {} AS mode_group, COUNT(DISTINCT q.dxcc),
+                  COUNT(DISTINCT CASE WHEN EXISTS(SELECT 1 FROM confirmations c
+                             WHERE c.qso_id = q.id AND c.source IN ({}) AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0)
+                        THEN q.dxcc END)
+           FROM qsos q
+           WHERE q.dxcc IS NOT NULL{}{}
+           GROUP BY mode_group"#,
+        mode_group_sql("q.mode"),
+        sources_sql(&sources),
+        counted_qso_filter(pool, "q").await,
+        dxcc_counting_filter(pool).await
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query DXCC by mode: {}", e))?;
+
+    let total = get_current_entity_count() as i64;
+    Ok(DXCC_MODE_AWARDS
+        .into_iter()
+        .map(|(award, group)| {
+            let (worked, confirmed) = rows
+                .iter()
+                .find(|(g, _, _)| g == group.as_str())
+                .map_or((0, 0), |(_, worked, confirmed)| (*worked, *confirmed));
+            DxccModeGroupProgress { mode_group: award.to_string(), worked, confirmed, total }
+        })
+        .collect())
+}
+
+/// US states worked (optionally on one band and/or mode) and confirmed,
+/// over the WAS_DXCC entities
+pub async fn was_progress(pool: &Pool<Sqlite>, band: Option<&str>, mode: Option<&str>) -> WasProgress {
+    let counted = counted_qso_filter(pool, "q").await;
 
-    // DXCC entities that count for WAS (Worked All States):
-    // - 291: United States of America (continental)
-    // - 6: Alaska (separate DXCC entity, but state AK counts for WAS)
-    // - 110: Hawaii (separate DXCC entity, but state HI counts for WAS)
-    
     // Get unique worked US states (including Alaska and Hawaii)
-    let worked_states: Vec<(String,)> = if let (Some(b), Some(m)) = (&band, &mode) {
-        sqlx::query_as(
-            "SELECT DISTINCT state FROM qsos WHERE dxcc IN (291, 6, 110) AND state IS NOT NULL AND band = ? AND mode = ?",
-        )
-        .bind(b)
-        .bind(m)
-        .fetch_all(pool)
-        .await
-        .unwrap_or_default()
-    } else {
-        sqlx::query_as("SELECT DISTINCT state FROM qsos WHERE dxcc IN (291, 6, 110) AND state IS NOT NULL")
-            .fetch_all(pool)
-            .await
-            .unwrap_or_default()
-    };
+    let worked_sql = format!(
+        "SELECT DISTINCT UPPER(TRIM(q.state)) FROM qsos q WHERE {}{}{}",
+        was_qso_sql("q"),
+        band_mode_sql(band, mode),
+        counted
+    );
+    let mut query = sqlx::query_as(&worked_sql);
+    for value in [band, mode].into_iter().flatten() {
+        query = query.bind(value);
+    }
+    let worked_states: Vec<(String,)> = query.fetch_all(pool).await.unwrap_or_default();
 
     // Get confirmed states (including Alaska and Hawaii)
-    let confirmed_states: Vec<(String,)> = sqlx::query_as(
-        r#"SELECT DISTINCT q.state FROM qsos q
+    let sources = accepted_confirmation_sources(pool).await;
+    let confirmed_states: Vec<(String,)> = sqlx::query_as(&format!(
+        r#"SELECT DISTINCT UPPER(TRIM(q.state)) FROM qsos q
            JOIN confirmations c ON c.qso_id = q.id
-           WHERE q.dxcc IN (291, 6, 110) AND q.state IS NOT NULL AND c.source = 'LOTW' AND c.qsl_rcvd = 'Y'"#,
-    )
+           WHERE {} AND c.source IN ({}) AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0{}"#,
+        was_qso_sql("q"),
+        sources_sql(&sources),
+        counted
+    ))
     .fetch_all(pool)
     .await
     .unwrap_or_default();
 
-    Ok(WasProgress {
+    WasProgress {
         worked: worked_states.len() as i64,
         confirmed: confirmed_states.len() as i64,
         total: 50,
         worked_states: worked_states.into_iter().map(|(s,)| s).collect(),
         confirmed_states: confirmed_states.into_iter().map(|(s,)| s).collect(),
+    }
+}
+
+/// Distinct values of `column` over QSOs matching `condition`, worked and confirmed
+async fn worked_and_confirmed(
+    pool: &Pool<Sqlite>,
+    column: &str,
+    condition: &str,
+    binds: &[&str],
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let counted = counted_qso_filter(pool, "q").await;
+    let worked_sql = format!(
+        "SELECT DISTINCT CAST({} AS TEXT) FROM qsos q WHERE {} IS NOT NULL{}{}",
+        column, column, condition, counted
+    );
+    let mut query = sqlx::query_scalar(&worked_sql);
+    for value in binds {
+        query = query.bind(*value);
+    }
+    let worked: Vec<String> = query
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to query worked {}: {}", column, e))?;
+
+    let sources = accepted_confirmation_sources(pool).await;
+    let confirmed_sql = format!(
+        r#"SELECT DISTINCT CAST({} AS TEXT) FROM qsos q
+           JOIN confirmations c ON c.qso_id = q.id
+           WHERE {} IS NOT NULL AND c.source IN ({}) AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0{}{}"#,
+        column,
+        column,
+        sources_sql(&sources),
+        condition,
+        counted
+    );
+    let mut query = sqlx::query_scalar(&confirmed_sql);
+    for value in binds {
+        query = query.bind(*value);
+    }
+    let confirmed: Vec<String> = query
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to query confirmed {}: {}", column, e))?;
+
+    Ok((worked, confirmed))
+}
+
+/// Continents worked and confirmed: the QSO's CONT when logged, since entities
+/// such as Russia or Turkey span two; otherwise the DXCC entity's continent
+pub async fn wac_progress(pool: &Pool<Sqlite>) -> Result<CountProgress, String> {
+    let (worked, confirmed) =
+        worked_and_confirmed(pool, "COALESCE(NULLIF(UPPER(TRIM(q.continent)), ''), q.dxcc)", "", &[]).await?;
+    let continents = |keys: Vec<String>| {
+        keys.iter()
+            .filter_map(|key| match key.parse::<i32>() {
+                Ok(dxcc) => crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc)).map(|e| e.continent),
+                Err(_) => Some(key.as_str()),
+            })
+            .filter(|continent| WAC_CONTINENTS.contains(continent))
+            .collect::<BTreeSet<_>>()
+            .len() as i64
+    };
+
+    Ok(CountProgress {
+        worked: continents(worked),
+        confirmed: continents(confirmed),
+        total: WAC_CONTINENTS.len() as i64,
+    })
+}
+
+/// CQ zones worked and confirmed
+pub async fn waz_progress(pool: &Pool<Sqlite>) -> Result<CountProgress, String> {
+    let (worked, confirmed) = worked_and_confirmed(pool, "q.cqz", " AND q.cqz BETWEEN 1 AND 40", &[]).await?;
+    Ok(CountProgress {
+        worked: worked.len() as i64,
+        confirmed: confirmed.len() as i64,
+        total: 40,
+    })
+}
+
+/// VUCC grids (4-character) worked and confirmed on one band, or on any band from 6m up.
+/// Only the other station's grid (`gridsquare`) counts: `my_gridsquare` is where
+/// we operated from, which counts toward their VUCC, not ours.
+pub async fn vucc_progress(pool: &Pool<Sqlite>, band: Option<&str>) -> Result<VuccProgress, String> {
+    let vhf_bands = &BAND_ORDER[band_sort_index("6m")..];
+    let band = band.map(|b| b.trim().to_lowercase());
+    let bands: Vec<&str> = match band.as_deref() {
+        Some(band) if vhf_bands.contains(&band) => vec![band],
+        Some(band) => return Err(format!("VUCC isn't awarded on {}", band)),
+        None => vhf_bands.to_vec(),
+    };
+    let condition = format!(
+        " AND LENGTH(q.gridsquare) >= 4 AND LOWER(q.band) IN ({})",
+        vec!["?"; bands.len()].join(",")
+    );
+    let (worked, confirmed) =
+        worked_and_confirmed(pool, "UPPER(SUBSTR(q.gridsquare, 1, 4))", &condition, &bands).await?;
+
+    Ok(VuccProgress {
+        worked: worked.len() as i64,
+        confirmed: confirmed.len() as i64,
+        // 100 grids on 6m/2m, 50 on 1.25m/70cm, 25 from 33cm up
+        target: match band.as_deref() {
+            Some("1.25m") | Some("70cm") => 50,
+            Some("33cm") | Some("23cm") => 25,
+            _ => 100,
+        },
+        band,
+    })
+}
+
+/// WWFF references worked (their WWFF_REF) and activated (our MY_WWFF_REF)
+pub async fn wwff_progress(pool: &Pool<Sqlite>) -> Result<WwffProgress, String> {
+    // References worked (their WWFF_REF)
+    let counted = counted_qso_filter(pool, "qsos").await;
+    let worked_refs: Vec<(String,)> = sqlx::query_as(&format!(
+        "SELECT DISTINCT wwff_ref FROM qsos WHERE wwff_ref IS NOT NULL AND wwff_ref != ''{}",
+        counted
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // References activated (our MY_WWFF_REF, kept in adif_fields on import)
+    let activated_refs: Vec<(String,)> = sqlx::query_as(&format!(
+        r#"SELECT DISTINCT json_extract(adif_fields, '$.my_wwff_ref') FROM qsos
+           WHERE json_valid(adif_fields) AND json_extract(adif_fields, '$.my_wwff_ref') IS NOT NULL{}"#,
+        counted
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (worked, worked_by_prefix) = to_wwff_groups(worked_refs);
+    let (activated, activated_by_prefix) = to_wwff_groups(activated_refs);
+
+    Ok(WwffProgress {
+        worked,
+        activated,
+        worked_by_prefix,
+        activated_by_prefix,
+    })
+}
+
+/// Parse the enabled-awards setting into PROGRESS_AWARDS names, in display order.
+/// Unset means every award; unknown names are dropped.
+pub fn parse_enabled_awards(value: Option<&str>) -> Vec<&'static str> {
+    let Some(value) = value.filter(|v| !v.trim().is_empty()) else {
+        return PROGRESS_AWARDS.to_vec();
+    };
+    let listed: Vec<String> = value.split(',').map(|a| a.trim().to_uppercase()).collect();
+    PROGRESS_AWARDS
+        .iter()
+        .copied()
+        .filter(|award| listed.iter().any(|l| l == award))
+        .collect()
+}
+
+/// Overall progress for one of PROGRESS_AWARDS, None for any other name
+async fn award_progress(pool: &Pool<Sqlite>, award: &str) -> Result<Option<AwardProgress>, String> {
+    Ok(Some(match award {
+        "DXCC" => AwardProgress::Dxcc(dxcc_progress(pool, None, None).await),
+        "WAS" => AwardProgress::Was(was_progress(pool, None, None).await),
+        "WAC" => AwardProgress::Count(wac_progress(pool).await?),
+        "WAZ" => AwardProgress::Count(waz_progress(pool).await?),
+        "VUCC" => AwardProgress::Vucc(vucc_progress(pool, None).await?),
+        "WWFF" => AwardProgress::Wwff(wwff_progress(pool).await?),
+        _ => return Ok(None),
+    }))
+}
+
+/// Overall progress for each enabled award, keyed by award name
+pub async fn all_award_progress(pool: &Pool<Sqlite>) -> Result<BTreeMap<String, AwardProgress>, String> {
+    let enabled = parse_enabled_awards(read_setting(pool, ENABLED_AWARDS_SETTING).await.as_deref());
+    let mut progress = BTreeMap::new();
+    for award in enabled {
+        if let Some(entry) = award_progress(pool, award).await? {
+            progress.insert(award.to_string(), entry);
+        }
+    }
+    Ok(progress)
+}
+
+/// Record the current worked/confirmed counts of every award with confirmations.
+/// All awards are recorded regardless of `enabled_awards`, so history has no gaps
+/// when the profile changes.
+pub async fn take_snapshot(pool: &Pool<Sqlite>) -> Result<Vec<AwardSnapshot>, String> {
+    let taken_at: String = sqlx::query_scalar("SELECT datetime('now')")
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut snapshots = Vec::new();
+    for award in PROGRESS_AWARDS {
+        let Some((worked, confirmed)) = award_progress(pool, award).await?.and_then(|p| p.worked_confirmed()) else {
+            continue;
+        };
+        sqlx::query("INSERT INTO award_snapshots (award, worked, confirmed, taken_at) VALUES (?, ?, ?, ?)")
+            .bind(award)
+            .bind(worked)
+            .bind(confirmed)
+            .bind(&taken_at)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to save {} snapshot: {}", award, e))?;
+        snapshots.push(AwardSnapshot {
+            award: award.to_string(),
+            worked,
+            confirmed,
+            taken_at: taken_at.clone(),
+        });
+    }
+    Ok(snapshots)
+}
+
+/// Take a snapshot after a confirmation sync when `award_snapshot_after_sync` is on.
+/// Never fails the sync; errors are only logged.
+pub async fn snapshot_after_sync(pool: &Pool<Sqlite>) {
+    if !read_bool_setting(pool, SNAPSHOT_AFTER_SYNC_SETTING, false).await {
+        return;
+    }
+    if let Err(e) = take_snapshot(pool).await {
+        log::warn!("Award snapshot after sync failed: {}", e);
+    }
+}
+
+/// Recorded snapshots of one award, oldest first
+pub async fn award_history(pool: &Pool<Sqlite>, award: &str) -> Result<Vec<AwardSnapshot>, String> {
+    sqlx::query_as(
+        r#"SELECT award, worked, confirmed, taken_at FROM award_snapshots
+           WHERE award = ? ORDER BY taken_at, id"#,
+    )
+    .bind(award.trim().to_uppercase())
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load award history: {}", e))
+}
+
+/// DXCC entities Honor Roll may be short of: all but this many current entities
+const HONOR_ROLL_MISSING: i64 = 9;
+
+/// VUCC endorsements come in steps of this many grids past the base award
+const VUCC_ENDORSEMENT_STEP: i64 = 25;
+
+/// Levels of an award in order as (threshold, name), out of `total` possible
+/// credits: the base award, then endorsements. DXCC adds Honor Roll and
+/// #1 Honor Roll; VUCC endorses every 25 grids past its target.
+pub fn award_levels(award: &str, total: i64) -> Vec<(i64, String)> {
+    let mut levels: Vec<(i64, String)> = match AWARD_MILESTONES.iter().find(|(name, _)| *name == award) {
+        Some((_, thresholds)) => thresholds
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| (t as i64, if i == 0 { award.to_string() } else { format!("{} {}", award, t) }))
+            .collect(),
+        None => vec![(total, award.to_string())],
+    };
+    match award {
+        "DXCC" => {
+            levels.push((total - HONOR_ROLL_MISSING, "Honor Roll".to_string()));
+            levels.push((total, "#1 Honor Roll".to_string()));
+        }
+        "VUCC" => {
+            // Up to every 4-character grid there is
+            levels.extend(
+                (1..)
+                    .map(|step| total + step * VUCC_ENDORSEMENT_STEP)
+                    .take_while(|&t| t <= 32_400)
+                    .map(|t| (t, format!("VUCC {}", t))),
+            );
+        }
+        _ => {}
+    }
+    levels
+}
+
+/// The first level of `award` not yet reached with `confirmed` credits, None when all are
+pub fn next_milestone(award: &str, confirmed: i64, total: i64) -> Option<NextMilestone> {
+    let (threshold, level) = award_levels(award, total).into_iter().find(|(t, _)| *t > confirmed)?;
+    Some(NextMilestone {
+        award: award.to_string(),
+        level,
+        confirmed,
+        threshold,
+        remaining: threshold - confirmed,
     })
 }
+
+/// The next level of each enabled award, from the same confirmed counts as
+/// get_all_award_progress. WWFF has no confirmations and is skipped.
+pub async fn next_milestones(pool: &Pool<Sqlite>) -> Result<Vec<NextMilestone>, String> {
+    let mut milestones = Vec::new();
+    for (award, progress) in all_award_progress(pool).await? {
+        let (confirmed, total) = match progress {
+            AwardProgress::Dxcc(p) => (p.confirmed, p.total),
+            AwardProgress::Was(p) => (p.confirmed, p.total),
+            AwardProgress::Count(p) => (p.confirmed, p.total),
+            AwardProgress::Vucc(p) => (p.confirmed, p.target),
+            AwardProgress::Wwff(_) => continue,
+        };
+        milestones.extend(next_milestone(&award, confirmed, total));
+    }
+    Ok(milestones)
+}
+
+/// Latest QSO per DXCC entity, optionally for one entity only, sorted by entity name
+pub async fn last_contacts(pool: &Pool<Sqlite>, dxcc: Option<i32>) -> Result<Vec<EntityLastContact>, String> {
+    let mut contacts: Vec<EntityLastContact> = sqlx::query_as(&format!(
+        r#"SELECT dxcc, qso_id, call, qso_date, time_on, band, mode
+           FROM (
+             SELECT q.dxcc, q.id AS qso_id, q.call, q.qso_date, q.time_on, q.band, q.mode,
+                    ROW_NUMBER() OVER (
+                      PARTITION BY q.dxcc
+                      ORDER BY q.qso_date DESC, q.time_on DESC, q.id DESC
+                    ) AS rn
+             FROM qsos q
+             WHERE q.dxcc IS NOT NULL AND (?1 IS NULL OR q.dxcc = ?1){}
+           )
+           WHERE rn = 1"#,
+        counted_qso_filter(pool, "q").await
+    ))
+    .bind(dxcc)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query last contacts: {}", e))?;
+
+    for contact in &mut contacts {
+        contact.entity_name = entity_name(contact.dxcc);
+    }
+    contacts.sort_by(|a, b| a.entity_name.cmp(&b.entity_name));
+    Ok(contacts)
+}
+
+/// Validate, deduplicate and group WWFF references by country prefix
+fn to_wwff_groups(refs: Vec<(String,)>) -> (i64, Vec<WwffPrefixGroup>) {
+    let groups = wwff::group_by_prefix(refs.iter().map(|(r,)| r));
+    let count = groups.values().map(|r| r.len() as i64).sum();
+    let groups = groups
+        .into_iter()
+        .map(|(prefix, references)| WwffPrefixGroup { prefix, references })
+        .collect();
+    (count, groups)
+}
+
+/// Build the band/mode matrix for one DXCC entity from a single grouped query
+pub async fn entity_band_mode_matrix(
+    pool: &Pool<Sqlite>,
+    dxcc: i32,
+) -> Result<EntityBandModeMatrix, String> {
+    let sources = accepted_confirmation_sources(pool).await;
+    let rows: Vec<(String, String, i64, i64)> = sqlx::query_as(&format!(
+        r#"SELECT LOWER(q.band), UPPER(q.mode), COUNT(DISTINCT q.id),
+                  MAX(CASE WHEN c.qsl_rcvd = 'Y' AND c.needs_reverify = 0 THEN 1 ELSE 0 END)
+           FROM qsos q
+           LEFT JOIN confirmations c ON c.qso_id = q.id AND c.source IN ({})
+           WHERE q.dxcc = ?{}{}
+           GROUP BY LOWER(q.band), UPPER(q.mode)
+           ORDER BY LOWER(q.band), UPPER(q.mode)"#,
+        sources_sql(&sources),
+        counted_qso_filter(pool, "q").await,
+        dxcc_counting_filter(pool).await
+    ))
+    .bind(dxcc)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query entity slots: {}", e))?;
+
+    let slots: Vec<EntitySlot> = rows
+        .into_iter()
+        .map(|(band, mode, qso_count, confirmed)| EntitySlot {
+            band,
+            mode,
+            qso_count,
+            confirmed: confirmed != 0,
+        })
+        .collect();
+
+    let mut bands_worked: Vec<String> = Vec::new();
+    let mut bands_confirmed: Vec<String> = Vec::new();
+    for slot in &slots {
+        if !bands_worked.contains(&slot.band) {
+            bands_worked.push(slot.band.clone());
+        }
+        if slot.confirmed && !bands_confirmed.contains(&slot.band) {
+            bands_confirmed.push(slot.band.clone());
+        }
+    }
+
+    let entity_name = crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc))
+        .map(|e| e.name.to_string());
+
+    Ok(EntityBandModeMatrix {
+        dxcc,
+        entity_name,
+        slots,
+        bands_worked,
+        bands_confirmed,
+    })
+}
+
+/// Build the grid map around `center` with worked/confirmed status per cell
+pub async fn grid_map_data(
+    pool: &Pool<Sqlite>,
+    center: &str,
+    radius: i32,
+    precision: GridPrecision,
+) -> Result<GridMapData, String> {
+    let radius = radius.clamp(0, MAX_GRID_MAP_RADIUS);
+    let region = grid_map::region_around(center, radius, precision)
+        .ok_or_else(|| format!("Invalid grid: {}", center))?;
+
+    // Worked grids truncated to the map precision; shorter grids can't be placed
+    let chars = precision.chars() as i64;
+    let sources = accepted_confirmation_sources(pool).await;
+    let rows: Vec<(String, i64)> = sqlx::query_as(&format!(
+        r#"SELECT UPPER(SUBSTR(q.gridsquare, 1, ?1)),
+                  MAX(CASE WHEN c.qsl_rcvd = 'Y' AND c.needs_reverify = 0 THEN 1 ELSE 0 END)
+           FROM qsos q
+           LEFT JOIN confirmations c ON c.qso_id = q.id AND c.source IN ({})
+           WHERE LENGTH(q.gridsquare) >= ?1{}
+           GROUP BY UPPER(SUBSTR(q.gridsquare, 1, ?1))"#,
+        sources_sql(&sources),
+        counted_qso_filter(pool, "q").await
+    ))
+    .bind(chars)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query worked grids: {}", e))?;
+
+    let status: std::collections::HashMap<String, bool> =
+        rows.into_iter().map(|(grid, confirmed)| (grid, confirmed != 0)).collect();
+
+    let mut cells = Vec::new();
+    for (row, grids) in region.iter().enumerate() {
+        for (col, grid) in grids.iter().enumerate() {
+            let Some(grid) = grid else { continue };
+            let confirmed = status.get(&grid.to_uppercase());
+            cells.push(GridMapCell {
+                grid: grid.clone(),
+                row,
+                col,
+                worked: confirmed.is_some(),
+                confirmed: confirmed.copied().unwrap_or(false),
+            });
+        }
+    }
+
+    let side = (radius * 2 + 1) as usize;
+    Ok(GridMapData {
+        center: center.trim().to_uppercase(),
+        precision: precision.chars(),
+        radius,
+        rows: side,
+        cols: side,
+        worked: cells.iter().filter(|c| c.worked).count(),
+        confirmed: cells.iter().filter(|c| c.confirmed).count(),
+        cells,
+    })
+}
+
+/// Count subdivisions of a DXCC entity worked and confirmed, matched on the QSO STATE field
+pub async fn subdivision_progress(pool: &Pool<Sqlite>, dxcc: i32) -> Result<SubdivisionProgress, String> {
+    let defined: Vec<(String, String)> =
+        sqlx::query_as("SELECT code, name FROM subdivisions WHERE dxcc = ? ORDER BY code")
+            .bind(dxcc)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("Failed to query subdivisions: {}", e))?;
+    if defined.is_empty() {
+        return Err(format!("No subdivisions defined for DXCC {}", dxcc));
+    }
+
+    let sources = accepted_confirmation_sources(pool).await;
+    let rows: Vec<(String, i64)> = sqlx::query_as(&format!(
+        r#"SELECT q.state, MAX(CASE WHEN c.qsl_rcvd = 'Y' AND c.needs_reverify = 0 THEN 1 ELSE 0 END)
+           FROM qsos q
+           LEFT JOIN confirmations c ON c.qso_id = q.id AND c.source IN ({})
+           WHERE q.dxcc = ? AND q.state IS NOT NULL AND q.state != ''{}
+           GROUP BY q.state"#,
+        sources_sql(&sources),
+        counted_qso_filter(pool, "q").await
+    ))
+    .bind(dxcc)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query worked subdivisions: {}", e))?;
+
+    // Several spellings of one code ("1", "01") collapse to the same key
+    let mut status: std::collections::HashMap<String, bool> = std::collections::HashMap::new();
+    for (state, confirmed) in rows {
+        if let Some(key) = subdivision_key(&state) {
+            *status.entry(key).or_insert(false) |= confirmed != 0;
+        }
+    }
+
+    let subdivisions: Vec<SubdivisionStatus> = defined
+        .into_iter()
+        .map(|(code, name)| {
+            let confirmed = subdivision_key(&code).and_then(|k| status.get(&k).copied());
+            SubdivisionStatus {
+                code,
+                name,
+                worked: confirmed.is_some(),
+                confirmed: confirmed.unwrap_or(false),
+            }
+        })
+        .collect();
+
+    let entity_name = crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc))
+        .map(|e| e.name.to_string());
+
+    Ok(SubdivisionProgress {
+        dxcc,
+        entity_name,
+        total: subdivisions.len() as i64,
+        worked: subdivisions.iter().filter(|s| s.worked).count() as i64,
+        confirmed: subdivisions.iter().filter(|s| s.confirmed).count() as i64,
+        subdivisions,
+    })
+}
+
+/// Earliest confirmed QSO for each slot. `slot_sql` is the slot expression over
+/// `q`, `filter_sql` an extra condition; both are built in this module.
+async fn earliest_confirmed_per_slot(
+    pool: &Pool<Sqlite>,
+    slot_sql: &str,
+    filter_sql: &str,
+) -> Result<Vec<AwardProofQso>, String> {
+    let sources = accepted_confirmation_sources(pool).await;
+    sqlx::query_as(&format!(
+        r#"SELECT slot, qso_id, call, qso_date, time_on, band, mode, confirmation_source, confirmation_date
+           FROM (
+             SELECT {slot} AS slot, q.id AS qso_id, q.call, q.qso_date, q.time_on, q.band, q.mode,
+                    c.source AS confirmation_source, c.qsl_rcvd_date AS confirmation_date,
+                    ROW_NUMBER() OVER (
+                      PARTITION BY {slot}
+                      ORDER BY q.qso_date, q.time_on, q.id, c.qsl_rcvd_date, c.source
+                    ) AS rn
+             FROM qsos q
+             JOIN confirmations c ON c.qso_id = q.id
+             WHERE c.source IN ({sources}) AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0 AND {filter}{counted}
+           )
+           WHERE rn = 1
+           ORDER BY slot"#,
+        slot = slot_sql,
+        sources = sources_sql(&sources),
+        filter = filter_sql,
+        counted = counted_qso_filter(pool, "q").await,
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query award proof QSOs: {}", e))
+}
+
+/// Proof QSOs for a DXCC application, one per confirmed entity
+pub async fn dxcc_application(pool: &Pool<Sqlite>) -> Result<Vec<AwardProofQso>, String> {
+    let filter = format!("q.dxcc IS NOT NULL{}", dxcc_counting_filter(pool).await);
+    let mut proofs = earliest_confirmed_per_slot(pool, "CAST(q.dxcc AS TEXT)", &filter).await?;
+    for proof in &mut proofs {
+        let entity_id = format!("{:03}", proof.slot.parse::<i32>().unwrap_or(0));
+        proof.slot_name = crate::reference::dxcc::get_entity_by_id(&entity_id)
+            .map(|e| e.name.to_string())
+            .unwrap_or_else(|| format!("DXCC {}", proof.slot));
+    }
+    proofs.sort_by(|a, b| a.slot_name.cmp(&b.slot_name));
+    Ok(proofs)
+}
+
+/// Proof QSOs for a WAS application, one per confirmed state (same entities as get_was_progress)
+pub async fn was_application(pool: &Pool<Sqlite>) -> Result<Vec<AwardProofQso>, String> {
+    let mut proofs = earliest_confirmed_per_slot(pool, "UPPER(TRIM(q.state))", &was_qso_sql("q")).await?;
+    for proof in &mut proofs {
+        proof.slot_name = proof.slot.clone();
+    }
+    Ok(proofs)
+}
+
+/// Proof QSOs for an award application
+pub async fn award_application(pool: &Pool<Sqlite>, award: AwardType) -> Result<Vec<AwardProofQso>, String> {
+    match award {
+        AwardType::Dxcc => dxcc_application(pool).await,
+        AwardType::Was => was_application(pool).await,
+    }
+}
+
+/// ADIF with one record per proof QSO, in record sheet order, carrying the
+/// confirmation that earned the slot
+pub async fn award_export_adif(pool: &Pool<Sqlite>, award: AwardType) -> Result<String, String> {
+    let proofs = award_application(pool, award).await?;
+    let ids: Vec<i64> = proofs.iter().map(|p| p.qso_id).collect();
+    let qsos: std::collections::HashMap<i64, serde_json::Value> =
+        super::adif::fetch_export_qsos(pool, Some(&ids), None)
+            .await?
+            .into_iter()
+            .filter_map(|q| Some((q.get("id")?.as_i64()?, q)))
+            .collect();
+
+    let records: Vec<_> = proofs
+        .iter()
+        .filter_map(|proof| {
+            let mut record = crate::adif::writer::qso_to_adif(qsos.get(&proof.qso_id)?);
+            let (rcvd, date) = match proof.confirmation_source.as_str() {
+                "LOTW" => ("LOTW_QSL_RCVD", "LOTW_QSLRDATE"),
+                "EQSL" => ("EQSL_QSL_RCVD", "EQSL_QSLRDATE"),
+                _ => ("QSL_RCVD", "QSLRDATE"),
+            };
+            record.insert(rcvd.to_string(), "Y".to_string());
+            if let Some(d) = proof.confirmation_date.as_deref() {
+                let d: String = d.chars().filter(|c| c.is_ascii_digit()).take(8).collect();
+                if d.len() == 8 {
+                    record.insert(date.to_string(), d);
+                }
+            }
+            Some(record)
+        })
+        .collect();
+    Ok(crate::adif::write_adif(&records, "GoQSO"))
+}
+
+// ============================================================================
+// Award Commands
+// ============================================================================
+
+#[command]
+pub async fn get_dxcc_progress(
+    state: tauri::State<'_, AppState>,
+    band: Option<String>,
+    mode: Option<String>,
+) -> Result<DxccProgress, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    Ok(dxcc_progress(pool, band.as_deref(), mode.as_deref()).await)
+}
+
+/// Confirmed DXCC entities for DXCC Phone, CW and Digital separately
+#[command]
+pub async fn get_dxcc_by_mode_group(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<DxccModeGroupProgress>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    dxcc_by_mode_group(pool).await
+}
+
+#[command]
+pub async fn get_was_progress(
+    state: tauri::State<'_, AppState>,
+    band: Option<String>,
+    mode: Option<String>,
+) -> Result<WasProgress, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    Ok(was_progress(pool, band.as_deref(), mode.as_deref()).await)
+}
+
+#[command]
+pub async fn get_wwff_progress(
+    state: tauri::State<'_, AppState>,
+) -> Result<WwffProgress, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    wwff_progress(pool).await
+}
+
+#[command]
+pub async fn get_vucc_progress(
+    state: tauri::State<'_, AppState>,
+    band: Option<String>,
+) -> Result<VuccProgress, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    vucc_progress(pool, band.as_deref()).await
+}
+
+/// Progress for every award in the `enabled_awards` profile, keyed by award name
+#[command]
+pub async fn get_all_award_progress(
+    state: tauri::State<'_, AppState>,
+) -> Result<BTreeMap<String, AwardProgress>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    all_award_progress(pool).await
+}
+
+/// Record the current worked/confirmed counts of every award
+#[command]
+pub async fn take_award_snapshot(state: tauri::State<'_, AppState>) -> Result<Vec<AwardSnapshot>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    take_snapshot(pool).await
+}
+
+/// Snapshots of one award (e.g. "DXCC") over time, for charting progress
+#[command]
+pub async fn get_award_history(
+    state: tauri::State<'_, AppState>,
+    award: String,
+) -> Result<Vec<AwardSnapshot>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    award_history(pool, &award).await
+}
+
+/// How many more confirmations each enabled award's next level needs
+#[command]
+pub async fn get_next_milestones(state: tauri::State<'_, AppState>) -> Result<Vec<NextMilestone>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    next_milestones(pool).await
+}
+
+/// Every band/mode worked for a single DXCC entity, with confirmation status per slot
+#[command]
+pub async fn get_entity_band_mode_matrix(
+    state: tauri::State<'_, AppState>,
+    dxcc: i32,
+) -> Result<EntityBandModeMatrix, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    entity_band_mode_matrix(pool, dxcc).await
+}
+
+/// Worked/needed grids in a square region around a center grid, at 4- or 6-char precision
+#[command]
+pub async fn get_grid_map_data(
+    state: tauri::State<'_, AppState>,
+    center_grid: String,
+    radius_fields: i32,
+    precision: Option<u8>,
+) -> Result<GridMapData, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let precision = GridPrecision::from_chars(precision.unwrap_or(4))
+        .ok_or("Precision must be 4 or 6")?;
+    grid_map_data(pool, &center_grid, radius_fields, precision).await
+}
+
+/// Sub-entity award progress (e.g. JA prefectures for DXCC 339)
+#[command]
+pub async fn get_subdivision_progress(
+    state: tauri::State<'_, AppState>,
+    dxcc: i32,
+) -> Result<SubdivisionProgress, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    subdivision_progress(pool, dxcc).await
+}
+
+/// DXCC record sheet: the earliest confirmed QSO for each entity
+#[command]
+pub async fn get_dxcc_application(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<AwardProofQso>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    dxcc_application(pool).await
+}
+
+/// WAS record sheet: the earliest confirmed QSO for each state
+#[command]
+pub async fn get_was_application(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<AwardProofQso>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    was_application(pool).await
+}
+
+/// Export the award application's proof QSOs (one per confirmed slot) as ADIF
+#[command]
+pub async fn export_adif_for_award(
+    state: tauri::State<'_, AppState>,
+    award: AwardType,
+) -> Result<String, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    award_export_adif(pool, award).await
+}
+
+/// LoTW's granted award credit, to reconcile against locally computed progress
+#[command]
+pub async fn get_granted_credits(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<GrantedCredit>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    granted_credits(pool).await
+}
+
+/// Entities where our confirmed DXCC count and LoTW's granted credit disagree
+#[command]
+pub async fn get_confirmation_reconciliation(
+    state: tauri::State<'_, AppState>,
+) -> Result<ConfirmationReconciliation, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    confirmation_reconciliation(pool).await
+}
+
+/// Most recent QSO with every DXCC entity worked
+#[command]
+pub async fn get_last_contact_per_entity(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<EntityLastContact>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    last_contacts(pool, None).await
+}
+
+/// Most recent QSO with one DXCC entity (None if never worked)
+#[command]
+pub async fn get_last_contact_for_entity(
+    state: tauri::State<'_, AppState>,
+    dxcc: i32,
+) -> Result<Option<EntityLastContact>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    Ok(last_contacts(pool, Some(dxcc)).await?.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    #[test]
+    fn test_parse_confirmation_sources() {
+        assert_eq!(parse_confirmation_sources(None), vec!["LOTW"]);
+        assert_eq!(parse_confirmation_sources(Some("")), vec!["LOTW"]);
+        assert_eq!(parse_confirmation_sources(Some("lotw, card")), vec!["LOTW", "CARD"]);
+        assert_eq!(parse_confirmation_sources(Some("CARD,CARD,'; DROP")), vec!["CARD"]);
+    }
+
+    /// Test: Japan worked on 20m and 40m FT8, confirmed only on 20m
+    #[tokio::test]
+    async fn test_entity_matrix_two_bands_one_confirmed() {
+        let pool = test_pool().await;
+
+        for (id, call, band, dxcc) in [
+            (1, "JA1ABC", "20m", 339),
+            (2, "JA2XYZ", "20m", 339),
+            (3, "JA3DEF", "40m", 339),
+            (4, "W1AW", "20m", 291),
+        ] {
+            insert_qso(call).id(id).band(band).dxcc(dxcc).insert(&pool).await;
+        }
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (1, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let matrix = entity_band_mode_matrix(&pool, 339).await.unwrap();
+
+        assert_eq!(matrix.entity_name.as_deref(), Some("Japan"));
+        assert_eq!(matrix.slots.len(), 2);
+        assert_eq!(matrix.slots[0].band, "20m");
+        assert_eq!(matrix.slots[0].qso_count, 2);
+        assert!(matrix.slots[0].confirmed);
+        assert_eq!(matrix.slots[1].band, "40m");
+        assert!(!matrix.slots[1].confirmed);
+        assert_eq!(matrix.bands_worked, vec!["20m", "40m"]);
+        assert_eq!(matrix.bands_confirmed, vec!["20m"]);
+    }
+
+    /// Test: CARD confirmations only count once enabled in the accepted-sources setting
+    #[tokio::test]
+    async fn test_entity_matrix_card_source_setting() {
+        let pool = test_pool().await;
+        insert_qso("JA1ABC").id(1).dxcc(339).insert(&pool).await;
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (1, 'CARD', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let matrix = entity_band_mode_matrix(&pool, 339).await.unwrap();
+        assert!(!matrix.slots[0].confirmed);
+
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'LOTW,CARD', datetime('now'))")
+            .bind(CONFIRMATION_SOURCES_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let matrix = entity_band_mode_matrix(&pool, 339).await.unwrap();
+        assert!(matrix.slots[0].confirmed);
+    }
+
+    /// Test: Grid map covers the requested region and marks worked grids
+    #[tokio::test]
+    async fn test_grid_map_marks_worked() {
+        let pool = test_pool().await;
+        for (id, grid) in [(1, "FN31pr"), (2, "fn32"), (3, "EM12"), (4, "FN")] {
+            insert_qso(&format!("W{}AW", id)).id(id).band("6m").set("gridsquare", grid).insert(&pool).await;
+        }
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (1, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let map = grid_map_data(&pool, "FN31", 1, GridPrecision::Square).await.unwrap();
+        assert_eq!((map.rows, map.cols), (3, 3));
+        assert_eq!(map.cells.len(), 9);
+        assert_eq!(map.worked, 2);
+        assert_eq!(map.confirmed, 1);
+        let fn31 = map.cells.iter().find(|c| c.grid == "FN31").unwrap();
+        assert!(fn31.worked && fn31.confirmed);
+        let fn32 = map.cells.iter().find(|c| c.grid == "FN32").unwrap();
+        assert!(fn32.worked && !fn32.confirmed);
+        assert_eq!((fn32.row, fn32.col), (0, 1));
+
+        // At subsquare precision only the 6-char grid places
+        let map = grid_map_data(&pool, "FN31pr", 2, GridPrecision::Subsquare).await.unwrap();
+        assert_eq!(map.cells.len(), 25);
+        assert_eq!(map.worked, 1);
+        assert!(map.cells.iter().any(|c| c.grid == "FN31pr" && c.worked));
+    }
+
+    /// Test: JA prefecture counting against the seeded subdivisions table
+    #[tokio::test]
+    async fn test_subdivision_progress_ja_prefectures() {
+        let pool = test_pool().await;
+
+        // Tokyo twice (one confirmed), Hokkaido as "1", Osaka unconfirmed,
+        // a non-JA QSO with a state and a JA QSO without one
+        for (id, call, dxcc, state) in [
+            (1, "JA1AAA", 339, Some("10")),
+            (2, "JA1BBB", 339, Some("10")),
+            (3, "JA8CCC", 339, Some("1")),
+            (4, "JA3DDD", 339, Some("25")),
+            (5, "W1AW", 291, Some("10")),
+            (6, "JA2EEE", 339, None),
+        ] {
+            insert_qso(call).id(id).dxcc(dxcc).set("state", state).insert(&pool).await;
+        }
+        for (qso_id, source) in [(2, "LOTW"), (3, "LOTW"), (4, "EQSL")] {
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, ?, 'Y')")
+                .bind(qso_id)
+                .bind(source)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let progress = subdivision_progress(&pool, 339).await.unwrap();
+        assert_eq!(progress.total, 47);
+        assert_eq!(progress.worked, 3);
+        // eQSL isn't an accepted source by default
+        assert_eq!(progress.confirmed, 2);
+
+        let tokyo = progress.subdivisions.iter().find(|s| s.code == "10").unwrap();
+        assert_eq!(tokyo.name, "Tokyo");
+        assert!(tokyo.worked && tokyo.confirmed);
+        let hokkaido = progress.subdivisions.iter().find(|s| s.code == "01").unwrap();
+        assert!(hokkaido.worked && hokkaido.confirmed);
+        let osaka = progress.subdivisions.iter().find(|s| s.code == "25").unwrap();
+        assert!(osaka.worked && !osaka.confirmed);
+
+        assert!(subdivision_progress(&pool, 291).await.is_err());
+    }
+
+    /// Test: A LoTW confirmation for a previously unconfirmed entity shows up in the delta
+    #[tokio::test]
+    async fn test_newly_confirmed_after_sync() {
+        let pool = test_pool().await;
+
+        for (id, call, dxcc, state, grid, cqz) in [
+            (1, "W1AW", 291, Some("CT"), "FN31", 5),
+            (2, "JA1ABC", 339, None, "PM95", 25),
+        ] {
+            insert_qso(call)
+                .id(id)
+                .dxcc(dxcc)
+                .set("state", state)
+                .set("gridsquare", grid)
+                .set("cqz", cqz)
+                .insert(&pool)
+                .await;
+        }
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (1, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let before = confirmed_credits(&pool).await.unwrap();
+        assert!(before.dxcc.contains(&291) && before.states.contains("CT"));
+
+        // Sync confirms the JA QSO
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (2, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let after = confirmed_credits(&pool).await.unwrap();
+
+        let delta = before.newly_confirmed(&after);
+        assert_eq!(delta.new_dxcc_confirmed, ["Japan"]);
+        assert!(delta.new_states_confirmed.is_empty());
+        assert_eq!(delta.new_grids_confirmed, ["PM95"]);
+        assert_eq!(delta.new_zones_confirmed, ["25"]);
+
+        // Nothing new on a repeat sync
+        assert_eq!(after.newly_confirmed(&after), ConfirmationDelta::default());
+    }
+
+    #[test]
+    fn test_milestone_crossing_dxcc_100() {
+        let mut before = ConfirmedCredits::default();
+        before.award_counts.insert("DXCC".to_string(), 99);
+        before.award_counts.insert("WAS".to_string(), 1);
+        let mut after = before.clone();
+        after.award_counts.insert("DXCC".to_string(), 101);
+
+        let delta = before.newly_confirmed(&after);
+        assert_eq!(
+            delta.milestones_reached,
+            [AwardMilestone { award: "DXCC".to_string(), threshold: 100, confirmed: 101 }]
+        );
+
+        // Already past it: nothing to announce
+        let mut later = after.clone();
+        later.award_counts.insert("DXCC".to_string(), 102);
+        assert!(after.newly_confirmed(&later).milestones_reached.is_empty());
+    }
+
+    /// Test: Milestone counts match the award pages: DC isn't a WAS state and
+    /// excluded entities don't count for DXCC
+    #[tokio::test]
+    async fn test_milestone_counts_follow_award_progress() {
+        let pool = test_pool().await;
+        for (id, call, dxcc, state) in [
+            (1, "W3DC", 291, Some("dc")),
+            (2, "W1AW", 291, Some("CT ")),
+            (3, "JA1ABC", 339, None),
+        ] {
+            insert_qso(call).id(id).dxcc(dxcc).set("state", state).insert(&pool).await;
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, 'LOTW', 'Y')")
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, '339', datetime('now'))")
+            .bind(EXCLUDED_ENTITIES_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let credits = confirmed_credits(&pool).await.unwrap();
+        assert_eq!(credits.states, BTreeSet::from(["CT".to_string()]));
+        assert_eq!(credits.award_count("WAS"), 1);
+        assert_eq!(credits.award_count("DXCC"), 1);
+        assert_eq!(was_progress(&pool, None, None).await.worked_states, ["CT"]);
+    }
+
+    #[tokio::test]
+    async fn test_application_one_proof_per_confirmed_slot() {
+        let pool = test_pool().await;
+
+        for (id, call, date, dxcc, state) in [
+            (1, "W1AW", "20260301", 291, Some("CT")),
+            (2, "K1ABC", "20260105", 291, Some("ct")),
+            (3, "W6XYZ", "20260201", 291, Some("CA")),
+            (4, "JA1ABC", "20260110", 339, None),
+            (5, "JA2XYZ", "20260101", 339, None),
+            (6, "G0ABC", "20260101", 223, None),
+        ] {
+            insert_qso(call).id(id).date(date).dxcc(dxcc).set("state", state).insert(&pool).await;
+        }
+        // Everything confirmed except the England QSO; QSO 2 by both LoTW and card
+        for (qso_id, source, rcvd) in [
+            (1, "LOTW", "Y"),
+            (2, "LOTW", "Y"),
+            (2, "CARD", "Y"),
+            (3, "LOTW", "Y"),
+            (4, "LOTW", "Y"),
+            (5, "LOTW", "Y"),
+            (6, "LOTW", "N"),
+        ] {
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd, qsl_rcvd_date) VALUES (?, ?, ?, '2026-04-01')")
+                .bind(qso_id)
+                .bind(source)
+                .bind(rcvd)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let dxcc = dxcc_application(&pool).await.unwrap();
+        let slots: Vec<(&str, i64)> = dxcc.iter().map(|p| (p.slot_name.as_str(), p.qso_id)).collect();
+        assert_eq!(slots, [("Japan", 5), ("United States of America", 2)]);
+        assert_eq!(dxcc[1].confirmation_source, "LOTW");
+        assert_eq!(dxcc[1].confirmation_date.as_deref(), Some("2026-04-01"));
+
+        let was = was_application(&pool).await.unwrap();
+        let slots: Vec<(&str, i64)> = was.iter().map(|p| (p.slot.as_str(), p.qso_id)).collect();
+        assert_eq!(slots, [("CA", 3), ("CT", 2)]);
+    }
+
+    #[test]
+    fn test_parse_credit_granted() {
+        assert_eq!(parse_credit_granted("DXCC,DXCC_BAND"), ["DXCC", "DXCC_BAND"]);
+        assert_eq!(parse_credit_granted(" dxcc-mode , DXCC:LOTW,WAS:CARD,"), ["DXCC_MODE", "DXCC", "WAS"]);
+        assert!(parse_credit_granted("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_granted_credits_vs_local() {
+        let pool = test_pool().await;
+
+        for (id, call, band, dxcc, credit) in [
+            (1, "JA1ABC", "20m", 339, Some("DXCC,DXCC_BAND")),
+            (2, "JA1ABC", "40m", 339, Some("DXCC_BAND")),
+            (3, "G0ABC", "20m", 223, None),
+            (4, "W1AW", "20m", 291, Some("VUCC")),
+        ] {
+            insert_qso(call).id(id).band(band).dxcc(dxcc).insert(&pool).await;
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd, credit_granted) VALUES (?, 'LOTW', 'Y', ?)")
+                .bind(id)
+                .bind(credit)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let credits = granted_credits(&pool).await.unwrap();
+        let summary: Vec<(&str, i64, i64, Option<i64>)> = credits
+            .iter()
+            .map(|c| (c.award.as_str(), c.granted_qsos, c.granted_slots, c.confirmed_slots))
+            .collect();
+        // England and the US are confirmed but not yet granted DXCC credit
+        assert_eq!(
+            summary,
+            [("DXCC", 1, 1, Some(3)), ("DXCC_BAND", 2, 2, Some(4)), ("VUCC", 1, 1, None)]
+        );
+    }
+
+    /// Test: an LoTW confirmation without DXCC credit shows as confirmed-not-granted
+    #[tokio::test]
+    async fn test_reconciliation_confirmation_without_dxcc_credit() {
+        let pool = test_pool().await;
+
+        // Japan granted; England confirmed, credit pending; US granted but its
+        // LoTW record came in unconfirmed
+        for (id, call, dxcc, rcvd, credit) in [
+            (1, "JA1ABC", 339, "Y", Some("DXCC,DXCC_BAND")),
+            (2, "G0ABC", 223, "Y", None),
+            (3, "W1AW", 291, "N", Some("DXCC")),
+        ] {
+            insert_qso(call).id(id).dxcc(dxcc).insert(&pool).await;
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd, credit_granted) VALUES (?, 'LOTW', ?, ?)")
+                .bind(id)
+                .bind(rcvd)
+                .bind(credit)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let rec = confirmation_reconciliation(&pool).await.unwrap();
+        assert_eq!((rec.confirmed, rec.granted), (2, 2));
+        let ids = |e: &[ReconciledEntity]| e.iter().map(|e| e.dxcc).collect::<Vec<_>>();
+        assert_eq!(ids(&rec.confirmed_not_granted), [223]);
+        assert_eq!(ids(&rec.granted_not_confirmed), [291]);
+    }
+
+    /// Test: exclude_test_data drops source 'TEST' QSOs from worked and confirmed counts
+    #[tokio::test]
+    async fn test_progress_excludes_test_data() {
+        let pool = test_pool().await;
+
+        for (id, call, dxcc, state, source) in [
+            (1, "W1AW", 291, "CT", "WSJT-X"),
+            (2, "K5TEST", 291, "TX", "TEST"),
+            (3, "JA1TEST", 339, "", "TEST"),
+        ] {
+            insert_qso(call).id(id).dxcc(dxcc).set("state", state).source(source).insert(&pool).await;
+        }
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (2, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let dxcc = dxcc_progress(&pool, None, None).await;
+        let was = was_progress(&pool, Some("20m"), Some("FT8")).await;
+        assert_eq!((dxcc.worked, dxcc.confirmed), (2, 1));
+        assert_eq!((was.worked, was.confirmed), (2, 1));
+
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'true', datetime('now'))")
+            .bind(crate::commands::settings::EXCLUDE_TEST_DATA_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let dxcc = dxcc_progress(&pool, None, None).await;
+        let was = was_progress(&pool, Some("20m"), Some("FT8")).await;
+        assert_eq!((dxcc.worked, dxcc.confirmed), (1, 0));
+        assert_eq!((was.worked, was.confirmed), (1, 0));
+        assert_eq!(was.worked_states, vec!["CT"]);
+        assert!(confirmed_credits(&pool).await.unwrap().dxcc.is_empty());
+    }
+
+    /// Test: Excluded entities drop out of the DXCC count; ATNO only counts first contacts
+    #[tokio::test]
+    async fn test_dxcc_excluded_entities_and_atno() {
+        let pool = test_pool().await;
+
+        for (id, call, date, band, dxcc) in [
+            (1, "W1AW", "20260101", "20m", 291),
+            (2, "JA1ABC", "20260101", "20m", 339),
+            (3, "JA1XYZ", "20260201", "40m", 339),
+            (4, "G0XYZ", "20260101", "40m", 223),
+        ] {
+            insert_qso(call).id(id).date(date).band(band).dxcc(dxcc).insert(&pool).await;
+        }
+        // Only the later Japan QSO is confirmed
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (1, 'LOTW', 'Y'), (3, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let set = |key: &'static str, value: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query("INSERT OR REPLACE INTO settings (key, value, updated_at) VALUES (?, ?, datetime('now'))")
+                    .bind(key)
+                    .bind(value)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+        };
+
+        let progress = dxcc_progress(&pool, None, None).await;
+        assert_eq!((progress.worked, progress.confirmed), (3, 2));
+
+        // Own entity excluded (junk entries ignored)
+        set(EXCLUDED_ENTITIES_SETTING, "291, x").await;
+        let progress = dxcc_progress(&pool, None, None).await;
+        assert_eq!((progress.worked, progress.confirmed), (2, 1));
+        assert_eq!(dxcc_application(&pool).await.unwrap().len(), 1);
+        assert!(entity_band_mode_matrix(&pool, 291).await.unwrap().slots.is_empty());
+
+        // ATNO: Japan's first QSO was on 20m and is unconfirmed
+        set(ATNO_ONLY_SETTING, "true").await;
+        let progress = dxcc_progress(&pool, None, None).await;
+        assert_eq!((progress.worked, progress.confirmed), (2, 0));
+        assert_eq!(dxcc_progress(&pool, Some("40m"), None).await.worked, 1);
+        assert_eq!(entity_band_mode_matrix(&pool, 339).await.unwrap().bands_worked, vec!["20m"]);
+    }
+
+    /// Test: The latest QSO is returned for each entity, across dates and times
+    #[tokio::test]
+    async fn test_last_contact_per_entity() {
+        let pool = test_pool().await;
+
+        for (call, dxcc, date, time, band) in [
+            ("JA1ABC", 339, "20190501", "1200", "20m"),
+            ("JA2XYZ", 339, "20240310", "0815", "40m"),
+            ("JA3AAA", 339, "20240310", "0700", "15m"),
+            ("G0ABC", 223, "20260101", "2359", "20m"),
+            ("G4XYZ", 223, "20251231", "0001", "80m"),
+        ] {
+            insert_qso(call).date(date).time(time).band(band).dxcc(dxcc).insert(&pool).await;
+        }
+
+        let latest = last_contacts(&pool, None).await.unwrap();
+        let summary: Vec<(i32, &str, &str, &str)> = latest
+            .iter()
+            .map(|c| (c.dxcc, c.call.as_str(), c.qso_date.as_str(), c.band.as_str()))
+            .collect();
+        // Sorted by entity name: Japan, then United Kingdom
+        assert_eq!(summary, [(339, "JA2XYZ", "20240310", "40m"), (223, "G0ABC", "20260101", "20m")]);
+
+        let japan = last_contacts(&pool, Some(339)).await.unwrap();
+        assert_eq!(japan.len(), 1);
+        assert_eq!(japan[0].call, "JA2XYZ");
+        assert!(last_contacts(&pool, Some(291)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_award_export_one_record_per_confirmed_slot() {
+        let pool = test_pool().await;
+
+        for (id, call, dxcc, state) in [
+            (1, "W1AW", 291, "CT"),
+            (2, "K1ABC", 291, "CT"),
+            (3, "W6XYZ", 291, "CA"),
+            (4, "JA1ABC", 339, ""),
+            (5, "G0ABC", 223, ""),
+        ] {
+            insert_qso(call).id(id).date("20260301").dxcc(dxcc).set("state", state).insert(&pool).await;
+        }
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'LOTW,CARD', datetime('now'))")
+            .bind(CONFIRMATION_SOURCES_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (qso_id, source) in [(1, "LOTW"), (2, "LOTW"), (3, "CARD"), (4, "LOTW")] {
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd, qsl_rcvd_date) VALUES (?, ?, 'Y', '2026-04-01')")
+                .bind(qso_id)
+                .bind(source)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        for award in [AwardType::Dxcc, AwardType::Was] {
+            let proofs = award_application(&pool, award).await.unwrap();
+            let adif = award_export_adif(&pool, award).await.unwrap();
+            let records = crate::adif::parse_adif(&adif).unwrap().records;
+            assert_eq!(records.len(), proofs.len());
+            let calls: Vec<&str> = records.iter().map(|r| r.fields["CALL"].as_str()).collect();
+            let expected: Vec<&str> = proofs.iter().map(|p| p.call.as_str()).collect();
+            assert_eq!(calls, expected);
+        }
+
+        let adif = award_export_adif(&pool, AwardType::Was).await.unwrap();
+        let records = crate::adif::parse_adif(&adif).unwrap().records;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].fields["CALL"], "W6XYZ");
+        assert_eq!(records[0].fields["QSL_RCVD"], "Y");
+        assert_eq!(records[0].fields["QSLRDATE"], "20260401");
+        assert_eq!(records[1].fields["LOTW_QSL_RCVD"], "Y");
+        assert_eq!(records[1].fields["LOTW_QSLRDATE"], "20260401");
+    }
+
+    #[test]
+    fn test_parse_enabled_awards() {
+        assert_eq!(parse_enabled_awards(None), PROGRESS_AWARDS.to_vec());
+        assert_eq!(parse_enabled_awards(Some(" ")), PROGRESS_AWARDS.to_vec());
+        assert_eq!(parse_enabled_awards(Some("waz, dxcc,IOTA")), vec!["DXCC", "WAZ"]);
+    }
+
+    /// Test: Only the awards in the profile are computed
+    #[tokio::test]
+    async fn test_all_award_progress_only_enabled() {
+        let pool = test_pool().await;
+
+        for (id, call, band, dxcc, grid, cqz) in [
+            (1, "W1AW", "6m", 291, "FN31", 5),
+            (2, "JA1ABC", "20m", 339, "PM95", 25),
+            (3, "K1ABC", "2m", 291, "FN42", 5),
+        ] {
+            insert_qso(call)
+                .id(id)
+                .band(band)
+                .dxcc(dxcc)
+                .set("gridsquare", grid)
+                .set("cqz", cqz)
+                .insert(&pool)
+                .await;
+        }
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (2, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO settings (key, value, updated_at) VALUES (?, 'wac,waz,vucc', datetime('now'))",
+        )
+        .bind(ENABLED_AWARDS_SETTING)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let progress = all_award_progress(&pool).await.unwrap();
+        assert_eq!(progress.keys().collect::<Vec<_>>(), vec!["VUCC", "WAC", "WAZ"]);
+        let AwardProgress::Count(wac) = &progress["WAC"] else { panic!("WAC isn't a count") };
+        assert_eq!((wac.worked, wac.confirmed, wac.total), (2, 1, 6));
+        let AwardProgress::Count(waz) = &progress["WAZ"] else { panic!("WAZ isn't a count") };
+        assert_eq!((waz.worked, waz.confirmed), (2, 1));
+        // The 20m QSO doesn't count for VUCC
+        let AwardProgress::Vucc(vucc) = &progress["VUCC"] else { panic!("VUCC has the wrong shape") };
+        assert_eq!((vucc.worked, vucc.confirmed, vucc.target), (2, 0, 100));
+
+        assert_eq!(vucc_progress(&pool, Some("6M")).await.unwrap().worked, 1);
+        assert!(vucc_progress(&pool, Some("20m")).await.is_err());
+    }
+
+    /// Test: WAC takes the logged continent over the entity's, e.g. Asian Turkey
+    #[tokio::test]
+    async fn test_wac_prefers_logged_continent() {
+        let pool = test_pool().await;
+        insert_qso("TA2ABC").dxcc(390).set("continent", "as").insert(&pool).await;
+        insert_qso("TA1XYZ").dxcc(390).set("continent", "").insert(&pool).await;
+        insert_qso("JA1ABC").dxcc(339).insert(&pool).await;
+
+        // Asia from both the logged and the entity continent; Europe from Turkey's entity
+        let wac = wac_progress(&pool).await.unwrap();
+        assert_eq!((wac.worked, wac.confirmed), (2, 0));
+
+        sqlx::query("DELETE FROM qsos WHERE call = 'TA1XYZ'").execute(&pool).await.unwrap();
+        assert_eq!(wac_progress(&pool).await.unwrap().worked, 1);
+    }
+
+    /// Test: Two snapshots around a new confirmation both show up in the history
+    #[tokio::test]
+    async fn test_award_snapshot_history() {
+        let pool = test_pool().await;
+
+        insert_qso("JA1ABC").id(1).dxcc(339).set("cqz", 25).insert(&pool).await;
+
+        let first = take_snapshot(&pool).await.unwrap();
+        // WWFF has nothing confirmed to chart
+        assert_eq!(first.iter().map(|s| s.award.as_str()).collect::<Vec<_>>(), vec!["DXCC", "WAS", "WAC", "WAZ", "VUCC"]);
+
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (1, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        take_snapshot(&pool).await.unwrap();
+
+        let history = award_history(&pool, "dxcc").await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!((history[0].worked, history[0].confirmed), (1, 0));
+        assert_eq!((history[1].worked, history[1].confirmed), (1, 1));
+        assert!(history.iter().all(|s| s.award == "DXCC"));
+        assert_eq!(award_history(&pool, "WAZ").await.unwrap().len(), 2);
+    }
+
+    /// Test: Phone, CW and Digital DXCC are counted separately
+    #[tokio::test]
+    async fn test_dxcc_by_mode_group() {
+        let pool = test_pool().await;
+
+        for (id, call, mode, dxcc, confirmed) in [
+            (1, "JA1ABC", "SSB", 339, true),
+            (2, "JA1ABC", "CW", 339, false),
+            (3, "JA1ABC", "FT8", 339, true),
+            (4, "G0XYZ", "FM", 223, false),
+            (5, "G0XYZ", "RTTY", 223, true),
+            (6, "DL1AB", "SSTV", 230, true),
+            (7, "VK2ABC", "usb", 150, false),
+        ] {
+            insert_qso(call).id(id).mode(mode).dxcc(dxcc).insert(&pool).await;
+            if confirmed {
+                sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, 'LOTW', 'Y')")
+                    .bind(id)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let groups = dxcc_by_mode_group(&pool).await.unwrap();
+        let counts: Vec<(&str, i64, i64)> =
+            groups.iter().map(|g| (g.mode_group.as_str(), g.worked, g.confirmed)).collect();
+        // SSTV counts for none of the three
+        assert_eq!(counts, vec![("PHONE", 3, 1), ("CW", 1, 0), ("DIGITAL", 2, 2)]);
+        assert!(groups.iter().all(|g| g.total == get_current_entity_count() as i64));
+    }
+
+    /// Test: VUCC counts their grid only, never my_gridsquare
+    #[tokio::test]
+    async fn test_vucc_ignores_my_gridsquare() {
+        let pool = test_pool().await;
+
+        for (id, call, grid, my_grid) in [
+            (1, "W1AW", Some("FN31"), Some("EM12")),
+            (2, "K2ABC", None, Some("EM13")),
+            (3, "N3XYZ", Some(""), Some("EM14")),
+            (4, "W4DEF", Some("EM73pr"), None),
+        ] {
+            insert_qso(call)
+                .id(id)
+                .band("6m")
+                .set("gridsquare", grid)
+                .set("my_gridsquare", my_grid)
+                .insert(&pool)
+                .await;
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, 'LOTW', 'Y')")
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let progress = vucc_progress(&pool, Some("6m")).await.unwrap();
+        // FN31 and EM73; none of the EM12/EM13/EM14 station grids
+        assert_eq!((progress.worked, progress.confirmed), (2, 2));
+        assert_eq!(vucc_progress(&pool, None).await.unwrap().worked, 2);
+    }
+
+    #[test]
+    fn test_next_milestone_thresholds() {
+        let was = next_milestone("WAS", 43, 50).unwrap();
+        assert_eq!((was.level.as_str(), was.threshold, was.remaining), ("WAS", 50, 7));
+        assert_eq!(next_milestone("WAS", 50, 50), None);
+
+        let dxcc = next_milestone("DXCC", 93, 340).unwrap();
+        assert_eq!((dxcc.level.as_str(), dxcc.remaining), ("DXCC", 7));
+        let dxcc = next_milestone("DXCC", 100, 340).unwrap();
+        assert_eq!((dxcc.level.as_str(), dxcc.remaining), ("DXCC 150", 50));
+        let dxcc = next_milestone("DXCC", 308, 340).unwrap();
+        assert_eq!((dxcc.level.as_str(), dxcc.remaining), ("DXCC 325", 17));
+        let dxcc = next_milestone("DXCC", 325, 340).unwrap();
+        assert_eq!((dxcc.level.as_str(), dxcc.threshold, dxcc.remaining), ("Honor Roll", 331, 6));
+        let dxcc = next_milestone("DXCC", 331, 340).unwrap();
+        assert_eq!((dxcc.level.as_str(), dxcc.remaining), ("#1 Honor Roll", 9));
+        assert_eq!(next_milestone("DXCC", 340, 340), None);
+
+        let vucc = next_milestone("VUCC", 104, 100).unwrap();
+        assert_eq!((vucc.level.as_str(), vucc.remaining), ("VUCC 125", 21));
+        assert_eq!(next_milestone("WAC", 5, 6).unwrap().remaining, 1);
+    }
+
+    /// Test: Next milestones follow the confirmed counts of the enabled awards
+    #[tokio::test]
+    async fn test_next_milestones() {
+        let pool = test_pool().await;
+
+        insert_qso("W1AW").id(1).dxcc(291).set("state", "CT").insert(&pool).await;
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (1, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'DXCC,WAS,WWFF', datetime('now'))")
+            .bind(ENABLED_AWARDS_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let milestones = next_milestones(&pool).await.unwrap();
+        assert_eq!(
+            milestones.iter().map(|m| (m.award.as_str(), m.confirmed, m.remaining)).collect::<Vec<_>>(),
+            vec![("DXCC", 1, 99), ("WAS", 1, 49)]
+        );
+    }
+}
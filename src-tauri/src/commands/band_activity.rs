@@ -2,14 +2,32 @@
 //!
 //! This module handles:
 //! - save_band_activity: Store TX/RX messages from WSJT-X
+//! - save_rx_decodes: Store one WSJT-X cycle's RX decodes in a single transaction
 //! - get_recent_activity: Retrieve recent band activity
 //! - prune_band_activity: Clean up old messages
+//! - get_band_activity_stats: Decode counts and low-confidence decode rate
+//! - get_recent_entities: Distinct DXCC entities decoded recently, freshest first
+//! - export_band_activity: Stored decodes over a time range as CSV or JSON, for offline analysis
+//! - Decode filtering (`band_activity_filter_mode` setting) to limit RX noise
 
 use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Sqlite};
 use tauri::command;
 
+use super::settings::{counted_qso_filter, read_setting};
 use super::state::AppState;
+use super::time_utils::format_time_from_ms;
+use super::worked_cache::WorkedCache;
+use crate::udp::wsjtx::MessageType;
+
+/// Settings key controlling which RX decodes are persisted
+pub const FILTER_MODE_SETTING: &str = "band_activity_filter_mode";
+
+/// Age in minutes beyond which prune_band_activity deletes by default
+pub const DEFAULT_PRUNE_MINUTES: i32 = 60;
+
+/// Quiet time after a burst of decodes at which the cycle's decodes are written
+pub const DECODE_CYCLE_GAP: std::time::Duration = std::time::Duration::from_secs(3);
 
 // ============================================================================
 // Data Types
@@ -30,10 +48,220 @@ pub struct BandActivityMessage {
     pub mode: Option<String>,
 }
 
+/// A DXCC entity heard in recent decodes
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentEntity {
+    pub dxcc: i32,
+    pub name: Option<String>,
+    /// created_at of the first decode in the window (UTC, "YYYY-MM-DD HH:MM:SS")
+    pub first_seen: String,
+    pub last_seen: String,
+    /// Station heard first
+    pub first_call: String,
+    pub decode_count: i64,
+    /// Entity not in the log yet
+    pub needed: bool,
+}
+
+/// Aggregate RX decode statistics over a time window
+#[derive(Debug, Clone, Serialize)]
+pub struct BandActivityStats {
+    pub minutes: i32,
+    pub rx_decodes: i64,
+    pub low_confidence_decodes: i64,
+    /// low_confidence_decodes / rx_decodes (0.0 when nothing decoded)
+    pub low_confidence_rate: f64,
+}
+
+/// One stored message in a band activity export
+#[derive(Debug, Clone, Serialize, PartialEq, sqlx::FromRow)]
+pub struct BandActivityExportRow {
+    /// When it was stored (UTC, "YYYY-MM-DD HH:MM:SS")
+    pub created_at: String,
+    /// HHMMSS from WSJT-X
+    pub time_utc: String,
+    pub direction: String,
+    pub call: Option<String>,
+    pub dx_call: Option<String>,
+    /// Grid from the message itself, when it carried one
+    #[sqlx(skip)]
+    pub grid: Option<String>,
+    pub snr: Option<i32>,
+    /// Dial frequency plus audio offset, in Hz
+    pub freq_hz: Option<f64>,
+    pub mode: Option<String>,
+    pub message: String,
+}
+
+/// Output format for export_band_activity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BandActivityExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// An RX decode waiting to be written with the rest of its cycle
+#[derive(Debug, Clone)]
+pub struct RxDecodeRow {
+    /// Cycle start, milliseconds since UTC midnight
+    pub time_ms: u32,
+    pub message: String,
+    pub snr: i32,
+    pub delta_freq: i32,
+    pub de_call: String,
+    pub dx_call: Option<String>,
+    pub mode: String,
+    pub low_confidence: bool,
+    pub dxcc: Option<i32>,
+    /// Dial frequency in Hz, None until the instance has sent a Status
+    pub dial_freq: Option<f64>,
+}
+
+/// RX decodes of the current WSJT-X cycle. FT8/FT4 decodes arrive in a burst
+/// per cycle, all stamped with the cycle's start time, so a decode with a new
+/// time closes the cycle; the listener also closes it after DECODE_CYCLE_GAP
+/// without decodes.
+#[derive(Debug, Default)]
+pub struct DecodeCycleBatch {
+    rows: Vec<RxDecodeRow>,
+}
+
+impl DecodeCycleBatch {
+    /// Add a decode; returns the previous cycle's decodes when this one starts a new cycle
+    pub fn push(&mut self, row: RxDecodeRow) -> Option<Vec<RxDecodeRow>> {
+        let finished = match self.rows.first() {
+            Some(first) if first.time_ms != row.time_ms => Some(std::mem::take(&mut self.rows)),
+            _ => None,
+        };
+        self.rows.push(row);
+        finished
+    }
+
+    /// Take the decodes collected so far, e.g. once the burst has gone quiet
+    pub fn take(&mut self) -> Vec<RxDecodeRow> {
+        std::mem::take(&mut self.rows)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+/// Which RX decodes get written to `band_activity`. TX is always stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BandActivityFilterMode {
+    /// Store every decode (default)
+    All,
+    /// Store only CQ calls
+    CqOnly,
+    /// Store CQ calls, messages involving my callsign, and needed entities/grids
+    Relevant,
+}
+
+impl BandActivityFilterMode {
+    /// Parse the setting value ("all", "cq-only", "relevant"); unknown values fall back to All
+    pub fn from_setting(value: Option<&str>) -> Self {
+        match value.map(|v| v.trim().to_lowercase()).as_deref() {
+            Some("cq-only") => Self::CqOnly,
+            Some("relevant") => Self::Relevant,
+            _ => Self::All,
+        }
+    }
+}
+
 // ============================================================================
 // Internal Functions
 // ============================================================================
 
+/// Decide whether an RX decode should be persisted under the given filter mode.
+/// `is_needed` is true when the sender is a new DXCC entity or grid for the log.
+pub fn should_store_rx_decode(
+    mode: BandActivityFilterMode,
+    msg_type: MessageType,
+    de_call: &str,
+    dx_call: Option<&str>,
+    my_call: Option<&str>,
+    is_needed: bool,
+) -> bool {
+    match mode {
+        BandActivityFilterMode::All => true,
+        BandActivityFilterMode::CqOnly => msg_type == MessageType::Cq,
+        BandActivityFilterMode::Relevant => {
+            if msg_type == MessageType::Cq || is_needed {
+                return true;
+            }
+            match my_call.filter(|c| !c.is_empty()) {
+                Some(me) => {
+                    de_call.eq_ignore_ascii_case(me)
+                        || dx_call.is_some_and(|dx| dx.eq_ignore_ascii_case(me))
+                }
+                None => false,
+            }
+        }
+    }
+}
+
+/// Read the band activity filter mode from settings
+pub async fn get_filter_mode(pool: &Pool<Sqlite>) -> BandActivityFilterMode {
+    let value = read_setting(pool, FILTER_MODE_SETTING).await;
+    BandActivityFilterMode::from_setting(value.as_deref())
+}
+
+/// Settings the decode filter needs, read once per decode cycle
+#[derive(Debug, Clone)]
+pub struct DecodeFilterSettings {
+    /// Cycle they were read for, milliseconds since UTC midnight
+    pub time_ms: u32,
+    pub mode: BandActivityFilterMode,
+    /// counted_qso_filter on `q`, for the needed-grid check
+    pub counted: String,
+}
+
+impl DecodeFilterSettings {
+    pub async fn read(pool: &Pool<Sqlite>, time_ms: u32) -> Self {
+        Self { time_ms, mode: get_filter_mode(pool).await, counted: counted_qso_filter(pool, "q").await }
+    }
+}
+
+/// Check whether a decoded station is a needed DXCC entity or grid (never worked).
+/// The entity comes from the worked index; the grid is a range lookup on the
+/// gridsquare index over counted QSOs (`counted` from counted_qso_filter on `q`).
+pub async fn is_needed_decode(
+    pool: &Pool<Sqlite>,
+    worked: &WorkedCache,
+    counted: &str,
+    dxcc: Option<i32>,
+    grid: Option<&str>,
+) -> bool {
+    if let Some(dxcc) = dxcc {
+        if worked.is_new_dxcc(pool, dxcc).await.unwrap_or(false) {
+            return true;
+        }
+    }
+
+    let Some(field_square) = grid.and_then(|g| g.get(..4)).map(str::to_uppercase) else {
+        return false;
+    };
+    let Some(last) = field_square.chars().last() else {
+        return false;
+    };
+    // [FN31, FN3:) holds every grid starting FN31; lowercase grids are checked too
+    let next = format!("{}{}", &field_square[..3], char::from_u32(last as u32 + 1).unwrap_or(last));
+    let worked_grid: bool = sqlx::query_scalar(&format!(
+        r#"SELECT EXISTS(SELECT 1 FROM qsos q
+           WHERE ((q.gridsquare >= ?1 AND q.gridsquare < ?2) OR (q.gridsquare >= LOWER(?1) AND q.gridsquare < LOWER(?2))){})"#,
+        counted
+    ))
+    .bind(&field_square)
+    .bind(&next)
+    .fetch_one(pool)
+    .await
+    .unwrap_or(true);
+    !worked_grid
+}
+
 /// Save a band activity message (TX or RX)
 /// This is called internally by the UDP listener, not exposed as a command
 pub async fn save_band_activity(
@@ -48,11 +276,13 @@ pub async fn save_band_activity(
     dx_call: Option<&str>,
     dial_freq: Option<f64>,
     mode: Option<&str>,
+    low_confidence: Option<bool>,
+    dxcc: Option<i32>,
 ) -> Result<(), String> {
     sqlx::query(
         r#"INSERT INTO band_activity 
-           (time_utc, time_ms, direction, message, snr, delta_freq, de_call, dx_call, dial_freq, mode)
-           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+           (time_utc, time_ms, direction, message, snr, delta_freq, de_call, dx_call, dial_freq, mode, low_confidence, dxcc)
+           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
     )
     .bind(time_utc)
     .bind(time_ms)
@@ -64,6 +294,8 @@ pub async fn save_band_activity(
     .bind(dx_call)
     .bind(dial_freq)
     .bind(mode)
+    .bind(low_confidence)
+    .bind(dxcc)
     .execute(pool)
     .await
     .map_err(|e| format!("Failed to save band activity: {}", e))?;
@@ -71,6 +303,159 @@ pub async fn save_band_activity(
     Ok(())
 }
 
+/// Write a cycle's RX decodes in one transaction, returning how many were stored
+pub async fn save_rx_decodes(pool: &Pool<Sqlite>, rows: &[RxDecodeRow]) -> Result<usize, String> {
+    if rows.is_empty() {
+        return Ok(0);
+    }
+    let mut tx = pool.begin().await.map_err(|e| format!("Failed to save band activity: {}", e))?;
+    for row in rows {
+        sqlx::query(
+            r#"INSERT INTO band_activity
+               (time_utc, time_ms, direction, message, snr, delta_freq, de_call, dx_call, dial_freq, mode, low_confidence, dxcc)
+               VALUES (?, ?, 'rx', ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(format_time_from_ms(row.time_ms))
+        .bind(row.time_ms as i64)
+        .bind(&row.message)
+        .bind(row.snr)
+        .bind(row.delta_freq)
+        .bind(&row.de_call)
+        .bind(&row.dx_call)
+        .bind(row.dial_freq)
+        .bind(&row.mode)
+        .bind(row.low_confidence)
+        .bind(row.dxcc)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to save band activity: {}", e))?;
+    }
+    tx.commit().await.map_err(|e| format!("Failed to save band activity: {}", e))?;
+
+    Ok(rows.len())
+}
+
+/// Distinct entities in RX decodes over the last `minutes`, freshest first.
+/// Rows stored before the dxcc column existed are resolved from the callsign.
+pub async fn recent_entities(pool: &Pool<Sqlite>, minutes: i32) -> Result<Vec<RecentEntity>, String> {
+    let rows: Vec<(Option<i32>, String, String)> = sqlx::query_as(
+        r#"SELECT dxcc, de_call, created_at FROM band_activity
+           WHERE direction = 'rx' AND de_call IS NOT NULL AND de_call != ''
+             AND created_at > datetime('now', ? || ' minutes')
+           ORDER BY created_at ASC, id ASC"#,
+    )
+    .bind(format!("-{}", minutes))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to get recent entities: {}", e))?;
+
+    let worked: std::collections::HashSet<i32> =
+        sqlx::query_scalar::<_, i32>("SELECT DISTINCT dxcc FROM qsos WHERE dxcc IS NOT NULL")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("Failed to get worked entities: {}", e))?
+            .into_iter()
+            .collect();
+
+    let mut entities: Vec<RecentEntity> = Vec::new();
+    let mut index: std::collections::HashMap<i32, usize> = std::collections::HashMap::new();
+    for (dxcc, call, created_at) in rows {
+        let Some(dxcc) = dxcc.or_else(|| crate::reference::lookup_call_full(&call).dxcc_as_i32()) else {
+            continue;
+        };
+        match index.get(&dxcc) {
+            Some(&i) => {
+                entities[i].last_seen = created_at;
+                entities[i].decode_count += 1;
+            }
+            None => {
+                index.insert(dxcc, entities.len());
+                entities.push(RecentEntity {
+                    dxcc,
+                    name: crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc))
+                        .map(|e| e.name.to_string()),
+                    first_seen: created_at.clone(),
+                    last_seen: created_at,
+                    first_call: call,
+                    decode_count: 1,
+                    needed: !worked.contains(&dxcc),
+                });
+            }
+        }
+    }
+
+    entities.reverse();
+    Ok(entities)
+}
+
+/// Stored messages with `created_at` between `from` and `to` (inclusive), oldest
+/// first. Bounds are "YYYY-MM-DD" or "YYYY-MM-DD HH:MM:SS" UTC; a date-only `to`
+/// covers that whole day.
+pub async fn band_activity_export_rows(
+    pool: &Pool<Sqlite>,
+    from: &str,
+    to: &str,
+) -> Result<Vec<BandActivityExportRow>, String> {
+    let to = if to.trim().len() == 10 { format!("{} 23:59:59", to.trim()) } else { to.trim().to_string() };
+    let mut rows: Vec<BandActivityExportRow> = sqlx::query_as(
+        r#"SELECT created_at, time_utc, direction, de_call AS call, dx_call, snr,
+                  dial_freq + COALESCE(delta_freq, 0) AS freq_hz, mode, message
+           FROM band_activity
+           WHERE created_at >= ? AND created_at <= ?
+           ORDER BY created_at ASC, id ASC"#,
+    )
+    .bind(from.trim())
+    .bind(&to)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to export band activity: {}", e))?;
+
+    for row in &mut rows {
+        row.grid = crate::udp::parse_ft8_message(&row.message)
+            .and_then(|(_, _, grid, _)| grid)
+            .filter(|g| crate::udp::wsjtx::is_valid_grid(g));
+    }
+    Ok(rows)
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render export rows as CSV (with a header line) or a JSON array
+pub fn render_band_activity_export(
+    rows: &[BandActivityExportRow],
+    format: BandActivityExportFormat,
+) -> Result<String, String> {
+    if format == BandActivityExportFormat::Json {
+        return serde_json::to_string_pretty(rows).map_err(|e| e.to_string());
+    }
+
+    let opt = |v: &Option<String>| v.as_deref().map(csv_field).unwrap_or_default();
+    let mut out = String::from("created_at,time_utc,direction,call,dx_call,grid,snr,freq_hz,mode,message\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&row.created_at),
+            csv_field(&row.time_utc),
+            csv_field(&row.direction),
+            opt(&row.call),
+            opt(&row.dx_call),
+            opt(&row.grid),
+            row.snr.map(|s| s.to_string()).unwrap_or_default(),
+            row.freq_hz.map(|f| format!("{:.0}", f)).unwrap_or_default(),
+            opt(&row.mode),
+            csv_field(&row.message),
+        ));
+    }
+    Ok(out)
+}
+
 // ============================================================================
 // Commands
 // ============================================================================
@@ -143,7 +528,21 @@ pub async fn get_recent_activity(
         .collect())
 }
 
-/// Clear old band activity messages (older than specified minutes)
+/// Delete band activity older than `minutes`, returning how many rows went
+pub async fn delete_band_activity_older_than(pool: &Pool<Sqlite>, minutes: i32) -> Result<i64, String> {
+    let result = sqlx::query(
+        r#"DELETE FROM band_activity WHERE created_at < datetime('now', ? || ' minutes')"#,
+    )
+    .bind(format!("-{}", minutes))
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to prune band activity: {}", e))?;
+
+    Ok(result.rows_affected() as i64)
+}
+
+/// Clear old band activity messages (older than specified minutes).
+/// Pruned rows are gone for good; export_band_activity them first to keep them.
 #[command]
 pub async fn prune_band_activity(
     state: tauri::State<'_, AppState>,
@@ -152,15 +551,283 @@ pub async fn prune_band_activity(
     let db_guard = state.db.lock().await;
     let pool = db_guard.as_ref().ok_or("Database not initialized")?;
 
-    let mins = older_than_minutes.unwrap_or(60);
+    delete_band_activity_older_than(pool, older_than_minutes.unwrap_or(DEFAULT_PRUNE_MINUTES)).await
+}
 
-    let result = sqlx::query(
-        r#"DELETE FROM band_activity WHERE created_at < datetime('now', ? || ' minutes')"#,
+/// Get RX decode counts and the low-confidence decode rate (signal quality indicator)
+#[command]
+pub async fn get_band_activity_stats(
+    state: tauri::State<'_, AppState>,
+    minutes: Option<i32>,
+) -> Result<BandActivityStats, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let mins = minutes.unwrap_or(60);
+
+    let (rx_decodes, low_confidence_decodes): (i64, i64) = sqlx::query_as(
+        r#"SELECT COUNT(*), COALESCE(SUM(CASE WHEN low_confidence = 1 THEN 1 ELSE 0 END), 0)
+           FROM band_activity
+           WHERE direction = 'rx' AND created_at > datetime('now', ? || ' minutes')"#,
     )
     .bind(format!("-{}", mins))
-    .execute(pool)
+    .fetch_one(pool)
     .await
-    .map_err(|e| format!("Failed to prune band activity: {}", e))?;
+    .map_err(|e| format!("Failed to get band activity stats: {}", e))?;
 
-    Ok(result.rows_affected() as i64)
+    Ok(BandActivityStats {
+        minutes: mins,
+        rx_decodes,
+        low_confidence_decodes,
+        low_confidence_rate: if rx_decodes > 0 {
+            low_confidence_decodes as f64 / rx_decodes as f64
+        } else {
+            0.0
+        },
+    })
+}
+
+/// Distinct DXCC entities heard in the last `minutes` (default 15), freshest first
+#[command]
+pub async fn get_recent_entities(
+    state: tauri::State<'_, AppState>,
+    minutes: Option<i32>,
+) -> Result<Vec<RecentEntity>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    recent_entities(pool, minutes.unwrap_or(15)).await
+}
+
+/// Export stored decodes between `from` and `to` (UTC) as CSV (default) or JSON
+#[command]
+pub async fn export_band_activity(
+    state: tauri::State<'_, AppState>,
+    from: String,
+    to: String,
+    format: Option<BandActivityExportFormat>,
+) -> Result<String, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let rows = band_activity_export_rows(pool, &from, &to).await?;
+    log::info!("Exported {} band activity rows ({} to {})", rows.len(), from, to);
+    render_band_activity_export(&rows, format.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    #[test]
+    fn test_filter_mode_from_setting() {
+        assert_eq!(BandActivityFilterMode::from_setting(None), BandActivityFilterMode::All);
+        assert_eq!(BandActivityFilterMode::from_setting(Some("all")), BandActivityFilterMode::All);
+        assert_eq!(BandActivityFilterMode::from_setting(Some("cq-only")), BandActivityFilterMode::CqOnly);
+        assert_eq!(BandActivityFilterMode::from_setting(Some("Relevant")), BandActivityFilterMode::Relevant);
+        assert_eq!(BandActivityFilterMode::from_setting(Some("bogus")), BandActivityFilterMode::All);
+    }
+
+    #[test]
+    fn test_filter_all_stores_everything() {
+        let mode = BandActivityFilterMode::All;
+        // "W1AW K5ABC -12"
+        assert!(should_store_rx_decode(mode, MessageType::Report, "K5ABC", Some("W1AW"), Some("N0CALL"), false));
+        // "CQ K5ABC EM12"
+        assert!(should_store_rx_decode(mode, MessageType::Cq, "K5ABC", None, None, false));
+    }
+
+    #[test]
+    fn test_filter_cq_only() {
+        let mode = BandActivityFilterMode::CqOnly;
+        // "CQ K5ABC EM12"
+        assert!(should_store_rx_decode(mode, MessageType::Cq, "K5ABC", None, Some("N0CALL"), false));
+        // "N0CALL K5ABC EM12" - addressed to me, but not a CQ
+        assert!(!should_store_rx_decode(mode, MessageType::Grid, "K5ABC", Some("N0CALL"), Some("N0CALL"), false));
+        // "W1AW K5ABC RR73"
+        assert!(!should_store_rx_decode(mode, MessageType::End, "K5ABC", Some("W1AW"), Some("N0CALL"), true));
+    }
+
+    #[test]
+    fn test_filter_relevant() {
+        let mode = BandActivityFilterMode::Relevant;
+        // "CQ K5ABC EM12"
+        assert!(should_store_rx_decode(mode, MessageType::Cq, "K5ABC", None, Some("N0CALL"), false));
+        // "N0CALL K5ABC -05" - calling me
+        assert!(should_store_rx_decode(mode, MessageType::Report, "K5ABC", Some("n0call"), Some("N0CALL"), false));
+        // "K5ABC N0CALL R-07" - my own transmission echoed back
+        assert!(should_store_rx_decode(mode, MessageType::Report, "N0CALL", Some("K5ABC"), Some("N0CALL"), false));
+        // "W1AW JA1XYZ PM95" - needed entity
+        assert!(should_store_rx_decode(mode, MessageType::Grid, "JA1XYZ", Some("W1AW"), Some("N0CALL"), true));
+        // "W1AW K5ABC RR73" - unrelated, already worked
+        assert!(!should_store_rx_decode(mode, MessageType::End, "K5ABC", Some("W1AW"), Some("N0CALL"), false));
+        // No callsign known yet
+        assert!(!should_store_rx_decode(mode, MessageType::Report, "K5ABC", Some("W1AW"), None, false));
+    }
+
+    /// Test: Needed entities come from the worked index; grids match any
+    /// counted QSO in the square, whatever its case, but not SWL reports
+    #[tokio::test]
+    async fn test_is_needed_decode() {
+        let pool = test_pool().await;
+        insert_qso("JA1ABC").dxcc(339).set("gridsquare", "pm95xx").insert(&pool).await;
+        insert_qso("K5ABC").dxcc(291).set("gridsquare", "EM12").insert(&pool).await;
+        insert_qso("G0XYZ").dxcc(223).set("gridsquare", "IO91").source("SWL").insert(&pool).await;
+        let worked = WorkedCache::new();
+        let counted = counted_qso_filter(&pool, "q").await;
+
+        assert!(!is_needed_decode(&pool, &worked, &counted, Some(339), Some("PM95")).await);
+        assert!(!is_needed_decode(&pool, &worked, &counted, Some(291), Some("em12ab")).await);
+        assert!(is_needed_decode(&pool, &worked, &counted, Some(230), None).await);
+        assert!(is_needed_decode(&pool, &worked, &counted, Some(291), Some("EM13")).await);
+        // Heard as an SWL only: still needed
+        assert!(is_needed_decode(&pool, &worked, &counted, Some(223), None).await);
+        assert!(is_needed_decode(&pool, &worked, &counted, None, Some("IO91")).await);
+        assert!(!is_needed_decode(&pool, &worked, &counted, None, Some("RR")).await);
+    }
+
+    #[tokio::test]
+    async fn test_decode_cycle_written_in_one_batch() {
+        let pool = test_pool().await;
+
+        let decode = |time_ms: u32, call: &str| RxDecodeRow {
+            time_ms,
+            message: format!("CQ {} EM12", call),
+            snr: -10,
+            delta_freq: 1500,
+            de_call: call.to_string(),
+            dx_call: None,
+            mode: "~".to_string(),
+            low_confidence: false,
+            dxcc: Some(291),
+            dial_freq: Some(14_074_000.0),
+        };
+
+        // A burst of decodes from the 12:00:00 cycle, then the first of 12:00:15
+        let mut batch = DecodeCycleBatch::default();
+        let mut writes = Vec::new();
+        for (time_ms, call) in [
+            (43_200_000, "K5ABC"),
+            (43_200_000, "W1XYZ"),
+            (43_200_000, "N0DEF"),
+            (43_200_000, "KD2GHI"),
+            (43_215_000, "K5ABC"),
+        ] {
+            if let Some(cycle) = batch.push(decode(time_ms, call)) {
+                writes.push(save_rx_decodes(&pool, &cycle).await.unwrap());
+            }
+        }
+        assert_eq!(writes, [4]);
+
+        let stored: Vec<(String, i64)> =
+            sqlx::query_as("SELECT time_utc, time_ms FROM band_activity WHERE direction = 'rx' ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(stored, vec![("120000".to_string(), 43_200_000); 4]);
+
+        // The next cycle is written once the band goes quiet
+        assert_eq!(save_rx_decodes(&pool, &batch.take()).await.unwrap(), 1);
+        assert!(batch.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recent_entities() {
+        let pool = test_pool().await;
+
+        insert_qso("JA1ABC").dxcc(339).insert(&pool).await;
+
+        for (call, dxcc, minutes_ago) in [
+            ("JA1XYZ", Some(339), 90), // outside the window
+            ("JA2ABC", Some(339), 10),
+            ("VK2XYZ", Some(150), 8),
+            ("JA3DEF", Some(339), 5),
+            ("G0ABC", None, 2), // stored before entities were recorded
+        ] {
+            sqlx::query(
+                r#"INSERT INTO band_activity (time_utc, direction, message, de_call, dxcc, created_at)
+                   VALUES ('120000', 'rx', 'CQ', ?, ?, datetime('now', ? || ' minutes'))"#,
+            )
+            .bind(call)
+            .bind(dxcc)
+            .bind(format!("-{}", minutes_ago))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let entities = recent_entities(&pool, 30).await.unwrap();
+        let summary: Vec<(i32, &str, i64, bool)> = entities
+            .iter()
+            .map(|e| (e.dxcc, e.first_call.as_str(), e.decode_count, e.needed))
+            .collect();
+        assert_eq!(summary, [(223, "G0ABC", 1, true), (150, "VK2XYZ", 1, true), (339, "JA2ABC", 2, false)]);
+        assert_eq!(entities[1].name.as_deref(), Some("Australia"));
+    }
+
+    #[tokio::test]
+    async fn test_export_band_activity() {
+        let pool = test_pool().await;
+
+        for (created_at, direction, message, snr, delta, de_call, dx_call) in [
+            ("2026-10-14 23:59:45", "rx", "CQ JA1XYZ PM95", Some(-3), Some(900), Some("JA1XYZ"), None),
+            ("2026-10-15 12:00:15", "rx", "CQ K5ABC EM12", Some(-12), Some(1500), Some("K5ABC"), None),
+            ("2026-10-15 12:00:30", "tx", "K5ABC W1AW FN31", None, Some(1200), Some("W1AW"), Some("K5ABC")),
+            ("2026-10-15 12:01:00", "rx", "W1AW K5ABC R-05", Some(-10), Some(1500), Some("K5ABC"), Some("W1AW")),
+            ("2026-10-16 00:00:00", "rx", "CQ VK2XYZ QF56", Some(-20), Some(600), Some("VK2XYZ"), None),
+        ] {
+            let time_utc = created_at[11..].replace(':', "");
+            if direction == "rx" {
+                let part = |i: usize| time_utc[i..i + 2].parse::<u32>().unwrap();
+                let row = RxDecodeRow {
+                    time_ms: (part(0) * 3600 + part(2) * 60 + part(4)) * 1000,
+                    message: message.to_string(),
+                    snr: snr.unwrap(),
+                    delta_freq: delta.unwrap(),
+                    de_call: de_call.unwrap().to_string(),
+                    dx_call: dx_call.map(str::to_string),
+                    mode: "FT8".to_string(),
+                    low_confidence: false,
+                    dxcc: None,
+                    dial_freq: Some(14_074_000.0),
+                };
+                save_rx_decodes(&pool, &[row]).await.unwrap();
+            } else {
+                save_band_activity(
+                    &pool, &time_utc, None, direction, message, snr, delta, de_call, dx_call,
+                    Some(14_074_000.0), Some("FT8"), None, None,
+                )
+                .await
+                .unwrap();
+            }
+            sqlx::query("UPDATE band_activity SET created_at = ? WHERE id = (SELECT MAX(id) FROM band_activity)")
+                .bind(created_at)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let rows = band_activity_export_rows(&pool, "2026-10-15", "2026-10-15").await.unwrap();
+        let calls: Vec<_> = rows.iter().map(|r| r.call.as_deref().unwrap()).collect();
+        assert_eq!(calls, ["K5ABC", "W1AW", "K5ABC"]);
+        assert_eq!(rows[0].grid.as_deref(), Some("EM12"));
+        assert_eq!(rows[0].freq_hz, Some(14_075_500.0));
+        assert_eq!(rows[2].grid, None);
+
+        let csv = render_band_activity_export(&rows, BandActivityExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "created_at,time_utc,direction,call,dx_call,grid,snr,freq_hz,mode,message");
+        assert_eq!(lines[1], "2026-10-15 12:00:15,120015,rx,K5ABC,,EM12,-12,14075500,FT8,CQ K5ABC EM12");
+        assert_eq!(lines[2], "2026-10-15 12:00:30,120030,tx,W1AW,K5ABC,FN31,,14075200,FT8,K5ABC W1AW FN31");
+
+        let json = render_band_activity_export(&rows, BandActivityExportFormat::Json).unwrap();
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[2]["message"], "W1AW K5ABC R-05");
+        assert_eq!(parsed[2]["snr"], -10);
+
+        assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
 }
@@ -0,0 +1,258 @@
+//! Callsign exception commands
+//!
+//! This module handles:
+//! - get_callsign_exceptions: List user-defined exact-callsign DXCC overrides
+//! - add_callsign_exception: Add or replace an override
+//! - remove_callsign_exception: Delete an override
+//! - get_prefix_discrepancies: Prefixes whose LoTW-confirmed DXCC disagrees with our rules
+//!
+//! Overrides are stored in `callsign_exceptions` and mirrored into the
+//! in-memory table `lookup_call_full` consults before prefix rules.
+
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use std::collections::{BTreeSet, HashMap};
+use tauri::command;
+
+use super::state::AppState;
+use crate::reference::exceptions;
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct CallsignException {
+    pub call: String,
+    pub dxcc: i32,
+    pub note: Option<String>,
+}
+
+/// A prefix rule that LoTW keeps contradicting: confirmed QSOs with calls
+/// under `prefix` carry `lotw_dxcc` where the rule says `our_dxcc`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PrefixDiscrepancy {
+    pub prefix: String,
+    pub our_dxcc: i32,
+    pub our_country: String,
+    pub lotw_dxcc: i32,
+    pub lotw_country: String,
+    /// Distinct confirmed calls where LoTW gave `lotw_dxcc`
+    pub disagreeing_calls: Vec<String>,
+    /// Distinct confirmed calls matched by the prefix
+    pub confirmed_calls: usize,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Load user exceptions from the database into the lookup table
+pub async fn load_callsign_exceptions(pool: &Pool<Sqlite>) -> Result<usize, String> {
+    let rows: Vec<(String, i32)> = sqlx::query_as("SELECT call, dxcc FROM callsign_exceptions")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to load callsign exceptions: {}", e))?;
+
+    let count = rows.len();
+    let map: HashMap<String, String> = rows
+        .into_iter()
+        .map(|(call, dxcc)| (call, format!("{:03}", dxcc)))
+        .collect();
+    exceptions::set_user_exceptions(map);
+    Ok(count)
+}
+
+/// Store an override and refresh the lookup table
+pub async fn save_callsign_exception(
+    pool: &Pool<Sqlite>,
+    call: &str,
+    dxcc: i32,
+    note: Option<&str>,
+) -> Result<(), String> {
+    let call = call.trim().to_uppercase();
+    if call.is_empty() || call.contains(char::is_whitespace) {
+        return Err(format!("Invalid callsign: {:?}", call));
+    }
+    if crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc)).is_none() {
+        return Err(format!("Unknown DXCC entity: {}", dxcc));
+    }
+
+    sqlx::query(
+        r#"INSERT INTO callsign_exceptions (call, dxcc, note) VALUES (?, ?, ?)
+           ON CONFLICT(call) DO UPDATE SET dxcc = excluded.dxcc, note = excluded.note"#,
+    )
+    .bind(&call)
+    .bind(dxcc)
+    .bind(note)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to save callsign exception: {}", e))?;
+
+    load_callsign_exceptions(pool).await?;
+    Ok(())
+}
+
+/// Fewest distinct calls that must disagree before a prefix is reported;
+/// a single station is a callsign exception, not a wrong rule
+const MIN_DISAGREEING_CALLS: usize = 2;
+
+fn entity_name(dxcc: i32) -> String {
+    crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc))
+        .map(|e| e.name.to_string())
+        .unwrap_or_else(|| format!("DXCC {}", dxcc))
+}
+
+/// Compare `lookup_call_full` with the DXCC LoTW confirmed for each call and
+/// report prefixes where most confirmed calls agree with LoTW on some other
+/// entity, at least MIN_DISAGREEING_CALLS of them. Most disagreements first.
+pub async fn prefix_discrepancies(pool: &Pool<Sqlite>) -> Result<Vec<PrefixDiscrepancy>, String> {
+    // LoTW's DXCC replaces ours when a confirmation is matched
+    let rows: Vec<(String, i32)> = sqlx::query_as(
+        r#"SELECT DISTINCT UPPER(q.call), q.dxcc FROM qsos q
+           JOIN confirmations c ON c.qso_id = q.id AND c.source = 'LOTW' AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0
+           WHERE q.dxcc IS NOT NULL"#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query confirmed QSOs: {}", e))?;
+
+    // prefix -> (our dxcc, every confirmed call, lotw dxcc -> disagreeing calls)
+    type PrefixCalls = (i32, BTreeSet<String>, HashMap<i32, BTreeSet<String>>);
+    let mut by_prefix: HashMap<&'static str, PrefixCalls> = HashMap::new();
+    for (call, lotw_dxcc) in rows {
+        let Some(rule) = crate::reference::prefix_rule_for(&call) else {
+            continue;
+        };
+        let Some(ours) = crate::reference::lookup_call_full(&call).dxcc_as_i32() else {
+            continue;
+        };
+        let (_, calls, disagreeing) =
+            by_prefix.entry(rule.prefix).or_insert_with(|| (rule.entity_id.parse().unwrap_or(0), BTreeSet::new(), HashMap::new()));
+        calls.insert(call.clone());
+        if lotw_dxcc != ours {
+            disagreeing.entry(lotw_dxcc).or_default().insert(call);
+        }
+    }
+
+    let mut discrepancies: Vec<PrefixDiscrepancy> = by_prefix
+        .into_iter()
+        .filter_map(|(prefix, (our_dxcc, calls, disagreeing))| {
+            let (lotw_dxcc, wrong) = disagreeing.into_iter().max_by_key(|(dxcc, wrong)| (wrong.len(), -dxcc))?;
+            (wrong.len() >= MIN_DISAGREEING_CALLS && wrong.len() * 2 > calls.len()).then(|| PrefixDiscrepancy {
+                prefix: prefix.to_string(),
+                our_dxcc,
+                our_country: entity_name(our_dxcc),
+                lotw_dxcc,
+                lotw_country: entity_name(lotw_dxcc),
+                disagreeing_calls: wrong.into_iter().collect(),
+                confirmed_calls: calls.len(),
+            })
+        })
+        .collect();
+    discrepancies.sort_by(|a, b| {
+        b.disagreeing_calls.len().cmp(&a.disagreeing_calls.len()).then_with(|| a.prefix.cmp(&b.prefix))
+    });
+    Ok(discrepancies)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn get_callsign_exceptions(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<CallsignException>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    sqlx::query_as("SELECT call, dxcc, note FROM callsign_exceptions ORDER BY call")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn add_callsign_exception(
+    state: tauri::State<'_, AppState>,
+    call: String,
+    dxcc: i32,
+    note: Option<String>,
+) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    save_callsign_exception(pool, &call, dxcc, note.as_deref()).await
+}
+
+#[command]
+pub async fn remove_callsign_exception(
+    state: tauri::State<'_, AppState>,
+    call: String,
+) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    sqlx::query("DELETE FROM callsign_exceptions WHERE call = ?")
+        .bind(call.trim().to_uppercase())
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to remove callsign exception: {}", e))?;
+
+    load_callsign_exceptions(pool).await?;
+    Ok(())
+}
+
+/// Prefixes whose rule LoTW confirmations systematically contradict,
+/// candidates for callsign exceptions or a rule fix
+#[command]
+pub async fn get_prefix_discrepancies(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<PrefixDiscrepancy>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    prefix_discrepancies(pool).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    /// Test: Confirmed QSOs where LoTW keeps giving another entity reveal a misrouted prefix
+    #[tokio::test]
+    async fn test_prefix_discrepancies() {
+        let pool = test_pool().await;
+
+        // Our rules send KG4 to the USA; LoTW puts 2x2 KG4 calls in Guantanamo Bay
+        for (call, band, dxcc, confirmed) in [
+            ("KG4AA", "20m", 105, true),
+            ("KG4AB", "20m", 105, true),
+            ("KG4AB", "40m", 105, true),
+            ("KG4BHR", "20m", 291, true),
+            ("KG4ZZ", "20m", 105, false), // not confirmed, LoTW hasn't spoken
+            ("W1AW", "20m", 291, true),
+            ("JA1ABC", "20m", 339, true),
+            ("VE3ABC", "20m", 291, true), // a one-off, not a pattern
+        ] {
+            let id: i64 = insert_qso(call).date("20260115").band(band).dxcc(dxcc).insert(&pool).await;
+            if confirmed {
+                sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, 'LOTW', 'Y')")
+                    .bind(id)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let found = prefix_discrepancies(&pool).await.unwrap();
+        assert_eq!(found.len(), 1, "{:?}", found);
+        let kg4 = &found[0];
+        assert_eq!(kg4.prefix, "KG4");
+        assert_eq!((kg4.our_dxcc, kg4.lotw_dxcc), (291, 105));
+        assert_eq!(kg4.disagreeing_calls, vec!["KG4AA".to_string(), "KG4AB".to_string()]);
+        assert_eq!(kg4.confirmed_calls, 3);
+    }
+}
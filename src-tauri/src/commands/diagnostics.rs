@@ -4,6 +4,7 @@
 //! - get_qso_diagnostics: Detailed statistics and potential issues
 
 use serde::Serialize;
+use sqlx::{Pool, Sqlite};
 use tauri::command;
 
 use super::state::AppState;
@@ -33,6 +34,20 @@ pub struct DiagnosticReport {
     pub qsos_not_in_lotw_window: Vec<QsoDiagnostic>,
 }
 
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// QSO count per source label, largest first
+pub async fn source_counts(pool: &Pool<Sqlite>) -> Vec<(String, i64)> {
+    sqlx::query_as(
+        "SELECT COALESCE(source, 'unknown') as src, COUNT(*) as cnt FROM qsos GROUP BY source ORDER BY cnt DESC, src",
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default()
+}
+
 // ============================================================================
 // Diagnostic Commands
 // ============================================================================
@@ -62,12 +77,7 @@ pub async fn get_qso_diagnostics(
     .map_err(|e| e.to_string())?;
 
     // QSOs by source
-    let by_source: Vec<(String, i64)> = sqlx::query_as(
-        "SELECT COALESCE(source, 'unknown') as src, COUNT(*) as cnt FROM qsos GROUP BY source ORDER BY cnt DESC",
-    )
-    .fetch_all(pool)
-    .await
-    .unwrap_or_default();
+    let by_source = source_counts(pool).await;
 
     // Find potential duplicates (same call+date+band but different times within 5 min)
     let dupe_candidates: Vec<(String,)> = sqlx::query_as(
@@ -0,0 +1,143 @@
+//! DXpedition list commands
+//!
+//! This module handles:
+//! - get_dxpeditions: List the DXpedition / special-event callsign patterns
+//! - add_dxpedition: Add or replace a pattern
+//! - remove_dxpedition: Delete a pattern
+//!
+//! Patterns are stored in `dxpeditions` and mirrored into the in-memory list
+//! used to flag decodes (`dxpedition` in wsjtx-decode) and QSOs.
+
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use tauri::command;
+
+use super::state::AppState;
+use super::time_utils::is_valid_adif_date;
+use crate::reference::dxpeditions::{self, DxpeditionPattern};
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Dxpedition {
+    pub pattern: String,
+    pub note: Option<String>,
+    /// Last day the pattern is active (YYYYMMDD), None if open-ended
+    pub end_date: Option<String>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Load the patterns from the database into the in-memory list
+pub async fn load_dxpeditions(pool: &Pool<Sqlite>) -> Result<usize, String> {
+    let rows: Vec<(String, Option<String>)> = sqlx::query_as("SELECT pattern, end_date FROM dxpeditions")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to load DXpeditions: {}", e))?;
+
+    let count = rows.len();
+    dxpeditions::set_dxpeditions(
+        rows.into_iter()
+            .map(|(pattern, end_date)| DxpeditionPattern { pattern, end_date })
+            .collect(),
+    );
+    Ok(count)
+}
+
+/// Store a pattern and refresh the in-memory list
+pub async fn save_dxpedition(
+    pool: &Pool<Sqlite>,
+    pattern: &str,
+    note: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<(), String> {
+    let pattern = pattern.trim().to_uppercase();
+    if pattern.is_empty() || pattern.chars().all(|c| c == '*') || pattern.contains(char::is_whitespace) {
+        return Err(format!("Invalid DXpedition pattern: {:?}", pattern));
+    }
+    let end_date = end_date.map(str::trim).filter(|d| !d.is_empty());
+    if let Some(date) = end_date.filter(|d| !is_valid_adif_date(d)) {
+        return Err(format!("Invalid end date (expected YYYYMMDD): {}", date));
+    }
+
+    sqlx::query(
+        r#"INSERT INTO dxpeditions (pattern, note, end_date) VALUES (?, ?, ?)
+           ON CONFLICT(pattern) DO UPDATE SET note = excluded.note, end_date = excluded.end_date"#,
+    )
+    .bind(&pattern)
+    .bind(note)
+    .bind(end_date)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to save DXpedition: {}", e))?;
+
+    load_dxpeditions(pool).await?;
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn get_dxpeditions(state: tauri::State<'_, AppState>) -> Result<Vec<Dxpedition>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    sqlx::query_as("SELECT pattern, note, end_date FROM dxpeditions ORDER BY pattern")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn add_dxpedition(
+    state: tauri::State<'_, AppState>,
+    pattern: String,
+    note: Option<String>,
+    end_date: Option<String>,
+) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    save_dxpedition(pool, &pattern, note.as_deref(), end_date.as_deref()).await
+}
+
+#[command]
+pub async fn remove_dxpedition(state: tauri::State<'_, AppState>, pattern: String) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    sqlx::query("DELETE FROM dxpeditions WHERE pattern = ?")
+        .bind(pattern.trim().to_uppercase())
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to remove DXpedition: {}", e))?;
+
+    load_dxpeditions(pool).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::test_pool;
+
+    #[tokio::test]
+    async fn test_dxpedition_flagging() {
+        let pool = test_pool().await;
+
+        save_dxpedition(&pool, "tx7*", Some("Marquesas"), Some("20260331")).await.unwrap();
+        assert!(save_dxpedition(&pool, "*", None, None).await.is_err());
+        assert!(save_dxpedition(&pool, "3Y0K", None, Some("2026-03-31")).await.is_err());
+
+        assert!(dxpeditions::is_dxpedition("TX7G", "20260315"));
+        assert!(!dxpeditions::is_dxpedition("W1AW", "20260315"));
+        // Over once the end date passes
+        assert!(!dxpeditions::is_dxpedition("TX7G", "20260401"));
+    }
+}
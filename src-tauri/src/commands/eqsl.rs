@@ -0,0 +1,119 @@
+//! eQSL.cc sync commands
+//!
+//! This module handles:
+//! - upload_to_eqsl: Upload pending QSOs to the eQSL outbox
+
+use serde::Serialize;
+use sqlx::Row;
+use tauri::command;
+
+use super::adif::{fetch_pending_uploads, mark_qsos_sent, rows_to_adif_records};
+use super::state::AppState;
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct EqslUploadResult {
+    pub qsos_exported: usize,
+    pub accepted: usize,
+    pub rejected: usize,
+    pub success: bool,
+    pub message: String,
+    pub warnings: Vec<String>,
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn upload_to_eqsl(
+    state: tauri::State<'_, AppState>,
+    username: String,
+    password: String,
+    qth_nickname: Option<String>,
+) -> Result<EqslUploadResult, String> {
+    log::info!("Starting eQSL upload");
+
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let rows = fetch_pending_uploads(pool, "EQSL").await?;
+
+    if rows.is_empty() {
+        return Ok(EqslUploadResult {
+            qsos_exported: 0,
+            accepted: 0,
+            rejected: 0,
+            success: true,
+            message: "No pending QSOs to upload".to_string(),
+            warnings: Vec::new(),
+        });
+    }
+
+    let qso_ids: Vec<i64> = rows.iter().map(|r| r.get::<i64, _>("id")).collect();
+    let mut records = rows_to_adif_records(&rows);
+    let qso_count = records.len();
+
+    // eQSL picks the sending QTH profile from APP_EQSL_QTH_NICKNAME on each record
+    if let Some(nickname) = qth_nickname.as_deref().filter(|n| !n.trim().is_empty()) {
+        for record in &mut records {
+            record.insert("APP_EQSL_QTH_NICKNAME".to_string(), nickname.trim().to_string());
+        }
+    }
+
+    log::info!("Exporting {} QSOs for eQSL upload", qso_count);
+
+    let adif_content = crate::adif::write_adif(&records, "GoQSO");
+
+    let client = crate::eqsl::EqslClient::new(username, password);
+    let response = client.upload_adif(&adif_content).await?;
+
+    log::info!(
+        "eQSL upload: {} accepted, {} rejected, {} errors",
+        response.accepted,
+        response.rejected,
+        response.errors.len()
+    );
+
+    let success = response.is_success();
+
+    if success {
+        mark_qsos_sent(pool, &qso_ids, "EQSL").await?;
+        log::info!("Marked {} QSOs as sent to eQSL", qso_ids.len());
+
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT OR REPLACE INTO settings (key, value, updated_at) VALUES ('eqsl_last_upload', ?, ?)",
+        )
+        .bind(&now)
+        .bind(&now)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to save last upload date: {}", e))?;
+    }
+
+    let message = if !response.errors.is_empty() {
+        format!("Rejected by eQSL: {}", response.errors.join("; "))
+    } else if !success {
+        "eQSL sent an unrecognized response; QSOs left pending".to_string()
+    } else if response.rejected > 0 {
+        format!(
+            "Uploaded {} QSO(s) to eQSL - {} rejected (duplicates or invalid)",
+            response.accepted, response.rejected
+        )
+    } else {
+        format!("Successfully uploaded {} QSO(s) to eQSL", response.accepted)
+    };
+
+    Ok(EqslUploadResult {
+        qsos_exported: qso_count,
+        accepted: response.accepted,
+        rejected: response.rejected,
+        success,
+        message,
+        warnings: response.warnings,
+    })
+}
@@ -5,11 +5,48 @@
 //! - sync_fcc_database: Download and import FCC database
 //! - lookup_fcc_callsign: Single callsign lookup
 //! - lookup_fcc_callsigns: Batch callsign lookup
+//! - lookup_my_station: Prefill the station profile from the operator's own license
 
 use tauri::{command, Emitter, Manager};
 
+use super::jobs::{JobHandle, JOB_FCC_SYNC};
+use super::settings::{MY_CITY_SETTING, MY_GRID_SETTING, MY_STATE_SETTING};
 use super::state::AppState;
-use crate::fcc::{FccLicenseInfo, FccSyncStatus};
+use super::udp::MY_CALLSIGN_SETTING;
+use crate::fcc::{FccLicenseInfo, FccSyncStatus, StationLookupStatus, StationPrefill};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Write the prefill into the station profile, leaving values the user already set
+pub async fn apply_station_prefill(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    prefill: &StationPrefill,
+) -> Result<(), String> {
+    if prefill.status != StationLookupStatus::Found {
+        return Ok(());
+    }
+    for (key, value) in [
+        (MY_CALLSIGN_SETTING, Some(&prefill.call)),
+        (MY_GRID_SETTING, prefill.grid.as_ref()),
+        (MY_STATE_SETTING, prefill.state.as_ref()),
+        (MY_CITY_SETTING, prefill.city.as_ref()),
+    ] {
+        let Some(value) = value else { continue };
+        sqlx::query(
+            r#"INSERT INTO settings (key, value, updated_at) VALUES (?, ?, datetime('now'))
+               ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+               WHERE TRIM(settings.value) = ''"#,
+        )
+        .bind(key)
+        .bind(value)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to save {}: {}", key, e))?;
+    }
+    Ok(())
+}
 
 // ============================================================================
 // FCC Commands
@@ -32,8 +69,20 @@ pub async fn sync_fcc_database(
     app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
 ) -> Result<FccSyncStatus, String> {
+    let job = state.jobs.start(JOB_FCC_SYNC, "FCC database sync").await;
+    let result = run_fcc_sync(&app, &state, &job).await;
+    job.finish(&result);
+    result
+}
+
+/// Download and import the FCC database, reporting progress as events and on the job
+async fn run_fcc_sync(app: &tauri::AppHandle, state: &AppState, job: &JobHandle) -> Result<FccSyncStatus, String> {
     let db_guard = state.db.lock().await;
     let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+    let progress = |fraction: f64, message: String| {
+        job.progress(Some(fraction), message.clone());
+        let _ = app.emit("fcc-sync-progress", message);
+    };
 
     // Mark sync as in progress
     sqlx::query("UPDATE fcc_sync_status SET sync_in_progress = 1, error_message = NULL WHERE id = 1")
@@ -41,8 +90,7 @@ pub async fn sync_fcc_database(
         .await
         .map_err(|e| format!("Failed to update sync status: {}", e))?;
 
-    // Emit progress event
-    let _ = app.emit("fcc-sync-progress", "Starting FCC database download...");
+    progress(0.0, "Starting FCC database download...".to_string());
 
     // Get app data directory
     let data_dir = app
@@ -51,38 +99,23 @@ pub async fn sync_fcc_database(
         .map_err(|e| format!("Failed to get app data dir: {}", e))?;
 
     // Download the database
-    let _ = app.emit("fcc-sync-progress", "Downloading FCC database (~25MB)...");
+    progress(0.1, "Downloading FCC database (~25MB)...".to_string());
 
     let en_path = match crate::fcc::download_fcc_database(&data_dir).await {
         Ok(path) => path,
         Err(e) => {
-            let _ = sqlx::query(
-                "UPDATE fcc_sync_status SET sync_in_progress = 0, error_message = ? WHERE id = 1",
-            )
-            .bind(&e)
-            .execute(pool)
-            .await;
-
+            crate::fcc::record_sync_error(pool, &e).await;
             return Err(e);
         }
     };
 
     // Parse and import
-    let _ = app.emit(
-        "fcc-sync-progress",
-        "Importing FCC records into database...",
-    );
+    progress(0.5, "Importing FCC records into database...".to_string());
 
     let record_count = match crate::fcc::parse_fcc_database(&en_path, pool).await {
         Ok(count) => count,
         Err(e) => {
-            let _ = sqlx::query(
-                "UPDATE fcc_sync_status SET sync_in_progress = 0, error_message = ? WHERE id = 1",
-            )
-            .bind(&e)
-            .execute(pool)
-            .await;
-
+            crate::fcc::record_sync_error(pool, &e).await;
             return Err(e);
         }
     };
@@ -101,10 +134,7 @@ pub async fn sync_fcc_database(
     .await
     .map_err(|e| format!("Failed to update sync status: {}", e))?;
 
-    let _ = app.emit(
-        "fcc-sync-progress",
-        format!("Imported {} FCC records", record_count),
-    );
+    progress(1.0, format!("Imported {} FCC records", record_count));
 
     // Return updated status
     crate::fcc::get_sync_status(pool).await
@@ -133,3 +163,19 @@ pub async fn lookup_fcc_callsigns(
 
     Ok(crate::fcc::lookup_callsigns(pool, &callsigns).await)
 }
+
+/// Look up the operator's callsign and prefill empty profile fields
+/// (callsign, grid, state, city). Returns `not_ready` until the FCC
+/// database has been imported.
+#[command]
+pub async fn lookup_my_station(
+    state: tauri::State<'_, AppState>,
+    callsign: String,
+) -> Result<StationPrefill, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let prefill = crate::fcc::lookup_station(pool, &callsign).await;
+    apply_station_prefill(pool, &prefill).await?;
+    Ok(prefill)
+}
@@ -0,0 +1,314 @@
+//! Background job commands
+//!
+//! This module handles:
+//! - get_jobs: Queued, running and recently finished jobs, newest first
+//!
+//! Long-running work (FCC sync, LoTW download/upload, log imports) runs as a
+//! job so the UI can show it in one activity panel. `JobQueue::start` records
+//! the job as queued, waits until no other job of the same kind is running,
+//! then marks it running. The returned `JobHandle` reports progress and
+//! records the outcome with `finish`; a handle dropped without finishing
+//! (an early `?` return) marks its job failed. `JobQueue::run` wraps a
+//! future that has no progress to report.
+//!
+//! Start a job before taking the database lock: a queued job waits for the
+//! running one, which may itself be waiting for the database.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::command;
+use tokio::sync::{Mutex as TokioMutex, OwnedMutexGuard};
+
+use super::state::AppState;
+
+/// Job kinds; jobs of one kind run one at a time
+pub const JOB_FCC_SYNC: &str = "fcc_sync";
+pub const JOB_LOTW: &str = "lotw";
+pub const JOB_IMPORT: &str = "import";
+
+/// Finished jobs kept for the activity panel; older ones are forgotten
+pub const MAX_FINISHED_JOBS: usize = 50;
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl JobStatus {
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Job {
+    pub id: u64,
+    pub kind: String,
+    pub label: String,
+    pub status: JobStatus,
+    /// Fraction done (0.0 to 1.0) when the job can tell
+    pub progress: Option<f64>,
+    /// Latest progress message
+    pub message: Option<String>,
+    /// The command's result, for completed jobs
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    /// UTC "YYYY-MM-DD HH:MM:SS"
+    pub queued_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+/// Every job since startup, up to MAX_FINISHED_JOBS finished ones
+pub struct JobQueue {
+    jobs: Mutex<Vec<Job>>,
+    next_id: AtomicU64,
+    /// One lock per kind, held by the running job of that kind
+    slots: Mutex<HashMap<String, Arc<TokioMutex<()>>>>,
+}
+
+/// A running job. Report progress with `progress`, end it with `finish`.
+pub struct JobHandle {
+    queue: Arc<JobQueue>,
+    id: u64,
+    finished: bool,
+    _slot: OwnedMutexGuard<()>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn now() -> String {
+    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        JobQueue {
+            jobs: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Queue a job, wait for any running job of the same kind, and start it
+    pub async fn start(self: &Arc<Self>, kind: &str, label: &str) -> JobHandle {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.jobs.lock().unwrap().push(Job {
+            id,
+            kind: kind.to_string(),
+            label: label.to_string(),
+            status: JobStatus::Queued,
+            progress: None,
+            message: None,
+            result: None,
+            error: None,
+            queued_at: now(),
+            started_at: None,
+            finished_at: None,
+        });
+
+        let slot = self.slots.lock().unwrap().entry(kind.to_string()).or_default().clone();
+        let guard = slot.lock_owned().await;
+        self.update(id, |job| {
+            job.status = JobStatus::Running;
+            job.started_at = Some(now());
+        });
+        log::debug!("Job {} started: {}", id, label);
+
+        JobHandle {
+            queue: self.clone(),
+            id,
+            finished: false,
+            _slot: guard,
+        }
+    }
+
+    /// Run `work` as a job with no progress to report, recording its result
+    pub async fn run<T: Serialize>(
+        self: &Arc<Self>,
+        kind: &str,
+        label: &str,
+        work: impl std::future::Future<Output = Result<T, String>>,
+    ) -> Result<T, String> {
+        let job = self.start(kind, label).await;
+        let result = work.await;
+        job.finish(&result);
+        result
+    }
+
+    fn update(&self, id: u64, f: impl FnOnce(&mut Job)) {
+        if let Some(job) = self.jobs.lock().unwrap().iter_mut().find(|j| j.id == id) {
+            f(job);
+        }
+    }
+
+    /// End a job, forgetting the oldest finished jobs past MAX_FINISHED_JOBS
+    fn end(&self, id: u64, result: Result<serde_json::Value, String>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.iter_mut().find(|j| j.id == id) {
+            match result {
+                Ok(value) => {
+                    job.status = JobStatus::Completed;
+                    job.progress = Some(1.0);
+                    job.result = Some(value);
+                }
+                Err(e) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(e);
+                }
+            }
+            job.finished_at = Some(now());
+        }
+
+        let finished = jobs.iter().filter(|j| j.status.is_finished()).count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        jobs.retain(|j| {
+            let drop = excess > 0 && j.status.is_finished();
+            if drop {
+                excess -= 1;
+            }
+            !drop
+        });
+    }
+
+    /// All known jobs, newest first
+    pub fn list(&self) -> Vec<Job> {
+        self.jobs.lock().unwrap().iter().rev().cloned().collect()
+    }
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobHandle {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Report how far along the job is
+    pub fn progress(&self, progress: Option<f64>, message: impl Into<String>) {
+        let message = message.into();
+        self.queue.update(self.id, |job| {
+            job.progress = progress.map(|p| p.clamp(0.0, 1.0)).or(job.progress);
+            job.message = Some(message);
+        });
+    }
+
+    /// Record the job's outcome: completed with the serialized result, or failed
+    pub fn finish<T: Serialize>(mut self, result: &Result<T, String>) {
+        self.finished = true;
+        let outcome = match result {
+            Ok(value) => serde_json::to_value(value).map_err(|e| e.to_string()),
+            Err(e) => Err(e.clone()),
+        };
+        self.queue.end(self.id, outcome);
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.queue.end(self.id, Err("Stopped before finishing".to_string()));
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Queued, running and recently finished background jobs, newest first
+#[command]
+pub async fn get_jobs(state: tauri::State<'_, AppState>) -> Result<Vec<Job>, String> {
+    Ok(state.jobs.list())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_of(queue: &JobQueue, id: u64) -> JobStatus {
+        queue.list().into_iter().find(|j| j.id == id).unwrap().status
+    }
+
+    /// Test: running -> completed with the result, running -> failed with the error
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let queue = Arc::new(JobQueue::new());
+
+        let job = queue.start(JOB_IMPORT, "ADIF import").await;
+        let id = job.id();
+        assert_eq!(status_of(&queue, id), JobStatus::Running);
+        job.progress(Some(0.5), "Halfway");
+        let running = &queue.list()[0];
+        assert_eq!((running.progress, running.message.as_deref()), (Some(0.5), Some("Halfway")));
+        job.finish(&Ok::<_, String>(serde_json::json!({"imported": 3})));
+        let done = &queue.list()[0];
+        assert_eq!(done.status, JobStatus::Completed);
+        assert_eq!(done.result, Some(serde_json::json!({"imported": 3})));
+        assert!(done.finished_at.is_some());
+
+        let job = queue.start(JOB_IMPORT, "ADIF import").await;
+        job.finish(&Err::<(), _>("Bad file".to_string()));
+        let failed = &queue.list()[0];
+        assert_eq!((failed.status, failed.error.as_deref()), (JobStatus::Failed, Some("Bad file")));
+
+        // Dropped without finishing, e.g. by an early return
+        let id = queue.start(JOB_LOTW, "LoTW download").await.id();
+        assert_eq!(status_of(&queue, id), JobStatus::Failed);
+
+        let result = queue.run(JOB_LOTW, "LoTW upload", async { Ok::<_, String>(7) }).await;
+        assert_eq!(result, Ok(7));
+        let done = &queue.list()[0];
+        assert_eq!((done.status, done.result.clone()), (JobStatus::Completed, Some(serde_json::json!(7))));
+    }
+
+    /// Test: A second job of the same kind stays queued until the first finishes
+    #[tokio::test]
+    async fn test_same_kind_jobs_queue() {
+        let queue = Arc::new(JobQueue::new());
+        let first = queue.start(JOB_FCC_SYNC, "FCC sync").await;
+
+        let waiting = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.start(JOB_FCC_SYNC, "FCC sync").await.id() }
+        });
+        tokio::task::yield_now().await;
+        let second = queue.list()[0].id;
+        assert_eq!(status_of(&queue, second), JobStatus::Queued);
+
+        // Other kinds aren't held up
+        let other = queue.start(JOB_IMPORT, "Import").await;
+        assert_eq!(status_of(&queue, other.id()), JobStatus::Running);
+
+        first.finish(&Ok::<_, String>(()));
+        assert_eq!(waiting.await.unwrap(), second);
+    }
+
+    #[tokio::test]
+    async fn test_finished_jobs_pruned() {
+        let queue = Arc::new(JobQueue::new());
+        let running = queue.start(JOB_LOTW, "LoTW upload").await;
+        for _ in 0..MAX_FINISHED_JOBS + 5 {
+            queue.start(JOB_IMPORT, "Import").await.finish(&Ok::<_, String>(()));
+        }
+        let jobs = queue.list();
+        assert_eq!(jobs.len(), MAX_FINISHED_JOBS + 1);
+        assert!(jobs.iter().any(|j| j.id == running.id()));
+    }
+}
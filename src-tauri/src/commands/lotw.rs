@@ -2,6 +2,9 @@
 //!
 //! This module handles:
 //! - sync_lotw_download: Download confirmations from LoTW
+//! - resync_lotw_range: Re-download and re-match confirmations for QSOs in a date range
+//! - import_lotw_file: Apply a LoTW report saved from the website like a download
+//! - verify_lotw_credentials: Check a LoTW login without downloading the report
 //! - get_sync_status: Get upload/download status
 //! - detect_tqsl_path: Find TQSL installation
 //! - upload_to_lotw: Upload QSOs via TQSL
@@ -11,9 +14,29 @@ use sqlx::Row;
 use std::io::Write;
 use tauri::command;
 
-use super::adif::row_to_json;
+use super::adif::{fetch_pending_uploads, mark_qsos_sent, rows_to_lotw_records};
+use super::awards::{confirmed_credits, snapshot_after_sync, ConfirmationDelta};
+use super::webhook::{confirmation_events, notify};
+use super::qso::GRID_SOURCE_LOTW;
+use super::jobs::JOB_LOTW;
+use super::settings::{read_bool_setting, read_setting};
 use super::state::AppState;
-use super::time_utils::extract_hhmm;
+use super::time_utils::{extract_hhmm, is_valid_adif_date, time_to_minutes};
+use super::validation::{qso_fields_from_row, upload_blockers, ValidationIssue};
+use crate::adif::parse_adif;
+use crate::adif::parser::{parse_adif_bytes, AdifFile, AdifRecord};
+use crate::lotw::sync::SinceTracker;
+use crate::lotw::client::LotwError;
+use crate::lotw::{LotwClient, LotwQueryOptions};
+
+/// Settings key: when the last LoTW download completed (UTC, "YYYY-MM-DD HH:MM:SS")
+pub const LOTW_LAST_SYNC_AT_SETTING: &str = "lotw_last_sync_at";
+
+/// Settings key: hold back QSOs without a grid from LoTW uploads (default off)
+pub const LOTW_UPLOAD_REQUIRE_GRID_SETTING: &str = "lotw_upload_require_grid";
+
+/// Settings key: minutes a LoTW record's TIME_ON may differ from the logged QSO (default 0)
+pub const LOTW_MATCH_TOLERANCE_SETTING: &str = "lotw_match_tolerance_minutes";
 
 // ============================================================================
 // Data Types
@@ -39,7 +62,7 @@ pub struct UnmatchedQso {
     pub mode: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 pub struct LotwDownloadResult {
     pub total_records: i32,
     pub matched: i32,
@@ -47,6 +70,11 @@ pub struct LotwDownloadResult {
     pub unmatched_qsos: Vec<UnmatchedQso>,
     pub errors: Vec<String>,
     pub last_qsl: Option<String>,
+    /// Stored as `lotw_last_download` (None if the sync was incomplete)
+    pub next_since_date: Option<String>,
+    /// Award credits this sync confirmed for the first time
+    #[serde(flatten)]
+    pub newly_confirmed: ConfirmationDelta,
 }
 
 #[derive(Debug, Serialize)]
@@ -54,36 +82,102 @@ pub struct LotwUploadResult {
     pub qsos_exported: usize,
     pub success: bool,
     pub message: String,
+    /// Pending QSOs held back by the upload gate, with the reasons
+    pub excluded: Vec<ValidationIssue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LotwCredentialStatus {
+    Valid,
+    /// LoTW rejected the username or password
+    InvalidCredentials,
+    /// LoTW couldn't be reached
+    NetworkError,
+    /// LoTW answered, but not with a report or a login failure
+    ServiceError,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LotwCredentialCheck {
+    pub status: LotwCredentialStatus,
+    pub message: String,
 }
 
 // ============================================================================
-// Commands
+// Helper Functions
 // ============================================================================
 
-#[command]
-pub async fn sync_lotw_download(
-    state: tauri::State<'_, AppState>,
-    username: String,
-    password: String,
-    since_date: Option<String>,
-) -> Result<LotwDownloadResult, String> {
-    log::info!("Starting LoTW confirmation download, since_date={:?}", since_date);
-
-    use crate::lotw::{LotwClient, LotwQueryOptions};
+/// Pending LoTW uploads split into the QSOs that pass the upload gate and
+/// the issues that kept the rest out. Excluded QSOs stay pending, so they
+/// go up on a later upload once they are fixed.
+pub async fn lotw_upload_set(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+) -> Result<(Vec<sqlx::sqlite::SqliteRow>, Vec<ValidationIssue>), String> {
+    let require_grid = read_bool_setting(pool, LOTW_UPLOAD_REQUIRE_GRID_SETTING, false).await;
+    let mut excluded = Vec::new();
+    let rows = fetch_pending_uploads(pool, "LOTW")
+        .await?
+        .into_iter()
+        .filter(|row| {
+            let blockers = upload_blockers(&qso_fields_from_row(row), require_grid);
+            let ok = blockers.is_empty();
+            excluded.extend(blockers);
+            ok
+        })
+        .collect();
+    Ok((rows, excluded))
+}
 
-    let client = LotwClient::new(username, password);
+/// Store the grid from a LoTW confirmation. It was uploaded by the other
+/// station, so it replaces whatever the QSO had.
+pub async fn apply_lotw_grid(pool: &sqlx::Pool<sqlx::Sqlite>, qso_id: i64, grid: &str) -> Result<(), String> {
+    if !crate::udp::wsjtx::is_valid_grid(grid) {
+        return Ok(());
+    }
+    sqlx::query("UPDATE qsos SET gridsquare = ?, grid_source = ? WHERE id = ?")
+        .bind(grid.to_uppercase())
+        .bind(GRID_SOURCE_LOTW)
+        .bind(qso_id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to update QSO {}: {}", qso_id, e))?;
+    Ok(())
+}
 
-    let options = LotwQueryOptions {
-        qso_qslsince: since_date.clone(),
-        qso_qsldetail: true,
-        qso_withown: true,
-        ..Default::default()
+/// Try a LoTW login with the client's smallest report query
+pub async fn check_lotw_credentials(client: &LotwClient) -> LotwCredentialCheck {
+    let (status, message) = match client.verify_credentials().await {
+        Ok(()) => (LotwCredentialStatus::Valid, "LoTW accepted the username and password".to_string()),
+        Err(LotwError::AuthError(_)) => (
+            LotwCredentialStatus::InvalidCredentials,
+            "LoTW rejected the username or password".to_string(),
+        ),
+        Err(LotwError::NetworkError(e)) => (LotwCredentialStatus::NetworkError, format!("Could not reach LoTW: {}", e)),
+        Err(e) => (LotwCredentialStatus::ServiceError, e.to_string()),
     };
+    LotwCredentialCheck { status, message }
+}
 
-    log::info!("LoTW query options: qso_qslsince={:?}", since_date);
+/// Confirmations matched to the log by one download
+struct LotwMatchOutcome {
+    matched: i32,
+    unmatched: i32,
+    unmatched_qsos: Vec<UnmatchedQso>,
+    errors: Vec<String>,
+    since_tracker: SinceTracker,
+}
+
+/// Fetch and parse a LoTW confirmation report
+async fn fetch_lotw_confirmations(
+    username: String,
+    password: String,
+    options: &LotwQueryOptions,
+) -> Result<(AdifFile, Option<String>), String> {
+    let client = LotwClient::new(username, password);
 
     let result = client
-        .download_confirmations(&options)
+        .download_confirmations(options)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -93,25 +187,74 @@ pub async fn sync_lotw_download(
         result.last_qsl
     );
 
-    use crate::adif::parse_adif;
     let adif_file =
         parse_adif(&result.adif_content).map_err(|e| format!("Failed to parse LoTW response: {}", e))?;
 
     log::info!("Parsed {} QSL records from LoTW", adif_file.records.len());
+    Ok((adif_file, result.last_qsl))
+}
 
-    let db_guard = state.db.lock().await;
-    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+/// Minutes of TIME_ON difference allowed when matching LoTW records
+pub async fn lotw_match_tolerance(pool: &sqlx::Pool<sqlx::Sqlite>) -> u32 {
+    read_setting(pool, LOTW_MATCH_TOLERANCE_SETTING)
+        .await
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// The logged QSO a LoTW record confirms: same call, band and date, with
+/// TIME_ON compared to the minute (LoTW sends HHMM, we store HHMMSS). The
+/// closest QSO within `tolerance_minutes` wins. Used by both the LoTW
+/// download and the lotwreport.adi file import so they match identically.
+pub async fn find_lotw_match(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    call: &str,
+    band: &str,
+    qso_date: &str,
+    time_on: &str,
+    tolerance_minutes: u32,
+) -> Result<Option<i64>, String> {
+    let Some(target) = time_to_minutes(&extract_hhmm(time_on)) else {
+        return Ok(None);
+    };
+
+    let candidates: Vec<(i64, String)> = sqlx::query_as(
+        r#"SELECT id, time_on FROM qsos
+           WHERE UPPER(call) = UPPER(?) AND UPPER(band) = UPPER(?) AND qso_date = ?"#,
+    )
+    .bind(call.trim())
+    .bind(band.trim())
+    .bind(qso_date.trim())
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
 
+    Ok(candidates
+        .into_iter()
+        .filter_map(|(id, logged)| {
+            let diff = time_to_minutes(&extract_hhmm(&logged))?.abs_diff(target);
+            (diff <= tolerance_minutes).then_some((diff, id))
+        })
+        .min()
+        .map(|(_, id)| id))
+}
+
+/// Match QSL records to logged QSOs, recording each confirmation and the
+/// location details LoTW reports
+async fn match_lotw_records(pool: &sqlx::Pool<sqlx::Sqlite>, records: &[AdifRecord]) -> Result<LotwMatchOutcome, String> {
     let mut matched = 0;
     let mut unmatched = 0;
     let mut errors: Vec<String> = Vec::new();
     let mut unmatched_qsos: Vec<UnmatchedQso> = Vec::new();
+    let mut since_tracker = SinceTracker::new();
+    let tolerance = lotw_match_tolerance(pool).await;
 
-    for record in &adif_file.records {
+    for record in records {
         let call = match record.call() {
             Some(c) => c.to_string(),
             None => {
                 errors.push("Record missing CALL field".to_string());
+                since_tracker.record_failure();
                 continue;
             }
         };
@@ -121,29 +264,14 @@ pub async fn sync_lotw_download(
         let qso_date = record.qso_date().map(|s| s.to_string()).unwrap_or_default();
         let time_on = record.time_on().map(|s| s.to_string()).unwrap_or_default();
 
-        let time_prefix = extract_hhmm(&time_on);
-
-        let match_result = sqlx::query(
-            r#"SELECT id FROM qsos 
-               WHERE UPPER(call) = ? AND UPPER(band) = ? AND qso_date = ?
-                 AND SUBSTR(time_on, 1, 4) = ?
-               LIMIT 1"#,
-        )
-        .bind(call.to_uppercase())
-        .bind(&band)
-        .bind(&qso_date)
-        .bind(&time_prefix)
-        .fetch_optional(pool)
-        .await;
+        let match_result = find_lotw_match(pool, &call, &band, &qso_date, &time_on, tolerance).await;
 
         match match_result {
-            Ok(Some(row)) => {
-                let qso_id: i64 = row.get("id");
+            Ok(Some(qso_id)) => {
 
                 let qsl_date = record.get("QSLRDATE").map(|s| s.to_string());
                 let dxcc: Option<i32> = record.get("DXCC").and_then(|s| s.parse().ok());
                 let state = record.get("STATE").map(|s| s.to_string());
-                let gridsquare = record.get("GRIDSQUARE").map(|s| s.to_string());
                 let cqz: Option<i32> = record.get("CQZ").and_then(|s| s.parse().ok());
                 let ituz: Option<i32> = record.get("ITUZ").and_then(|s| s.parse().ok());
                 let country = record.get("COUNTRY").map(|s| s.to_string());
@@ -156,7 +284,8 @@ pub async fn sync_lotw_download(
                          qsl_rcvd = 'Y',
                          qsl_rcvd_date = COALESCE(excluded.qsl_rcvd_date, qsl_rcvd_date),
                          credit_granted = COALESCE(excluded.credit_granted, credit_granted),
-                         verified_at = datetime('now')"#,
+                         verified_at = datetime('now'),
+                         needs_reverify = 0"#,
                 )
                 .bind(qso_id)
                 .bind(&qsl_date)
@@ -170,7 +299,6 @@ pub async fn sync_lotw_download(
                        dxcc = COALESCE(?, dxcc),
                        country = COALESCE(?, country),
                        state = COALESCE(?, state),
-                       gridsquare = COALESCE(?, gridsquare),
                        cqz = COALESCE(?, cqz),
                        ituz = COALESCE(?, ituz),
                        updated_at = datetime('now')
@@ -179,19 +307,23 @@ pub async fn sync_lotw_download(
                 .bind(dxcc)
                 .bind(&country)
                 .bind(&state)
-                .bind(&gridsquare)
                 .bind(cqz)
                 .bind(ituz)
                 .bind(qso_id)
                 .execute(pool)
                 .await
                 .map_err(|e| format!("Failed to update QSO {}: {}", qso_id, e))?;
+                if let Some(grid) = record.get("GRIDSQUARE") {
+                    apply_lotw_grid(pool, qso_id, grid).await?;
+                }
 
                 matched += 1;
+                since_tracker.record_processed(record.get("QSLRDATE").map(String::as_str));
                 log::debug!("Matched LoTW QSL: {} on {} {}", call, band, qso_date);
             }
             Ok(None) => {
                 unmatched += 1;
+                since_tracker.record_processed(record.get("QSLRDATE").map(String::as_str));
                 unmatched_qsos.push(UnmatchedQso {
                     call: call.clone(),
                     qso_date: qso_date.clone(),
@@ -210,22 +342,287 @@ pub async fn sync_lotw_download(
             }
             Err(e) => {
                 errors.push(format!("DB error matching {}: {}", call, e));
+                since_tracker.record_failure();
             }
         }
     }
 
+    Ok(LotwMatchOutcome {
+        matched,
+        unmatched,
+        unmatched_qsos,
+        errors,
+        since_tracker,
+    })
+}
+
+/// Download confirmations from LoTW and apply them to the log.
+/// Shared by the sync command and the startup auto-download; the database
+/// lock is only taken once the download has finished.
+pub async fn download_lotw_confirmations(
+    state: &AppState,
+    username: String,
+    password: String,
+    since_date: Option<String>,
+) -> Result<LotwDownloadResult, String> {
+    log::info!("Starting LoTW confirmation download, since_date={:?}", since_date);
+
+    let options = LotwQueryOptions {
+        qso_qslsince: since_date.clone(),
+        qso_qsldetail: true,
+        qso_withown: true,
+        ..Default::default()
+    };
+
+    log::info!("LoTW query options: qso_qslsince={:?}", since_date);
+
+    let (adif_file, last_qsl) = fetch_lotw_confirmations(username, password, &options).await?;
+
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let credits_before = confirmed_credits(pool).await?;
+
+    let LotwMatchOutcome { matched, unmatched, unmatched_qsos, errors, since_tracker } =
+        match_lotw_records(pool, &adif_file.records).await?;
+
     log::info!("LoTW sync complete: {} matched, {} unmatched", matched, unmatched);
 
+    // Only advance the stored since date when every record was processed,
+    // otherwise keep the prior value so the next sync re-fetches
+    let next_since_date = since_tracker.next_since_date();
+    match &next_since_date {
+        Some(since) => {
+            sqlx::query(
+                r#"INSERT INTO settings (key, value, updated_at)
+                   VALUES ('lotw_last_download', ?, datetime('now'))
+                   ON CONFLICT(key) DO UPDATE SET
+                     value = excluded.value,
+                     updated_at = datetime('now')"#,
+            )
+            .bind(since)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to save last download date: {}", e))?;
+            log::info!("Advanced lotw_last_download to {}", since);
+        }
+        None if !errors.is_empty() => {
+            log::warn!("LoTW sync had {} errors, keeping previous lotw_last_download", errors.len());
+        }
+        None => {}
+    }
+    sqlx::query(
+        r#"INSERT INTO settings (key, value, updated_at)
+           VALUES (?, datetime('now'), datetime('now'))
+           ON CONFLICT(key) DO UPDATE SET
+             value = excluded.value,
+             updated_at = datetime('now')"#,
+    )
+    .bind(LOTW_LAST_SYNC_AT_SETTING)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to save last sync time: {}", e))?;
+    state.worked.rebuild_or_log(pool).await;
+
+    let newly_confirmed = credits_before.newly_confirmed(&confirmed_credits(pool).await?);
+    if !newly_confirmed.new_dxcc_confirmed.is_empty() {
+        log::info!("LoTW sync confirmed new entities: {:?}", newly_confirmed.new_dxcc_confirmed);
+    }
+    for milestone in &newly_confirmed.milestones_reached {
+        log::info!("LoTW sync reached {} {} ({} confirmed)", milestone.award, milestone.threshold, milestone.confirmed);
+    }
+    notify(pool, confirmation_events(&newly_confirmed)).await;
+    snapshot_after_sync(pool).await;
+
     Ok(LotwDownloadResult {
         total_records: adif_file.records.len() as i32,
         matched,
         unmatched,
         unmatched_qsos,
         errors,
-        last_qsl: result.last_qsl,
+        last_qsl,
+        next_since_date,
+        newly_confirmed,
+    })
+}
+
+/// A QSO date as YYYYMMDD, accepting YYYY-MM-DD too
+fn compact_date(date: &str) -> Option<String> {
+    let compact = date.trim().replace('-', "");
+    is_valid_adif_date(&compact).then_some(compact)
+}
+
+/// LoTW query for every confirmation of QSOs made from `from_date` through
+/// `to_date`. The QSL-since date goes back to the start so confirmations
+/// already downloaded come back again.
+pub fn range_query_options(from_date: &str, to_date: &str) -> Result<LotwQueryOptions, String> {
+    let from = compact_date(from_date).ok_or_else(|| format!("Invalid from date: {}", from_date))?;
+    let to = compact_date(to_date).ok_or_else(|| format!("Invalid to date: {}", to_date))?;
+    if from > to {
+        return Err(format!("From date {} is after to date {}", from_date, to_date));
+    }
+    let dashed = |d: &str| format!("{}-{}-{}", &d[..4], &d[4..6], &d[6..]);
+    Ok(LotwQueryOptions {
+        qso_qslsince: Some("1900-01-01".to_string()),
+        qso_startdate: Some(dashed(&from)),
+        qso_enddate: Some(dashed(&to)),
+        qso_qsldetail: true,
+        qso_withown: true,
+        ..Default::default()
+    })
+}
+
+/// Match only the records for QSOs inside the range. `lotw_last_download`
+/// and the last sync time are left alone: this repairs a period, it isn't
+/// a sync.
+async fn apply_lotw_range(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    records: &[AdifRecord],
+    from_date: &str,
+    to_date: &str,
+) -> Result<LotwMatchOutcome, String> {
+    let from = compact_date(from_date).ok_or_else(|| format!("Invalid from date: {}", from_date))?;
+    let to = compact_date(to_date).ok_or_else(|| format!("Invalid to date: {}", to_date))?;
+    let in_range: Vec<AdifRecord> = records
+        .iter()
+        .filter(|r| r.qso_date().is_some_and(|d| *d >= from && *d <= to))
+        .cloned()
+        .collect();
+    if in_range.len() < records.len() {
+        log::warn!("Ignoring {} LoTW records outside {}..{}", records.len() - in_range.len(), from, to);
+    }
+    match_lotw_records(pool, &in_range).await
+}
+
+/// Re-download confirmations for QSOs in a date range and re-run matching
+pub async fn resync_lotw_confirmations(
+    state: &AppState,
+    username: String,
+    password: String,
+    from_date: String,
+    to_date: String,
+) -> Result<LotwDownloadResult, String> {
+    let options = range_query_options(&from_date, &to_date)?;
+    log::info!("Re-downloading LoTW confirmations for QSOs {} to {}", from_date, to_date);
+
+    let (adif_file, last_qsl) = fetch_lotw_confirmations(username, password, &options).await?;
+
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let credits_before = confirmed_credits(pool).await?;
+    let outcome = apply_lotw_range(pool, &adif_file.records, &from_date, &to_date).await?;
+    state.worked.rebuild_or_log(pool).await;
+    let newly_confirmed = credits_before.newly_confirmed(&confirmed_credits(pool).await?);
+    notify(pool, confirmation_events(&newly_confirmed)).await;
+    snapshot_after_sync(pool).await;
+
+    log::info!("LoTW range resync: {} matched, {} unmatched", outcome.matched, outcome.unmatched);
+    Ok(LotwDownloadResult {
+        total_records: adif_file.records.len() as i32,
+        matched: outcome.matched,
+        unmatched: outcome.unmatched,
+        unmatched_qsos: outcome.unmatched_qsos,
+        errors: outcome.errors,
+        last_qsl,
+        next_since_date: None,
+        newly_confirmed,
     })
 }
 
+/// Read and parse a LoTW report saved to disk (lotwreport.adi)
+pub fn read_lotw_report(path: &str) -> Result<AdifFile, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    parse_adif_bytes(&bytes).map_err(|e| format!("Failed to parse LoTW report: {}", e))
+}
+
+/// Apply a saved LoTW report with the same matching and location updates as
+/// the download. `lotw_last_download` and the last sync time are left alone:
+/// a report saved from the website can cover any period.
+pub async fn apply_lotw_report(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    adif_file: &AdifFile,
+) -> Result<LotwDownloadResult, String> {
+    let credits_before = confirmed_credits(pool).await?;
+    let outcome = match_lotw_records(pool, &adif_file.records).await?;
+    let newly_confirmed = credits_before.newly_confirmed(&confirmed_credits(pool).await?);
+    notify(pool, confirmation_events(&newly_confirmed)).await;
+    snapshot_after_sync(pool).await;
+
+    log::info!("LoTW report file: {} matched, {} unmatched", outcome.matched, outcome.unmatched);
+    Ok(LotwDownloadResult {
+        total_records: adif_file.records.len() as i32,
+        matched: outcome.matched,
+        unmatched: outcome.unmatched,
+        unmatched_qsos: outcome.unmatched_qsos,
+        errors: outcome.errors,
+        last_qsl: adif_file.header.get("APP_LOTW_LASTQSL").cloned(),
+        next_since_date: None,
+        newly_confirmed,
+    })
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn sync_lotw_download(
+    state: tauri::State<'_, AppState>,
+    username: String,
+    password: String,
+    since_date: Option<String>,
+) -> Result<LotwDownloadResult, String> {
+    let download = download_lotw_confirmations(&state, username, password, since_date);
+    state.jobs.run(JOB_LOTW, "LoTW download", download).await
+}
+
+/// Check a LoTW username and password with a minimal query, e.g. before saving them
+#[command]
+pub async fn verify_lotw_credentials(username: String, password: String) -> Result<LotwCredentialCheck, String> {
+    let username = username.trim().to_string();
+    if username.is_empty() || password.is_empty() {
+        return Err("LoTW username and password are required".to_string());
+    }
+    Ok(check_lotw_credentials(&LotwClient::new(username, password)).await)
+}
+
+/// Repair confirmations for QSOs made from `from_date` through `to_date`
+/// (YYYYMMDD or YYYY-MM-DD) without moving the incremental sync date
+#[command]
+pub async fn resync_lotw_range(
+    state: tauri::State<'_, AppState>,
+    username: String,
+    password: String,
+    from_date: String,
+    to_date: String,
+) -> Result<LotwDownloadResult, String> {
+    let label = format!("LoTW resync {} to {}", from_date, to_date);
+    let resync = resync_lotw_confirmations(&state, username, password, from_date, to_date);
+    state.jobs.run(JOB_LOTW, &label, resync).await
+}
+
+/// Import a LoTW report downloaded manually from the LoTW website: confirmations,
+/// DXCC/state/zones and grids, like `sync_lotw_download`
+#[command]
+pub async fn import_lotw_file(
+    state: tauri::State<'_, AppState>,
+    path: String,
+) -> Result<LotwDownloadResult, String> {
+    state.jobs.run(JOB_LOTW, "LoTW report import", import_lotw_report(&state, &path)).await
+}
+
+async fn import_lotw_report(state: &AppState, path: &str) -> Result<LotwDownloadResult, String> {
+    let adif_file = read_lotw_report(path)?;
+
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let result = apply_lotw_report(pool, &adif_file).await?;
+    state.worked.rebuild_or_log(pool).await;
+    Ok(result)
+}
+
 #[command]
 pub async fn get_sync_status(state: tauri::State<'_, AppState>) -> Result<SyncStatus, String> {
     let db_guard = state.db.lock().await;
@@ -288,42 +685,37 @@ pub async fn upload_to_lotw(
     state: tauri::State<'_, AppState>,
     tqsl_path: String,
 ) -> Result<LotwUploadResult, String> {
+    state.jobs.run(JOB_LOTW, "LoTW upload", upload_pending_to_lotw(&state, &tqsl_path)).await
+}
+
+/// Sign and upload every pending QSO with TQSL, marking them sent on success
+async fn upload_pending_to_lotw(state: &AppState, tqsl_path: &str) -> Result<LotwUploadResult, String> {
     log::info!("Starting LoTW upload via TQSL");
 
     let db_guard = state.db.lock().await;
     let pool = db_guard.as_ref().ok_or("Database not initialized")?;
 
-    let rows = sqlx::query(
-        r#"
-        SELECT q.* FROM qsos q
-        LEFT JOIN confirmations c ON q.id = c.qso_id AND c.source = 'LOTW'
-        WHERE c.id IS NULL OR c.qsl_sent IS NULL OR c.qsl_sent != 'Y'
-        ORDER BY q.qso_date DESC, q.time_on DESC
-        "#,
-    )
-    .fetch_all(pool)
-    .await
-    .map_err(|e| format!("Failed to query pending QSOs: {}", e))?;
+    let (rows, excluded) = lotw_upload_set(pool).await?;
+    if !excluded.is_empty() {
+        let held: std::collections::HashSet<i64> = excluded.iter().map(|i| i.qso_id).collect();
+        log::warn!("Holding back {} QSO(s) from LoTW upload", held.len());
+    }
 
     if rows.is_empty() {
         return Ok(LotwUploadResult {
             qsos_exported: 0,
             success: true,
             message: "No pending QSOs to upload".to_string(),
+            excluded,
         });
     }
 
     let qso_ids: Vec<i64> = rows.iter().map(|r| r.get::<i64, _>("id")).collect();
-    let qsos: Vec<serde_json::Value> = rows.iter().map(|r| row_to_json(r)).collect();
-    let qso_count = qsos.len();
+    let records = rows_to_lotw_records(&rows);
+    let qso_count = records.len();
 
     log::info!("Exporting {} QSOs for LoTW upload", qso_count);
 
-    let records: Vec<std::collections::HashMap<String, String>> = qsos
-        .iter()
-        .map(|q| crate::adif::writer::qso_to_adif(q))
-        .collect();
-
     let adif_content = crate::adif::write_adif(&records, "GoQSO");
 
     let temp_dir = std::env::temp_dir();
@@ -337,7 +729,7 @@ pub async fn upload_to_lotw(
 
     log::info!("Wrote ADIF to: {}", temp_file.display());
 
-    let output = std::process::Command::new(&tqsl_path)
+    let output = std::process::Command::new(tqsl_path)
         .args(["-d", "-u", "-a", "compliant", "-x"])
         .arg(&temp_file)
         .output()
@@ -355,23 +747,7 @@ pub async fn upload_to_lotw(
     let success = matches!(output.status.code(), Some(0) | Some(9));
 
     if matches!(output.status.code(), Some(0) | Some(8) | Some(9)) {
-        let today = chrono::Utc::now().format("%Y%m%d").to_string();
-        for qso_id in &qso_ids {
-            sqlx::query(
-                r#"
-                INSERT INTO confirmations (qso_id, source, qsl_sent, qsl_sent_date)
-                VALUES (?, 'LOTW', 'Y', ?)
-                ON CONFLICT(qso_id, source) DO UPDATE SET
-                    qsl_sent = 'Y',
-                    qsl_sent_date = COALESCE(confirmations.qsl_sent_date, excluded.qsl_sent_date)
-                "#,
-            )
-            .bind(qso_id)
-            .bind(&today)
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to mark QSO {} as sent: {}", qso_id, e))?;
-        }
+        mark_qsos_sent(pool, &qso_ids, "LOTW").await?;
         log::info!("Marked {} QSOs as sent to LoTW", qso_ids.len());
 
         let now = chrono::Utc::now().to_rfc3339();
@@ -406,5 +782,206 @@ pub async fn upload_to_lotw(
         qsos_exported: qso_count,
         success,
         message,
+        excluded,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    /// Test: Test-source and malformed QSOs are kept out of the upload set
+    #[tokio::test]
+    async fn test_upload_gate_excludes_bad_qsos() {
+        let pool = test_pool().await;
+
+        for (call, date, band, grid, source) in [
+            ("JA1ABC", "20260115", "20m", Some("PM95"), "WSJT-X"), // good
+            ("K5ABC", "20260115", "20m", None, "WSJT-X"),          // good, no grid
+            ("W1TEST", "20260115", "20m", Some("FN31"), "TEST"),   // test QSO
+            ("G0XYZ", "20261315", "20m", Some("IO91"), "ADIF"),    // bad date
+            ("F5ABC", "20260115", "11m", Some("JN18"), "ADIF"),    // unknown band
+        ] {
+            insert_qso(call)
+                .date(date)
+                .time("1230")
+                .band(band)
+                .set("gridsquare", grid)
+                .source(source)
+                .insert(&pool)
+                .await;
+        }
+
+        let calls = |rows: &[sqlx::sqlite::SqliteRow]| {
+            let mut calls: Vec<String> = rows.iter().map(|r| r.get("call")).collect();
+            calls.sort();
+            calls
+        };
+
+        let (rows, excluded) = lotw_upload_set(&pool).await.unwrap();
+        assert_eq!(calls(&rows), vec!["JA1ABC", "K5ABC"]);
+        let reasons: Vec<(&str, &str)> = excluded.iter().map(|i| (i.call.as_str(), i.field.as_str())).collect();
+        assert!(reasons.contains(&("W1TEST", "source")));
+        assert!(reasons.contains(&("G0XYZ", "qso_date")));
+        assert!(reasons.contains(&("F5ABC", "band")));
+
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'true', datetime('now'))")
+            .bind(LOTW_UPLOAD_REQUIRE_GRID_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let (rows, excluded) = lotw_upload_set(&pool).await.unwrap();
+        assert_eq!(calls(&rows), vec!["JA1ABC"]);
+        assert!(excluded.iter().any(|i| i.call == "K5ABC" && i.field == "gridsquare"));
+    }
+
+    /// Test: A LoTW grid replaces an inferred one and is marked as from LoTW
+    #[tokio::test]
+    async fn test_lotw_grid_sets_source() {
+        let pool = test_pool().await;
+
+        let id = insert_qso("JA1ABC")
+            .date("20260115")
+            .time("1230")
+            .set("gridsquare", "PM95")
+            .set("grid_source", "prior_qso")
+            .insert(&pool)
+            .await;
+
+        let stored = || async {
+            sqlx::query_as::<_, (Option<String>, Option<String>)>("SELECT gridsquare, grid_source FROM qsos")
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        };
+
+        apply_lotw_grid(&pool, id, "RR73").await.unwrap();
+        assert_eq!(stored().await, (Some("PM95".into()), Some("prior_qso".into())));
+
+        apply_lotw_grid(&pool, id, "pm96").await.unwrap();
+        assert_eq!(stored().await, (Some("PM96".into()), Some("lotw".into())));
+    }
+
+    /// Test: A range resync asks LoTW for that range only and matches nothing outside it
+    #[tokio::test]
+    async fn test_range_resync_matches_only_range() {
+        let options = range_query_options("2026-01-01", "20260131").unwrap();
+        assert_eq!(options.qso_startdate.as_deref(), Some("2026-01-01"));
+        assert_eq!(options.qso_enddate.as_deref(), Some("2026-01-31"));
+        assert_eq!(options.qso_qslsince.as_deref(), Some("1900-01-01"));
+        assert!(range_query_options("20260201", "20260101").is_err());
+        assert!(range_query_options("2026-1-1", "20260101").is_err());
+
+        let pool = test_pool().await;
+
+        for (call, date) in [("JA1ABC", "20260115"), ("G0XYZ", "20260210")] {
+            insert_qso(call).date(date).time("123000").insert(&pool).await;
+        }
+
+        let adif = "<EOH>\n\
+            <CALL:6>JA1ABC <BAND:3>20M <MODE:3>FT8 <QSO_DATE:8>20260115 <TIME_ON:6>123000 <QSL_RCVD:1>Y <QSLRDATE:8>20260301 <EOR>\n\
+            <CALL:5>G0XYZ <BAND:3>20M <MODE:3>FT8 <QSO_DATE:8>20260210 <TIME_ON:6>123000 <QSL_RCVD:1>Y <QSLRDATE:8>20260301 <EOR>\n";
+        let file = parse_adif(adif).unwrap();
+        let outcome = apply_lotw_range(&pool, &file.records, "20260101", "20260131").await.unwrap();
+        assert_eq!((outcome.matched, outcome.unmatched), (1, 0));
+
+        let confirmed: Vec<String> = sqlx::query_scalar(
+            "SELECT q.call FROM confirmations c JOIN qsos q ON q.id = c.qso_id WHERE c.source = 'LOTW'",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(confirmed, ["JA1ABC"]);
+
+        // The incremental sync date is untouched
+        let last_download: Option<String> =
+            sqlx::query_scalar("SELECT value FROM settings WHERE key = 'lotw_last_download'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap();
+        assert_eq!(last_download, None);
+    }
+
+    async fn pool_with_qsos(times: &[(&str, &str)]) -> sqlx::Pool<sqlx::Sqlite> {
+        let pool = test_pool().await;
+        for (call, time_on) in times {
+            insert_qso(call).date("20260115").time(time_on).insert(&pool).await;
+        }
+        pool
+    }
+
+    /// Test: 4-char LoTW times match 6-char logged times, and the reverse
+    #[tokio::test]
+    async fn test_download_matches_hhmm_times() {
+        let pool = pool_with_qsos(&[("JA1ABC", "123045"), ("G0XYZ", "1400")]).await;
+        let adif = "<CALL:6>JA1ABC <BAND:3>20M <MODE:3>FT8 <QSO_DATE:8>20260115 <TIME_ON:4>1230 <QSL_RCVD:1>Y <EOR>\n\
+            <CALL:5>G0XYZ <BAND:3>20M <MODE:3>FT8 <QSO_DATE:8>20260115 <TIME_ON:6>140012 <QSL_RCVD:1>Y <EOR>\n\
+            <CALL:5>K5ABC <BAND:3>20M <MODE:3>FT8 <QSO_DATE:8>20260115 <TIME_ON:4>1400 <QSL_RCVD:1>Y <EOR>\n";
+        let file = parse_adif(adif).unwrap();
+        let outcome = match_lotw_records(&pool, &file.records).await.unwrap();
+        assert_eq!((outcome.matched, outcome.unmatched), (2, 1));
+    }
+
+    /// Test: a minute off only matches within the configured tolerance
+    #[tokio::test]
+    async fn test_match_tolerance() {
+        let pool = pool_with_qsos(&[("JA1ABC", "123045"), ("JA1ABC", "123300")]).await;
+        assert_eq!(find_lotw_match(&pool, "ja1abc", "20M", "20260115", "1231", 0).await.unwrap(), None);
+        let nearest = find_lotw_match(&pool, "JA1ABC", "20m", "20260115", "1232", 2).await.unwrap();
+        let second: i64 = sqlx::query_scalar("SELECT id FROM qsos WHERE time_on = '123300'").fetch_one(&pool).await.unwrap();
+        assert_eq!(nearest, Some(second));
+
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, '1', datetime('now'))")
+            .bind(LOTW_MATCH_TOLERANCE_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(lotw_match_tolerance(&pool).await, 1);
+    }
+
+    /// Test: A report saved from the LoTW website confirms QSOs and fills their location
+    #[tokio::test]
+    async fn test_import_lotw_report_file() {
+        let pool = test_pool().await;
+
+        insert_qso("W1AW").date("20260115").time("123015").dxcc(291).insert(&pool).await;
+
+        let report = "ARRL Logbook of the World Status Report
+<PROGRAMID:4>LoTW
+<APP_LoTW_LASTQSL:19>2026-02-01 10:00:00
+<APP_LoTW_NUMREC:1>2
+<eoh>
+<CALL:4>W1AW <BAND:3>20M <MODE:3>FT8 <QSO_DATE:8>20260115 <TIME_ON:6>123000 <QSL_RCVD:1>Y
+<QSLRDATE:8>20260201 <DXCC:3>291 <STATE:2>CT <CQZ:1>5 <ITUZ:1>8 <GRIDSQUARE:6>FN31pr <eor>
+<CALL:6>JA1ABC <BAND:3>40M <MODE:3>FT8 <QSO_DATE:8>20260116 <TIME_ON:6>090000 <QSL_RCVD:1>Y <eor>
+";
+        let path = std::env::temp_dir().join(format!("goqso_lotwreport_{}.adi", uuid::Uuid::new_v4()));
+        std::fs::write(&path, report).unwrap();
+        let adif_file = read_lotw_report(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let result = apply_lotw_report(&pool, &adif_file).await.unwrap();
+        assert_eq!((result.total_records, result.matched, result.unmatched), (2, 1, 1));
+        assert_eq!(result.unmatched_qsos[0].call, "JA1ABC");
+        assert_eq!(result.last_qsl.as_deref(), Some("2026-02-01 10:00:00"));
+        assert_eq!(result.next_since_date, None);
+
+        let (state, cqz, grid, grid_source): (Option<String>, Option<i32>, Option<String>, Option<String>) =
+            sqlx::query_as("SELECT state, cqz, gridsquare, grid_source FROM qsos")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((state.as_deref(), cqz), (Some("CT"), Some(5)));
+        assert_eq!((grid.as_deref(), grid_source.as_deref()), (Some("FN31PR"), Some("lotw")));
+        let confirmed: String = sqlx::query_scalar("SELECT qsl_rcvd_date FROM confirmations WHERE source = 'LOTW'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(confirmed, "20260201");
+        // A file import isn't a sync
+        assert_eq!(read_setting(&pool, "lotw_last_download").await, None);
+
+        assert!(read_lotw_report("/nonexistent/lotwreport.adi").is_err());
+    }
+}
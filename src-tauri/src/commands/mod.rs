@@ -8,24 +8,42 @@
 //! - `time_utils` - Time parsing, normalization, and validation utilities
 //! - `udp` - WSJT-X UDP listener commands
 //! - `qso` - QSO CRUD operations and history
+//! - `worked_cache` - In-memory worked-before index for live dupe/new-DXCC checks
 //! - `adif` - ADIF import/export
 //! - `lotw` - LoTW sync (download/upload)
+//! - `eqsl` - eQSL outbox upload
+//! - `qsl_card` - Paper QSL card tracking
 //! - `awards` - Award progress (DXCC, WAS, VUCC)
 //! - `settings` - Application settings
 //! - `band_activity` - Band activity storage and retrieval
+//! - `stats` - Operating statistics (rate meter)
 //! - `fcc` - FCC database commands
+//! - `callsign_exceptions` - User exact-callsign DXCC overrides
+//! - `dxpeditions` - User-maintained DXpedition callsign patterns
+//! - `webhook` - Best-effort webhook notifications for notable events
+//! - `jobs` - Background job queue and status for the activity panel
+//! - `validation` - Log validation before export
 //! - `diagnostics` - Debug and diagnostic commands
 
 mod state;
 pub mod time_utils;
 pub mod udp;
 pub mod qso;
+pub mod worked_cache;
 pub mod adif;
 pub mod lotw;
+pub mod eqsl;
+pub mod qsl_card;
 pub mod awards;
 pub mod settings;
 pub mod band_activity;
+pub mod stats;
 pub mod fcc;
+pub mod callsign_exceptions;
+pub mod dxpeditions;
+pub mod webhook;
+pub mod jobs;
+pub mod validation;
 pub mod diagnostics;
 
 // Re-export AppState for use in main.rs
@@ -0,0 +1,219 @@
+//! Paper QSL card tracking commands
+//!
+//! This module handles:
+//! - mark_card_sent: Record that a paper QSL card was sent for a QSO
+//! - mark_card_confirmed: Record that a paper QSL card was received for a QSO
+//! - get_card_qsl_queue: QSOs with no confirmation that are candidates for a card request
+//!
+//! Card QSLs are stored in `confirmations` with source 'CARD'. Whether they count
+//! toward awards is controlled by the `award_confirmation_sources` setting.
+
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use tauri::command;
+
+use super::state::AppState;
+use super::time_utils::{is_valid_adif_date, normalize_date_to_yyyymmdd};
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct CardQueueEntry {
+    pub qso_id: i64,
+    pub call: String,
+    pub qso_date: String,
+    pub time_on: String,
+    pub band: String,
+    pub mode: String,
+    pub dxcc: Option<i32>,
+    pub country: Option<String>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Normalize a user-entered date (YYYYMMDD or YYYY-MM-DD) to ADIF format
+fn parse_card_date(date: &str) -> Result<String, String> {
+    let normalized = normalize_date_to_yyyymmdd(date.trim());
+    if !is_valid_adif_date(&normalized) {
+        return Err(format!("Invalid date: {}", date));
+    }
+    Ok(normalized)
+}
+
+async fn ensure_qso_exists(pool: &Pool<Sqlite>, qso_id: i64) -> Result<(), String> {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM qsos WHERE id = ?)")
+        .bind(qso_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !exists {
+        return Err(format!("QSO {} not found", qso_id));
+    }
+    Ok(())
+}
+
+/// Record a paper card sent for a QSO
+pub async fn record_card_sent(pool: &Pool<Sqlite>, qso_id: i64, sent_date: &str) -> Result<(), String> {
+    let sent_date = parse_card_date(sent_date)?;
+    ensure_qso_exists(pool, qso_id).await?;
+
+    sqlx::query(
+        r#"INSERT INTO confirmations (qso_id, source, qsl_sent, qsl_sent_date)
+           VALUES (?, 'CARD', 'Y', ?)
+           ON CONFLICT(qso_id, source) DO UPDATE SET
+             qsl_sent = 'Y',
+             qsl_sent_date = excluded.qsl_sent_date"#,
+    )
+    .bind(qso_id)
+    .bind(&sent_date)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to mark card sent: {}", e))?;
+
+    Ok(())
+}
+
+/// Record a paper card received for a QSO
+pub async fn record_card_confirmed(pool: &Pool<Sqlite>, qso_id: i64, rcvd_date: &str) -> Result<(), String> {
+    let rcvd_date = parse_card_date(rcvd_date)?;
+    ensure_qso_exists(pool, qso_id).await?;
+
+    sqlx::query(
+        r#"INSERT INTO confirmations (qso_id, source, qsl_rcvd, qsl_rcvd_date, verified_at)
+           VALUES (?, 'CARD', 'Y', ?, datetime('now'))
+           ON CONFLICT(qso_id, source) DO UPDATE SET
+             qsl_rcvd = 'Y',
+             qsl_rcvd_date = excluded.qsl_rcvd_date,
+             verified_at = datetime('now'),
+             needs_reverify = 0"#,
+    )
+    .bind(qso_id)
+    .bind(&rcvd_date)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to mark card confirmed: {}", e))?;
+
+    Ok(())
+}
+
+/// QSOs with no confirmation from any source and no card sent yet
+pub async fn card_qsl_queue(pool: &Pool<Sqlite>, limit: i64) -> Result<Vec<CardQueueEntry>, String> {
+    sqlx::query_as(
+        r#"SELECT q.id AS qso_id, q.call, q.qso_date, q.time_on, q.band, q.mode, q.dxcc, q.country
+           FROM qsos q
+           WHERE NOT EXISTS (
+               SELECT 1 FROM confirmations c WHERE c.qso_id = q.id AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0
+           )
+           AND NOT EXISTS (
+               SELECT 1 FROM confirmations c WHERE c.qso_id = q.id AND c.source = 'CARD' AND c.qsl_sent = 'Y'
+           )
+           ORDER BY q.qso_date DESC, q.time_on DESC
+           LIMIT ?"#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query card queue: {}", e))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn mark_card_sent(
+    state: tauri::State<'_, AppState>,
+    qso_id: i64,
+    sent_date: String,
+) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    record_card_sent(pool, qso_id, &sent_date).await
+}
+
+#[command]
+pub async fn mark_card_confirmed(
+    state: tauri::State<'_, AppState>,
+    qso_id: i64,
+    rcvd_date: String,
+) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    record_card_confirmed(pool, qso_id, &rcvd_date).await
+}
+
+#[command]
+pub async fn get_card_qsl_queue(
+    state: tauri::State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<CardQueueEntry>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    card_qsl_queue(pool, limit.unwrap_or(500)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    async fn setup_test_db() -> Pool<Sqlite> {
+        let pool = test_pool().await;
+
+        for (id, call, date) in [(1, "JA1ABC", "20260101"), (2, "VK2XYZ", "20260102"), (3, "G0ABC", "20260103")] {
+            insert_qso(call).id(id).date(date).insert(&pool).await;
+        }
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_mark_card_sent_and_confirmed() {
+        let pool = setup_test_db().await;
+
+        record_card_sent(&pool, 1, "2026-02-01").await.unwrap();
+        record_card_confirmed(&pool, 1, "20260315").await.unwrap();
+
+        let row: (String, String, String, String) = sqlx::query_as(
+            "SELECT qsl_sent, qsl_sent_date, qsl_rcvd, qsl_rcvd_date FROM confirmations WHERE qso_id = 1 AND source = 'CARD'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(row, ("Y".into(), "20260201".into(), "Y".into(), "20260315".into()));
+    }
+
+    #[tokio::test]
+    async fn test_mark_card_rejects_bad_input() {
+        let pool = setup_test_db().await;
+
+        assert!(record_card_sent(&pool, 1, "not a date").await.is_err());
+        assert!(record_card_confirmed(&pool, 99, "20260315").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_card_qsl_queue() {
+        let pool = setup_test_db().await;
+
+        // QSO 1 confirmed on LoTW, QSO 2 has a card out, QSO 3 is a candidate
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (1, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        record_card_sent(&pool, 2, "20260201").await.unwrap();
+
+        let queue = card_qsl_queue(&pool, 100).await.unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].call, "G0ABC");
+
+        // Received card removes it from the queue
+        record_card_confirmed(&pool, 3, "20260301").await.unwrap();
+        assert!(card_qsl_queue(&pool, 100).await.unwrap().is_empty());
+    }
+}
@@ -3,19 +3,50 @@
 //! This module handles all QSO (contact) database operations:
 //! - get_qsos: Fetch QSOs with pagination and confirmation status
 //! - add_qso: Create new QSO with DXCC lookup
+//! - get_qso_defaults / set_qso_defaults: Field defaults for manual entry
 //! - update_qso: Update existing QSO fields
+//! - correct_callsign: Fix a QSO's callsign, re-deriving its location and flagging its confirmations
+//! - relabel_source: Set the source label of a set of QSOs (e.g. "N3FJP import 2019")
 //! - delete_qso: Remove single QSO
 //! - remove_duplicate_qsos: Clean up duplicate entries
 //! - clear_all_qsos: Delete all QSOs (testing)
 //! - add_test_qsos: Insert sample data (testing)
 //! - get_callsign_history: Previous QSOs with a callsign
 //! - check_qso_status: Check dupe/new DXCC status
+//! - get_qso_path: Short/long path beam headings and distances for a QSO
+//! - get_qsos_by_distance: QSOs farthest from the home grid first (DX records)
+//! - get_p2p_qsos / get_s2s_qsos: Park-to-park and summit-to-summit contacts
+//! - get_swl_reports: Imported shortwave listener reports (source 'SWL'), not counted as QSOs
 
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use tauri::{command, Emitter};
 
+use super::settings::{read_bool_setting, read_setting, counted_qso_filter, HOME_GRID_SETTING, MY_GRID_SETTING};
 use super::state::AppState;
+use super::worked_cache::WorkedQuery;
+use crate::adif::activation::{both_activating, ActivationProgram};
+use crate::adif::bands::{mhz_to_hz, parse_mhz_to_hz};
+use crate::adif::modes::{get_mode_group, ModeGroup};
+use crate::reference::dxpeditions::is_dxpedition;
+
+/// `grid_source` values: where a QSO's gridsquare came from, most trusted first
+pub const GRID_SOURCE_STATION: &str = "station";
+pub const GRID_SOURCE_LOTW: &str = "lotw";
+pub const GRID_SOURCE_MANUAL: &str = "manual";
+pub const GRID_SOURCE_ADIF: &str = "adif";
+pub const GRID_SOURCE_FCC: &str = "fcc";
+pub const GRID_SOURCE_PRIOR_QSO: &str = "prior_qso";
+
+/// Longest source label relabel_source accepts
+pub const MAX_SOURCE_LABEL_LEN: usize = 64;
+
+/// `source` of imported SWL reports; they are kept out of awards and stats
+pub const SOURCE_SWL: &str = "SWL";
+
+/// Sources the app gives meaning to (SWL reports, `add_test_qsos` data, WSJT-X
+/// logging); relabel_source neither sets them nor changes QSOs carrying them
+pub const RESERVED_SOURCES: &[&str] = &[SOURCE_SWL, "TEST", "WSJT-X", "WSJTX"];
 
 // ============================================================================
 // Data Types
@@ -38,6 +69,7 @@ pub struct Qso {
     pub continent: Option<String>,
     pub state: Option<String>,
     pub gridsquare: Option<String>,
+    pub grid_source: Option<String>,
     pub cqz: Option<i32>,
     pub ituz: Option<i32>,
     pub rst_sent: Option<String>,
@@ -56,6 +88,57 @@ pub struct Qso {
     pub lotw_rcvd: Option<String>,
     #[serde(default)]
     pub eqsl_rcvd: Option<String>,
+    // Callsign as shown in the log view; `call` is always the full callsign
+    #[serde(default)]
+    pub display_call: String,
+    /// Call matched an active DXpedition pattern on the QSO date
+    #[serde(default)]
+    pub dxpedition: bool,
+    /// Park-to-park: both POTA_REF and MY_POTA_REF are set
+    #[serde(default)]
+    pub p2p: bool,
+    /// Summit-to-summit: both SOTA_REF and MY_SOTA_REF are set
+    #[serde(default)]
+    pub s2s: bool,
+    /// A confirmation was received for an earlier callsign and needs re-verification
+    #[serde(default)]
+    pub needs_reverify: bool,
+    /// Worked, sent and awaiting confirmation, or confirmed (with the sources)
+    #[serde(default)]
+    pub confirmation_state: ConfirmationState,
+}
+
+/// Where a QSO stands between working it and having it confirmed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ConfirmationState {
+    /// Logged, not sent to any confirmation service yet
+    #[default]
+    Worked,
+    /// Sent (e.g. uploaded to LoTW), no confirmation received yet
+    SentPendingConfirmation,
+    /// Confirmed by these sources (LOTW, EQSL, CARD, ...)
+    Confirmed { sources: Vec<String> },
+}
+
+impl ConfirmationState {
+    /// From the comma-separated sources with qsl_rcvd = 'Y' and whether any qsl_sent = 'Y'
+    pub fn from_confirmations(confirmed_sources: Option<&str>, sent: bool) -> Self {
+        let mut sources: Vec<String> = confirmed_sources
+            .unwrap_or_default()
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        if !sources.is_empty() {
+            sources.sort();
+            ConfirmationState::Confirmed { sources }
+        } else if sent {
+            ConfirmationState::SentPendingConfirmation
+        } else {
+            ConfirmationState::Worked
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -70,6 +153,34 @@ pub struct NewQso {
     pub rst_sent: Option<String>,
     pub rst_rcvd: Option<String>,
     pub source: Option<String>,
+    /// SNR (dB) we reported to them, used for auto-RST on digital modes
+    #[serde(default)]
+    pub snr_sent: Option<i32>,
+    /// SNR (dB) they reported to us, used for auto-RST on digital modes
+    #[serde(default)]
+    pub snr_rcvd: Option<i32>,
+    #[serde(default)]
+    pub station_callsign: Option<String>,
+    #[serde(default)]
+    pub my_gridsquare: Option<String>,
+    #[serde(default)]
+    pub tx_pwr: Option<f64>,
+}
+
+/// Values filled into manually entered QSOs when left blank. Entry
+/// conveniences only; station identity lives in the station profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QsoDefaults {
+    #[serde(default)]
+    pub tx_pwr: Option<f64>,
+    #[serde(default)]
+    pub my_gridsquare: Option<String>,
+    #[serde(default)]
+    pub station_callsign: Option<String>,
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub band: Option<String>,
 }
 
 /// Summary of previous QSOs with a callsign
@@ -107,55 +218,409 @@ pub struct QsoStatus {
     pub previous_qso_count: i32,
 }
 
+/// Beam headings from my grid to the other station's grid
+#[derive(Debug, Serialize)]
+pub struct QsoPath {
+    pub my_grid: String,
+    pub their_grid: String,
+    #[serde(flatten)]
+    pub path: crate::reference::grid_location::GridPath,
+}
+
+/// A contact where both stations were activating (P2P or S2S)
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ActivationQso {
+    pub id: i64,
+    pub call: String,
+    pub qso_date: String,
+    pub time_on: String,
+    pub band: String,
+    pub mode: String,
+    pub their_ref: String,
+    pub my_ref: String,
+}
+
+/// A shortwave listener report from an imported log
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SwlReport {
+    pub id: i64,
+    pub call: String,
+    pub qso_date: String,
+    pub time_on: String,
+    pub band: String,
+    pub mode: String,
+    pub country: Option<String>,
+    pub rst_rcvd: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// A QSO with its great-circle distance from the home grid
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct DxRecord {
+    pub id: i64,
+    pub call: String,
+    pub qso_date: String,
+    pub band: String,
+    pub mode: String,
+    pub gridsquare: String,
+    pub country: Option<String>,
+    pub distance_km: f64,
+}
+
+/// Result of correcting a QSO's callsign
+#[derive(Debug, Serialize)]
+pub struct CallsignCorrection {
+    pub qso_id: i64,
+    pub old_call: String,
+    pub new_call: String,
+    pub dxcc: Option<i32>,
+    pub country: Option<String>,
+    /// Received confirmations flagged for re-verification
+    pub confirmations_flagged: u64,
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Convert frequency in MHz to band string
-pub fn freq_to_band(freq_mhz: f64) -> String {
-    match freq_mhz {
-        f if f >= 1.8 && f < 2.0 => "160m".to_string(),
-        f if f >= 3.5 && f < 4.0 => "80m".to_string(),
-        f if f >= 5.0 && f < 5.5 => "60m".to_string(),
-        f if f >= 7.0 && f < 7.3 => "40m".to_string(),
-        f if f >= 10.1 && f < 10.15 => "30m".to_string(),
-        f if f >= 14.0 && f < 14.35 => "20m".to_string(),
-        f if f >= 18.068 && f < 18.168 => "17m".to_string(),
-        f if f >= 21.0 && f < 21.45 => "15m".to_string(),
-        f if f >= 24.89 && f < 24.99 => "12m".to_string(),
-        f if f >= 28.0 && f < 29.7 => "10m".to_string(),
-        f if f >= 50.0 && f < 54.0 => "6m".to_string(),
-        f if f >= 144.0 && f < 148.0 => "2m".to_string(),
-        f if f >= 420.0 && f < 450.0 => "70cm".to_string(),
-        _ => format!("{:.3}MHz", freq_mhz),
+/// Setting that enables filling blank RST fields on manually added QSOs
+pub const AUTO_RST_SETTING: &str = "auto_rst";
+
+/// Fill a blank RST for the mode: SNR for digital modes, 59/599 for phone/CW.
+/// A non-blank RST is kept; a digital mode with no SNR stays blank.
+pub fn default_rst(mode: &str, rst: Option<String>, snr: Option<i32>) -> Option<String> {
+    if rst.as_deref().is_some_and(|r| !r.trim().is_empty()) {
+        return rst;
+    }
+    match get_mode_group(mode) {
+        ModeGroup::Data => snr.map(|n| format!("{:+03}", n)),
+        ModeGroup::Phone | ModeGroup::Image => Some("59".to_string()),
+        ModeGroup::CW => Some("599".to_string()),
     }
 }
 
-// ============================================================================
-// QSO Commands
-// ============================================================================
+/// Setting holding the QsoDefaults JSON for manual entry
+pub const QSO_DEFAULTS_SETTING: &str = "qso_defaults";
 
-#[command]
-pub async fn get_qsos(
-    state: tauri::State<'_, AppState>,
-    limit: i32,
-    offset: i32,
-) -> Result<Vec<Qso>, String> {
-    log::info!("Getting QSOs: limit={}, offset={}", limit, offset);
+fn is_blank(value: Option<&str>) -> bool {
+    value.is_none_or(|v| v.trim().is_empty())
+}
 
-    let db_guard = state.db.lock().await;
-    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+impl QsoDefaults {
+    /// Fill the fields the operator left blank; anything entered is kept
+    pub fn apply(&self, qso: &mut NewQso) {
+        if qso.tx_pwr.is_none() {
+            qso.tx_pwr = self.tx_pwr;
+        }
+        if is_blank(qso.my_gridsquare.as_deref()) {
+            qso.my_gridsquare = self.my_gridsquare.clone().or(qso.my_gridsquare.take());
+        }
+        if is_blank(qso.station_callsign.as_deref()) {
+            qso.station_callsign = self.station_callsign.clone().or(qso.station_callsign.take());
+        }
+        if let Some(mode) = self.mode.as_ref().filter(|_| is_blank(Some(&qso.mode))) {
+            qso.mode = mode.clone();
+        }
+        if let Some(band) = self.band.as_ref().filter(|_| is_blank(Some(&qso.band))) {
+            qso.band = band.clone();
+        }
+    }
+}
+
+/// The saved defaults; none if unset or unreadable
+pub async fn read_qso_defaults(pool: &sqlx::Pool<sqlx::Sqlite>) -> QsoDefaults {
+    read_setting(pool, QSO_DEFAULTS_SETTING)
+        .await
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub async fn save_qso_defaults(pool: &sqlx::Pool<sqlx::Sqlite>, defaults: &QsoDefaults) -> Result<(), String> {
+    let json = serde_json::to_string(defaults).map_err(|e| e.to_string())?;
+    sqlx::query(
+        r#"INSERT INTO settings (key, value, updated_at) VALUES (?, ?, datetime('now'))
+           ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')"#,
+    )
+    .bind(QSO_DEFAULTS_SETTING)
+    .bind(&json)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to save QSO defaults: {}", e))?;
+    Ok(())
+}
+
+/// Setting key for log display: "full" (default) or "base" (strip /P, HK0/ etc.)
+pub const CALLSIGN_DISPLAY_SETTING: &str = "callsign_display";
+
+/// Whether the log should display base callsigns instead of full ones
+pub async fn use_base_callsign(pool: &sqlx::Pool<sqlx::Sqlite>) -> bool {
+    read_setting(pool, CALLSIGN_DISPLAY_SETTING)
+        .await
+        .map(|v| v.trim().eq_ignore_ascii_case("base"))
+        .unwrap_or(false)
+}
+
+/// Callsign to display for a stored call under the current setting
+pub fn display_callsign(call: &str, base_only: bool) -> String {
+    if base_only {
+        crate::reference::base_callsign(call)
+    } else {
+        call.to_string()
+    }
+}
+
+/// Short and long path for a QSO. My grid is the QSO's MY_GRIDSQUARE, or the
+/// most recently logged one when the QSO has none.
+pub async fn qso_path(pool: &sqlx::Pool<sqlx::Sqlite>, id: i64) -> Result<QsoPath, String> {
+    let (their_grid, my_grid): (Option<String>, Option<String>) =
+        sqlx::query_as("SELECT gridsquare, my_gridsquare FROM qsos WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("QSO {} not found", id))?;
+
+    let my_grid = match my_grid.filter(|g| !g.trim().is_empty()) {
+        Some(grid) => Some(grid),
+        None => sqlx::query_scalar(
+            r#"SELECT my_gridsquare FROM qsos
+               WHERE my_gridsquare IS NOT NULL AND my_gridsquare != ''
+               ORDER BY qso_date DESC, time_on DESC LIMIT 1"#,
+        )
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?,
+    };
+
+    let my_grid = my_grid.ok_or("No station grid logged")?.trim().to_uppercase();
+    let their_grid = their_grid
+        .filter(|g| !g.trim().is_empty())
+        .ok_or_else(|| format!("QSO {} has no grid square", id))?
+        .trim()
+        .to_uppercase();
+    let path = crate::reference::grid_location::grid_path(&my_grid, &their_grid)
+        .ok_or_else(|| format!("Invalid grid square: {} or {}", my_grid, their_grid))?;
+
+    Ok(QsoPath { my_grid, their_grid, path })
+}
+
+/// The home grid for distance records: `home_grid`, else the station profile grid
+pub async fn home_grid(pool: &sqlx::Pool<sqlx::Sqlite>) -> Option<String> {
+    for key in [HOME_GRID_SETTING, MY_GRID_SETTING] {
+        if let Some(grid) = read_setting(pool, key).await.filter(|g| !g.trim().is_empty()) {
+            return Some(grid.trim().to_uppercase());
+        }
+    }
+    None
+}
+
+/// QSOs sorted by distance from `home`, farthest first. QSOs without a
+/// valid grid square are left out.
+pub async fn qsos_by_distance(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    home: &str,
+    limit: usize,
+) -> Result<Vec<DxRecord>, String> {
+    use crate::reference::grid_location::grid_distance_km;
+
+    if crate::reference::grid_location::grid_to_latlon(home).is_none() {
+        return Err(format!("Invalid home grid: {}", home));
+    }
+
+    let sql = format!(
+        r#"SELECT id, call, qso_date, band, mode, gridsquare, country, 0.0 AS distance_km FROM qsos q
+           WHERE gridsquare IS NOT NULL AND gridsquare != ''{}"#,
+        counted_qso_filter(pool, "q").await
+    );
+    let rows: Vec<DxRecord> = sqlx::query_as(&sql)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut records: Vec<DxRecord> = rows
+        .into_iter()
+        .filter_map(|record| {
+            let distance_km = grid_distance_km(home, record.gridsquare.trim())?;
+            Some(DxRecord { distance_km, ..record })
+        })
+        .collect();
+    records.sort_by(|a, b| b.distance_km.total_cmp(&a.distance_km));
+    records.truncate(limit);
+    Ok(records)
+}
 
+/// Change a QSO's callsign and cascade: DXCC, country, continent and zones are
+/// looked up again, grids inferred from the old call are dropped, and received
+/// confirmations are flagged `needs_reverify` since they were for the old call.
+pub async fn apply_callsign_correction(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    qso_id: i64,
+    new_call: &str,
+) -> Result<CallsignCorrection, String> {
+    let new_call = new_call.trim().to_uppercase();
+    if new_call.is_empty() {
+        return Err("Callsign cannot be empty".to_string());
+    }
+    let old_call: String = sqlx::query_scalar("SELECT call FROM qsos WHERE id = ?")
+        .bind(qso_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("QSO {} not found", qso_id))?;
+
+    let lookup = crate::reference::lookup_call_full(&new_call);
+    let dxcc = lookup.dxcc_as_i32();
+    sqlx::query(
+        r#"UPDATE qsos SET call = ?, dxcc = ?, country = ?, continent = ?, cqz = ?, ituz = ?,
+               gridsquare = CASE WHEN grid_source IN (?, ?) THEN NULL ELSE gridsquare END,
+               grid_source = CASE WHEN grid_source IN (?, ?) THEN NULL ELSE grid_source END,
+               updated_at = datetime('now')
+           WHERE id = ?"#,
+    )
+    .bind(&new_call)
+    .bind(dxcc)
+    .bind(&lookup.country)
+    .bind(&lookup.continent)
+    .bind(lookup.cqz)
+    .bind(lookup.ituz)
+    .bind(GRID_SOURCE_PRIOR_QSO)
+    .bind(GRID_SOURCE_FCC)
+    .bind(GRID_SOURCE_PRIOR_QSO)
+    .bind(GRID_SOURCE_FCC)
+    .bind(qso_id)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to correct callsign: {}", e))?;
+
+    let mut confirmations_flagged = 0;
+    if !old_call.eq_ignore_ascii_case(&new_call) {
+        confirmations_flagged = sqlx::query(
+            "UPDATE confirmations SET needs_reverify = 1 WHERE qso_id = ? AND qsl_rcvd = 'Y'",
+        )
+        .bind(qso_id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to flag confirmations: {}", e))?
+        .rows_affected();
+    }
+
+    Ok(CallsignCorrection {
+        qso_id,
+        old_call,
+        new_call,
+        dxcc,
+        country: lookup.country,
+        confirmations_flagged,
+    })
+}
+
+/// Source for a QSO added by hand: "manual" unless the caller names a valid,
+/// non-reserved label
+fn new_qso_source(source: Option<&str>) -> Result<String, String> {
+    source.map_or_else(|| Ok("manual".to_string()), validate_source_label)
+}
+
+/// Trim a user-given source label, rejecting empty, overlong, control-character
+/// and reserved labels
+pub fn validate_source_label(label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Source cannot be empty".to_string());
+    }
+    if RESERVED_SOURCES.iter().any(|r| r.eq_ignore_ascii_case(label)) {
+        return Err(format!("Source {} is reserved", label));
+    }
+    if label.chars().count() > MAX_SOURCE_LABEL_LEN {
+        return Err(format!("Source is longer than {} characters", MAX_SOURCE_LABEL_LEN));
+    }
+    if label.chars().any(char::is_control) {
+        return Err("Source cannot contain control characters".to_string());
+    }
+    Ok(label.to_string())
+}
+
+/// Set `source` on the given QSOs, returning how many were changed. Fails
+/// without changing anything if any of them has a reserved source.
+pub async fn relabel_qso_source(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    qso_ids: &[i64],
+    new_source: &str,
+) -> Result<u64, String> {
+    let new_source = validate_source_label(new_source)?;
+    if qso_ids.is_empty() {
+        return Ok(0);
+    }
+    let placeholders = vec!["?"; qso_ids.len()].join(",");
+    let reserved = RESERVED_SOURCES.iter().map(|r| format!("'{}'", r)).collect::<Vec<_>>().join(",");
+
+    let reserved_sql = format!(
+        "SELECT id FROM qsos WHERE id IN ({}) AND UPPER(source) IN ({}) ORDER BY id",
+        placeholders, reserved
+    );
+    let mut query = sqlx::query_scalar::<_, i64>(&reserved_sql);
+    for id in qso_ids {
+        query = query.bind(id);
+    }
+    let reserved_ids = query
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to check sources: {}", e))?;
+    if !reserved_ids.is_empty() {
+        let ids = reserved_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+        return Err(format!("QSOs with a reserved source can't be relabeled: {}", ids));
+    }
+
+    let sql = format!(
+        "UPDATE qsos SET source = ?, updated_at = datetime('now') WHERE id IN ({}) AND source IS NOT ?",
+        placeholders
+    );
+    let mut query = sqlx::query(&sql).bind(&new_source);
+    for id in qso_ids {
+        query = query.bind(id);
+    }
+    let result = query
+        .bind(&new_source)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to relabel source: {}", e))?;
+    Ok(result.rows_affected())
+}
+
+/// QSOs where both stations were activating in `program`, newest first
+pub async fn activation_qsos(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    program: ActivationProgram,
+) -> Result<Vec<ActivationQso>, String> {
+    let (their_col, my_col) = program.columns();
+    let sql = format!(
+        r#"SELECT id, call, qso_date, time_on, band, mode, TRIM({0}) AS their_ref, TRIM({1}) AS my_ref
+           FROM qsos q
+           WHERE TRIM(COALESCE({0}, '')) != '' AND TRIM(COALESCE({1}, '')) != ''{2}
+           ORDER BY qso_date DESC, time_on DESC"#,
+        their_col,
+        my_col,
+        counted_qso_filter(pool, "q").await
+    );
+    sqlx::query_as(&sql).fetch_all(pool).await.map_err(|e| e.to_string())
+}
+
+/// A page of QSOs, newest first, with their confirmation status
+pub async fn fetch_qsos(pool: &sqlx::Pool<sqlx::Sqlite>, limit: i32, offset: i32) -> Result<Vec<Qso>, String> {
     let rows = sqlx::query(
         r#"
         SELECT 
             q.id, q.uuid, q.call, q.qso_date, q.qso_date_off, q.time_on, q.time_off, 
             q.band, q.mode, q.freq,
-            q.dxcc, q.country, q.continent, q.state, q.gridsquare, q.cqz, q.ituz,
+            q.dxcc, q.country, q.continent, q.state, q.gridsquare, q.grid_source, q.cqz, q.ituz,
             q.rst_sent, q.rst_rcvd, q.station_callsign, q.operator, q.my_gridsquare, q.tx_pwr,
             q.adif_fields, q.user_data, q.source, q.created_at, q.updated_at,
+            q.pota_ref, q.my_pota_ref, q.sota_ref, q.my_sota_ref,
             lotw.qsl_rcvd as lotw_rcvd,
-            eqsl.qsl_rcvd as eqsl_rcvd
+            eqsl.qsl_rcvd as eqsl_rcvd,
+            EXISTS(SELECT 1 FROM confirmations c WHERE c.qso_id = q.id AND c.needs_reverify = 1) as needs_reverify,
+            (SELECT GROUP_CONCAT(UPPER(c.source)) FROM confirmations c
+             WHERE c.qso_id = q.id AND c.qsl_rcvd = 'Y' AND c.needs_reverify = 0) as confirmed_sources,
+            EXISTS(SELECT 1 FROM confirmations c WHERE c.qso_id = q.id AND c.qsl_sent = 'Y') as qsl_sent
         FROM qsos q
         LEFT JOIN confirmations lotw ON q.id = lotw.qso_id AND lotw.source = 'LOTW'
         LEFT JOIN confirmations eqsl ON q.id = eqsl.qso_id AND eqsl.source = 'EQSL'
@@ -169,6 +634,7 @@ pub async fn get_qsos(
     .await
     .map_err(|e| e.to_string())?;
 
+    let base_only = use_base_callsign(pool).await;
     let qsos: Vec<Qso> = rows
         .iter()
         .map(|row| Qso {
@@ -187,6 +653,7 @@ pub async fn get_qsos(
             continent: row.get("continent"),
             state: row.get("state"),
             gridsquare: row.get("gridsquare"),
+            grid_source: row.get("grid_source"),
             cqz: row.get("cqz"),
             ituz: row.get("ituz"),
             rst_sent: row.get("rst_sent"),
@@ -202,31 +669,78 @@ pub async fn get_qsos(
             updated_at: row.get("updated_at"),
             lotw_rcvd: row.get("lotw_rcvd"),
             eqsl_rcvd: row.get("eqsl_rcvd"),
+            display_call: display_callsign(row.get("call"), base_only),
+            dxpedition: is_dxpedition(row.get("call"), row.get("qso_date")),
+            p2p: both_activating(row.get("pota_ref"), row.get("my_pota_ref")),
+            s2s: both_activating(row.get("sota_ref"), row.get("my_sota_ref")),
+            needs_reverify: row.get("needs_reverify"),
+            confirmation_state: ConfirmationState::from_confirmations(
+                row.get("confirmed_sources"),
+                row.get("qsl_sent"),
+            ),
         })
         .collect();
 
     Ok(qsos)
 }
 
+/// SWL reports in the log, newest first
+pub async fn swl_reports(pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<Vec<SwlReport>, String> {
+    sqlx::query_as(
+        r#"SELECT id, call, qso_date, time_on, band, mode, country, rst_rcvd, comment
+           FROM qsos WHERE source = ?
+           ORDER BY qso_date DESC, time_on DESC"#,
+    )
+    .bind(SOURCE_SWL)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// QSO Commands
+// ============================================================================
+
 #[command]
-pub async fn add_qso(state: tauri::State<'_, AppState>, qso: NewQso) -> Result<Qso, String> {
+pub async fn get_qsos(
+    state: tauri::State<'_, AppState>,
+    limit: i32,
+    offset: i32,
+) -> Result<Vec<Qso>, String> {
+    log::info!("Getting QSOs: limit={}, offset={}", limit, offset);
+
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    fetch_qsos(pool, limit, offset).await
+}
+
+#[command]
+pub async fn add_qso(state: tauri::State<'_, AppState>, mut qso: NewQso) -> Result<Qso, String> {
     log::info!("Adding QSO: {}", qso.call);
+    let source = new_qso_source(qso.source.as_deref())?;
 
     let db_guard = state.db.lock().await;
     let pool = db_guard.as_ref().ok_or("Database not initialized")?;
 
+    read_qso_defaults(pool).await.apply(&mut qso);
+    if read_bool_setting(pool, AUTO_RST_SETTING, true).await {
+        qso.rst_sent = default_rst(&qso.mode, qso.rst_sent.take(), qso.snr_sent);
+        qso.rst_rcvd = default_rst(&qso.mode, qso.rst_rcvd.take(), qso.snr_rcvd);
+    }
+
     let uuid = uuid::Uuid::new_v4().to_string();
-    let source = qso.source.unwrap_or_else(|| "manual".to_string());
 
     // Look up DXCC entity for the callsign
     let lookup = crate::reference::lookup_call_full(&qso.call);
     // Convert DXCC from ARRL 3-digit string to integer for database storage
     let dxcc_int = lookup.dxcc_as_i32();
+    let grid_source = qso.gridsquare.as_ref().filter(|g| !g.is_empty()).map(|_| GRID_SOURCE_MANUAL.to_string());
 
     let result = sqlx::query(
         r#"
-        INSERT INTO qsos (uuid, call, qso_date, time_on, band, mode, freq, dxcc, country, continent, cqz, ituz, gridsquare, rst_sent, rst_rcvd, source, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
+        INSERT INTO qsos (uuid, call, qso_date, time_on, band, mode, freq, freq_hz, dxcc, country, continent, cqz, ituz, gridsquare, grid_source, rst_sent, rst_rcvd, station_callsign, my_gridsquare, tx_pwr, source, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
         "#,
     )
     .bind(&uuid)
@@ -236,20 +750,29 @@ pub async fn add_qso(state: tauri::State<'_, AppState>, qso: NewQso) -> Result<Q
     .bind(&qso.band)
     .bind(&qso.mode)
     .bind(qso.freq)
+    .bind(qso.freq.and_then(mhz_to_hz).map(|hz| hz as i64))
     .bind(dxcc_int)
     .bind(&lookup.country)
     .bind(&lookup.continent)
     .bind(lookup.cqz)
     .bind(lookup.ituz)
     .bind(&qso.gridsquare)
+    .bind(&grid_source)
     .bind(&qso.rst_sent)
     .bind(&qso.rst_rcvd)
+    .bind(&qso.station_callsign)
+    .bind(&qso.my_gridsquare)
+    .bind(qso.tx_pwr)
     .bind(&source)
     .execute(pool)
     .await
     .map_err(|e| e.to_string())?;
 
     let id = result.last_insert_rowid();
+    let _ = state.worked.refresh_qso(pool, id).await;
+    if let Some(warning) = super::validation::calling_freq_warning(&qso.mode, qso.freq) {
+        log::warn!("QSO {} with {}: {}", id, qso.call, warning);
+    }
 
     // Add to sync queue for LoTW upload
     let _ = sqlx::query(
@@ -260,6 +783,8 @@ pub async fn add_qso(state: tauri::State<'_, AppState>, qso: NewQso) -> Result<Q
     .await;
 
     let now = chrono::Utc::now().to_rfc3339();
+    let display_call = display_callsign(&qso.call, use_base_callsign(pool).await);
+    let dxpedition = is_dxpedition(&qso.call, &qso.qso_date);
 
     Ok(Qso {
         id,
@@ -277,14 +802,15 @@ pub async fn add_qso(state: tauri::State<'_, AppState>, qso: NewQso) -> Result<Q
         continent: lookup.continent,
         state: None,
         gridsquare: qso.gridsquare,
+        grid_source,
         cqz: lookup.cqz,
         ituz: lookup.ituz,
         rst_sent: qso.rst_sent,
         rst_rcvd: qso.rst_rcvd,
-        station_callsign: None,
+        station_callsign: qso.station_callsign,
         operator: None,
-        my_gridsquare: None,
-        tx_pwr: None,
+        my_gridsquare: qso.my_gridsquare,
+        tx_pwr: qso.tx_pwr,
         adif_fields: None,
         user_data: None,
         source,
@@ -292,9 +818,31 @@ pub async fn add_qso(state: tauri::State<'_, AppState>, qso: NewQso) -> Result<Q
         updated_at: now,
         lotw_rcvd: None,
         eqsl_rcvd: None,
+        display_call,
+        dxpedition,
+        p2p: false,
+        s2s: false,
+        needs_reverify: false,
+        confirmation_state: ConfirmationState::Worked,
     })
 }
 
+#[command]
+pub async fn get_qso_defaults(state: tauri::State<'_, AppState>) -> Result<QsoDefaults, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    Ok(read_qso_defaults(pool).await)
+}
+
+#[command]
+pub async fn set_qso_defaults(state: tauri::State<'_, AppState>, defaults: QsoDefaults) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    save_qso_defaults(pool, &defaults).await
+}
+
 #[command]
 pub async fn update_qso(
     state: tauri::State<'_, AppState>,
@@ -341,6 +889,23 @@ pub async fn update_qso(
         }
     }
 
+    // Keep the exact frequency in step with the edited MHz value
+    if let Some(freq) = obj.get("freq") {
+        match freq.as_str().and_then(parse_mhz_to_hz).or_else(|| freq.as_f64().and_then(mhz_to_hz)) {
+            Some(hz) => set_clauses.push(format!("freq_hz = {}", hz)),
+            None => set_clauses.push("freq_hz = NULL".to_string()),
+        }
+    }
+
+    // A grid typed in by the operator
+    if let Some(grid) = obj.get("gridsquare") {
+        if grid.as_str().unwrap_or("").is_empty() {
+            set_clauses.push("grid_source = NULL".to_string());
+        } else {
+            set_clauses.push(format!("grid_source = '{}'", GRID_SOURCE_MANUAL));
+        }
+    }
+
     if set_clauses.is_empty() {
         return Ok(());
     }
@@ -356,11 +921,51 @@ pub async fn update_qso(
     query = query.bind(id);
 
     query.execute(pool).await.map_err(|e| e.to_string())?;
+    let _ = state.worked.refresh_qso(pool, id).await;
 
     log::info!("Updated QSO {}: {:?}", id, obj.keys().collect::<Vec<_>>());
     Ok(())
 }
 
+/// Correct a QSO's callsign, re-deriving its location and flagging its
+/// confirmations for re-verification. Use instead of update_qso for calls.
+#[command]
+pub async fn correct_callsign(
+    state: tauri::State<'_, AppState>,
+    qso_id: i64,
+    new_call: String,
+) -> Result<CallsignCorrection, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let correction = apply_callsign_correction(pool, qso_id, &new_call).await?;
+    let _ = state.worked.refresh_qso(pool, qso_id).await;
+    log::info!(
+        "Corrected QSO {} callsign {} -> {} ({} confirmations to re-verify)",
+        qso_id,
+        correction.old_call,
+        correction.new_call,
+        correction.confirmations_flagged
+    );
+    Ok(correction)
+}
+
+/// Set the source label of the given QSOs, e.g. to tell one import from another.
+/// Returns how many QSOs changed.
+#[command]
+pub async fn relabel_source(
+    state: tauri::State<'_, AppState>,
+    qso_ids: Vec<i64>,
+    new_source: String,
+) -> Result<u64, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let changed = relabel_qso_source(pool, &qso_ids, &new_source).await?;
+    log::info!("Relabeled {} QSOs as source {:?}", changed, new_source.trim());
+    Ok(changed)
+}
+
 #[command]
 pub async fn delete_qso(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
     log::info!("Deleting QSO: {}", id);
@@ -373,6 +978,7 @@ pub async fn delete_qso(state: tauri::State<'_, AppState>, id: i64) -> Result<()
         .execute(pool)
         .await
         .map_err(|e| e.to_string())?;
+    state.worked.remove(id);
 
     Ok(())
 }
@@ -416,6 +1022,7 @@ pub async fn remove_duplicate_qsos(state: tauri::State<'_, AppState>) -> Result<
 
     let deleted = result.rows_affected() as i64;
     log::info!("Removed {} duplicate QSOs", deleted);
+    state.worked.rebuild_or_log(pool).await;
 
     Ok(deleted)
 }
@@ -434,6 +1041,7 @@ pub async fn clear_all_qsos(state: tauri::State<'_, AppState>) -> Result<i64, St
 
     let deleted = result.rows_affected() as i64;
     log::info!("Deleted {} QSOs", deleted);
+    state.worked.rebuild_or_log(pool).await;
 
     Ok(deleted)
 }
@@ -479,8 +1087,8 @@ pub async fn add_test_qsos(
 
         sqlx::query(
             r#"
-            INSERT INTO qsos (uuid, call, qso_date, time_on, band, mode, freq, dxcc, country, continent, gridsquare, rst_sent, rst_rcvd, source, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'TEST', datetime('now'), datetime('now'))
+            INSERT INTO qsos (uuid, call, qso_date, time_on, band, mode, freq, freq_hz, dxcc, country, continent, gridsquare, rst_sent, rst_rcvd, source, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'TEST', datetime('now'), datetime('now'))
             "#,
         )
         .bind(&uuid)
@@ -490,6 +1098,7 @@ pub async fn add_test_qsos(
         .bind(band)
         .bind(mode)
         .bind(freq)
+        .bind(mhz_to_hz(*freq).map(|hz| hz as i64))
         .bind(dxcc)
         .bind(country)
         .bind(continent)
@@ -504,6 +1113,7 @@ pub async fn add_test_qsos(
     }
 
     log::info!("Added {} test QSOs", count);
+    state.worked.rebuild_or_log(pool).await;
     let _ = app.emit("test-qsos-added", count);
 
     Ok(count)
@@ -594,132 +1204,181 @@ pub async fn check_qso_status(
     let db_guard = state.db.lock().await;
     let pool = db_guard.as_ref().ok_or("Database not initialized")?;
 
-    // Check for dupe
-    let dupe_query = if let Some(excl_id) = exclude_id {
-        sqlx::query(
-            "SELECT COUNT(*) as cnt FROM qsos WHERE call = ? AND band = ? AND mode = ? AND qso_date = ? AND id != ?",
-        )
-        .bind(&call)
-        .bind(&band)
-        .bind(&mode)
-        .bind(&qso_date)
-        .bind(excl_id)
-    } else {
-        sqlx::query(
-            "SELECT COUNT(*) as cnt FROM qsos WHERE call = ? AND band = ? AND mode = ? AND qso_date = ?",
-        )
-        .bind(&call)
-        .bind(&band)
-        .bind(&mode)
-        .bind(&qso_date)
-    };
-    let is_dupe: i64 = dupe_query
-        .fetch_one(pool)
-        .await
-        .map(|r| r.get("cnt"))
-        .unwrap_or(0);
-
-    // Check previous QSOs
-    let prev_count: i64 = if let Some(excl_id) = exclude_id {
-        sqlx::query("SELECT COUNT(*) as cnt FROM qsos WHERE call = ? AND id != ?")
-            .bind(&call)
-            .bind(excl_id)
-            .fetch_one(pool)
-            .await
-            .map(|r| r.get("cnt"))
-            .unwrap_or(0)
-    } else {
-        sqlx::query("SELECT COUNT(*) as cnt FROM qsos WHERE call = ?")
-            .bind(&call)
-            .fetch_one(pool)
-            .await
-            .map(|r| r.get("cnt"))
-            .unwrap_or(0)
+    // Answered from the in-memory index; no SQLite round trips on the live path
+    let query = WorkedQuery {
+        call: &call,
+        band: &band,
+        mode: &mode,
+        dxcc,
+        qso_date: &qso_date,
     };
+    state.worked.status(pool, &query, exclude_id).await
+}
 
-    // DXCC status checks
-    let (is_new_dxcc, is_new_band_dxcc, is_new_mode_dxcc) = if let Some(dxcc_id) = dxcc {
-        let any_with_dxcc: i64 = if let Some(excl_id) = exclude_id {
-            sqlx::query("SELECT COUNT(*) as cnt FROM qsos WHERE dxcc = ? AND id != ?")
-                .bind(dxcc_id)
-                .bind(excl_id)
-                .fetch_one(pool)
-                .await
-                .map(|r| r.get("cnt"))
-                .unwrap_or(0)
-        } else {
-            sqlx::query("SELECT COUNT(*) as cnt FROM qsos WHERE dxcc = ?")
-                .bind(dxcc_id)
-                .fetch_one(pool)
-                .await
-                .map(|r| r.get("cnt"))
-                .unwrap_or(0)
-        };
+/// Beam headings and distances for a QSO, both ways around
+#[command]
+pub async fn get_qso_path(state: tauri::State<'_, AppState>, id: i64) -> Result<QsoPath, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
 
-        let any_on_band: i64 = if let Some(excl_id) = exclude_id {
-            sqlx::query("SELECT COUNT(*) as cnt FROM qsos WHERE dxcc = ? AND band = ? AND id != ?")
-                .bind(dxcc_id)
-                .bind(&band)
-                .bind(excl_id)
-                .fetch_one(pool)
-                .await
-                .map(|r| r.get("cnt"))
-                .unwrap_or(0)
-        } else {
-            sqlx::query("SELECT COUNT(*) as cnt FROM qsos WHERE dxcc = ? AND band = ?")
-                .bind(dxcc_id)
-                .bind(&band)
-                .fetch_one(pool)
-                .await
-                .map(|r| r.get("cnt"))
-                .unwrap_or(0)
-        };
+    qso_path(pool, id).await
+}
 
-        let any_on_mode: i64 = if let Some(excl_id) = exclude_id {
-            sqlx::query("SELECT COUNT(*) as cnt FROM qsos WHERE dxcc = ? AND mode = ? AND id != ?")
-                .bind(dxcc_id)
-                .bind(&mode)
-                .bind(excl_id)
-                .fetch_one(pool)
-                .await
-                .map(|r| r.get("cnt"))
-                .unwrap_or(0)
-        } else {
-            sqlx::query("SELECT COUNT(*) as cnt FROM qsos WHERE dxcc = ? AND mode = ?")
-                .bind(dxcc_id)
-                .bind(&mode)
-                .fetch_one(pool)
-                .await
-                .map(|r| r.get("cnt"))
-                .unwrap_or(0)
-        };
+/// Personal DX records: QSOs farthest from the home grid first
+#[command]
+pub async fn get_qsos_by_distance(
+    state: tauri::State<'_, AppState>,
+    limit: usize,
+) -> Result<Vec<DxRecord>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
 
-        (any_with_dxcc == 0, any_on_band == 0, any_on_mode == 0)
-    } else {
-        (false, false, false)
-    };
+    let home = home_grid(pool).await.ok_or("No home grid set")?;
+    qsos_by_distance(pool, &home, limit).await
+}
 
-    Ok(QsoStatus {
-        is_dupe: is_dupe > 0,
-        is_new_dxcc,
-        is_new_band_dxcc,
-        is_new_mode_dxcc,
-        has_previous_qso: prev_count > 0,
-        previous_qso_count: prev_count as i32,
-    })
+/// Park-to-park contacts (both stations activating a POTA park)
+#[command]
+pub async fn get_p2p_qsos(state: tauri::State<'_, AppState>) -> Result<Vec<ActivationQso>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    activation_qsos(pool, ActivationProgram::Pota).await
+}
+
+/// Summit-to-summit contacts (both stations activating a SOTA summit)
+#[command]
+pub async fn get_s2s_qsos(state: tauri::State<'_, AppState>) -> Result<Vec<ActivationQso>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    activation_qsos(pool, ActivationProgram::Sota).await
 }
+
+/// Shortwave listener reports imported from ADIF (SWL=Y), newest first
+#[command]
+pub async fn get_swl_reports(state: tauri::State<'_, AppState>) -> Result<Vec<SwlReport>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    swl_reports(pool).await
+}
+
 /// Result of the repair operation
 #[derive(Debug, Serialize)]
 pub struct RepairResult {
     pub qsos_checked: i32,
     pub qsos_repaired: i32,
     pub grids_cleared: i32,
+    pub bands_corrected: i32,
+    pub freqs_filled: i32,
+    pub countries_normalized: i32,
+    pub dates_off_fixed: i32,
     pub errors: Vec<String>,
 }
 
+/// Set `country` to the canonical uppercased DXCC entity name (the form
+/// `lookup_call_full` stores) on every QSO with a known dxcc.
+/// Returns how many QSOs were changed.
+pub async fn normalize_countries(pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<i32, String> {
+    let dxccs: Vec<i64> = sqlx::query_scalar("SELECT DISTINCT dxcc FROM qsos WHERE dxcc IS NOT NULL")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut normalized = 0;
+    for dxcc in dxccs {
+        let Some(entity) = crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc)) else {
+            continue;
+        };
+        let canonical = entity.name.to_uppercase();
+        let result = sqlx::query(
+            r#"UPDATE qsos SET country = ?, updated_at = datetime('now')
+               WHERE dxcc = ? AND (country IS NULL OR country != ?)"#,
+        )
+        .bind(&canonical)
+        .bind(dxcc)
+        .bind(&canonical)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to normalize country for DXCC {}: {}", dxcc, e))?;
+        normalized += result.rows_affected() as i32;
+    }
+    Ok(normalized)
+}
+
+/// Correct each QSO's band from its frequency when that is unambiguous, and
+/// fill a missing frequency with the standard FT8/FT4 dial frequency. Phone,
+/// CW and other modes keep an empty frequency rather than a made-up one.
+/// Returns (bands corrected, frequencies filled); per-QSO failures go to `errors`.
+pub async fn reconcile_band_freqs(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    errors: &mut Vec<String>,
+) -> Result<(i32, i32), String> {
+    use crate::adif::bands::{reconcile_band_freq, BandFreqFix};
+
+    let mut bands_corrected = 0;
+    let mut freqs_filled = 0;
+    
+    let band_rows = sqlx::query("SELECT id, call, band, mode, COALESCE(freq_hz / 1000000.0, freq) AS freq FROM qsos")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    for row in &band_rows {
+        let id: i64 = row.get("id");
+        let call: String = row.get("call");
+        let band: Option<String> = row.try_get("band").ok();
+        let freq: Option<f64> = row.try_get("freq").ok();
+        let mode: Option<String> = row.try_get("mode").ok();
+        
+        match reconcile_band_freq(band.as_deref(), freq, mode.as_deref()) {
+            BandFreqFix::CorrectBand(new_band) => {
+                log::warn!("Correcting band for {} (id={}): {:?} -> {} (freq {:?} MHz)",
+                           call, id, band, new_band, freq);
+                let result = sqlx::query(
+                    "UPDATE qsos SET band = ?, updated_at = datetime('now') WHERE id = ?"
+                )
+                .bind(new_band)
+                .bind(id)
+                .execute(pool)
+                .await;
+                
+                match result {
+                    Ok(_) => bands_corrected += 1,
+                    Err(e) => errors.push(format!("Failed to correct band for {}: {}", call, e)),
+                }
+            }
+            BandFreqFix::FillFreq(new_freq) => {
+                log::info!("Filling missing freq for {} (id={}): {} MHz from band {:?}",
+                           call, id, new_freq, band);
+                let result = sqlx::query(
+                    "UPDATE qsos SET freq = ?, freq_hz = ?, updated_at = datetime('now') WHERE id = ?"
+                )
+                .bind(new_freq)
+                .bind(mhz_to_hz(new_freq).map(|hz| hz as i64))
+                .bind(id)
+                .execute(pool)
+                .await;
+                
+                match result {
+                    Ok(_) => freqs_filled += 1,
+                    Err(e) => errors.push(format!("Failed to fill freq for {}: {}", call, e)),
+                }
+            }
+            BandFreqFix::NoChange => {}
+        }
+    }
+
+    Ok((bands_corrected, freqs_filled))
+}
+
 /// Repair QSO data issues:
 /// 1. Re-lookup DXCC for QSOs with NULL dxcc field
 /// 2. Clear invalid grids (FT8 messages like RR73, RRR, 73)
+/// 3. Correct band from frequency, or fill a missing FT8/FT4 frequency from band
+/// 4. Normalize country names to the DXCC entity name
+/// 5. Set QSO_DATE_OFF to the next day on QSOs that crossed midnight
 /// 
 /// This is a one-time repair command to fix data quality issues.
 #[command]
@@ -774,7 +1433,9 @@ pub async fn repair_qso_data(state: tauri::State<'_, AppState>) -> Result<Repair
         
         // Update the QSO
         let result = sqlx::query(
-            "UPDATE qsos SET dxcc = ?, country = ?, continent = ?, cqz = ?, ituz = ?, gridsquare = ?, updated_at = datetime('now') WHERE id = ?"
+            r#"UPDATE qsos SET dxcc = ?, country = ?, continent = ?, cqz = ?, ituz = ?, gridsquare = ?,
+                   grid_source = CASE WHEN ? IS NULL THEN NULL ELSE grid_source END,
+                   updated_at = datetime('now') WHERE id = ?"#
         )
         .bind(dxcc_int)
         .bind(&lookup.country)
@@ -782,6 +1443,7 @@ pub async fn repair_qso_data(state: tauri::State<'_, AppState>) -> Result<Repair
         .bind(lookup.cqz)
         .bind(lookup.ituz)
         .bind(&valid_grid)
+        .bind(&valid_grid)
         .bind(id)
         .execute(pool)
         .await;
@@ -814,7 +1476,7 @@ pub async fn repair_qso_data(state: tauri::State<'_, AppState>) -> Result<Repair
             log::warn!("Clearing invalid grid '{}' for {} (id={})", grid, call, id);
             
             let result = sqlx::query(
-                "UPDATE qsos SET gridsquare = NULL, updated_at = datetime('now') WHERE id = ?"
+                "UPDATE qsos SET gridsquare = NULL, grid_source = NULL, updated_at = datetime('now') WHERE id = ?"
             )
             .bind(id)
             .execute(pool)
@@ -828,13 +1490,435 @@ pub async fn repair_qso_data(state: tauri::State<'_, AppState>) -> Result<Repair
         }
     }
     
-    log::info!("QSO repair complete: {} checked, {} repaired, {} grids cleared, {} errors",
-               qsos_checked, qsos_repaired, grids_cleared, errors.len());
+    // Step 3: Reconcile band with frequency (frequency wins when unambiguous)
+    let (bands_corrected, freqs_filled) = reconcile_band_freqs(pool, &mut errors).await?;
+    
+    // Step 4: Canonical country names so country filters match
+    let countries_normalized = match normalize_countries(pool).await {
+        Ok(n) => n,
+        Err(e) => {
+            errors.push(e);
+            0
+        }
+    };
+    
+    // Step 5: End dates for QSOs that crossed midnight
+    let dates_off_fixed = match super::validation::set_overnight_dates(pool).await {
+        Ok(n) => n,
+        Err(e) => {
+            errors.push(e);
+            0
+        }
+    };
+    
+    log::info!("QSO repair complete: {} checked, {} repaired, {} grids cleared, {} bands corrected, {} freqs filled, {} countries normalized, {} end dates fixed, {} errors",
+               qsos_checked, qsos_repaired, grids_cleared, bands_corrected, freqs_filled, countries_normalized, dates_off_fixed, errors.len());
+    state.worked.rebuild_or_log(pool).await;
     
     Ok(RepairResult {
         qsos_checked,
         qsos_repaired,
         grids_cleared,
+        bands_corrected,
+        freqs_filled,
+        countries_normalized,
+        dates_off_fixed,
         errors,
     })
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    #[test]
+    fn test_default_rst_ft8_uses_snr() {
+        assert_eq!(default_rst("FT8", None, Some(-12)), Some("-12".to_string()));
+        assert_eq!(default_rst("FT8", Some("".to_string()), Some(5)), Some("+05".to_string()));
+        assert_eq!(default_rst("FT8", None, None), None);
+    }
+
+    #[test]
+    fn test_default_rst_ssb() {
+        assert_eq!(default_rst("SSB", None, None), Some("59".to_string()));
+        assert_eq!(default_rst("SSB", None, Some(-10)), Some("59".to_string()));
+    }
+
+    #[test]
+    fn test_default_rst_cw() {
+        assert_eq!(default_rst("CW", Some("  ".to_string()), None), Some("599".to_string()));
+    }
+
+    #[test]
+    fn test_default_rst_keeps_entered_value() {
+        assert_eq!(default_rst("CW", Some("579".to_string()), None), Some("579".to_string()));
+        assert_eq!(default_rst("FT8", Some("-03".to_string()), Some(-20)), Some("-03".to_string()));
+    }
+
+    fn new_qso(band: &str, mode: &str) -> NewQso {
+        NewQso {
+            call: "W1AW".to_string(),
+            qso_date: "20260301".to_string(),
+            time_on: "1200".to_string(),
+            band: band.to_string(),
+            mode: mode.to_string(),
+            freq: None,
+            gridsquare: None,
+            rst_sent: None,
+            rst_rcvd: None,
+            source: None,
+            snr_sent: None,
+            snr_rcvd: None,
+            station_callsign: None,
+            my_gridsquare: Some(" ".to_string()),
+            tx_pwr: None,
+        }
+    }
+
+    /// Test: Defaults fill blank fields only
+    #[tokio::test]
+    async fn test_qso_defaults_fill_unset_fields() {
+        let pool = test_pool().await;
+        assert_eq!(read_qso_defaults(&pool).await, QsoDefaults::default());
+
+        let defaults = QsoDefaults {
+            tx_pwr: Some(100.0),
+            my_gridsquare: Some("FN31".to_string()),
+            station_callsign: Some("K1ABC".to_string()),
+            mode: Some("SSB".to_string()),
+            band: Some("20m".to_string()),
+        };
+        save_qso_defaults(&pool, &defaults).await.unwrap();
+        let defaults = read_qso_defaults(&pool).await;
+
+        let mut blank = new_qso("", "");
+        defaults.apply(&mut blank);
+        assert_eq!((blank.band.as_str(), blank.mode.as_str()), ("20m", "SSB"));
+        assert_eq!(blank.my_gridsquare.as_deref(), Some("FN31"));
+        assert_eq!(blank.station_callsign.as_deref(), Some("K1ABC"));
+        assert_eq!(blank.tx_pwr, Some(100.0));
+
+        let mut entered = new_qso("40m", "CW");
+        entered.tx_pwr = Some(5.0);
+        entered.station_callsign = Some("K1ABC/P".to_string());
+        defaults.apply(&mut entered);
+        assert_eq!((entered.band.as_str(), entered.mode.as_str()), ("40m", "CW"));
+        assert_eq!(entered.station_callsign.as_deref(), Some("K1ABC/P"));
+        assert_eq!(entered.tx_pwr, Some(5.0));
+        // Blank grid still gets the default
+        assert_eq!(entered.my_gridsquare.as_deref(), Some("FN31"));
+    }
+
+    /// Test: Inconsistent or blank country strings take the DXCC entity name
+    #[tokio::test]
+    async fn test_normalize_countries() {
+        let pool = test_pool().await;
+
+        for (id, call, dxcc, country) in [
+            (1, "W1AW", Some(291), Some("USA")),
+            (2, "K1ABC", Some(291), Some("UNITED STATES OF AMERICA")),
+            (3, "JA1ABC", Some(339), None),
+            (4, "XX9XX", None, Some("Nowhere")),
+        ] {
+            insert_qso(call).id(id).dxcc(dxcc).set("country", country).insert(&pool).await;
+        }
+
+        assert_eq!(normalize_countries(&pool).await.unwrap(), 2);
+
+        let countries: Vec<Option<String>> = sqlx::query_scalar("SELECT country FROM qsos ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            countries,
+            [
+                Some("UNITED STATES OF AMERICA".to_string()),
+                Some("UNITED STATES OF AMERICA".to_string()),
+                Some("JAPAN".to_string()),
+                Some("Nowhere".to_string()),
+            ]
+        );
+
+        // Already canonical: nothing to do
+        assert_eq!(normalize_countries(&pool).await.unwrap(), 0);
+    }
+
+    /// Test: get_qsos reports worked, sent-pending and confirmed (per source) QSOs
+    #[tokio::test]
+    async fn test_confirmation_state() {
+        let pool = test_pool().await;
+
+        for (id, call, time_on) in [(1, "W1AW", "1200"), (2, "JA1ABC", "1300"), (3, "VK2XYZ", "1400"), (4, "G0XYZ", "1500")] {
+            insert_qso(call).id(id).time(time_on).insert(&pool).await;
+        }
+        for (qso_id, source, sent, rcvd) in [
+            (2, "LOTW", "Y", None),
+            (3, "LOTW", "Y", Some("Y")),
+            (4, "LOTW", "Y", Some("Y")),
+            (4, "EQSL", "N", Some("Y")),
+        ] {
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_sent, qsl_rcvd) VALUES (?, ?, ?, ?)")
+                .bind(qso_id)
+                .bind(source)
+                .bind(sent)
+                .bind(rcvd)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let states: Vec<(String, ConfirmationState)> = fetch_qsos(&pool, 10, 0)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|q| (q.call, q.confirmation_state))
+            .collect();
+        let confirmed = |sources: &[&str]| ConfirmationState::Confirmed {
+            sources: sources.iter().map(|s| s.to_string()).collect(),
+        };
+        assert_eq!(
+            states,
+            [
+                ("G0XYZ".to_string(), confirmed(&["EQSL", "LOTW"])),
+                ("VK2XYZ".to_string(), confirmed(&["LOTW"])),
+                ("JA1ABC".to_string(), ConfirmationState::SentPendingConfirmation),
+                ("W1AW".to_string(), ConfirmationState::Worked),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&states[1].1).unwrap(),
+            serde_json::json!({"status": "confirmed", "sources": ["LOTW"]})
+        );
+    }
+
+    #[test]
+    fn test_new_qso_source() {
+        assert_eq!(new_qso_source(None), Ok("manual".to_string()));
+        assert_eq!(new_qso_source(Some(" Field Day ")), Ok("Field Day".to_string()));
+        assert!(new_qso_source(Some("wsjt-x")).is_err());
+        assert!(new_qso_source(Some(SOURCE_SWL)).is_err());
+    }
+
+    #[test]
+    fn test_validate_source_label() {
+        assert_eq!(validate_source_label("  N3FJP import 2019 "), Ok("N3FJP import 2019".to_string()));
+        assert!(validate_source_label("   ").is_err());
+        assert!(validate_source_label("bad\nlabel").is_err());
+        assert!(validate_source_label(&"X".repeat(MAX_SOURCE_LABEL_LEN + 1)).is_err());
+        for reserved in ["TEST", "swl", " WSJT-X ", "wsjtx"] {
+            assert!(validate_source_label(reserved).is_err(), "{} should be reserved", reserved);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relabel_source() {
+        let pool = test_pool().await;
+
+        for (id, call, source) in [(1, "W1AW", "ADIF"), (2, "K1ABC", "ADIF"), (3, "JA1ABC", "ADIF"), (4, "G0XYZ", "WSJT-X")] {
+            insert_qso(call).id(id).date("20190601").source(source).insert(&pool).await;
+        }
+
+        assert_eq!(relabel_qso_source(&pool, &[1, 2, 99], "N3FJP import 2019").await, Ok(2));
+        // Already labeled, nothing changes
+        assert_eq!(relabel_qso_source(&pool, &[1], "N3FJP import 2019").await, Ok(0));
+        assert!(relabel_qso_source(&pool, &[3], " ").await.is_err());
+        assert!(relabel_qso_source(&pool, &[3], "test").await.is_err());
+        // A WSJT-X QSO in the set: nothing is relabeled
+        assert_eq!(
+            relabel_qso_source(&pool, &[3, 4], "Import").await,
+            Err("QSOs with a reserved source can't be relabeled: 4".to_string())
+        );
+
+        let by_source = crate::commands::diagnostics::source_counts(&pool).await;
+        assert_eq!(
+            by_source,
+            [("N3FJP import 2019".to_string(), 2), ("ADIF".to_string(), 1), ("WSJT-X".to_string(), 1)]
+        );
+    }
+
+    /// Test: QSO without MY_GRIDSQUARE falls back to the last logged station grid
+    #[tokio::test]
+    async fn test_qso_path_uses_logged_station_grid() {
+        let pool = test_pool().await;
+
+        for (id, call, grid, my_grid) in [(1, "JA1ABC", Some("PM95"), None), (2, "G0ABC", Some("IO91"), Some("fn31"))] {
+            insert_qso(call).id(id).set("gridsquare", grid).set("my_gridsquare", my_grid).insert(&pool).await;
+        }
+
+        let path = qso_path(&pool, 1).await.unwrap();
+        assert_eq!((path.my_grid.as_str(), path.their_grid.as_str()), ("FN31", "PM95"));
+        let lp = (path.path.short_path_bearing + 180.0) % 360.0;
+        assert!((path.path.long_path_bearing - lp).abs() < 1e-9);
+        assert!(qso_path(&pool, 99).await.is_err());
+    }
+
+    /// Test: the farthest QSO sorts first; QSOs without a usable grid are skipped
+    #[tokio::test]
+    async fn test_qsos_by_distance_farthest_first() {
+        let pool = test_pool().await;
+
+        for (call, grid) in [("W2ABC", Some("FN20")), ("VK2ABC", Some("QF56")), ("G0ABC", Some("IO91")), ("K5ABC", None), ("N0ABC", Some("ZZ99"))] {
+            insert_qso(call).set("gridsquare", grid).insert(&pool).await;
+        }
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'fn31pr', datetime('now'))")
+            .bind(HOME_GRID_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let home = home_grid(&pool).await.unwrap();
+        assert_eq!(home, "FN31PR");
+        let records = qsos_by_distance(&pool, &home, 10).await.unwrap();
+        let calls: Vec<&str> = records.iter().map(|r| r.call.as_str()).collect();
+        assert_eq!(calls, ["VK2ABC", "G0ABC", "W2ABC"]);
+        assert!(records[0].distance_km > 15_000.0);
+
+        assert_eq!(qsos_by_distance(&pool, &home, 1).await.unwrap().len(), 1);
+        assert!(qsos_by_distance(&pool, "XX", 10).await.is_err());
+    }
+
+    /// Test: P2P needs both park references; S2S is tracked separately
+    #[tokio::test]
+    async fn test_p2p_and_s2s_qsos() {
+        let pool = test_pool().await;
+
+        let rows = [
+            ("K1ABC", Some("US-0001"), Some("US-4567"), None, None),
+            ("K2ABC", Some("US-0002"), None, None, None),
+            ("K3ABC", None, Some("US-4567"), Some("W7W/LC-001"), Some("W7W/KG-042")),
+        ];
+        for (call, pota, my_pota, sota, my_sota) in rows {
+            insert_qso(call)
+                .mode("CW")
+                .set("pota_ref", pota)
+                .set("my_pota_ref", my_pota)
+                .set("sota_ref", sota)
+                .set("my_sota_ref", my_sota)
+                .insert(&pool)
+                .await;
+        }
+
+        let p2p = activation_qsos(&pool, ActivationProgram::Pota).await.unwrap();
+        assert_eq!(p2p.len(), 1);
+        assert_eq!((p2p[0].call.as_str(), p2p[0].their_ref.as_str(), p2p[0].my_ref.as_str()), ("K1ABC", "US-0001", "US-4567"));
+
+        let s2s = activation_qsos(&pool, ActivationProgram::Sota).await.unwrap();
+        assert_eq!(s2s.iter().map(|q| q.call.as_str()).collect::<Vec<_>>(), ["K3ABC"]);
+    }
+
+    #[tokio::test]
+    async fn test_correct_callsign_cascades() {
+        let pool = test_pool().await;
+
+        // Busted call JA1ABC logged for what was really VK2ABC, grid taken from a prior QSO
+        let id = insert_qso("JA1ABC")
+            .dxcc(339)
+            .set("country", "JAPAN")
+            .set("continent", "AS")
+            .set("cqz", 25)
+            .set("ituz", 45)
+            .set("gridsquare", "PM95")
+            .set("grid_source", "prior_qso")
+            .insert(&pool)
+            .await;
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, 'LOTW', 'Y')")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let correction = apply_callsign_correction(&pool, id, " vk2abc ").await.unwrap();
+        assert_eq!(correction.old_call, "JA1ABC");
+        assert_eq!(correction.new_call, "VK2ABC");
+        assert_eq!(correction.dxcc, Some(150));
+        assert_eq!(correction.confirmations_flagged, 1);
+
+        let (call, dxcc, continent, cqz, grid): (String, Option<i32>, Option<String>, Option<i32>, Option<String>) =
+            sqlx::query_as("SELECT call, dxcc, continent, cqz, gridsquare FROM qsos WHERE id = ?")
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let expected = crate::reference::lookup_call_full("VK2ABC");
+        assert_eq!((call.as_str(), dxcc, continent.as_deref()), ("VK2ABC", Some(150), Some("OC")));
+        assert_eq!(cqz, expected.cqz);
+        assert_ne!(cqz, Some(25));
+        assert_eq!(grid, None);
+
+        let flagged: bool = sqlx::query_scalar("SELECT needs_reverify FROM confirmations WHERE qso_id = ?")
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(flagged);
+
+        assert!(apply_callsign_correction(&pool, id, "  ").await.is_err());
+        assert!(apply_callsign_correction(&pool, id + 1, "W1AW").await.is_err());
+    }
+
+    /// Test: a confirmation flagged by a callsign correction no longer counts as confirmed
+    #[tokio::test]
+    async fn test_corrected_call_drops_out_of_confirmed() {
+        use crate::commands::awards::{confirmed_credits, dxcc_progress, was_progress};
+
+        let pool = test_pool().await;
+
+        let id = insert_qso("W5ABC").dxcc(291).set("state", "TX").insert(&pool).await;
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, 'LOTW', 'Y')")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(dxcc_progress(&pool, None, None).await.confirmed, 1);
+        assert_eq!(was_progress(&pool, None, None).await.confirmed, 1);
+
+        apply_callsign_correction(&pool, id, "W5ABD").await.unwrap();
+
+        let dxcc = dxcc_progress(&pool, None, None).await;
+        assert_eq!((dxcc.worked, dxcc.confirmed), (1, 0));
+        let was = was_progress(&pool, None, None).await;
+        assert_eq!((was.worked, was.confirmed), (1, 0));
+        let credits = confirmed_credits(&pool).await.unwrap();
+        assert!(credits.dxcc.is_empty() && credits.states.is_empty());
+        let qso = &fetch_qsos(&pool, 10, 0).await.unwrap()[0];
+        assert!(qso.needs_reverify);
+        assert_eq!(qso.confirmation_state, ConfirmationState::Worked);
+    }
+
+    /// Test: the repair fills a missing frequency for FT8/FT4 only; SSB and CW stay empty
+    #[tokio::test]
+    async fn test_repair_fills_digital_freqs_only() {
+        let pool = test_pool().await;
+
+        for (call, band, mode, freq) in [
+            ("JA1ABC", "20m", "FT8", None),
+            ("G0XYZ", "20m", "FT4", None),
+            ("VK2ABC", "20m", "SSB", None),
+            ("K5ABC", "40m", "CW", None),
+            ("W1AW", "20m", "FT8", Some(7.074)),
+        ] {
+            insert_qso(call).band(band).mode(mode).set("freq", freq).insert(&pool).await;
+        }
+
+        let mut errors = Vec::new();
+        assert_eq!(reconcile_band_freqs(&pool, &mut errors).await.unwrap(), (1, 2));
+        assert!(errors.is_empty());
+
+        let rows: Vec<(String, String, Option<f64>)> = sqlx::query_as("SELECT call, band, freq FROM qsos ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        let rows: Vec<(&str, &str, Option<f64>)> = rows.iter().map(|(c, b, f)| (c.as_str(), b.as_str(), *f)).collect();
+        assert_eq!(
+            rows,
+            [
+                ("JA1ABC", "20m", Some(14.074)),
+                ("G0XYZ", "20m", Some(14.080)),
+                ("VK2ABC", "20m", None),
+                ("K5ABC", "40m", None),
+                ("W1AW", "40m", Some(7.074)),
+            ]
+        );
+    }
+}
@@ -9,9 +9,38 @@
 use serde::{Deserialize, Serialize};
 use tauri::command;
 
+use super::qso::SOURCE_SWL;
 use super::state::AppState;
 use crate::db::DbStats;
 
+/// Settings key: the active station profile's grid square
+pub const MY_GRID_SETTING: &str = "my_gridsquare";
+
+/// Settings key: the operator's home grid, the origin for DX distance records.
+/// Falls back to the station profile grid when unset.
+pub const HOME_GRID_SETTING: &str = "home_grid";
+
+/// Settings key: the active station profile's US state
+pub const MY_STATE_SETTING: &str = "my_state";
+
+/// Settings key: the active station profile's city
+pub const MY_CITY_SETTING: &str = "my_city";
+
+/// Settings key: drop acknowledgments ("RR73", "73", "RRR") found in RST fields
+/// on import and WSJT-X logging (on by default)
+pub const SANITIZE_RST_SETTING: &str = "sanitize_rst";
+
+/// Settings key: keep other programs' APP_ fields from imported ADIF in
+/// adif_fields (under their full lowercased name) instead of dropping them
+pub const PRESERVE_APP_FIELDS_SETTING: &str = "preserve_app_fields";
+
+/// Settings key: leave QSOs with source 'TEST' (from `add_test_qsos`) out of
+/// awards, stats and exports
+pub const EXCLUDE_TEST_DATA_SETTING: &str = "exclude_test_data";
+
+/// Settings key: drop SWL reports (SWL=Y) on ADIF import instead of logging them with source 'SWL'
+pub const SKIP_SWL_IMPORT_SETTING: &str = "skip_swl_import";
+
 // ============================================================================
 // Data Types
 // ============================================================================
@@ -28,6 +57,39 @@ pub struct CallsignInfo {
     pub longitude: Option<f64>,
 }
 
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Read a setting value directly from the pool (for internal use)
+pub async fn read_setting(pool: &sqlx::Pool<sqlx::Sqlite>, key: &str) -> Option<String> {
+    sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Read a boolean setting ("true"/"1"/"yes"), falling back to `default` when unset
+pub async fn read_bool_setting(pool: &sqlx::Pool<sqlx::Sqlite>, key: &str, default: bool) -> bool {
+    match read_setting(pool, key).await {
+        Some(v) => matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes"),
+        None => default,
+    }
+}
+
+/// Condition to append to a QSO query's WHERE clause leaving out records that
+/// don't count toward awards and stats: SWL reports always, and source 'TEST'
+/// when `exclude_test_data` is on (`" AND q.source IS NOT 'SWL'"` for `table` = "q")
+pub async fn counted_qso_filter(pool: &sqlx::Pool<sqlx::Sqlite>, table: &str) -> String {
+    let mut filter = format!(" AND {}.source IS NOT '{}'", table, SOURCE_SWL);
+    if read_bool_setting(pool, EXCLUDE_TEST_DATA_SETTING, false).await {
+        filter.push_str(&format!(" AND {}.source IS NOT 'TEST'", table));
+    }
+    filter
+}
+
 // ============================================================================
 // Settings Commands
 // ============================================================================
@@ -6,10 +6,20 @@ use std::sync::Arc;
 use sqlx::{Pool, Sqlite};
 use tokio::sync::Mutex as TokioMutex;
 
-use crate::udp::UdpListenerState;
+use super::jobs::JobQueue;
+use super::worked_cache::WorkedCache;
+use crate::qso_tracker::QsoTrackers;
+use crate::udp::UdpInstances;
 
-/// Application state holding the database connection pool and UDP listener
+/// Application state holding the database connection pool, UDP listeners,
+/// the QSO trackers of each listener, the in-memory worked-before index and
+/// the background job queue
 pub struct AppState {
     pub db: Arc<TokioMutex<Option<Pool<Sqlite>>>>,
-    pub udp_state: Arc<UdpListenerState>,
+    pub udp_state: Arc<UdpInstances>,
+    /// The UDP message loop, awaited on exit so its last writes finish
+    pub udp_task: std::sync::Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+    pub trackers: Arc<QsoTrackers>,
+    pub worked: Arc<WorkedCache>,
+    pub jobs: Arc<JobQueue>,
 }
@@ -0,0 +1,504 @@
+//! Operating statistics commands
+//!
+//! This module handles:
+//! - get_rate_stats: QSO rate meter (last 10/60 minutes, projected hourly rate)
+//! - get_yearly_stats / get_monthly_stats: Worked and confirmed totals per year or month
+//! - get_entity_rarity: Worked DXCC entities, fewest QSOs first, with confirmation status
+//! - get_activity_by_hour: QSO counts per UTC hour of TIME_ON for each band (heatmap)
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use tauri::command;
+
+use super::awards::{accepted_confirmation_sources, sources_sql, was_qso_sql};
+use super::settings::counted_qso_filter;
+use super::state::AppState;
+use super::time_utils::normalize_time_to_hhmmss;
+use crate::adif::bands::band_sort_index;
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RateStats {
+    pub window_minutes: i64,
+    pub qsos_in_window: i64,
+    pub last_10_minutes: i64,
+    pub last_60_minutes: i64,
+    /// QSOs/hour extrapolated from the last 10 minutes
+    pub rate_10: f64,
+    /// QSOs/hour over the last 60 minutes
+    pub rate_60: f64,
+    /// QSOs/hour projected from the requested window
+    pub projected_hourly_rate: f64,
+}
+
+/// Worked and confirmed totals for one year, or one month of it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PeriodStats {
+    pub year: i32,
+    /// 1-12 for monthly stats, None for yearly
+    pub month: Option<u32>,
+    pub qsos: i64,
+    pub dxcc: i64,
+    /// US states (as counted for WAS)
+    pub states: i64,
+    pub confirmed_qsos: i64,
+    pub confirmed_dxcc: i64,
+}
+
+/// How often one worked DXCC entity appears in the log
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EntityRarity {
+    pub dxcc: i32,
+    pub entity: String,
+    pub qsos: i64,
+    pub bands: i64,
+    pub confirmed_qsos: i64,
+    /// Worked but no QSO confirmed by an accepted source yet
+    pub unconfirmed: bool,
+    pub last_qso_date: String,
+}
+
+/// One heatmap row: QSOs on a band in each UTC hour
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BandHourActivity {
+    /// Lowercase band, e.g. "20m"
+    pub band: String,
+    /// QSO count per UTC hour, index 0 = 00z through 23 = 23z
+    pub hours: Vec<i64>,
+    pub total: i64,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Shortest span a projection is spread over, so one QSO at session start
+/// doesn't project to hundreds per hour
+const MIN_PROJECTION_MINUTES: i64 = 10;
+
+/// Combine an ADIF date and time into a UTC timestamp, falling back to created_at
+fn qso_timestamp(qso_date: &str, time_on: &str, created_at: &str) -> Option<DateTime<Utc>> {
+    let combined = format!("{}{}", qso_date, normalize_time_to_hhmmss(time_on));
+    if let Ok(dt) = NaiveDateTime::parse_from_str(&combined, "%Y%m%d%H%M%S") {
+        return Some(dt.and_utc());
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(created_at) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Compute rate statistics from QSO timestamps.
+///
+/// Timestamps are full UTC datetimes, so windows spanning midnight count
+/// QSOs on both sides of the day boundary.
+pub fn compute_rate_stats(timestamps: &[DateTime<Utc>], now: DateTime<Utc>, window_minutes: i64) -> RateStats {
+    let window_minutes = window_minutes.max(1);
+    let count_since = |minutes: i64| {
+        let start = now - Duration::minutes(minutes);
+        timestamps.iter().filter(|t| **t > start && **t <= now).count() as i64
+    };
+
+    let last_10_minutes = count_since(10);
+    let last_60_minutes = count_since(60);
+    let qsos_in_window = count_since(window_minutes);
+
+    // Spread the projection over the time actually covered by QSOs in the window
+    // (never less than MIN_PROJECTION_MINUTES) so a session that just started
+    // isn't diluted by the empty part of the window.
+    let window_start = now - Duration::minutes(window_minutes);
+    let earliest = timestamps.iter().filter(|t| **t > window_start && **t <= now).min();
+    let projected_hourly_rate = match earliest {
+        Some(first) => {
+            let span = (now - *first).num_minutes().clamp(MIN_PROJECTION_MINUTES.min(window_minutes), window_minutes);
+            qsos_in_window as f64 * 60.0 / span as f64
+        }
+        None => 0.0,
+    };
+
+    RateStats {
+        window_minutes,
+        qsos_in_window,
+        last_10_minutes,
+        last_60_minutes,
+        rate_10: last_10_minutes as f64 * 6.0,
+        rate_60: last_60_minutes as f64,
+        projected_hourly_rate,
+    }
+}
+
+/// Load QSO timestamps that can fall inside the window and compute rates
+pub async fn rate_stats(pool: &Pool<Sqlite>, now: DateTime<Utc>, window_minutes: i64) -> Result<RateStats, String> {
+    let longest = window_minutes.max(60);
+    // Include the previous UTC day so windows crossing midnight are complete
+    let since_date = (now - Duration::minutes(longest)).format("%Y%m%d").to_string();
+
+    let rows: Vec<(String, String, String)> = sqlx::query_as(&format!(
+        "SELECT qso_date, time_on, COALESCE(created_at, '') FROM qsos WHERE qso_date >= ?{}",
+        counted_qso_filter(pool, "qsos").await
+    ))
+    .bind(&since_date)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query QSOs: {}", e))?;
+
+    let timestamps: Vec<DateTime<Utc>> = rows
+        .iter()
+        .filter_map(|(date, time, created)| qso_timestamp(date, time, created))
+        .collect();
+
+    Ok(compute_rate_stats(&timestamps, now, window_minutes))
+}
+
+/// Totals grouped by the `qso_date` prefix: per year, or per month of `year`.
+/// Confirmations use the accepted award sources.
+pub async fn period_stats(pool: &Pool<Sqlite>, year: Option<i32>) -> Result<Vec<PeriodStats>, String> {
+    type PeriodRow = (String, i64, i64, i64, i64, i64);
+
+    let prefix_len = if year.is_some() { 6 } else { 4 };
+    let sources = accepted_confirmation_sources(pool).await;
+    let rows: Vec<PeriodRow> = sqlx::query_as(&format!(
+        r#"SELECT SUBSTR(q.qso_date, 1, {prefix_len}) AS period,
+                  COUNT(*),
+                  COUNT(DISTINCT q.dxcc),
+                  COUNT(DISTINCT CASE WHEN {was} THEN UPPER(TRIM(q.state)) END),
+                  COUNT(c.qso_id),
+                  COUNT(DISTINCT CASE WHEN c.qso_id IS NOT NULL THEN q.dxcc END)
+           FROM qsos q
+           LEFT JOIN (SELECT DISTINCT qso_id FROM confirmations
+                      WHERE source IN ({sources}) AND qsl_rcvd = 'Y' AND needs_reverify = 0) c ON c.qso_id = q.id
+           WHERE LENGTH(q.qso_date) = 8 AND (? IS NULL OR SUBSTR(q.qso_date, 1, 4) = ?){filter}
+           GROUP BY period
+           ORDER BY period"#,
+        sources = sources_sql(&sources),
+        was = was_qso_sql("q"),
+        filter = counted_qso_filter(pool, "q").await,
+    ))
+    .bind(year.map(|y| format!("{:04}", y)))
+    .bind(year.map(|y| format!("{:04}", y)))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query period stats: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(period, qsos, dxcc, states, confirmed_qsos, confirmed_dxcc)| {
+            Some(PeriodStats {
+                year: period.get(..4)?.parse().ok()?,
+                month: period.get(4..6).and_then(|m| m.parse().ok()),
+                qsos,
+                dxcc,
+                states,
+                confirmed_qsos,
+                confirmed_dxcc,
+            })
+        })
+        .collect())
+}
+
+/// Worked entities ranked by how few QSOs the log has with each, fewest first
+pub async fn entity_rarity(pool: &Pool<Sqlite>) -> Result<Vec<EntityRarity>, String> {
+    type RarityRow = (i32, Option<String>, i64, i64, i64, String);
+
+    let sources = accepted_confirmation_sources(pool).await;
+    let rows: Vec<RarityRow> = sqlx::query_as(&format!(
+        r#"SELECT q.dxcc,
+                  MAX(q.country),
+                  COUNT(*) AS qsos,
+                  COUNT(DISTINCT LOWER(q.band)),
+                  COUNT(c.qso_id),
+                  MAX(q.qso_date)
+           FROM qsos q
+           LEFT JOIN (SELECT DISTINCT qso_id FROM confirmations
+                      WHERE source IN ({sources}) AND qsl_rcvd = 'Y' AND needs_reverify = 0) c ON c.qso_id = q.id
+           WHERE q.dxcc IS NOT NULL AND q.dxcc > 0{filter}
+           GROUP BY q.dxcc
+           ORDER BY qsos ASC, q.dxcc ASC"#,
+        sources = sources_sql(&sources),
+        filter = counted_qso_filter(pool, "q").await,
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query entity rarity: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(dxcc, country, qsos, bands, confirmed_qsos, last_qso_date)| {
+            let entity = crate::reference::dxcc::get_entity_by_id(&format!("{:03}", dxcc))
+                .map(|e| e.name.to_string())
+                .or(country)
+                .unwrap_or_default();
+            EntityRarity {
+                dxcc,
+                entity,
+                qsos,
+                bands,
+                confirmed_qsos,
+                unconfirmed: confirmed_qsos == 0,
+                last_qso_date,
+            }
+        })
+        .collect())
+}
+
+/// QSO counts by TIME_ON hour for each band, bands in frequency order.
+/// QSOs without a band or a readable TIME_ON hour are left out.
+pub async fn activity_by_hour(pool: &Pool<Sqlite>) -> Result<Vec<BandHourActivity>, String> {
+    let rows: Vec<(String, String, i64)> = sqlx::query_as(&format!(
+        r#"SELECT LOWER(TRIM(q.band)) AS band, SUBSTR(q.time_on, 1, 2) AS hour, COUNT(*)
+           FROM qsos q
+           WHERE TRIM(COALESCE(q.band, '')) <> '' AND LENGTH(q.time_on) >= 4{filter}
+           GROUP BY band, hour"#,
+        filter = counted_qso_filter(pool, "q").await,
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query activity by hour: {}", e))?;
+
+    let mut activity: Vec<BandHourActivity> = Vec::new();
+    for (band, hour, count) in rows {
+        let Some(hour) = hour.parse::<usize>().ok().filter(|h| *h < 24) else {
+            continue;
+        };
+        let index = match activity.iter().position(|a| a.band == band) {
+            Some(index) => index,
+            None => {
+                activity.push(BandHourActivity { band, hours: vec![0; 24], total: 0 });
+                activity.len() - 1
+            }
+        };
+        activity[index].hours[hour] += count;
+        activity[index].total += count;
+    }
+    activity.sort_by(|a, b| band_sort_index(&a.band).cmp(&band_sort_index(&b.band)).then_with(|| a.band.cmp(&b.band)));
+    Ok(activity)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// QSO rate meter for contest operating
+#[command]
+pub async fn get_rate_stats(
+    state: tauri::State<'_, AppState>,
+    window_minutes: Option<i64>,
+) -> Result<RateStats, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    rate_stats(pool, Utc::now(), window_minutes.unwrap_or(60)).await
+}
+
+/// Year-in-review totals, oldest year first
+#[command]
+pub async fn get_yearly_stats(state: tauri::State<'_, AppState>) -> Result<Vec<PeriodStats>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    period_stats(pool, None).await
+}
+
+/// Totals for each month of `year` that has QSOs
+#[command]
+pub async fn get_monthly_stats(state: tauri::State<'_, AppState>, year: i32) -> Result<Vec<PeriodStats>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    period_stats(pool, Some(year)).await
+}
+
+/// Worked entities, fewest QSOs first, flagging the ones never confirmed
+#[command]
+pub async fn get_entity_rarity(state: tauri::State<'_, AppState>) -> Result<Vec<EntityRarity>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    entity_rarity(pool).await
+}
+
+/// Heatmap of QSOs per UTC hour and band, to see when each band has worked for me
+#[command]
+pub async fn get_activity_by_hour(state: tauri::State<'_, AppState>) -> Result<Vec<BandHourActivity>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    activity_by_hour(pool).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 10, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_rates_10_and_60_minutes() {
+        let now = at(12, 0);
+        // 3 QSOs in the last 10 minutes, 5 more earlier in the hour, 2 older
+        let ts: Vec<_> = [
+            at(11, 52), at(11, 55), at(11, 59),
+            at(11, 15), at(11, 20), at(11, 30), at(11, 40), at(11, 45),
+            at(10, 30), at(9, 0),
+        ]
+        .to_vec();
+
+        let stats = compute_rate_stats(&ts, now, 60);
+        assert_eq!(stats.last_10_minutes, 3);
+        assert_eq!(stats.last_60_minutes, 8);
+        assert_eq!(stats.rate_10, 18.0);
+        assert_eq!(stats.rate_60, 8.0);
+        assert_eq!(stats.qsos_in_window, 8);
+        // Earliest QSO in window was 45 minutes ago
+        assert!((stats.projected_hourly_rate - 8.0 * 60.0 / 45.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_sparse_and_empty_periods() {
+        let now = at(12, 0);
+        assert_eq!(compute_rate_stats(&[], now, 60).projected_hourly_rate, 0.0);
+
+        // A single QSO a minute ago projects over the 10-minute floor
+        let stats = compute_rate_stats(&[at(11, 59)], now, 60);
+        assert_eq!(stats.projected_hourly_rate, 6.0);
+
+        // Future timestamps (clock skew) are ignored
+        assert_eq!(compute_rate_stats(&[at(12, 5)], now, 60).last_60_minutes, 0);
+    }
+
+    #[test]
+    fn test_qso_timestamp_parsing() {
+        assert_eq!(qso_timestamp("20260310", "1159", ""), Some(at(11, 59)));
+        assert_eq!(qso_timestamp("20260310", "115900", ""), Some(at(11, 59)));
+        assert_eq!(qso_timestamp("bad", "", "2026-03-10T11:59:00Z"), Some(at(11, 59)));
+        assert_eq!(qso_timestamp("bad", "", "2026-03-10 11:59:00"), Some(at(11, 59)));
+        assert_eq!(qso_timestamp("bad", "", ""), None);
+    }
+
+    /// Test: Rates across the UTC day boundary from the database
+    #[tokio::test]
+    async fn test_rate_stats_across_midnight() {
+        let pool = test_pool().await;
+
+        for (date, time) in [
+            ("20260309", "2330"),
+            ("20260309", "2355"),
+            ("20260310", "0002"),
+            ("20260310", "0005"),
+            ("20260309", "2200"), // outside the hour
+        ] {
+            insert_qso("W1AW").date(date).time(time).insert(&pool).await;
+        }
+
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 0, 10, 0).unwrap();
+        let stats = rate_stats(&pool, now, 60).await.unwrap();
+        assert_eq!(stats.last_10_minutes, 2);
+        assert_eq!(stats.last_60_minutes, 4);
+    }
+
+    /// Test: Yearly and monthly totals across two years
+    #[tokio::test]
+    async fn test_period_stats_two_years() {
+        let pool = test_pool().await;
+
+        for (id, call, date, dxcc, state) in [
+            (1, "W1AW", "20251230", 291, Some("CT")),
+            (2, "JA1ABC", "20251231", 339, None),
+            (3, "W6XYZ", "20260102", 291, Some("CA")),
+            (4, "K1ABC", "20260115", 291, Some("ct")),
+            (5, "JA2XYZ", "20260301", 339, None),
+            (6, "G0ABC", "20260302", 223, None),
+        ] {
+            insert_qso(call).id(id).date(date).dxcc(dxcc).set("state", state).insert(&pool).await;
+        }
+        // Only LoTW counts by default; the card and eQSL rows are ignored
+        for (qso_id, source) in [(2, "LOTW"), (3, "LOTW"), (3, "CARD"), (5, "LOTW"), (6, "EQSL")] {
+            sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (?, ?, 'Y')")
+                .bind(qso_id)
+                .bind(source)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let yearly = period_stats(&pool, None).await.unwrap();
+        assert_eq!(
+            yearly,
+            [
+                PeriodStats { year: 2025, month: None, qsos: 2, dxcc: 2, states: 1, confirmed_qsos: 1, confirmed_dxcc: 1 },
+                PeriodStats { year: 2026, month: None, qsos: 4, dxcc: 3, states: 2, confirmed_qsos: 2, confirmed_dxcc: 2 },
+            ]
+        );
+
+        let monthly = period_stats(&pool, Some(2026)).await.unwrap();
+        assert_eq!(monthly.iter().map(|m| (m.month, m.qsos)).collect::<Vec<_>>(), [(Some(1), 2), (Some(3), 2)]);
+        assert_eq!((monthly[0].states, monthly[0].confirmed_qsos), (2, 1));
+        assert!(period_stats(&pool, Some(2024)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_entity_rarity_fewest_qsos_first() {
+        let pool = test_pool().await;
+
+        for (id, call, band, dxcc) in [
+            (1, "W1AW", "20m", 291),
+            (2, "K1ABC", "40m", 291),
+            (3, "W6XYZ", "20m", 291),
+            (4, "JA1ABC", "20m", 339),
+            (5, "JA2XYZ", "20m", 339),
+            (6, "VP8ABC", "20m", 240),
+        ] {
+            insert_qso(call).id(id).band(band).dxcc(dxcc).insert(&pool).await;
+        }
+        sqlx::query("INSERT INTO confirmations (qso_id, source, qsl_rcvd) VALUES (4, 'LOTW', 'Y')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let rarity = entity_rarity(&pool).await.unwrap();
+        let order: Vec<(i32, i64)> = rarity.iter().map(|e| (e.dxcc, e.qsos)).collect();
+        assert_eq!(order, [(240, 1), (339, 2), (291, 3)]);
+        assert_eq!(rarity[0].entity, "South Sandwich Is.");
+        assert!(rarity[0].unconfirmed);
+        assert!(!rarity[1].unconfirmed);
+        assert_eq!(rarity[2].bands, 2);
+    }
+
+    #[tokio::test]
+    async fn test_activity_by_hour() {
+        let pool = test_pool().await;
+
+        for (id, band, time_on) in [
+            (1, "20m", "000500"),
+            (2, "20M", "001000"),
+            (3, "20m", "231500"),
+            (4, "40m", "030000"),
+            (5, "40m", "0345"),
+            (6, "20m", "1200"),
+            (7, "160m", "2359"),
+            (8, "40m", "9900"), // not a valid hour
+        ] {
+            insert_qso(&format!("W{}AW", id)).id(id).time(time_on).band(band).insert(&pool).await;
+        }
+
+        let activity = activity_by_hour(&pool).await.unwrap();
+        let bands: Vec<(&str, i64)> = activity.iter().map(|a| (a.band.as_str(), a.total)).collect();
+        assert_eq!(bands, [("160m", 1), ("40m", 2), ("20m", 4)]);
+
+        let twenty = &activity[2];
+        assert_eq!((twenty.hours[0], twenty.hours[12], twenty.hours[23]), (2, 1, 1));
+        assert_eq!(twenty.hours.iter().sum::<i64>(), 4);
+        assert_eq!(activity[1].hours[3], 2);
+        assert_eq!(activity[0].hours[23], 1);
+    }
+}
@@ -78,7 +78,6 @@ pub fn extract_hhmm(time_str: &str) -> String {
 }
 
 /// Convert time string to minutes since midnight for time difference calculations
-#[allow(dead_code)]
 pub fn time_to_minutes(time_str: &str) -> Option<u32> {
     let clean = time_str.trim();
     if clean.len() < 4 {
@@ -120,6 +119,36 @@ pub fn time_difference_minutes(time1: &str, time2: &str) -> Option<u32> {
     Some(if diff > 720 { 1440 - diff } else { diff })
 }
 
+/// QSO date (YYYYMMDD) and time (HHMMSS) for a WSJT-X logged QSO, using `now`
+/// as the UTC clock for anything WSJT-X left out.
+///
+/// A full "YYYY-MM-DD HH:MM:SS" datetime is taken as sent. A bare time gets the
+/// UTC day that puts it nearest `now`: 23:59 arriving at 00:01 keeps yesterday,
+/// 00:01 arriving while our clock still reads 23:59 gets tomorrow.
+pub fn resolve_qso_datetime(datetime_on: &str, now: chrono::DateTime<chrono::Utc>) -> (String, String) {
+    use chrono::Timelike;
+
+    let clean = datetime_on.trim();
+    if clean.is_empty() {
+        return (now.format("%Y%m%d").to_string(), now.format("%H%M%S").to_string());
+    }
+    if let Some((date_part, time_part)) = clean.split_once(' ').filter(|(d, _)| d.contains('-')) {
+        return (date_part.replace('-', ""), normalize_time_to_hhmmss(time_part));
+    }
+
+    let time_on = normalize_time_to_hhmmss(clean);
+    let mut date = now.date_naive();
+    if let Some(seconds) = time_to_seconds(&time_on) {
+        let offset = seconds as i64 - now.num_seconds_from_midnight() as i64;
+        if offset > 12 * 3600 {
+            date = date.pred_opt().unwrap_or(date);
+        } else if offset < -12 * 3600 {
+            date = date.succ_opt().unwrap_or(date);
+        }
+    }
+    (date.format("%Y%m%d").to_string(), time_on)
+}
+
 /// Normalize date string to 8-character YYYYMMDD format (ADIF standard)
 #[allow(dead_code)]
 pub fn normalize_date_to_yyyymmdd(date_str: &str) -> String {
@@ -241,4 +270,24 @@ mod tests {
         assert!(is_valid_adif_time("123456"));
         assert!(!is_valid_adif_time("2400"));
     }
+
+    #[test]
+    fn test_resolve_qso_datetime_midnight_rollover() {
+        use chrono::TimeZone;
+        let at = |h, m| chrono::Utc.with_ymd_and_hms(2026, 3, 2, h, m, 0).unwrap();
+
+        // Full datetime from WSJT-X wins over our clock
+        assert_eq!(
+            resolve_qso_datetime("2026-03-01 23:59:30", at(0, 1)),
+            ("20260301".to_string(), "235930".to_string())
+        );
+        // Bare 23:59 arriving after midnight belongs to the day before
+        assert_eq!(resolve_qso_datetime("235930", at(0, 1)), ("20260301".to_string(), "235930".to_string()));
+        // Bare 00:01 while our clock still reads 23:59 belongs to the next day
+        let late = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 23, 59, 0).unwrap();
+        assert_eq!(resolve_qso_datetime("0001", late), ("20260302".to_string(), "000100".to_string()));
+        // Same-day times and an empty datetime use today
+        assert_eq!(resolve_qso_datetime("1200", at(12, 5)), ("20260302".to_string(), "120000".to_string()));
+        assert_eq!(resolve_qso_datetime("", at(0, 1)), ("20260302".to_string(), "000100".to_string()));
+    }
 }
@@ -1,17 +1,46 @@
 //! UDP Listener Commands
 //!
-//! Commands for managing the WSJT-X UDP listener.
+//! Commands for managing the WSJT-X UDP listener, and for QSOs held for
+//! review when WSJT-X logged an exchange the tracker never saw complete.
+//! `log_from_decode` logs a QSO straight from a `wsjtx-decode` payload.
+//! On exit, `flush_on_shutdown` logs exchanges that were complete but never
+//! reported as logged by WSJT-X.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::{command, Emitter};
 use tokio::sync::mpsc;
 
 use super::state::AppState;
-use super::time_utils::{format_time_from_ms, get_current_utc_time, normalize_time_to_hhmmss, is_valid_adif_date, is_valid_adif_time, time_to_seconds};
-use super::qso::freq_to_band;
-use super::band_activity::save_band_activity;
+use super::time_utils::{format_time_from_ms, get_current_utc_time, is_valid_adif_date, is_valid_adif_time, resolve_qso_datetime, time_to_seconds};
+use super::qso::{read_qso_defaults, GRID_SOURCE_FCC, GRID_SOURCE_PRIOR_QSO, GRID_SOURCE_STATION};
+use super::settings::{read_bool_setting, read_setting, SANITIZE_RST_SETTING};
+use super::stats::rate_stats;
+use super::webhook::{notify, WebhookEvent};
+use super::worked_cache::{recent_slot_key, WorkedCache};
+use super::band_activity::{
+    delete_band_activity_older_than, is_needed_decode, save_band_activity, save_rx_decodes,
+    should_store_rx_decode, BandActivityFilterMode, DecodeCycleBatch, DecodeFilterSettings, RxDecodeRow, DECODE_CYCLE_GAP,
+    DEFAULT_PRUNE_MINUTES,
+};
+use crate::adif::bands::band_label_for_hz;
+use crate::adif::propagation::{default_prop_mode_rules, infer_prop_mode, parse_prop_mode_rules, PropModeRule};
 use crate::udp::{UdpMessage, start_listener, QsoLoggedMessage};
-use crate::udp::wsjtx::{is_valid_grid, normalize_rst};
+use crate::udp::listener::{ReplyOutcome, DEFAULT_UDP_PORT};
+use crate::udp::wsjtx::{is_cq_modifier, is_valid_grid, normalize_rst, sanitize_rst, strip_hash_brackets};
+use crate::reference::dxpeditions::is_dxpedition;
+use crate::qso_tracker::{needs_review, should_autolog, QsoEvent as TrackerEvent, QsoInProgress, QsoTrackers};
+
+/// Settings key: refuse to auto-log QSOs built mostly from low-confidence decodes
+pub const REJECT_LOW_CONFIDENCE_SETTING: &str = "reject_low_confidence_autolog";
+
+/// Settings key: the callsign of the active station profile
+pub const MY_CALLSIGN_SETTING: &str = "my_callsign";
+
+/// Settings key: hold WSJT-X logged QSOs for review when the tracked exchange is incomplete
+pub const REQUIRE_COMPLETE_EXCHANGE_SETTING: &str = "require_complete_exchange";
+
+/// Settings key: JSON array of PropModeRule replacing the built-in PROP_MODE inference table
+pub const PROP_MODE_RULES_SETTING: &str = "prop_mode_rules";
 
 #[derive(Debug, Clone, Serialize)]
 pub struct UdpStatus {
@@ -19,6 +48,41 @@ pub struct UdpStatus {
     pub port: u16,
     pub connected: bool,
     pub wsjtx_version: Option<String>,
+    pub instances: Vec<UdpInstanceStatus>,
+}
+
+/// One WSJT-X instance, identified by the port it reports to
+#[derive(Debug, Clone, Serialize)]
+pub struct UdpInstanceStatus {
+    pub port: u16,
+    pub running: bool,
+    pub connected: bool,
+    pub wsjtx_id: Option<String>,
+    pub wsjtx_version: Option<String>,
+}
+
+/// A WSJT-X logged QSO waiting for the operator to confirm or discard it
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PendingReviewQso {
+    pub id: i64,
+    pub call: String,
+    pub datetime_on: Option<String>,
+    pub freq_hz: Option<i64>,
+    pub mode: Option<String>,
+    pub reason: String,
+    pub received_at: String,
+}
+
+/// The parts of a `wsjtx-decode` event needed to log a QSO from it
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecodePayload {
+    pub time_ms: u32,
+    pub snr: i32,
+    pub delta_freq: u32,
+    pub mode: String,
+    pub message: String,
+    #[serde(default)]
+    pub instance: u16,
 }
 
 /// QSO Event for Frontend
@@ -31,20 +95,46 @@ pub struct QsoEvent {
     pub rst_sent: String,
     pub rst_rcvd: String,
     pub band: String,
+    /// Port of the WSJT-X instance that logged it
+    pub instance: u16,
 }
 
 impl QsoEvent {
-    pub fn from_wsjtx(qso: &QsoLoggedMessage) -> Self {
+    pub fn from_wsjtx(qso: &QsoLoggedMessage, instance: u16) -> Self {
         let freq_mhz = qso.freq_hz as f64 / 1_000_000.0;
         Self {
+            instance,
             call: qso.call.clone(),
             grid: qso.grid.clone(),
             freq_mhz,
             mode: qso.mode.clone(),
             rst_sent: qso.report_sent.clone(),
             rst_rcvd: qso.report_rcvd.clone(),
-            band: freq_to_band(freq_mhz),
+            band: band_label_for_hz(qso.freq_hz),
+        }
+    }
+}
+
+/// The operator's callsign: the station callsign saved with the QSO defaults
+/// (what manual entry stamps on QSOs), else `my_callsign`. None when neither is set.
+pub async fn read_my_callsign(pool: &sqlx::Pool<sqlx::Sqlite>) -> Option<String> {
+    let from_defaults = read_qso_defaults(pool).await.station_callsign;
+    let call = match from_defaults.filter(|call| !call.trim().is_empty()) {
+        Some(call) => Some(call),
+        None => read_setting(pool, MY_CALLSIGN_SETTING).await,
+    };
+    call.map(|call| call.trim().to_uppercase()).filter(|call| !call.is_empty())
+}
+
+/// True when WSJT-X is running under a different callsign than the profile.
+/// Nothing to compare (either side blank) is not a mismatch.
+pub fn is_callsign_mismatch(wsjtx_call: &str, profile_call: Option<&str>) -> bool {
+    let wsjtx_call = wsjtx_call.trim();
+    match profile_call.map(str::trim) {
+        Some(profile) if !profile.is_empty() && !wsjtx_call.is_empty() => {
+            !profile.eq_ignore_ascii_case(wsjtx_call)
         }
+        _ => false,
     }
 }
 
@@ -56,26 +146,97 @@ fn parse_tx_message_calls(message: &str) -> (Option<String>, Option<String>) {
     }
     
     if parts[0] == "CQ" {
-        if parts.len() >= 2 {
-            return (Some(parts[1].to_string()), None);
-        }
-        return (None, None);
+        // Skip "CQ DX" / "CQ POTA" style modifiers to reach our call
+        let call = parts[1..].iter().find(|p| !is_cq_modifier(p));
+        return (call.map(|c| strip_hash_brackets(c).to_string()), None);
     }
     
     if parts.len() >= 2 {
-        let dx_call = parts[0].to_string();
-        let de_call = parts[1].to_string();
+        let dx_call = strip_hash_brackets(parts[0]).to_string();
+        let de_call = strip_hash_brackets(parts[1]).to_string();
         return (Some(de_call), Some(dx_call));
     }
     
     (None, None)
 }
 
+/// Build a logged QSO from a decode heard on `dial_freq_hz`.
+/// The decode must be an exchange between two callsigns (not a CQ or free text);
+/// the other station is the one sending it. RST sent defaults to the decode's SNR.
+pub fn qso_from_decode(
+    decode: &DecodePayload,
+    dial_freq_hz: u64,
+    rst_sent: Option<String>,
+    rst_rcvd: Option<String>,
+) -> Result<QsoLoggedMessage, String> {
+    use crate::udp::wsjtx::{parse_ft8_message, MessageType};
+
+    let (call, dx_call, grid, msg_type) = parse_ft8_message(&decode.message)
+        .ok_or_else(|| format!("Not a callsign exchange: {}", decode.message))?;
+    if msg_type == MessageType::Cq || dx_call.is_none() {
+        return Err(format!("Not a callsign exchange: {}", decode.message));
+    }
+    let mode = crate::udp::wsjtx::decode_mode_to_adif(&decode.mode)
+        .ok_or_else(|| format!("Unknown decode mode: {:?}", decode.mode))?;
+
+    if dial_freq_hz == 0 {
+        return Err("Zero frequency".to_string());
+    }
+    let freq_hz = dial_freq_hz + decode.delta_freq as u64;
+    if crate::adif::bands::freq_to_band(freq_hz as f64 / 1_000_000.0).is_none() {
+        return Err(format!("Frequency {} Hz is outside the amateur bands", freq_hz));
+    }
+
+    let non_blank = |rst: Option<String>| rst.filter(|r| !r.trim().is_empty());
+    Ok(QsoLoggedMessage {
+        id: "DECODE".to_string(),
+        datetime_off: String::new(),
+        call,
+        grid: grid.unwrap_or_default(),
+        freq_hz,
+        mode: mode.to_string(),
+        report_sent: non_blank(rst_sent).unwrap_or_else(|| format!("{:+03}", decode.snr)),
+        report_rcvd: non_blank(rst_rcvd).unwrap_or_default(),
+        tx_power: String::new(),
+        comments: String::new(),
+        name: String::new(),
+        // Time of day only; the date is resolved against the clock on insert
+        datetime_on: format_time_from_ms(decode.time_ms),
+        operator_call: String::new(),
+        my_call: String::new(),
+        my_grid: String::new(),
+        exchange_sent: String::new(),
+        exchange_rcvd: String::new(),
+        adif_propagation_mode: String::new(),
+    })
+}
+
+/// What one WSJT-X instance is doing, tracked separately so two radios
+/// don't mix their exchanges
+#[derive(Default)]
+struct InstanceSession {
+    last_tx_msg: String,
+    /// Our callsign as reported by this instance's Status (used by the decode filter)
+    my_call: Option<String>,
+    /// Band this instance is tuned to (from Status), for the new-slot highlight
+    current_band: Option<&'static str>,
+    /// Dial frequency in Hz from the last Status, stored with RX decodes
+    dial_freq: Option<u64>,
+    /// Decode filter settings for the current cycle
+    decode_filter: Option<DecodeFilterSettings>,
+    /// This instance's RX decodes of the current cycle; instances run
+    /// different modes, so their cycles don't line up
+    decode_batch: DecodeCycleBatch,
+}
+
+/// Listen on `port`, plus `additional_ports` for further WSJT-X instances.
+/// Every event carries the `instance` (port) it came from.
 #[command]
 pub async fn start_udp_listener(
     app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     port: u16,
+    additional_ports: Option<Vec<u16>>,
 ) -> Result<(), String> {
     let udp_state = state.udp_state.clone();
     
@@ -83,47 +244,110 @@ pub async fn start_udp_listener(
         return Err("UDP listener already running".to_string());
     }
     
-    udp_state.set_port(port);
+    let mut ports = vec![port];
+    for extra in additional_ports.unwrap_or_default() {
+        if !ports.contains(&extra) {
+            ports.push(extra);
+        }
+    }
     
-    let (tx, mut rx) = mpsc::unbounded_channel::<UdpMessage>();
+    let (tx, mut rx) = mpsc::unbounded_channel::<(u16, UdpMessage)>();
     
-    start_listener(udp_state.clone(), tx)?;
-    log::info!("Started UDP listener on port {}", port);
+    for instance in udp_state.reset(&ports) {
+        start_listener(instance, tx.clone())?;
+    }
+    drop(tx);
+    log::info!("Started UDP listener on port(s) {:?}", ports);
     
     let app_handle = app.clone();
     let db_arc = state.db.clone();
+    let worked = state.worked.clone();
+    // Tracks exchanges with us so logged QSOs can be checked against what we decoded
+    let trackers = state.trackers.clone();
+    trackers.clear();
     
-    tauri::async_runtime::spawn(async move {
-        let mut last_tx_msg = String::new();
-        let mut recent_qso_keys: std::collections::VecDeque<String> = std::collections::VecDeque::new();
-        const MAX_RECENT_QSOS: usize = 10;
+    let task = tauri::async_runtime::spawn(async move {
+        let mut sessions: std::collections::HashMap<u16, InstanceSession> = std::collections::HashMap::new();
+        // The profile mismatch warning is only raised once per listener session
+        let mut mismatch_warned = false;
         
-        while let Some(msg) = rx.recv().await {
+        loop {
+            // RX decodes are written once per cycle rather than one by one
+            let next = if sessions.values().all(|s| s.decode_batch.is_empty()) {
+                rx.recv().await
+            } else {
+                match tokio::time::timeout(DECODE_CYCLE_GAP, rx.recv()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        // The bursts are over
+                        write_decode_cycles(&db_arc, &mut sessions).await;
+                        continue;
+                    }
+                }
+            };
+            let Some((instance, msg)) = next else { break };
+            let session = sessions.entry(instance).or_default();
             match msg {
                 UdpMessage::Decode(decode) => {
                     if let Some((de_call, dx_call, grid, msg_type)) = crate::udp::parse_ft8_message(&decode.message) {
                         let lookup = crate::reference::lookup_call_full(&de_call);
+                        trackers.with(instance, |t| {
+                            t.process_rx_decode(&decode.message, decode.snr, decode.delta_freq, decode.low_confidence)
+                        });
                         
+                        let mut new_slot = false;
+                        let mut grid_hint = None;
                         let db_guard = db_arc.lock().await;
                         if let Some(pool) = db_guard.as_ref() {
-                            let time_utc = format_time_from_ms(decode.time_ms);
-                            let _ = save_band_activity(
-                                pool,
-                                &time_utc,
-                                Some(decode.time_ms as i64),
-                                "rx",
-                                &decode.message,
-                                Some(decode.snr),
-                                Some(decode.delta_freq as i32),
-                                Some(&de_call),
+                            if let Some(partial) = grid.as_deref().filter(|g| g.len() < 4) {
+                                grid_hint = resolve_partial_grid(pool, &de_call, partial).await;
+                            }
+                            if let (Some(dxcc), Some(band)) = (lookup.dxcc_as_i32(), session.current_band) {
+                                new_slot = worked.is_new_slot(pool, dxcc, band).await.unwrap_or(false);
+                            }
+                            let filter = match &session.decode_filter {
+                                Some(filter) if filter.time_ms == decode.time_ms => filter.clone(),
+                                _ => {
+                                    let filter = DecodeFilterSettings::read(pool, decode.time_ms).await;
+                                    session.decode_filter = Some(filter.clone());
+                                    filter
+                                }
+                            };
+                            let is_needed = filter.mode == BandActivityFilterMode::Relevant
+                                && is_needed_decode(pool, &worked, &filter.counted, lookup.dxcc_as_i32(), grid.as_deref()).await;
+                            let store = should_store_rx_decode(
+                                filter.mode,
+                                msg_type,
+                                &de_call,
                                 dx_call.as_deref(),
-                                None,
-                                Some(&decode.mode),
-                            ).await;
+                                session.my_call.as_deref(),
+                                is_needed,
+                            );
+
+                            if store {
+                                let row = RxDecodeRow {
+                                    time_ms: decode.time_ms,
+                                    message: decode.message.clone(),
+                                    snr: decode.snr,
+                                    delta_freq: decode.delta_freq as i32,
+                                    de_call: de_call.clone(),
+                                    dx_call: dx_call.clone(),
+                                    mode: decode.mode.clone(),
+                                    low_confidence: decode.low_confidence,
+                                    dxcc: lookup.dxcc_as_i32(),
+                                    dial_freq: session.dial_freq.map(|hz| hz as f64),
+                                };
+                                if let Some(cycle) = session.decode_batch.push(row) {
+                                    if let Err(e) = save_rx_decodes(pool, &cycle).await {
+                                        log::warn!("{}", e);
+                                    }
+                                }
+                            }
                         }
                         drop(db_guard);
                         
                         let _ = app_handle.emit("wsjtx-decode", serde_json::json!({
+                            "instance": instance,
                             "time_ms": decode.time_ms,
                             "snr": decode.snr,
                             "delta_time": decode.delta_time,
@@ -134,6 +358,7 @@ pub async fn start_udp_listener(
                             "dx_call": dx_call,
                             "call": de_call,
                             "grid": grid,
+                            "grid_hint": grid_hint,
                             "msg_type": format!("{:?}", msg_type),
                             "dxcc": lookup.dxcc_as_i32(),
                             "country": lookup.country,
@@ -141,50 +366,105 @@ pub async fn start_udp_listener(
                             "cqz": lookup.cqz,
                             "ituz": lookup.ituz,
                             "low_confidence": decode.low_confidence,
+                            "new_slot": new_slot,
+                            "dxpedition": is_dxpedition(&de_call, &chrono::Utc::now().format("%Y%m%d").to_string()),
                         }));
                     }
                 }
-                UdpMessage::QsoLogged(qso) => {
-                    let source_type = if qso.id == "TYPE5" { "QsoLogged(5)" } else if qso.id == "TYPE12" { "LoggedADIF(12)" } else { "Unknown" };
-                    
-                    let freq_mhz = qso.freq_hz / 1_000_000;
-                    let qso_key = format!("{}|{}", qso.call.to_uppercase(), freq_mhz);
-                    
-                    log::warn!("[QSO-HANDLER] Source={} call={} key={}", source_type, qso.call, qso_key);
-                    
-                    if recent_qso_keys.contains(&qso_key) {
-                        log::warn!("[QSO-HANDLER] DUPLICATE BLOCKED: {}", qso.call);
-                        continue;
-                    }
-                    
-                    recent_qso_keys.push_back(qso_key.clone());
-                    if recent_qso_keys.len() > MAX_RECENT_QSOS {
-                        recent_qso_keys.pop_front();
+                UdpMessage::QsoLogged(mut qso) => {
+                    // Record the callsign that actually made the QSO even if the
+                    // logged message left it blank
+                    if qso.my_call.is_empty() {
+                        if let Some(call) = &session.my_call {
+                            qso.my_call = call.clone();
+                        }
                     }
-                    
-                    log::info!("Received QSO from WSJT-X: {}", qso.call);
-                    
                     let db_guard = db_arc.lock().await;
-                    if let Some(pool) = db_guard.as_ref() {
-                        if let Err(e) = insert_qso_from_wsjtx(pool, &qso).await {
-                            log::error!("Failed to insert QSO: {}", e);
-                        } else {
+                    let outcome = match db_guard.as_ref() {
+                        Some(pool) => handle_logged_qso(pool, &worked, &trackers, instance, &qso).await,
+                        None => LoggedOutcome::Inserted(Err("Database not initialized".to_string())),
+                    };
+                    let rates = match (&outcome, db_guard.as_ref()) {
+                        (LoggedOutcome::Inserted(Ok(_)), Some(pool)) => rate_stats(pool, chrono::Utc::now(), 60).await.ok(),
+                        _ => None,
+                    };
+                    drop(db_guard);
+
+                    match outcome {
+                        LoggedOutcome::Repeat => continue,
+                        LoggedOutcome::Refused => {
+                            log::warn!("Refusing to auto-log {}: exchange built mostly from low-confidence decodes", qso.call);
+                            let _ = app_handle.emit("qso-rejected", serde_json::json!({
+                                "instance": instance,
+                                "call": qso.call,
+                                "reason": "low_confidence",
+                            }));
+                            continue;
+                        }
+                        LoggedOutcome::Held(held) => {
+                            match held {
+                                Ok(id) => {
+                                    log::warn!("Holding {} for review: exchange never completed", qso.call);
+                                    let _ = app_handle.emit("qso-pending-review", serde_json::json!({
+                                        "instance": instance,
+                                        "id": id,
+                                        "call": qso.call,
+                                        "reason": "incomplete_exchange",
+                                    }));
+                                }
+                                Err(e) => log::error!("Failed to hold QSO for review: {}", e),
+                            }
+                            continue;
+                        }
+                        LoggedOutcome::Inserted(Err(e)) => log::error!("Failed to insert QSO: {}", e),
+                        LoggedOutcome::Inserted(Ok(_)) => {
                             log::info!("QSO inserted successfully: {}", qso.call);
+                            if let Some(rates) = rates {
+                                let _ = app_handle.emit("rate-update", rates);
+                            }
                         }
                     }
-                    drop(db_guard);
                     
-                    let _ = app_handle.emit("qso-logged", QsoEvent::from_wsjtx(&qso));
+                    let _ = app_handle.emit("qso-logged", QsoEvent::from_wsjtx(&qso, instance));
                 }
                 UdpMessage::Heartbeat { id, version, .. } => {
                     let _ = app_handle.emit("wsjtx-heartbeat", serde_json::json!({
+                        "instance": instance,
                         "id": id,
                         "version": version,
                     }));
                 }
                 UdpMessage::Status { id, dial_freq, mode, dx_call, de_call, report, tx_enabled, transmitting, tx_message, .. } => {
-                    if transmitting && !tx_message.is_empty() && tx_message != last_tx_msg {
-                        last_tx_msg = tx_message.clone();
+                    if !de_call.is_empty() && session.my_call.as_deref() != Some(de_call.as_str()) {
+                        session.my_call = Some(de_call.clone());
+                        if !mismatch_warned {
+                            let db_guard = db_arc.lock().await;
+                            let profile_call = match db_guard.as_ref() {
+                                Some(pool) => read_my_callsign(pool).await,
+                                None => None,
+                            };
+                            drop(db_guard);
+                            if is_callsign_mismatch(&de_call, profile_call.as_deref()) {
+                                mismatch_warned = true;
+                                log::warn!("WSJT-X is using {} but the profile callsign is {:?}", de_call, profile_call);
+                                let _ = app_handle.emit("callsign-mismatch", serde_json::json!({
+                                    "instance": instance,
+                                    "wsjtx_call": de_call,
+                                    "profile_call": profile_call,
+                                }));
+                            }
+                        }
+                    }
+                    session.current_band = crate::adif::bands::freq_to_band(dial_freq as f64 / 1_000_000.0);
+                    session.dial_freq = Some(dial_freq);
+                    trackers.with(instance, |t| {
+                        t.update_status(&de_call, "", dial_freq, &mode, &dx_call);
+                        t.cleanup_stale();
+                    });
+                    
+                    if transmitting && !tx_message.is_empty() && tx_message != session.last_tx_msg {
+                        session.last_tx_msg = tx_message.clone();
+                        trackers.with(instance, |t| t.process_tx(&tx_message, None));
                         
                         let db_guard = db_arc.lock().await;
                         if let Some(pool) = db_guard.as_ref() {
@@ -203,16 +483,27 @@ pub async fn start_udp_listener(
                                 parsed_dx_call.as_deref(),
                                 Some(dial_freq as f64),
                                 Some(&mode),
+                                None,
+                                None,
                             ).await;
                         }
                         drop(db_guard);
                     }
                     
                     if !transmitting {
-                        last_tx_msg.clear();
+                        session.last_tx_msg.clear();
+                    }
+                    
+                    if let TrackerEvent::Finished(finished) = trackers.with(instance, |t| t.update_transmitting(transmitting)) {
+                        let _ = app_handle.emit("qso-finished", serde_json::json!({
+                            "instance": instance,
+                            "call": finished.their_call,
+                            "time_off": finished.time_off.map(|t| t.format("%H%M%S").to_string()),
+                        }));
                     }
                     
                     let _ = app_handle.emit("wsjtx-status", serde_json::json!({
+                        "instance": instance,
                         "id": id,
                         "dial_freq": dial_freq,
                         "mode": mode,
@@ -224,18 +515,27 @@ pub async fn start_udp_listener(
                         "tx_message": tx_message,
                     }));
                 }
+                UdpMessage::Reply(outcome) => {
+                    let (event, call) = match outcome {
+                        ReplyOutcome::Accepted(call) => ("reply-accepted", call),
+                        ReplyOutcome::Ignored(call) => ("reply-ignored", call),
+                    };
+                    log::info!("Reply to {}: {}", call, event);
+                    let _ = app_handle.emit(event, serde_json::json!({ "instance": instance, "call": call }));
+                }
                 UdpMessage::Connected => {
-                    let _ = app_handle.emit("udp-connected", ());
+                    let _ = app_handle.emit("udp-connected", serde_json::json!({ "instance": instance }));
                 }
                 UdpMessage::Clear { id, window } => {
                     log::debug!("Clear window {} from {}", window, id);
                     let _ = app_handle.emit("wsjtx-clear", serde_json::json!({
+                        "instance": instance,
                         "id": id,
                         "window": window,
                     }));
                 }
                 UdpMessage::Disconnected => {
-                    let _ = app_handle.emit("udp-disconnected", ());
+                    let _ = app_handle.emit("udp-disconnected", serde_json::json!({ "instance": instance }));
                 }
                 UdpMessage::Error(e) => {
                     log::error!("UDP error: {}", e);
@@ -243,19 +543,36 @@ pub async fn start_udp_listener(
                 }
             }
         }
+        write_decode_cycles(&db_arc, &mut sessions).await;
     });
+    *state.udp_task.lock().unwrap() = Some(task);
     
     Ok(())
 }
 
+/// Write every instance's pending RX decodes, logging rather than failing
+async fn write_decode_cycles(
+    db: &tokio::sync::Mutex<Option<sqlx::Pool<sqlx::Sqlite>>>,
+    sessions: &mut std::collections::HashMap<u16, InstanceSession>,
+) {
+    let db_guard = db.lock().await;
+    let Some(pool) = db_guard.as_ref() else { return };
+    for session in sessions.values_mut().filter(|s| !s.decode_batch.is_empty()) {
+        if let Err(e) = save_rx_decodes(pool, &session.decode_batch.take()).await {
+            log::warn!("{}", e);
+        }
+    }
+}
+
 #[command]
 pub async fn stop_udp_listener(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    state.udp_state.set_running(false);
+    state.udp_state.stop_all();
     log::info!("Stopping UDP listener");
     Ok(())
 }
 
 #[command]
+#[allow(clippy::too_many_arguments)]
 pub async fn call_station(
     state: tauri::State<'_, AppState>,
     time_ms: u32,
@@ -265,12 +582,18 @@ pub async fn call_station(
     mode: String,
     message: String,
     low_confidence: bool,
+    instance: Option<u16>,
 ) -> Result<(), String> {
     use crate::udp::listener::send_reply;
     use crate::udp::wsjtx::ReplyMessage;
     
-    let id = state.udp_state.get_wsjtx_id()
-        .ok_or("WSJT-X not connected - no heartbeat received yet")?;
+    // The decode's instance, so the Reply reaches the WSJT-X that heard it
+    let target = state.udp_state.target(instance)?;
+    let id = match target.get_wsjtx_id() {
+        Some(id) => id,
+        None if target.is_closed() => return Err("WSJT-X closed".to_string()),
+        None => return Err("WSJT-X not connected - no heartbeat received yet".to_string()),
+    };
     
     let reply = ReplyMessage {
         id,
@@ -286,21 +609,407 @@ pub async fn call_station(
     
     log::info!("Calling station from message: {}", message);
     
-    send_reply(&state.udp_state, reply)
+    send_reply(&target, reply)?;
+
+    // reply-accepted / reply-ignored follows once WSJT-X's Status shows what it did
+    if let Some((call, ..)) = crate::udp::wsjtx::parse_ft8_message(&message) {
+        target.expect_reply_at(&call, std::time::Instant::now());
+    }
+    Ok(())
+}
+
+/// Log a QSO in one call from a `wsjtx-decode` payload. Separate from
+/// call_station: nothing is sent to WSJT-X. Returns None if already logged.
+#[command]
+pub async fn log_from_decode(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    decode: DecodePayload,
+    dial_freq: u64,
+    rst_sent: Option<String>,
+    rst_rcvd: Option<String>,
+) -> Result<Option<i64>, String> {
+    let mut qso = qso_from_decode(&decode, dial_freq, rst_sent, rst_rcvd)?;
+
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    qso.my_call = read_my_callsign(pool).await.unwrap_or_default();
+    let id = insert_qso_from_wsjtx(pool, &state.worked, &qso).await?;
+    if id.is_some() {
+        let _ = app.emit("qso-logged", QsoEvent::from_wsjtx(&qso, decode.instance));
+    }
+    Ok(id)
 }
 
 #[command]
 pub async fn get_udp_status(state: tauri::State<'_, AppState>) -> Result<UdpStatus, String> {
+    let instances: Vec<UdpInstanceStatus> = state
+        .udp_state
+        .all()
+        .iter()
+        .map(|instance| UdpInstanceStatus {
+            port: instance.get_port(),
+            running: instance.is_running(),
+            // Connected means WSJT-X heartbeats are arriving, not just that we're listening
+            connected: instance.is_running() && instance.is_connected(),
+            wsjtx_id: instance.get_wsjtx_id(),
+            wsjtx_version: instance.get_wsjtx_version(),
+        })
+        .collect();
+    let primary = instances.first();
     Ok(UdpStatus {
-        running: state.udp_state.is_running(),
-        port: state.udp_state.get_port(),
-        connected: state.udp_state.is_running(),
-        wsjtx_version: None,
+        running: instances.iter().any(|i| i.running),
+        port: primary.map_or(DEFAULT_UDP_PORT, |i| i.port),
+        connected: instances.iter().any(|i| i.connected),
+        wsjtx_version: instances
+            .iter()
+            .find(|i| i.connected)
+            .or(primary)
+            .and_then(|i| i.wsjtx_version.clone()),
+        instances,
     })
 }
 
-/// Insert a QSO from WSJT-X into the database
-async fn insert_qso_from_wsjtx(pool: &sqlx::Pool<sqlx::Sqlite>, qso: &QsoLoggedMessage) -> Result<(), String> {
+#[command]
+pub async fn get_pending_review(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<PendingReviewQso>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    pending_review_qsos(pool).await
+}
+
+/// Log a held QSO; returns the new QSO id (None if it turned out to be a duplicate)
+#[command]
+pub async fn confirm_pending(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+) -> Result<Option<i64>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    confirm_pending_qso(pool, &state.worked, id).await
+}
+
+/// Drop a held QSO without logging it
+#[command]
+pub async fn discard_pending(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    discard_pending_qso(pool, id).await
+}
+
+/// Store a logged QSO for review instead of inserting it
+pub async fn hold_for_review(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    qso: &QsoLoggedMessage,
+    reason: &str,
+) -> Result<i64, String> {
+    let payload = serde_json::to_string(qso).map_err(|e| e.to_string())?;
+    let result = sqlx::query(
+        r#"INSERT INTO pending_review_qsos (call, datetime_on, freq_hz, mode, reason, payload)
+           VALUES (?, ?, ?, ?, ?, ?)"#,
+    )
+    .bind(&qso.call)
+    .bind(&qso.datetime_on)
+    .bind(qso.freq_hz as i64)
+    .bind(&qso.mode)
+    .bind(reason)
+    .bind(payload)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to hold QSO for review: {}", e))?;
+    Ok(result.last_insert_rowid())
+}
+
+/// QSOs currently held for review, oldest first
+pub async fn pending_review_qsos(pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<Vec<PendingReviewQso>, String> {
+    sqlx::query_as(
+        r#"SELECT id, call, datetime_on, freq_hz, mode, reason, received_at
+           FROM pending_review_qsos ORDER BY id"#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to load pending QSOs: {}", e))
+}
+
+/// Insert a held QSO into the log and remove it from review
+pub async fn confirm_pending_qso(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    worked: &WorkedCache,
+    id: i64,
+) -> Result<Option<i64>, String> {
+    let payload: String = sqlx::query_scalar("SELECT payload FROM pending_review_qsos WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to load pending QSO: {}", e))?
+        .ok_or_else(|| format!("Pending QSO {} not found", id))?;
+    let qso: QsoLoggedMessage =
+        serde_json::from_str(&payload).map_err(|e| format!("Corrupt pending QSO {}: {}", id, e))?;
+
+    let qso_id = insert_qso_from_wsjtx(pool, worked, &qso).await?;
+    sqlx::query("DELETE FROM pending_review_qsos WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to clear pending QSO: {}", e))?;
+    Ok(qso_id)
+}
+
+/// Remove a held QSO from review without logging it
+pub async fn discard_pending_qso(pool: &sqlx::Pool<sqlx::Sqlite>, id: i64) -> Result<(), String> {
+    let result = sqlx::query("DELETE FROM pending_review_qsos WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to discard pending QSO: {}", e))?;
+    if result.rows_affected() == 0 {
+        return Err(format!("Pending QSO {} not found", id));
+    }
+    Ok(())
+}
+
+/// Most recent valid grid previously logged for a callsign
+pub async fn prior_grid_for_call(pool: &sqlx::Pool<sqlx::Sqlite>, call: &str) -> Option<String> {
+    let grids: Vec<String> = sqlx::query_scalar(
+        r#"SELECT gridsquare FROM qsos
+           WHERE UPPER(call) = UPPER(?) AND gridsquare IS NOT NULL AND gridsquare != ''
+           ORDER BY qso_date DESC, time_on DESC
+           LIMIT 10"#,
+    )
+    .bind(call)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    grids.into_iter().find(|g| !g.is_empty() && is_valid_grid(g))
+}
+
+/// Most likely full grid for a partial locator (e.g. just the field "PM"),
+/// from the grids logged with the callsign: the one logged most often that
+/// extends `partial`, the most recent on a tie
+pub async fn resolve_partial_grid(pool: &sqlx::Pool<sqlx::Sqlite>, call: &str, partial: &str) -> Option<String> {
+    let partial = partial.trim().to_uppercase();
+    if partial.is_empty() || !is_valid_grid(&partial) {
+        return None;
+    }
+    let grids: Vec<String> = sqlx::query_scalar(
+        r#"SELECT UPPER(gridsquare) AS grid FROM qsos
+           WHERE UPPER(call) = UPPER(?) AND UPPER(gridsquare) LIKE ? || '%'
+             AND LENGTH(gridsquare) > LENGTH(?)
+           GROUP BY grid
+           ORDER BY COUNT(*) DESC, MAX(qso_date || time_on) DESC"#,
+    )
+    .bind(call)
+    .bind(&partial)
+    .bind(&partial)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    grids.into_iter().find(|g| is_valid_grid(g))
+}
+
+/// Grid on the FCC license for a callsign (portable suffixes dropped), if any
+pub async fn fcc_grid_for_call(pool: &sqlx::Pool<sqlx::Sqlite>, call: &str) -> Option<String> {
+    crate::fcc::lookup_callsign(pool, &crate::reference::base_callsign(call))
+        .await
+        .and_then(|info| info.grid)
+        .filter(|g| is_valid_grid(g))
+}
+
+/// The PROP_MODE inference table: the user's rules if set and valid, else the defaults
+pub async fn prop_mode_rules(pool: &sqlx::Pool<sqlx::Sqlite>) -> Vec<PropModeRule> {
+    match read_setting(pool, PROP_MODE_RULES_SETTING).await {
+        Some(json) => parse_prop_mode_rules(&json).unwrap_or_else(|| {
+            log::warn!("Ignoring invalid {} setting", PROP_MODE_RULES_SETTING);
+            default_prop_mode_rules()
+        }),
+        None => default_prop_mode_rules(),
+    }
+}
+
+/// A tracked exchange as a WSJT-X logged QSO, timed by the wall clock at `now`
+fn logged_from_tracked(qso: &QsoInProgress, now: chrono::DateTime<chrono::Utc>) -> QsoLoggedMessage {
+    let at = |elapsed: std::time::Duration| {
+        let when = now - chrono::Duration::from_std(elapsed).unwrap_or_default();
+        when.format("%Y-%m-%d %H:%M:%S").to_string()
+    };
+    QsoLoggedMessage {
+        id: "TRACKER".to_string(),
+        datetime_off: qso
+            .time_off
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| at(qso.last_activity.elapsed())),
+        call: qso.their_call.clone(),
+        grid: qso.their_grid.clone().unwrap_or_default(),
+        freq_hz: qso.freq_hz,
+        mode: qso.mode.clone(),
+        report_sent: qso.report_sent.clone().unwrap_or_default(),
+        report_rcvd: qso.report_rcvd.clone().unwrap_or_default(),
+        tx_power: String::new(),
+        comments: String::new(),
+        name: String::new(),
+        datetime_on: at(qso.started_at.elapsed()),
+        operator_call: String::new(),
+        my_call: qso.my_call.clone(),
+        my_grid: String::new(),
+        exchange_sent: String::new(),
+        exchange_rcvd: String::new(),
+        adif_propagation_mode: String::new(),
+    }
+}
+
+/// Log tracked QSOs that are valid for logging and pass the same
+/// `should_autolog` check as QSOs WSJT-X logs. QSOs WSJT-X reported are no
+/// longer tracked; the insert's duplicate check is a backstop. Returns how
+/// many were inserted.
+pub async fn flush_in_progress(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    worked: &WorkedCache,
+    qsos: &[QsoInProgress],
+) -> Result<usize, String> {
+    let reject_low_confidence = read_bool_setting(pool, REJECT_LOW_CONFIDENCE_SETTING, false).await;
+    let now = chrono::Utc::now();
+    let mut inserted = 0;
+    for qso in qsos.iter().filter(|q| q.is_valid_for_logging()) {
+        if !should_autolog(reject_low_confidence, Some(qso)) {
+            log::warn!("Not logging in-progress QSO with {} on shutdown: mostly low-confidence decodes", qso.their_call);
+            continue;
+        }
+        match insert_qso_from_wsjtx(pool, worked, &logged_from_tracked(qso, now)).await {
+            Ok(Some(_)) => {
+                log::info!("Logged in-progress QSO with {} on shutdown", qso.their_call);
+                inserted += 1;
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Couldn't log in-progress QSO with {}: {}", qso.their_call, e),
+        }
+    }
+    Ok(inserted)
+}
+
+/// How long the exit flush waits for the listener to write its last decodes
+const SHUTDOWN_WAIT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Stop the listeners, wait for the last RX decodes to be written, log every
+/// complete exchange still in the trackers and prune old band activity.
+/// Called when the app exits.
+pub async fn flush_on_shutdown(state: &AppState) {
+    state.udp_state.stop_all();
+    let task = state.udp_task.lock().unwrap().take();
+    if let Some(task) = task {
+        // The loop ends once the stopped sockets drop their senders
+        if tokio::time::timeout(SHUTDOWN_WAIT, task).await.is_err() {
+            log::warn!("UDP listener didn't stop in time; its last decodes may be lost");
+        }
+    }
+    let qsos = state.trackers.loggable_qsos();
+
+    let db_guard = state.db.lock().await;
+    let Some(pool) = db_guard.as_ref() else { return };
+    if let Err(e) = flush_in_progress(pool, &state.worked, &qsos).await {
+        log::error!("Failed to flush in-progress QSOs: {}", e);
+    }
+    if let Err(e) = delete_band_activity_older_than(pool, DEFAULT_PRUNE_MINUTES).await {
+        log::warn!("{}", e);
+    }
+}
+
+/// Date, time and band a WSJT-X logged QSO is stored under, and the key both
+/// the listener's repeat check and the insert fast path use for it
+struct LoggedSlot {
+    qso_date: String,
+    time_on: String,
+    band: String,
+    key: String,
+}
+
+impl LoggedSlot {
+    fn of(qso: &QsoLoggedMessage, now: chrono::DateTime<chrono::Utc>) -> Self {
+        // Date and time from the same UTC clock, preferring what WSJT-X sent
+        let (qso_date, time_on) = resolve_qso_datetime(&qso.datetime_on, now);
+        let band = band_label_for_hz(qso.freq_hz);
+        let key = recent_slot_key(&qso.call, &qso_date, &time_on, &band, &qso.mode);
+        LoggedSlot { qso_date, time_on, band, key }
+    }
+}
+
+/// Insert a QSO WSJT-X logged whose slot the listener claimed. The claim is
+/// released unless a QSO was inserted, so a re-send of it isn't blocked.
+async fn insert_claimed_qso(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    worked: &WorkedCache,
+    qso: &QsoLoggedMessage,
+    slot_key: &str,
+) -> Result<Option<i64>, String> {
+    let result = insert_qso_from_wsjtx(pool, worked, qso).await;
+    if !matches!(result, Ok(Some(_))) {
+        worked.release_slot(slot_key);
+    }
+    result
+}
+
+/// What became of a QSO WSJT-X reported as logged
+#[derive(Debug)]
+enum LoggedOutcome {
+    /// A repeat (QsoLogged then LoggedADIF, or a re-send) of a QSO already handled
+    Repeat,
+    /// Refused: the exchange was built mostly from low-confidence decodes
+    Refused,
+    /// Held for review with this pending id: the exchange never completed
+    Held(Result<i64, String>),
+    /// Insert attempted; None when the QSO was already in the log
+    Inserted(Result<Option<i64>, String>),
+}
+
+/// Claim, vet and log a QSO WSJT-X reported. Refused and held QSOs keep their
+/// slot claim so the LoggedADIF copy that follows can't slip past the checks;
+/// only a failed insert gives it back.
+async fn handle_logged_qso(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    worked: &WorkedCache,
+    trackers: &QsoTrackers,
+    instance: u16,
+    qso: &QsoLoggedMessage,
+) -> LoggedOutcome {
+    let source_type = if qso.id == "TYPE5" { "QsoLogged(5)" } else if qso.id == "TYPE12" { "LoggedADIF(12)" } else { "Unknown" };
+    let slot = LoggedSlot::of(qso, chrono::Utc::now());
+    log::warn!("[QSO-HANDLER] Source={} call={} key={}", source_type, qso.call, slot.key);
+
+    if !worked.claim_slot(&slot.key) {
+        log::warn!("[QSO-HANDLER] DUPLICATE BLOCKED: {}", qso.call);
+        return LoggedOutcome::Repeat;
+    }
+
+    log::info!("Received QSO from WSJT-X: {}", qso.call);
+    // WSJT-X has logged it, whatever happens below; the exit flush mustn't
+    let tracked = trackers.with(instance, |t| t.take_qso(&qso.call));
+
+    let reject_low_confidence = read_bool_setting(pool, REJECT_LOW_CONFIDENCE_SETTING, false).await;
+    if !should_autolog(reject_low_confidence, tracked.as_ref()) {
+        return LoggedOutcome::Refused;
+    }
+
+    let require_complete = read_bool_setting(pool, REQUIRE_COMPLETE_EXCHANGE_SETTING, false).await;
+    if needs_review(require_complete, tracked.as_ref()) {
+        return LoggedOutcome::Held(hold_for_review(pool, qso, "incomplete_exchange").await);
+    }
+
+    LoggedOutcome::Inserted(insert_claimed_qso(pool, worked, qso, &slot.key).await)
+}
+
+/// Insert a QSO from WSJT-X into the database and the worked index.
+/// Returns None when the QSO is already logged.
+async fn insert_qso_from_wsjtx(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    worked: &WorkedCache,
+    qso: &QsoLoggedMessage,
+) -> Result<Option<i64>, String> {
     use sqlx::Row;
     
     if qso.call.is_empty() {
@@ -315,25 +1024,7 @@ async fn insert_qso_from_wsjtx(pool: &sqlx::Pool<sqlx::Sqlite>, qso: &QsoLoggedM
     
     let uuid = uuid::Uuid::new_v4().to_string();
     let freq_mhz = qso.freq_hz as f64 / 1_000_000.0;
-    let band = freq_to_band(freq_mhz);
-    let now = chrono::Utc::now();
-    
-    let (qso_date, time_on) = if !qso.datetime_on.is_empty() {
-        if qso.datetime_on.contains('-') && qso.datetime_on.contains(' ') {
-            let parts: Vec<&str> = qso.datetime_on.split(' ').collect();
-            if parts.len() >= 2 {
-                let date_part = parts[0].replace('-', "");
-                let time_part = normalize_time_to_hhmmss(parts[1]);
-                (date_part, time_part)
-            } else {
-                (now.format("%Y%m%d").to_string(), normalize_time_to_hhmmss(&qso.datetime_on))
-            }
-        } else {
-            (now.format("%Y%m%d").to_string(), normalize_time_to_hhmmss(&qso.datetime_on))
-        }
-    } else {
-        (now.format("%Y%m%d").to_string(), now.format("%H%M%S").to_string())
-    };
+    let LoggedSlot { qso_date, time_on, band, key: slot } = LoggedSlot::of(qso, chrono::Utc::now());
     
     if !is_valid_adif_date(&qso_date) {
         return Err(format!("Invalid date format: {}", qso_date));
@@ -342,6 +1033,12 @@ async fn insert_qso_from_wsjtx(pool: &sqlx::Pool<sqlx::Sqlite>, qso: &QsoLoggedM
         return Err(format!("Invalid time format: {}", time_on));
     }
     
+    // Fast path: this exact slot was logged in the last few minutes
+    if worked.recent_slot(&slot).is_some() {
+        log::info!("Skipping duplicate QSO: {} on {} (logged this session)", qso.call, band);
+        return Ok(None);
+    }
+
     let time_seconds = time_to_seconds(&time_on).unwrap_or(0) as i32;
     let exists: bool = sqlx::query_scalar(
         r#"SELECT EXISTS(SELECT 1 FROM qsos 
@@ -361,35 +1058,67 @@ async fn insert_qso_from_wsjtx(pool: &sqlx::Pool<sqlx::Sqlite>, qso: &QsoLoggedM
     
     if exists {
         log::info!("Skipping duplicate QSO: {} on {}", qso.call, band);
-        return Ok(());
+        return Ok(None);
     }
     
     let lookup = crate::reference::lookup_call_full(&qso.call);
     // Convert DXCC from ARRL 3-digit string to integer for database storage
     let dxcc_int = lookup.dxcc_as_i32();
+    let new_slot = match dxcc_int {
+        Some(dxcc) => worked.is_new_slot(pool, dxcc, &band).await.unwrap_or(false),
+        None => false,
+    };
     
+    // Use the grid the station sent, completed from its history if only the
+    // field came through; if it didn't send one, fall back to the grid from
+    // our last QSO with it, then its FCC license
+    let completed_grid = if qso.grid.len() == 2 {
+        resolve_partial_grid(pool, &qso.call, &qso.grid).await
+    } else {
+        None
+    };
+    let (validated_grid, grid_source) = if let Some(grid) = completed_grid {
+        log::info!("Completed grid {} for {} to {} from prior QSOs", qso.grid, qso.call, grid);
+        (Some(grid), Some(GRID_SOURCE_PRIOR_QSO))
+    } else if !qso.grid.is_empty() && is_valid_grid(&qso.grid) {
+        (Some(qso.grid.clone()), Some(GRID_SOURCE_STATION))
+    } else if let Some(grid) = prior_grid_for_call(pool, &qso.call).await {
+        log::info!("Using grid {} for {} from a prior QSO", grid, qso.call);
+        (Some(grid), Some(GRID_SOURCE_PRIOR_QSO))
+    } else if let Some(grid) = fcc_grid_for_call(pool, &qso.call).await {
+        log::info!("Using grid {} for {} from the FCC database", grid, qso.call);
+        (Some(grid), Some(GRID_SOURCE_FCC))
+    } else {
+        (None, None)
+    };
+    // WSJT-X's own propagation mode wins; otherwise infer it from mode and frequency
+    let prop_mode = if qso.adif_propagation_mode.is_empty() {
+        infer_prop_mode(&prop_mode_rules(pool).await, &qso.mode, freq_mhz)
+    } else {
+        Some(qso.adif_propagation_mode.clone())
+    };
+
     let adif_fields = serde_json::json!({
         "name": if qso.name.is_empty() { None } else { Some(&qso.name) },
         "comments": if qso.comments.is_empty() { None } else { Some(&qso.comments) },
         "tx_pwr": if qso.tx_power.is_empty() { None } else { Some(&qso.tx_power) },
         "operator": if qso.operator_call.is_empty() { None } else { Some(&qso.operator_call) },
-        "prop_mode": if qso.adif_propagation_mode.is_empty() { None } else { Some(&qso.adif_propagation_mode) },
     }).to_string();
     
-    // Validate grid before storing
-    let validated_grid = if is_valid_grid(&qso.grid) { Some(&qso.grid) } else { None };
-    
-    // Normalize RST values
-    let rst_sent = normalize_rst(&qso.report_sent);
-    let rst_rcvd = normalize_rst(&qso.report_rcvd);
+    // Normalize RST values, dropping "RR73"-style acknowledgments if enabled
+    let (rst_sent, rst_rcvd) = if read_bool_setting(pool, SANITIZE_RST_SETTING, true).await {
+        (sanitize_rst(&qso.report_sent, &qso.mode, None), sanitize_rst(&qso.report_rcvd, &qso.mode, None))
+    } else {
+        (Some(normalize_rst(&qso.report_sent)), Some(normalize_rst(&qso.report_rcvd)))
+    };
     
-    sqlx::query(
+    let result = sqlx::query(
         r#"INSERT INTO qsos (
-            uuid, call, qso_date, time_on, time_off, band, mode, freq,
-            dxcc, country, continent, cqz, ituz, gridsquare,
-            rst_sent, rst_rcvd, station_callsign, my_gridsquare,
+            uuid, call, qso_date, time_on, time_off, band, mode, freq, freq_hz,
+            dxcc, country, continent, cqz, ituz, gridsquare, grid_source,
+            rst_sent, rst_rcvd, station_callsign, my_gridsquare, prop_mode,
             adif_fields, source, created_at, updated_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'WSJT-X', datetime('now'), datetime('now'))"#
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'WSJT-X', datetime('now'), datetime('now'))"#
     )
     .bind(&uuid)
     .bind(&qso.call)
@@ -399,21 +1128,598 @@ async fn insert_qso_from_wsjtx(pool: &sqlx::Pool<sqlx::Sqlite>, qso: &QsoLoggedM
     .bind(&band)
     .bind(&qso.mode)
     .bind(freq_mhz)
+    .bind(qso.freq_hz as i64)
     .bind(dxcc_int)
     .bind(&lookup.country)
     .bind(&lookup.continent)
     .bind(lookup.cqz)
     .bind(lookup.ituz)
     .bind(validated_grid)
+    .bind(grid_source)
     .bind(&rst_sent)
     .bind(&rst_rcvd)
     .bind(if qso.my_call.is_empty() { None } else { Some(&qso.my_call) })
     .bind(if qso.my_grid.is_empty() { None } else { Some(&qso.my_grid) })
+    .bind(&prop_mode)
     .bind(&adif_fields)
     .execute(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-    
+    .await;
+
+    // The unique index has the last word if two copies race past the checks above
+    let id = match result {
+        Ok(result) => result.last_insert_rowid(),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            log::info!("Skipping duplicate QSO: {} on {} (already in log)", qso.call, band);
+            return Ok(None);
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let _ = worked.refresh_qso(pool, id).await;
+    worked.remember_slot(slot, id);
     log::info!("Inserted QSO: {} on {}", qso.call, band);
-    Ok(())
+    if let Some(warning) = super::validation::calling_freq_warning(&qso.mode, Some(freq_mhz)) {
+        log::warn!("QSO {} with {}: {}", id, qso.call, warning);
+    }
+    if new_slot {
+        let entity = lookup.country.clone().unwrap_or_default();
+        notify(pool, vec![WebhookEvent::NewBandSlot { call: qso.call.clone(), entity, band: band.clone() }]).await;
+    }
+    Ok(Some(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::test_pool;
+
+    fn logged(call: &str, grid: &str, datetime_on: &str) -> QsoLoggedMessage {
+        QsoLoggedMessage {
+            id: "WSJT-X".to_string(),
+            datetime_off: String::new(),
+            call: call.to_string(),
+            grid: grid.to_string(),
+            freq_hz: 14_074_000,
+            mode: "FT8".to_string(),
+            report_sent: "-10".to_string(),
+            report_rcvd: "-12".to_string(),
+            tx_power: String::new(),
+            comments: String::new(),
+            name: String::new(),
+            datetime_on: datetime_on.to_string(),
+            operator_call: String::new(),
+            my_call: "W1AW".to_string(),
+            my_grid: "FN31".to_string(),
+            exchange_sent: String::new(),
+            exchange_rcvd: String::new(),
+            adif_propagation_mode: String::new(),
+        }
+    }
+
+    async fn stored_grid_source(pool: &sqlx::Pool<sqlx::Sqlite>, id: i64) -> Option<String> {
+        sqlx::query_scalar("SELECT grid_source FROM qsos WHERE id = ?")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn stored_grid(pool: &sqlx::Pool<sqlx::Sqlite>, id: i64) -> (Option<String>, String) {
+        sqlx::query_as("SELECT gridsquare, adif_fields FROM qsos WHERE id = ?")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_callsign_mismatch_detection() {
+        assert!(is_callsign_mismatch("W1AW", Some("K1ABC")));
+        assert!(is_callsign_mismatch("W1AW/P", Some("W1AW")));
+        assert!(!is_callsign_mismatch("W1AW", Some("w1aw ")));
+        // No profile callsign configured, or WSJT-X not reporting one yet
+        assert!(!is_callsign_mismatch("W1AW", None));
+        assert!(!is_callsign_mismatch("W1AW", Some("")));
+        assert!(!is_callsign_mismatch("", Some("W1AW")));
+    }
+
+    /// Test: the QSO defaults' station callsign is used, without needing the FCC lookup
+    #[tokio::test]
+    async fn test_read_my_callsign() {
+        let pool = test_pool().await;
+        assert_eq!(read_my_callsign(&pool).await, None);
+
+        let defaults = crate::commands::qso::QsoDefaults {
+            station_callsign: Some("g4abc".to_string()),
+            ..Default::default()
+        };
+        crate::commands::qso::save_qso_defaults(&pool, &defaults).await.unwrap();
+        assert_eq!(read_my_callsign(&pool).await.as_deref(), Some("G4ABC"));
+
+        // `my_callsign` only fills in when the defaults have no station callsign
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'K1ABC', datetime('now'))")
+            .bind(MY_CALLSIGN_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(read_my_callsign(&pool).await.as_deref(), Some("G4ABC"));
+        crate::commands::qso::save_qso_defaults(&pool, &Default::default()).await.unwrap();
+        assert_eq!(read_my_callsign(&pool).await.as_deref(), Some("K1ABC"));
+    }
+
+    #[test]
+    fn test_parse_tx_message_calls() {
+        let calls = |m: &str| parse_tx_message_calls(m);
+        assert_eq!(calls("CQ W1AW FN31"), (Some("W1AW".into()), None));
+        assert_eq!(calls("CQ POTA W1AW FN31"), (Some("W1AW".into()), None));
+        assert_eq!(calls("CQ DX W1AW FN31"), (Some("W1AW".into()), None));
+        assert_eq!(calls("JA1ABC W1AW -10"), (Some("W1AW".into()), Some("JA1ABC".into())));
+        assert_eq!(calls("<PJ4/K1ABC> W1AW RR73"), (Some("W1AW".into()), Some("PJ4/K1ABC".into())));
+        assert_eq!(calls("CQ"), (None, None));
+    }
+
+    /// Test: A station that didn't send a grid gets the one from our previous QSO
+    #[tokio::test]
+    async fn test_grid_inferred_from_prior_qso() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+
+        let first = insert_qso_from_wsjtx(&pool, &worked, &logged("JA1ABC", "PM95", "2026-01-10 12:00:00"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored_grid(&pool, first).await.0.as_deref(), Some("PM95"));
+        assert_eq!(stored_grid_source(&pool, first).await.as_deref(), Some("station"));
+
+        // Next QSO: no grid in the exchange
+        let second = insert_qso_from_wsjtx(&pool, &worked, &logged("JA1ABC", "", "2026-02-10 12:00:00"))
+            .await
+            .unwrap()
+            .unwrap();
+        let (grid, adif_fields) = stored_grid(&pool, second).await;
+        assert_eq!(grid.as_deref(), Some("PM95"));
+        assert!(!adif_fields.contains("app_goqso_grid_source"));
+        assert_eq!(stored_grid_source(&pool, second).await.as_deref(), Some("prior_qso"));
+
+        // A grid the station sent is never overridden
+        let third = insert_qso_from_wsjtx(&pool, &worked, &logged("JA1ABC", "PM96", "2026-03-10 12:00:00"))
+            .await
+            .unwrap()
+            .unwrap();
+        let (grid, adif_fields) = stored_grid(&pool, third).await;
+        assert_eq!(grid.as_deref(), Some("PM96"));
+        assert!(!adif_fields.contains("app_goqso_grid_source"));
+
+        // Unknown station stays without a grid
+        let other = insert_qso_from_wsjtx(&pool, &worked, &logged("VK2XYZ", "", "2026-03-10 13:00:00"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored_grid(&pool, other).await.0, None);
+        assert_eq!(stored_grid_source(&pool, other).await, None);
+    }
+
+    /// Test: A field-only grid is completed from the call's most-logged grid in that field
+    #[tokio::test]
+    async fn test_resolve_partial_grid() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        for (grid, datetime_on) in [
+            ("PM95", "2026-01-10 12:00:00"),
+            ("PM95", "2026-01-11 12:00:00"),
+            ("PM96", "2026-01-12 12:00:00"),
+            ("QM05", "2026-01-13 12:00:00"),
+        ] {
+            insert_qso_from_wsjtx(&pool, &worked, &logged("JA1ABC", grid, datetime_on)).await.unwrap();
+        }
+
+        assert_eq!(resolve_partial_grid(&pool, "JA1ABC", "pm").await.as_deref(), Some("PM95"));
+        assert_eq!(resolve_partial_grid(&pool, "JA1ABC", "QM").await.as_deref(), Some("QM05"));
+        assert_eq!(resolve_partial_grid(&pool, "JA1ABC", "PN").await, None);
+        assert_eq!(resolve_partial_grid(&pool, "K5ABC", "PM").await, None);
+        assert_eq!(resolve_partial_grid(&pool, "JA1ABC", "RR73").await, None);
+
+        // At log time too
+        let id = insert_qso_from_wsjtx(&pool, &worked, &logged("JA1ABC", "PM", "2026-02-10 12:00:00"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored_grid(&pool, id).await.0.as_deref(), Some("PM95"));
+        assert_eq!(stored_grid_source(&pool, id).await.as_deref(), Some("prior_qso"));
+    }
+
+    /// Test: With no grid sent or logged before, the FCC license grid is used
+    #[tokio::test]
+    async fn test_grid_from_fcc() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        sqlx::query("INSERT INTO fcc_licenses (call, grid) VALUES ('K5ABC', 'EM12')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let id = insert_qso_from_wsjtx(&pool, &worked, &logged("K5ABC/P", "", "2026-01-10 12:00:00"))
+            .await
+            .unwrap()
+            .unwrap();
+        let (grid, adif_fields) = stored_grid(&pool, id).await;
+        assert_eq!(grid.as_deref(), Some("EM12"));
+        assert_eq!(stored_grid_source(&pool, id).await.as_deref(), Some("fcc"));
+        assert!(!adif_fields.contains("app_goqso_grid_source"));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_pending_logs_and_clears() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        let id = hold_for_review(&pool, &logged("G0XYZ", "IO91", "2026-03-10 12:00:00"), "incomplete_exchange")
+            .await
+            .unwrap();
+        let pending = pending_review_qsos(&pool).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].call, "G0XYZ");
+
+        let qso_id = confirm_pending_qso(&pool, &worked, id).await.unwrap().unwrap();
+        let call: String = sqlx::query_scalar("SELECT call FROM qsos WHERE id = ?")
+            .bind(qso_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(call, "G0XYZ");
+        assert!(pending_review_qsos(&pool).await.unwrap().is_empty());
+        assert!(confirm_pending_qso(&pool, &worked, id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_discard_pending_requires_existing_id() {
+        let pool = test_pool().await;
+        let id = hold_for_review(&pool, &logged("G0XYZ", "IO91", "2026-03-10 12:00:00"), "incomplete_exchange")
+            .await
+            .unwrap();
+
+        discard_pending_qso(&pool, id).await.unwrap();
+        assert!(pending_review_qsos(&pool).await.unwrap().is_empty());
+        assert_eq!(discard_pending_qso(&pool, id).await, Err(format!("Pending QSO {} not found", id)));
+    }
+
+    /// Test: QsoLogged repeated in quick succession (Type 5 then Type 12, or a
+    /// resend) is logged once, whether it hits the session fast path or the database
+    #[tokio::test]
+    async fn test_repeated_logged_message_inserted_once() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        let qso = logged("JA1ABC", "PM95", "2026-01-10 12:00:00");
+
+        let (a, b) = tokio::join!(
+            insert_qso_from_wsjtx(&pool, &worked, &qso),
+            insert_qso_from_wsjtx(&pool, &worked, &qso)
+        );
+        let c = insert_qso_from_wsjtx(&pool, &worked, &logged("JA1ABC", "PM95", "2026-01-10 12:00:20")).await;
+        let inserted: Vec<Option<i64>> = [a, b, c].into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(inserted.iter().filter(|id| id.is_some()).count(), 1);
+
+        // A fresh session (empty cache) still finds it in the database
+        let fresh = WorkedCache::new();
+        assert_eq!(insert_qso_from_wsjtx(&pool, &fresh, &qso).await.unwrap(), None);
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM qsos").fetch_one(&pool).await.unwrap();
+        assert_eq!(count, 1);
+    }
+
+    /// Test: VHF QSOs get an inferred PROP_MODE; WSJT-X's own value wins
+    #[tokio::test]
+    async fn test_prop_mode_inferred_on_insert() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        let stored = |id: i64| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, Option<String>>("SELECT prop_mode FROM qsos WHERE id = ?")
+                    .bind(id)
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let ms = QsoLoggedMessage { mode: "MSK144".to_string(), freq_hz: 50_260_000, ..logged("K2XYZ", "FN20", "2026-08-12 08:00:00") };
+        let id = insert_qso_from_wsjtx(&pool, &worked, &ms).await.unwrap().unwrap();
+        assert_eq!(stored(id).await.as_deref(), Some("MS"));
+
+        let eme = QsoLoggedMessage { mode: "Q65".to_string(), freq_hz: 144_120_000, ..logged("DL7APV", "JO62", "2026-08-12 09:00:00") };
+        let id = insert_qso_from_wsjtx(&pool, &worked, &eme).await.unwrap().unwrap();
+        assert_eq!(stored(id).await.as_deref(), Some("EME"));
+
+        let tropo = QsoLoggedMessage { freq_hz: 144_174_000, adif_propagation_mode: "TR".to_string(), ..logged("W2ABC", "FN30", "2026-08-12 10:00:00") };
+        let id = insert_qso_from_wsjtx(&pool, &worked, &tropo).await.unwrap().unwrap();
+        assert_eq!(stored(id).await.as_deref(), Some("TR"));
+
+        let hf = insert_qso_from_wsjtx(&pool, &worked, &logged("JA1ABC", "PM95", "2026-08-12 11:00:00")).await.unwrap().unwrap();
+        assert_eq!(stored(hf).await, None);
+    }
+
+    fn decode(message: &str) -> DecodePayload {
+        DecodePayload {
+            time_ms: 12 * 3_600_000 + 30 * 60_000 + 15_000,
+            snr: -7,
+            delta_freq: 1_234,
+            mode: "~".to_string(),
+            message: message.to_string(),
+            instance: 2237,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_from_decode() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+
+        let qso = qso_from_decode(&decode("K1ABC JA1XYZ PM95"), 14_074_000, None, Some("-12".to_string())).unwrap();
+        assert_eq!(qso.call, "JA1XYZ");
+        assert_eq!(qso.grid, "PM95");
+        assert_eq!(qso.freq_hz, 14_075_234);
+        assert_eq!(qso.report_sent, "-07");
+        assert_eq!(qso.report_rcvd, "-12");
+        assert_eq!(qso.datetime_on, "123015");
+
+        let id = insert_qso_from_wsjtx(&pool, &worked, &qso).await.unwrap().unwrap();
+        let (band, mode, dxcc, time_on): (String, String, i32, String) =
+            sqlx::query_as("SELECT band, mode, dxcc, time_on FROM qsos WHERE id = ?")
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(band, "20m");
+        assert_eq!(mode, "FT8");
+        assert_eq!(dxcc, 339);
+        assert_eq!(time_on, "123015");
+
+        // Logging the same decode again is a duplicate
+        assert_eq!(insert_qso_from_wsjtx(&pool, &worked, &qso).await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_log_from_decode_rejects_non_exchanges() {
+        assert!(qso_from_decode(&decode("CQ JA1XYZ PM95"), 14_074_000, None, None).is_err());
+        assert!(qso_from_decode(&decode("TNX 73 GL"), 14_074_000, None, None).is_err());
+        assert!(qso_from_decode(&decode("K1ABC JA1XYZ PM95"), 0, None, None).is_err());
+        assert!(qso_from_decode(&decode("K1ABC JA1XYZ PM95"), 12_000_000, None, None).is_err());
+        let unknown_mode = DecodePayload { mode: "?".to_string(), ..decode("K1ABC JA1XYZ PM95") };
+        assert!(qso_from_decode(&unknown_mode, 14_074_000, None, None).is_err());
+    }
+
+    /// Test: a QsoLogged replayed by WSJT-X is dropped by the listener's slot
+    /// claim while the session lasts, and by the database check after a restart
+    #[tokio::test]
+    async fn test_logged_message_replayed_across_restart() {
+        let pool = test_pool().await;
+        let now = chrono::Utc::now();
+        let worked = WorkedCache::new();
+        let qso = logged("JA1ABC", "PM95", "2026-01-10 12:00:00");
+
+        let slot = LoggedSlot::of(&qso, now);
+        assert_eq!(slot.key, "JA1ABC|20260110|1200|20m|FT8");
+        assert!(worked.claim_slot(&slot.key));
+        assert!(insert_qso_from_wsjtx(&pool, &worked, &qso).await.unwrap().is_some());
+
+        // Type 12 copy with a few seconds' difference: same slot, already claimed
+        let replay = logged("JA1ABC", "PM95", "2026-01-10 12:00:40");
+        assert!(!worked.claim_slot(&LoggedSlot::of(&replay, now).key));
+
+        // After a restart the cache is empty; the claim succeeds but nothing is inserted
+        let restarted = WorkedCache::new();
+        assert!(restarted.claim_slot(&LoggedSlot::of(&replay, now).key));
+        assert_eq!(insert_qso_from_wsjtx(&pool, &restarted, &replay).await.unwrap(), None);
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM qsos").fetch_one(&pool).await.unwrap();
+        assert_eq!(count, 1);
+    }
+
+    /// Test: interleaved decodes from an FT8 and an FT4 instance each close
+    /// their own cycle, instead of every switch between them closing one
+    #[test]
+    fn test_decode_batches_per_instance() {
+        let row = |time_ms: u32, mode: &str| RxDecodeRow {
+            time_ms,
+            message: "CQ K5ABC EM12".to_string(),
+            snr: -10,
+            delta_freq: 1500,
+            de_call: "K5ABC".to_string(),
+            dx_call: None,
+            mode: mode.to_string(),
+            low_confidence: false,
+            dxcc: Some(291),
+            dial_freq: Some(14_074_000.0),
+        };
+        let mut sessions: std::collections::HashMap<u16, InstanceSession> = std::collections::HashMap::new();
+        let mut closed = Vec::new();
+        for (instance, time_ms, mode) in [
+            (2237, 43_200_000, "~"),
+            (2238, 43_200_000, "+"),
+            (2237, 43_200_000, "~"),
+            (2238, 43_200_000, "+"),
+            (2238, 43_207_500, "+"),
+            (2237, 43_200_000, "~"),
+            (2237, 43_215_000, "~"),
+        ] {
+            let session = sessions.entry(instance).or_default();
+            if let Some(cycle) = session.decode_batch.push(row(time_ms, mode)) {
+                closed.push((instance, cycle.len()));
+            }
+        }
+        assert_eq!(closed, [(2238, 2), (2237, 3)]);
+    }
+
+    /// Test: a failed insert gives the slot claim back, so WSJT-X's re-send is logged
+    #[tokio::test]
+    async fn test_failed_insert_releases_slot_claim() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        let qso = logged("JA1ABC", "PM95", "2026-01-10 12:00:00");
+        let key = LoggedSlot::of(&qso, chrono::Utc::now()).key;
+
+        assert!(worked.claim_slot(&key));
+        let broken = QsoLoggedMessage { mode: String::new(), ..qso.clone() };
+        assert!(insert_claimed_qso(&pool, &worked, &broken, &key).await.is_err());
+
+        // The re-send claims the slot again and is inserted; after that it stays claimed
+        assert!(worked.claim_slot(&key));
+        assert!(insert_claimed_qso(&pool, &worked, &qso, &key).await.unwrap().is_some());
+        assert!(!worked.claim_slot(&key));
+    }
+
+    /// Test: the LoggedADIF copy of a refused or held QsoLogged isn't logged either
+    #[tokio::test]
+    async fn test_refused_and_held_qso_not_logged_by_resend() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        for key in [REJECT_LOW_CONFIDENCE_SETTING, REQUIRE_COMPLETE_EXCHANGE_SETTING] {
+            sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'true', datetime('now'))")
+                .bind(key)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let trackers = QsoTrackers::new();
+        trackers.with(2237, |t| {
+            t.update_status("K1ABC", "FN42", 14_074_000, "FT8", "");
+            // G0XYZ heard only through low-confidence decodes
+            t.process_rx_decode("K1ABC G0XYZ IO91", -20, 1500, true);
+            t.process_tx("G0XYZ K1ABC -19", None);
+            t.process_rx_decode("K1ABC G0XYZ R-22", -21, 1500, true);
+            t.process_tx("G0XYZ K1ABC RRR", None);
+            // DL1AB never sent a report
+            t.process_rx("K1ABC DL1AB JO62", -10, 900);
+            t.process_tx("DL1AB K1ABC -10", None);
+        });
+
+        let refused = logged("G0XYZ", "IO91", "2026-01-10 12:00:00");
+        let held = logged("DL1AB", "JO62", "2026-01-10 12:05:00");
+        let type5 = |qso: &QsoLoggedMessage| QsoLoggedMessage { id: "TYPE5".to_string(), ..qso.clone() };
+        let type12 = |qso: &QsoLoggedMessage| QsoLoggedMessage { id: "TYPE12".to_string(), ..qso.clone() };
+
+        let outcome = handle_logged_qso(&pool, &worked, &trackers, 2237, &type5(&refused)).await;
+        assert!(matches!(outcome, LoggedOutcome::Refused), "{:?}", outcome);
+        let outcome = handle_logged_qso(&pool, &worked, &trackers, 2237, &type12(&refused)).await;
+        assert!(matches!(outcome, LoggedOutcome::Repeat), "{:?}", outcome);
+
+        let outcome = handle_logged_qso(&pool, &worked, &trackers, 2237, &type5(&held)).await;
+        assert!(matches!(outcome, LoggedOutcome::Held(Ok(_))), "{:?}", outcome);
+        let outcome = handle_logged_qso(&pool, &worked, &trackers, 2237, &type12(&held)).await;
+        assert!(matches!(outcome, LoggedOutcome::Repeat), "{:?}", outcome);
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM qsos").fetch_one(&pool).await.unwrap();
+        assert_eq!(count, 0);
+        assert_eq!(pending_review_qsos(&pool).await.unwrap().len(), 1);
+    }
+
+    /// Test: WSJT-X's native Hz is stored exactly, and the band comes from it
+    #[tokio::test]
+    async fn test_freq_hz_stored_exactly() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        let qso = QsoLoggedMessage { freq_hz: 14_350_000, ..logged("JA1ABC", "PM95", "2026-01-10 12:00:00") };
+        let id = insert_qso_from_wsjtx(&pool, &worked, &qso).await.unwrap().unwrap();
+
+        let (band, freq_hz): (String, i64) = sqlx::query_as("SELECT band, freq_hz FROM qsos WHERE id = ?")
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!((band.as_str(), freq_hz), ("20m", 14_350_000));
+    }
+
+    /// Test: A complete exchange WSJT-X never logged is saved on shutdown, once
+    #[tokio::test]
+    async fn test_shutdown_flushes_in_progress_qso() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+
+        let trackers = crate::qso_tracker::QsoTrackers::new();
+        trackers.with(2237, |t| {
+            t.update_status("K1ABC", "FN42", 14_074_000, "FT8", "");
+            t.process_rx("K1ABC G0XYZ IO91", -15, 1500);
+            t.process_tx("G0XYZ K1ABC -19", None);
+            t.process_rx("K1ABC G0XYZ R-22", -14, 1500);
+            t.process_tx("G0XYZ K1ABC RRR", None);
+            // Still waiting for a report: not loggable
+            t.process_rx("K1ABC DL1AB JO62", -10, 900);
+        });
+        let qsos = trackers.loggable_qsos();
+        assert_eq!(qsos.len(), 1);
+
+        assert_eq!(flush_in_progress(&pool, &worked, &qsos).await.unwrap(), 1);
+        let (call, band, mode, gridsquare, rst_sent, rst_rcvd): (String, String, String, String, String, String) =
+            sqlx::query_as("SELECT call, band, mode, gridsquare, rst_sent, rst_rcvd FROM qsos")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((call.as_str(), band.as_str(), mode.as_str()), ("G0XYZ", "20m", "FT8"));
+        assert_eq!((gridsquare.as_str(), rst_sent.as_str(), rst_rcvd.as_str()), ("IO91", "-19", "-22"));
+
+        // A second exit (or WSJT-X logging it too) doesn't duplicate it
+        assert_eq!(flush_in_progress(&pool, &worked, &qsos).await.unwrap(), 0);
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM qsos").fetch_one(&pool).await.unwrap();
+        assert_eq!(count, 1);
+    }
+
+    /// Test: the exit flush skips exchanges the listener would refuse, and
+    /// exchanges WSJT-X already reported as logged
+    #[tokio::test]
+    async fn test_shutdown_flush_skips_refused_and_logged() {
+        let pool = test_pool().await;
+        let worked = WorkedCache::new();
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, 'true', datetime('now'))")
+            .bind(REJECT_LOW_CONFIDENCE_SETTING)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let trackers = crate::qso_tracker::QsoTrackers::new();
+        trackers.with(2237, |t| {
+            t.update_status("K1ABC", "FN42", 14_074_000, "FT8", "");
+            // Heard only through low-confidence decodes
+            t.process_rx_decode("K1ABC G0XYZ IO91", -20, 1500, true);
+            t.process_tx("G0XYZ K1ABC -19", None);
+            t.process_rx_decode("K1ABC G0XYZ R-22", -21, 1500, true);
+            t.process_tx("G0XYZ K1ABC RRR", None);
+
+            t.process_rx("K1ABC DL1AB JO62", -10, 900);
+            t.process_tx("DL1AB K1ABC -10", None);
+            t.process_rx("K1ABC DL1AB R-12", -10, 900);
+            t.process_tx("DL1AB K1ABC RRR", None);
+        });
+        assert_eq!(trackers.loggable_qsos().len(), 2);
+
+        // WSJT-X logs DL1AB, possibly minutes after the tracker first heard it
+        assert!(trackers.with(2237, |t| t.take_qso("dl1ab")).is_some());
+        let qsos = trackers.loggable_qsos();
+        assert_eq!(qsos.len(), 1);
+
+        assert_eq!(flush_in_progress(&pool, &worked, &qsos).await.unwrap(), 0);
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM qsos").fetch_one(&pool).await.unwrap();
+        assert_eq!(count, 0);
+    }
+
+    /// Test: The tracker event and the stored QSO get the same band at band edges and off-band
+    #[test]
+    fn test_logged_band_matches_event_band() {
+        let now = chrono::Utc::now();
+        for (freq_hz, band) in [
+            (135_700, "2200m"),
+            (479_000, "630m"),
+            (7_300_000, "40m"),
+            (10_150_000, "30m"),
+            (14_350_000, "20m"),
+            (14_350_001, "14.350MHz"),
+            (222_100_000, "1.25m"),
+            (1_296_100_000, "23cm"),
+            (300_000, "0.300MHz"),
+        ] {
+            let qso = QsoLoggedMessage { freq_hz, ..logged("JA1ABC", "PM95", "") };
+            assert_eq!(LoggedSlot::of(&qso, now).band, band, "{} Hz", freq_hz);
+            assert_eq!(QsoEvent::from_wsjtx(&qso, 0).band, band, "{} Hz", freq_hz);
+        }
+    }
 }
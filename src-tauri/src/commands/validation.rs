@@ -0,0 +1,788 @@
+//! Log validation commands
+//!
+//! This module handles:
+//! - validate_log: Check every QSO for problems before exporting to an award program
+//! - find_suspect_callsigns: Pairs of near-identical calls logged close together (likely busted calls)
+//!
+//! `upload_blockers` applies the stricter subset used to keep junk out of LoTW uploads.
+
+use serde::Serialize;
+use sqlx::{Pool, Row, Sqlite};
+use tauri::command;
+
+use super::qso::SOURCE_SWL;
+use super::state::AppState;
+use super::time_utils::{is_valid_adif_date, is_valid_adif_time, normalize_time_to_hhmmss, time_to_seconds};
+use crate::adif::bands::{band_sort_index, freq_to_band, get_ft4_freq, get_ft8_freq, reconcile_band_freq, BandFreqFix, BAND_ORDER};
+use crate::adif::modes::{get_mode_group, is_valid_mode, ModeGroup};
+use crate::udp::wsjtx::is_valid_grid;
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    /// Record will be rejected or miscredited by award programs
+    Error,
+    /// Likely wrong, worth reviewing
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub qso_id: i64,
+    pub call: String,
+    pub field: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationReport {
+    pub qsos_checked: usize,
+    pub qsos_with_issues: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// A QSO whose callsign is one character off another QSO's on the same band
+/// around the same time. Heuristic: a candidate for review, never corrected
+/// automatically.
+#[derive(Debug, Clone, Serialize)]
+pub struct SuspectCallsign {
+    /// The QSO thought to be busted (its call was logged less often)
+    pub qso_id: i64,
+    pub call: String,
+    /// The QSO with the similar call, probably the right one
+    pub likely_qso_id: i64,
+    pub likely_call: String,
+    pub band: String,
+    pub qso_date: String,
+    pub minutes_apart: i64,
+}
+
+/// The QSO fields the validator looks at
+#[derive(Debug, Clone, Default)]
+pub struct QsoFields {
+    pub id: i64,
+    pub call: String,
+    pub qso_date: String,
+    pub time_on: String,
+    pub qso_date_off: Option<String>,
+    pub time_off: Option<String>,
+    pub band: String,
+    pub mode: String,
+    pub freq: Option<f64>,
+    pub dxcc: Option<i32>,
+    pub gridsquare: Option<String>,
+    pub rst_sent: Option<String>,
+    pub rst_rcvd: Option<String>,
+    pub source: Option<String>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Check whether a signal report is plausible for the mode
+fn is_plausible_rst(rst: &str, mode: &str) -> bool {
+    let rst = rst.trim();
+    let upper = rst.to_uppercase();
+
+    // FT8 acknowledgments that leaked into the report field
+    if upper.contains("RR73") || upper.contains("RRR") || upper == "73" {
+        return false;
+    }
+
+    // Checked on bytes: the text is user-entered or imported and may not be ASCII
+    let is_rs = |b: &[u8]| b.len() == 2 && (b'1'..=b'5').contains(&b[0]) && (b'1'..=b'9').contains(&b[1]);
+    let is_rst = |b: &[u8]| b.len() == 3 && is_rs(&b[..2]) && (b'1'..=b'9').contains(&b[2]);
+    let bytes = rst.as_bytes();
+
+    match get_mode_group(mode) {
+        ModeGroup::Phone | ModeGroup::Image => is_rs(bytes),
+        ModeGroup::CW => is_rst(bytes),
+        // WSJT modes send dB reports; RTTY/PSK still use RST
+        ModeGroup::Data => {
+            rst.parse::<i32>().is_ok_and(|n| (-50..=50).contains(&n)) || is_rs(bytes) || is_rst(bytes)
+        }
+    }
+}
+
+/// How far (Hz) an FT8/FT4 QSO may be logged from the band's usual dial
+/// frequency: the audio passband above the dial plus some slack
+const CALLING_FREQ_TOLERANCE_HZ: u64 = 5_000;
+
+/// Soft warning when an FT8/FT4 QSO's frequency is far from the conventional
+/// dial frequency of its band, e.g. FT8 logged on 14.174 instead of 14.074.
+/// Other modes, and bands without a conventional frequency, are not checked.
+pub fn calling_freq_warning(mode: &str, freq_mhz: Option<f64>) -> Option<String> {
+    let freq_mhz = freq_mhz.filter(|f| *f > 0.0)?;
+    let band = freq_to_band(freq_mhz)?;
+    let mode = mode.trim().to_uppercase();
+    let dial_hz = match mode.as_str() {
+        "FT8" => get_ft8_freq(band)?,
+        "FT4" => get_ft4_freq(band)?,
+        _ => return None,
+    };
+    let freq_hz = (freq_mhz * 1_000_000.0).round() as u64;
+    let off_hz = freq_hz.abs_diff(dial_hz);
+    (off_hz > CALLING_FREQ_TOLERANCE_HZ).then(|| {
+        format!(
+            "{} at {:.3} MHz is {:.1} kHz from the usual {} {} frequency ({:.3} MHz)",
+            mode,
+            freq_mhz,
+            off_hz as f64 / 1000.0,
+            band,
+            mode,
+            dial_hz as f64 / 1_000_000.0
+        )
+    })
+}
+
+/// Longest QSO assumed when a TIME_OFF before TIME_ON is taken to mean the
+/// QSO ended the next day
+const OVERNIGHT_MAX_MINUTES: i64 = 120;
+
+/// How a QSO's end time relates to its start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeOffCheck {
+    /// No usable end time, or it's at or after the start
+    Ok,
+    /// TIME_OFF is before TIME_ON with no QSO_DATE_OFF, but a short
+    /// midnight-crossing QSO fits: QSO_DATE_OFF should be this date
+    Overnight(String),
+    /// The QSO ends before it starts
+    Inverted,
+}
+
+/// Compare a QSO's end with its start. `time_off` may also be the
+/// "YYYY-MM-DD HH:MM:SS" datetime WSJT-X logs, which carries its own date.
+/// Unparseable dates and times are left to the other checks.
+pub fn check_time_off(qso_date: &str, time_on: &str, qso_date_off: Option<&str>, time_off: Option<&str>) -> TimeOffCheck {
+    let Some(time_off) = time_off.map(str::trim).filter(|t| !t.is_empty()) else {
+        return TimeOffCheck::Ok;
+    };
+    let Some(start) = qso_minutes(qso_date, time_on) else {
+        return TimeOffCheck::Ok;
+    };
+    let date_off = qso_date_off.map(str::trim).filter(|d| !d.is_empty());
+    let (date_off, time_off, explicit_date) = match time_off.split_once(' ').filter(|(d, _)| d.contains('-')) {
+        Some((date, time)) => (date.replace('-', ""), normalize_time_to_hhmmss(time), true),
+        None => (date_off.unwrap_or(qso_date).to_string(), normalize_time_to_hhmmss(time_off), date_off.is_some()),
+    };
+    let Some(end) = qso_minutes(&date_off, &time_off) else {
+        return TimeOffCheck::Ok;
+    };
+    if end >= start {
+        return TimeOffCheck::Ok;
+    }
+
+    // Same date written for both ends of a QSO that crossed midnight
+    if !explicit_date || date_off == qso_date {
+        let next_day = chrono::NaiveDate::parse_from_str(qso_date, "%Y%m%d").ok().and_then(|d| d.succ_opt());
+        if let Some(next_day) = next_day.map(|d| d.format("%Y%m%d").to_string()) {
+            if qso_minutes(&next_day, &time_off).is_some_and(|end| end - start <= OVERNIGHT_MAX_MINUTES) {
+                return TimeOffCheck::Overnight(next_day);
+            }
+        }
+    }
+    TimeOffCheck::Inverted
+}
+
+/// Validate a single QSO and return every issue found
+pub fn validate_qso(qso: &QsoFields) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut push = |field: &str, severity: IssueSeverity, message: String| {
+        issues.push(ValidationIssue {
+            qso_id: qso.id,
+            call: qso.call.clone(),
+            field: field.to_string(),
+            severity,
+            message,
+        });
+    };
+
+    // Required fields
+    for (field, value) in [
+        ("call", &qso.call),
+        ("qso_date", &qso.qso_date),
+        ("time_on", &qso.time_on),
+        ("band", &qso.band),
+        ("mode", &qso.mode),
+    ] {
+        if value.trim().is_empty() {
+            push(field, IssueSeverity::Error, format!("Missing required field {}", field.to_uppercase()));
+        }
+    }
+
+    if !qso.qso_date.is_empty() && !is_valid_adif_date(&qso.qso_date) {
+        push("qso_date", IssueSeverity::Error, format!("Invalid date '{}' (expected YYYYMMDD)", qso.qso_date));
+    }
+    if !qso.time_on.is_empty() && !is_valid_adif_time(&qso.time_on) {
+        push("time_on", IssueSeverity::Error, format!("Invalid time '{}' (expected HHMM or HHMMSS)", qso.time_on));
+    }
+
+    match check_time_off(&qso.qso_date, &qso.time_on, qso.qso_date_off.as_deref(), qso.time_off.as_deref()) {
+        TimeOffCheck::Ok => {}
+        TimeOffCheck::Overnight(next_day) => push(
+            "time_off",
+            IssueSeverity::Warning,
+            format!("TIME_OFF is before TIME_ON; QSO_DATE_OFF should probably be {}", next_day),
+        ),
+        TimeOffCheck::Inverted => push(
+            "time_off",
+            IssueSeverity::Warning,
+            "QSO ends before it starts (TIME_OFF before TIME_ON)".to_string(),
+        ),
+    }
+
+    if let Some(grid) = qso.gridsquare.as_deref().filter(|g| !g.is_empty()) {
+        if !is_valid_grid(grid) {
+            push("gridsquare", IssueSeverity::Warning, format!("Invalid grid '{}'", grid));
+        }
+    }
+
+    // DXCC vs. what the callsign resolves to
+    if !qso.call.is_empty() {
+        let expected = crate::reference::lookup_call_full(&qso.call);
+        match (qso.dxcc, expected.dxcc_as_i32()) {
+            (None, Some(exp)) => push(
+                "dxcc",
+                IssueSeverity::Warning,
+                format!("Missing DXCC (callsign suggests {} - {})", exp, expected.country.unwrap_or_default()),
+            ),
+            (Some(actual), Some(exp)) if actual != exp => push(
+                "dxcc",
+                IssueSeverity::Warning,
+                format!(
+                    "DXCC {} does not match callsign prefix ({} - {})",
+                    actual,
+                    exp,
+                    expected.country.unwrap_or_default()
+                ),
+            ),
+            _ => {}
+        }
+    }
+
+    if let BandFreqFix::CorrectBand(freq_band) = reconcile_band_freq(Some(&qso.band), qso.freq, Some(&qso.mode)) {
+        if !qso.band.is_empty() {
+            push(
+                "band",
+                IssueSeverity::Warning,
+                format!("Band {} does not match frequency {:.3} MHz ({})", qso.band, qso.freq.unwrap_or(0.0), freq_band),
+            );
+        }
+    }
+
+    if let Some(message) = calling_freq_warning(&qso.mode, qso.freq) {
+        push("freq", IssueSeverity::Warning, message);
+    }
+
+    for (field, value) in [("rst_sent", &qso.rst_sent), ("rst_rcvd", &qso.rst_rcvd)] {
+        if let Some(rst) = value.as_deref().filter(|r| !r.trim().is_empty()) {
+            if !is_plausible_rst(rst, &qso.mode) {
+                push(field, IssueSeverity::Warning, format!("Suspicious report '{}' for {}", rst, qso.mode));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Read the validator fields from a `qsos` row (missing columns are left empty)
+pub fn qso_fields_from_row(row: &sqlx::sqlite::SqliteRow) -> QsoFields {
+    QsoFields {
+        id: row.get("id"),
+        call: row.try_get("call").unwrap_or_default(),
+        qso_date: row.try_get("qso_date").unwrap_or_default(),
+        time_on: row.try_get("time_on").unwrap_or_default(),
+        qso_date_off: row.try_get("qso_date_off").ok().flatten(),
+        time_off: row.try_get("time_off").ok().flatten(),
+        band: row.try_get("band").unwrap_or_default(),
+        mode: row.try_get("mode").unwrap_or_default(),
+        freq: row.try_get("freq").ok().flatten(),
+        dxcc: row.try_get("dxcc").ok().flatten(),
+        gridsquare: row.try_get("gridsquare").ok().flatten(),
+        rst_sent: row.try_get("rst_sent").ok().flatten(),
+        rst_rcvd: row.try_get("rst_rcvd").ok().flatten(),
+        source: row.try_get("source").ok().flatten(),
+    }
+}
+
+/// Reasons a QSO must not be uploaded: every validation error, plus an
+/// unknown band or mode, test QSOs and SWL reports, and (when `require_grid`)
+/// a missing grid.
+/// Warnings such as a suspicious RST don't block the upload.
+pub fn upload_blockers(qso: &QsoFields, require_grid: bool) -> Vec<ValidationIssue> {
+    let mut issues: Vec<ValidationIssue> =
+        validate_qso(qso).into_iter().filter(|i| i.severity == IssueSeverity::Error).collect();
+    let mut push = |field: &str, message: String| {
+        issues.push(ValidationIssue {
+            qso_id: qso.id,
+            call: qso.call.clone(),
+            field: field.to_string(),
+            severity: IssueSeverity::Error,
+            message,
+        });
+    };
+
+    if !qso.band.is_empty() && band_sort_index(&qso.band) == BAND_ORDER.len() {
+        push("band", format!("Unknown band '{}'", qso.band));
+    }
+    if !qso.mode.is_empty() && !is_valid_mode(&qso.mode) {
+        push("mode", format!("Unknown mode '{}'", qso.mode));
+    }
+    if qso.source.as_deref().is_some_and(|s| s.eq_ignore_ascii_case("TEST")) {
+        push("source", "Test QSO".to_string());
+    }
+    if qso.source.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(SOURCE_SWL)) {
+        push("source", "SWL report, not a two-way QSO".to_string());
+    }
+    if require_grid && qso.gridsquare.as_deref().is_none_or(|g| g.trim().is_empty()) {
+        push("gridsquare", "Missing grid".to_string());
+    }
+
+    issues
+}
+
+/// Validate every QSO in the log
+pub async fn validate_all(pool: &Pool<Sqlite>) -> Result<ValidationReport, String> {
+    let rows = sqlx::query(
+        r#"SELECT id, call, qso_date, time_on, qso_date_off, time_off, band, mode, freq, dxcc, gridsquare,
+                  rst_sent, rst_rcvd, source
+           FROM qsos ORDER BY qso_date DESC, time_on DESC"#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query QSOs: {}", e))?;
+
+    let mut issues: Vec<ValidationIssue> = Vec::new();
+    let mut qsos_with_issues = 0;
+
+    for row in &rows {
+        let qso = qso_fields_from_row(row);
+
+        let qso_issues = validate_qso(&qso);
+        if !qso_issues.is_empty() {
+            qsos_with_issues += 1;
+            issues.extend(qso_issues);
+        }
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == IssueSeverity::Error).count();
+
+    Ok(ValidationReport {
+        qsos_checked: rows.len(),
+        qsos_with_issues,
+        errors,
+        warnings: issues.len() - errors,
+        issues,
+    })
+}
+
+/// How far apart two QSOs can be and still be compared for busted calls
+pub const SUSPECT_CALL_WINDOW_MINUTES: i64 = 30;
+
+/// Whether two callsigns are exactly one insertion, deletion or substitution apart
+pub fn is_one_edit_apart(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if long.len() - short.len() > 1 || a == b {
+        return false;
+    }
+    let prefix = short.iter().zip(long).take_while(|(x, y)| x == y).count();
+    if short.len() == long.len() {
+        // One substitution: everything after it matches
+        short[prefix + 1..] == long[prefix + 1..]
+    } else {
+        // One insertion in the longer call
+        short[prefix..] == long[prefix + 1..]
+    }
+}
+
+/// Minutes since the epoch for a QSO_DATE/TIME_ON pair
+fn qso_minutes(qso_date: &str, time_on: &str) -> Option<i64> {
+    let date = chrono::NaiveDate::parse_from_str(qso_date, "%Y%m%d").ok()?;
+    let seconds = time_to_seconds(time_on)? as i64;
+    let days = date.signed_duration_since(chrono::NaiveDate::from_ymd_opt(1970, 1, 1)?).num_days();
+    Some(days * 1440 + seconds / 60)
+}
+
+/// Pairs of QSOs with callsigns one edit apart, on the same band within
+/// SUSPECT_CALL_WINDOW_MINUTES of each other. The call logged less often in
+/// the whole log is reported as the suspect.
+pub async fn suspect_callsigns(pool: &Pool<Sqlite>) -> Result<Vec<SuspectCallsign>, String> {
+    // (id, call, qso_date, time_on, band)
+    type CallRow = (i64, String, String, String, String);
+    let rows: Vec<CallRow> = sqlx::query_as(
+        "SELECT id, UPPER(call), qso_date, time_on, LOWER(band) FROM qsos ORDER BY qso_date, time_on, id",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query QSOs: {}", e))?;
+
+    let mut call_counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for (_, call, ..) in &rows {
+        *call_counts.entry(call.as_str()).or_default() += 1;
+    }
+
+    let qsos: Vec<(i64, &CallRow)> = rows
+        .iter()
+        .filter_map(|row| qso_minutes(&row.2, &row.3).map(|m| (m, row)))
+        .collect();
+
+    let mut suspects = Vec::new();
+    for (i, (minutes, a)) in qsos.iter().enumerate() {
+        for (other_minutes, b) in &qsos[i + 1..] {
+            let apart = other_minutes - minutes;
+            if apart > SUSPECT_CALL_WINDOW_MINUTES {
+                break;
+            }
+            if a.4 != b.4 || !is_one_edit_apart(&a.1, &b.1) {
+                continue;
+            }
+            // Fewer QSOs means more likely busted; on a tie, the later one
+            let (bad, good) = if call_counts[a.1.as_str()] < call_counts[b.1.as_str()] { (a, b) } else { (b, a) };
+            suspects.push(SuspectCallsign {
+                qso_id: bad.0,
+                call: bad.1.clone(),
+                likely_qso_id: good.0,
+                likely_call: good.1.clone(),
+                band: bad.4.clone(),
+                qso_date: bad.2.clone(),
+                minutes_apart: apart,
+            });
+        }
+    }
+    Ok(suspects)
+}
+
+/// Set QSO_DATE_OFF to the next day on QSOs whose TIME_OFF is before TIME_ON
+/// and that plausibly crossed midnight. True inversions are left for review.
+/// Returns how many QSOs were changed.
+pub async fn set_overnight_dates(pool: &Pool<Sqlite>) -> Result<i32, String> {
+    // (id, qso_date, time_on, qso_date_off, time_off)
+    let rows: Vec<(i64, String, String, Option<String>, String)> = sqlx::query_as(
+        "SELECT id, qso_date, time_on, qso_date_off, time_off FROM qsos WHERE time_off IS NOT NULL AND time_off != ''",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query QSOs: {}", e))?;
+
+    let mut fixed = 0;
+    for (id, qso_date, time_on, qso_date_off, time_off) in rows {
+        if let TimeOffCheck::Overnight(next_day) =
+            check_time_off(&qso_date, &time_on, qso_date_off.as_deref(), Some(&time_off))
+        {
+            sqlx::query("UPDATE qsos SET qso_date_off = ?, updated_at = datetime('now') WHERE id = ?")
+                .bind(&next_day)
+                .bind(id)
+                .execute(pool)
+                .await
+                .map_err(|e| format!("Failed to set QSO_DATE_OFF for QSO {}: {}", id, e))?;
+            fixed += 1;
+        }
+    }
+    Ok(fixed)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Validate the log, returning a fixable checklist of issues per QSO
+#[command]
+pub async fn validate_log(state: tauri::State<'_, AppState>) -> Result<ValidationReport, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let report = validate_all(pool).await?;
+    log::info!(
+        "Log validation: {} QSOs checked, {} errors, {} warnings",
+        report.qsos_checked,
+        report.errors,
+        report.warnings
+    );
+    Ok(report)
+}
+
+/// Likely busted callsigns, for review before an award submission
+#[command]
+pub async fn find_suspect_callsigns(state: tauri::State<'_, AppState>) -> Result<Vec<SuspectCallsign>, String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    suspect_callsigns(pool).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    fn good_qso() -> QsoFields {
+        QsoFields {
+            id: 1,
+            call: "JA1ABC".to_string(),
+            qso_date: "20260115".to_string(),
+            time_on: "123000".to_string(),
+            qso_date_off: Some("20260115".to_string()),
+            time_off: Some("123100".to_string()),
+            band: "20m".to_string(),
+            mode: "FT8".to_string(),
+            freq: Some(14.074),
+            dxcc: Some(339),
+            gridsquare: Some("PM95".to_string()),
+            rst_sent: Some("-10".to_string()),
+            rst_rcvd: Some("+05".to_string()),
+            source: Some("WSJT-X".to_string()),
+        }
+    }
+
+    fn fields_with_issues(qso: &QsoFields) -> Vec<String> {
+        validate_qso(qso).into_iter().map(|i| i.field).collect()
+    }
+
+    #[test]
+    fn test_clean_qso_has_no_issues() {
+        assert!(validate_qso(&good_qso()).is_empty());
+    }
+
+    #[test]
+    fn test_missing_required_fields() {
+        let qso = QsoFields { mode: String::new(), band: String::new(), ..good_qso() };
+        let issues = validate_qso(&qso);
+        assert!(issues.iter().any(|i| i.field == "mode" && i.severity == IssueSeverity::Error));
+        assert!(issues.iter().any(|i| i.field == "band" && i.severity == IssueSeverity::Error));
+    }
+
+    #[test]
+    fn test_invalid_date_and_time() {
+        let qso = QsoFields { qso_date: "2026-01-15".to_string(), time_on: "2561".to_string(), ..good_qso() };
+        let fields = fields_with_issues(&qso);
+        assert!(fields.contains(&"qso_date".to_string()));
+        assert!(fields.contains(&"time_on".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let qso = QsoFields { gridsquare: Some("RR73".to_string()), ..good_qso() };
+        assert_eq!(fields_with_issues(&qso), vec!["gridsquare"]);
+    }
+
+    #[test]
+    fn test_dxcc_mismatch() {
+        // JA1ABC logged as USA
+        let qso = QsoFields { dxcc: Some(291), ..good_qso() };
+        assert_eq!(fields_with_issues(&qso), vec!["dxcc"]);
+    }
+
+    #[test]
+    fn test_band_freq_mismatch() {
+        let qso = QsoFields { freq: Some(7.074), ..good_qso() };
+        assert_eq!(fields_with_issues(&qso), vec!["band"]);
+    }
+
+    #[test]
+    fn test_calling_frequency() {
+        // Dial plus a normal audio offset is fine
+        let on_freq = QsoFields { freq: Some(14.075_234), ..good_qso() };
+        assert!(validate_qso(&on_freq).is_empty());
+        let ft4 = QsoFields { mode: "FT4".to_string(), freq: Some(14.081_500), ..good_qso() };
+        assert!(validate_qso(&ft4).is_empty());
+
+        // FT8 100 kHz up the band is a warning, never an error
+        let off_freq = QsoFields { freq: Some(14.174), ..good_qso() };
+        let issues = validate_qso(&off_freq);
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].field.as_str(), issues[0].severity), ("freq", IssueSeverity::Warning));
+        assert!(upload_blockers(&off_freq, false).is_empty());
+
+        // Modes without a calling frequency aren't checked
+        assert_eq!(calling_freq_warning("CW", Some(14.174)), None);
+        assert_eq!(calling_freq_warning("FT8", None), None);
+    }
+
+    #[test]
+    fn test_suspicious_rst() {
+        let qso = QsoFields { rst_rcvd: Some("RR73".to_string()), ..good_qso() };
+        assert_eq!(fields_with_issues(&qso), vec!["rst_rcvd"]);
+
+        let ssb = QsoFields { mode: "SSB".to_string(), rst_sent: Some("59".to_string()), rst_rcvd: Some("-10".to_string()), ..good_qso() };
+        assert_eq!(fields_with_issues(&ssb), vec!["rst_rcvd"]);
+
+        let cw = QsoFields { mode: "CW".to_string(), rst_sent: Some("599".to_string()), rst_rcvd: Some("579".to_string()), ..good_qso() };
+        assert!(validate_qso(&cw).is_empty());
+
+        // Non-ASCII text is flagged, not a panic
+        for (mode, rst) in [("CW", "5é"), ("RTTY", "5é"), ("SSB", "é"), ("CW", "59é")] {
+            assert!(!is_plausible_rst(rst, mode), "{} {}", mode, rst);
+        }
+    }
+
+    #[test]
+    fn test_upload_blockers() {
+        assert!(upload_blockers(&good_qso(), true).is_empty());
+
+        let test_qso = QsoFields { source: Some("TEST".to_string()), ..good_qso() };
+        assert_eq!(upload_blockers(&test_qso, false)[0].field, "source");
+        let swl = QsoFields { source: Some("SWL".to_string()), ..good_qso() };
+        assert_eq!(upload_blockers(&swl, false)[0].field, "source");
+
+        let odd = QsoFields { band: "11m".to_string(), mode: "CHIRP".to_string(), ..good_qso() };
+        let fields: Vec<String> = upload_blockers(&odd, false).into_iter().map(|i| i.field).collect();
+        assert_eq!(fields, vec!["band", "mode"]);
+
+        // Warnings don't block; the grid is only required when asked for
+        let no_grid = QsoFields { gridsquare: None, rst_rcvd: Some("RR73".to_string()), ..good_qso() };
+        assert!(upload_blockers(&no_grid, false).is_empty());
+        assert_eq!(upload_blockers(&no_grid, true)[0].field, "gridsquare");
+    }
+
+    /// Test: Seeded known-bad QSOs are reported from the database
+    #[tokio::test]
+    async fn test_validate_all_seeded_db() {
+        let pool = test_pool().await;
+
+        for (call, date, band, freq, dxcc, grid, rst) in [
+            ("JA1ABC", "20260115", "20m", 14.074, 339, "PM95", "-10"),  // clean
+            ("K5ABC", "20261315", "20m", 14.074, 291, "EM12", "-10"),   // bad date
+            ("W1AW", "20260115", "20m", 7.074, 291, "FN31", "-10"),     // band/freq mismatch
+            ("G0XYZ", "20260115", "20m", 14.074, 223, "RR73", "73"),    // bad grid + RST
+        ] {
+            insert_qso(call)
+                .date(date)
+                .time("1230")
+                .band(band)
+                .set("freq", freq)
+                .dxcc(dxcc)
+                .set("gridsquare", grid)
+                .set("rst_rcvd", rst)
+                .insert(&pool)
+                .await;
+        }
+
+        let report = validate_all(&pool).await.unwrap();
+        assert_eq!(report.qsos_checked, 4);
+        assert_eq!(report.qsos_with_issues, 3);
+        assert_eq!(report.errors, 1);
+
+        let has = |call: &str, field: &str| report.issues.iter().any(|i| i.call == call && i.field == field);
+        assert!(has("K5ABC", "qso_date"));
+        assert!(has("W1AW", "band"));
+        assert!(has("G0XYZ", "gridsquare"));
+        assert!(has("G0XYZ", "rst_rcvd"));
+        assert!(!report.issues.iter().any(|i| i.call == "JA1ABC"));
+    }
+
+    #[test]
+    fn test_is_one_edit_apart() {
+        assert!(is_one_edit_apart("W1AW", "W1AWX"));
+        assert!(is_one_edit_apart("W1AWX", "W1AW"));
+        assert!(is_one_edit_apart("K5ABC", "K5ABD"));
+        assert!(is_one_edit_apart("JA1ABC", "JA1AC"));
+        assert!(!is_one_edit_apart("W1AW", "W1AW"));
+        assert!(!is_one_edit_apart("K5ABC", "K5BAC"));
+        assert!(!is_one_edit_apart("W1AW", "W1AW/P"));
+    }
+
+    /// Test: A one-character busted call next to the real one is flagged
+    #[tokio::test]
+    async fn test_suspect_callsigns() {
+        let pool = test_pool().await;
+
+        for (id, call, date, time, band) in [
+            (1, "W1AW", "20260115", "1200", "20m"),
+            (2, "W1AWX", "20260115", "1205", "20m"),  // busted
+            (3, "W1AW", "20260110", "1200", "40m"),
+            (4, "K5ABC", "20260115", "1210", "40m"),
+            (5, "K5ABD", "20260115", "1215", "20m"),  // other band
+            (6, "JA1ABC", "20260115", "1200", "15m"),
+            (7, "JA1ABD", "20260115", "1400", "15m"), // hours later
+        ] {
+            insert_qso(call).id(id).date(date).time(time).band(band).insert(&pool).await;
+        }
+
+        let suspects = suspect_callsigns(&pool).await.unwrap();
+        assert_eq!(suspects.len(), 1);
+        let s = &suspects[0];
+        assert_eq!((s.qso_id, s.call.as_str()), (2, "W1AWX"));
+        assert_eq!((s.likely_qso_id, s.likely_call.as_str()), (1, "W1AW"));
+        assert_eq!((s.band.as_str(), s.minutes_apart), ("20m", 5));
+    }
+
+    #[test]
+    fn test_midnight_crossing_is_valid() {
+        let qso = QsoFields {
+            qso_date: "20260115".to_string(),
+            time_on: "235800".to_string(),
+            qso_date_off: Some("20260116".to_string()),
+            time_off: Some("000100".to_string()),
+            ..good_qso()
+        };
+        assert_eq!(check_time_off("20260115", "235800", Some("20260116"), Some("000100")), TimeOffCheck::Ok);
+        assert!(validate_qso(&qso).is_empty());
+        // WSJT-X's full datetime carries the next day itself
+        assert_eq!(check_time_off("20260115", "235800", None, Some("2026-01-16 00:01:00")), TimeOffCheck::Ok);
+    }
+
+    #[test]
+    fn test_time_inversion_flagged() {
+        // Same date written on both ends of a midnight crossing: repairable
+        assert_eq!(
+            check_time_off("20260131", "235800", Some("20260131"), Some("000100")),
+            TimeOffCheck::Overnight("20260201".to_string())
+        );
+        assert_eq!(
+            check_time_off("20260115", "235800", None, Some("0001")),
+            TimeOffCheck::Overnight("20260116".to_string())
+        );
+        // Too long to be a midnight crossing, or a date off before the start: inverted
+        assert_eq!(check_time_off("20260115", "123000", None, Some("120000")), TimeOffCheck::Inverted);
+        assert_eq!(check_time_off("20260116", "235800", Some("20260115"), Some("000100")), TimeOffCheck::Inverted);
+
+        let qso = QsoFields { time_off: Some("122000".to_string()), ..good_qso() };
+        let issues = validate_qso(&qso);
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].field.as_str(), issues[0].severity), ("time_off", IssueSeverity::Warning));
+    }
+
+    /// Test: Repair moves QSO_DATE_OFF to the next day only when the QSO plausibly crossed midnight
+    #[tokio::test]
+    async fn test_set_overnight_dates() {
+        let pool = test_pool().await;
+
+        for (id, time_on, date_off, time_off) in [
+            (1, "235800", Some("20260115"), "000100"), // crossed midnight
+            (2, "235900", None, "0002"),               // crossed midnight, no date off
+            (3, "123000", Some("20260115"), "120000"), // true inversion
+            (4, "120000", Some("20260115"), "120500"), // fine
+        ] {
+            insert_qso("W1AW")
+                .id(id)
+                .date("20260115")
+                .time(time_on)
+                .set("qso_date_off", date_off)
+                .set("time_off", time_off)
+                .insert(&pool)
+                .await;
+        }
+
+        assert_eq!(set_overnight_dates(&pool).await.unwrap(), 2);
+        let dates: Vec<Option<String>> =
+            sqlx::query_scalar("SELECT qso_date_off FROM qsos ORDER BY id").fetch_all(&pool).await.unwrap();
+        assert_eq!(
+            dates,
+            vec![Some("20260116".to_string()), Some("20260116".to_string()), Some("20260115".to_string()), Some("20260115".to_string())]
+        );
+
+        let report = validate_all(&pool).await.unwrap();
+        let flagged: Vec<i64> = report.issues.iter().filter(|i| i.field == "time_off").map(|i| i.qso_id).collect();
+        assert_eq!(flagged, vec![3]);
+    }
+}
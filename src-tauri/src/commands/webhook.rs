@@ -0,0 +1,201 @@
+//! Webhook notification commands
+//!
+//! This module handles:
+//! - test_webhook: POST a test event to the configured webhook
+//!
+//! When `webhook_url` is set, notable events (a new DXCC entity confirmed, an
+//! award milestone, a QSO in a new entity/band slot) are POSTed to it as JSON
+//! for Discord, ntfy and the like. Delivery is best-effort: it runs in the
+//! background and failures are only logged.
+//!
+//! The payload is `{event, message, data, timestamp}` unless `webhook_template`
+//! holds a JSON template, in which `{{event}}` and `{{message}}` are replaced,
+//! e.g. `{"content": "{{message}}"}` for Discord.
+
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use tauri::command;
+
+use super::awards::{AwardMilestone, ConfirmationDelta};
+use super::settings::read_setting;
+use super::state::AppState;
+
+/// Settings key: URL notable events are POSTed to (disabled when unset)
+pub const WEBHOOK_URL_SETTING: &str = "webhook_url";
+
+/// Settings key: JSON payload template with `{{event}}`/`{{message}}` placeholders
+pub const WEBHOOK_TEMPLATE_SETTING: &str = "webhook_template";
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+/// Something worth telling the operator about outside the app
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    NewDxccConfirmed { entity: String },
+    AwardMilestone(AwardMilestone),
+    NewBandSlot { call: String, entity: String, band: String },
+    Test,
+}
+
+impl WebhookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WebhookEvent::NewDxccConfirmed { .. } => "new_dxcc_confirmed",
+            WebhookEvent::AwardMilestone(_) => "award_milestone",
+            WebhookEvent::NewBandSlot { .. } => "new_band_slot",
+            WebhookEvent::Test => "test",
+        }
+    }
+
+    /// One-line human-readable summary
+    pub fn message(&self) -> String {
+        match self {
+            WebhookEvent::NewDxccConfirmed { entity } => format!("New DXCC confirmed: {}", entity),
+            WebhookEvent::AwardMilestone(m) => {
+                format!("{} {} reached ({} confirmed)", m.award, m.threshold, m.confirmed)
+            }
+            WebhookEvent::NewBandSlot { call, entity, band } => {
+                format!("New band slot: {} on {} ({})", entity, band, call)
+            }
+            WebhookEvent::Test => "GoQSO webhook test".to_string(),
+        }
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Events for what a confirmation sync newly confirmed
+pub fn confirmation_events(delta: &ConfirmationDelta) -> Vec<WebhookEvent> {
+    let entities = delta
+        .new_dxcc_confirmed
+        .iter()
+        .map(|entity| WebhookEvent::NewDxccConfirmed { entity: entity.clone() });
+    let milestones = delta.milestones_reached.iter().cloned().map(WebhookEvent::AwardMilestone);
+    entities.chain(milestones).collect()
+}
+
+/// JSON body for an event: the default shape, or `template` with placeholders filled
+pub fn webhook_payload(
+    event: &WebhookEvent,
+    template: Option<&str>,
+    timestamp: &str,
+) -> Result<serde_json::Value, String> {
+    let message = event.message();
+    if let Some(template) = template.filter(|t| !t.trim().is_empty()) {
+        // Placeholders sit inside JSON strings, so substitute escaped text
+        let escape = |s: &str| {
+            let quoted = serde_json::to_string(s).unwrap_or_default();
+            quoted[1..quoted.len() - 1].to_string()
+        };
+        let filled = template
+            .replace("{{event}}", &escape(event.name()))
+            .replace("{{message}}", &escape(&message));
+        return serde_json::from_str(&filled).map_err(|e| format!("Invalid webhook template: {}", e));
+    }
+
+    let mut payload = serde_json::to_value(event).map_err(|e| e.to_string())?;
+    if let Some(obj) = payload.as_object_mut() {
+        obj.insert("message".to_string(), message.into());
+        obj.insert("timestamp".to_string(), timestamp.into());
+    }
+    Ok(payload)
+}
+
+/// POST the event to `url` and wait for the answer
+async fn post_webhook(url: &str, payload: &serde_json::Value) -> Result<(), String> {
+    let response = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| format!("Webhook request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Webhook returned HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// URL and payload for an event, or None when no webhook is configured
+async fn prepare(pool: &Pool<Sqlite>, event: &WebhookEvent) -> Result<Option<(String, serde_json::Value)>, String> {
+    let Some(url) = read_setting(pool, WEBHOOK_URL_SETTING).await.filter(|u| !u.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let template = read_setting(pool, WEBHOOK_TEMPLATE_SETTING).await;
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    let payload = webhook_payload(event, template.as_deref(), &timestamp)?;
+    Ok(Some((url.trim().to_string(), payload)))
+}
+
+/// Send events to the webhook in the background. Never fails or blocks the caller.
+pub async fn notify(pool: &Pool<Sqlite>, events: Vec<WebhookEvent>) {
+    let mut prepared = Vec::new();
+    for event in &events {
+        match prepare(pool, event).await {
+            Ok(Some(request)) => prepared.push(request),
+            Ok(None) => return,
+            Err(e) => log::warn!("Webhook {} skipped: {}", event.name(), e),
+        }
+    }
+    if prepared.is_empty() {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        for (url, payload) in prepared {
+            if let Err(e) = post_webhook(&url, &payload).await {
+                log::warn!("{}", e);
+            }
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Send a test event to the configured webhook and report the result
+#[command]
+pub async fn test_webhook(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    let db_guard = state.db.lock().await;
+    let pool = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let (url, payload) = prepare(pool, &WebhookEvent::Test).await?.ok_or("No webhook URL set")?;
+    drop(db_guard);
+    post_webhook(&url, &payload).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_dxcc_payload_shape() {
+        let event = WebhookEvent::NewDxccConfirmed { entity: "Japan".to_string() };
+        let payload = webhook_payload(&event, None, "2026-10-16T12:00:00+00:00").unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "event": "new_dxcc_confirmed",
+                "data": {"entity": "Japan"},
+                "message": "New DXCC confirmed: Japan",
+                "timestamp": "2026-10-16T12:00:00+00:00",
+            })
+        );
+    }
+
+    #[test]
+    fn test_template_payload() {
+        let event = WebhookEvent::NewDxccConfirmed { entity: "Cote d'Ivoire \"TU\"".to_string() };
+        let payload = webhook_payload(&event, Some(r#"{"content": "{{message}}", "tags": ["{{event}}"]}"#), "").unwrap();
+        assert_eq!(payload["content"], "New DXCC confirmed: Cote d'Ivoire \"TU\"");
+        assert_eq!(payload["tags"][0], "new_dxcc_confirmed");
+        assert!(webhook_payload(&event, Some("{not json"), "").is_err());
+    }
+}
@@ -0,0 +1,535 @@
+//! In-memory "worked before" index
+//!
+//! Answers dupe / new-DXCC checks for live decodes without hitting SQLite.
+//! The database stays the source of truth: the index is rebuilt at startup and
+//! after bulk changes (import, clear, repair), and updated per QSO on
+//! insert/edit/delete.
+//!
+//! It also remembers the exact slots (call, date, HHMM, band, mode) logged from
+//! WSJT-X in the last few minutes, so a repeated QsoLogged is dropped before any
+//! checks run and skips the database dupe check. Past the TTL, or after a
+//! restart, the database check alone catches replays.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use sqlx::{Pool, Sqlite};
+
+use super::qso::{QsoStatus, SOURCE_SWL};
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+/// The fields of a QSO the index keys on
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct WorkedEntry {
+    pub id: i64,
+    pub call: String,
+    pub qso_date: String,
+    pub band: String,
+    pub mode: String,
+    pub dxcc: Option<i32>,
+}
+
+/// The QSO being checked by `check_qso_status`
+#[derive(Debug, Clone, Copy)]
+pub struct WorkedQuery<'a> {
+    pub call: &'a str,
+    pub band: &'a str,
+    pub mode: &'a str,
+    pub dxcc: Option<i32>,
+    pub qso_date: &'a str,
+}
+
+type DupeKey = (String, String, String, String);
+
+/// Index from each lookup key to the QSO ids that match it.
+/// Keeping ids (not counts) lets a check exclude the QSO being edited.
+#[derive(Debug, Default)]
+pub struct WorkedIndex {
+    entries: HashMap<i64, WorkedEntry>,
+    by_dupe_key: HashMap<DupeKey, HashSet<i64>>,
+    by_call: HashMap<String, HashSet<i64>>,
+    by_dxcc: HashMap<i32, HashSet<i64>>,
+    by_dxcc_band: HashMap<(i32, String), HashSet<i64>>,
+    by_dxcc_mode: HashMap<(i32, String), HashSet<i64>>,
+}
+
+/// A slot WSJT-X logged recently; `id` is set once the QSO is inserted
+#[derive(Debug, Clone, Copy)]
+struct RecentSlot {
+    id: Option<i64>,
+    seen_at: Instant,
+}
+
+/// Slots logged recently, oldest first
+#[derive(Debug, Default)]
+struct RecentSlots {
+    slots: HashMap<String, RecentSlot>,
+    order: VecDeque<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct WorkedCache {
+    index: RwLock<WorkedIndex>,
+    loaded: RwLock<bool>,
+    recent: RwLock<RecentSlots>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn add_id<K: Hash + Eq>(map: &mut HashMap<K, HashSet<i64>>, key: K, id: i64) {
+    map.entry(key).or_default().insert(id);
+}
+
+fn remove_id<K: Hash + Eq>(map: &mut HashMap<K, HashSet<i64>>, key: &K, id: i64) {
+    if let Some(ids) = map.get_mut(key) {
+        ids.remove(&id);
+        if ids.is_empty() {
+            map.remove(key);
+        }
+    }
+}
+
+/// Number of ids under `key`, not counting `exclude_id`
+fn count<K: Hash + Eq>(map: &HashMap<K, HashSet<i64>>, key: &K, exclude_id: Option<i64>) -> usize {
+    map.get(key).map_or(0, |ids| {
+        ids.len() - exclude_id.map_or(0, |ex| ids.contains(&ex) as usize)
+    })
+}
+
+fn dupe_key(call: &str, band: &str, mode: &str, qso_date: &str) -> DupeKey {
+    (call.to_string(), band.to_string(), mode.to_string(), qso_date.to_string())
+}
+
+/// (entity, band) slot; band is lowercased since imports may carry "20M"
+fn slot_key(dxcc: i32, band: &str) -> (i32, String) {
+    (dxcc, band.to_lowercase())
+}
+
+/// Logged slots remembered for the WSJT-X fast path
+const MAX_RECENT_SLOTS: usize = 500;
+
+/// How long a logged slot is remembered
+const RECENT_SLOT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Exact logged slot: CALL|YYYYMMDD|HHMM|band|MODE
+pub fn recent_slot_key(call: &str, qso_date: &str, time_on: &str, band: &str, mode: &str) -> String {
+    format!(
+        "{}|{}|{}|{}|{}",
+        call.trim().to_uppercase(),
+        qso_date,
+        time_on.get(..4).unwrap_or(time_on),
+        band.to_lowercase(),
+        mode.trim().to_uppercase()
+    )
+}
+
+impl RecentSlots {
+    fn get(&self, key: &str, now: Instant) -> Option<RecentSlot> {
+        self.slots.get(key).copied().filter(|s| now.duration_since(s.seen_at) < RECENT_SLOT_TTL)
+    }
+
+    /// Forget expired slots and the oldest past MAX_RECENT_SLOTS. `order` is by
+    /// first sighting, so expired slots are always at the front.
+    fn prune(&mut self, now: Instant) {
+        while let Some(oldest) = self.order.front() {
+            let expired = self.get(oldest, now).is_none();
+            if !expired && self.order.len() <= MAX_RECENT_SLOTS {
+                break;
+            }
+            if let Some(oldest) = self.order.pop_front() {
+                self.slots.remove(&oldest);
+            }
+        }
+    }
+}
+
+impl WorkedIndex {
+    pub fn insert(&mut self, entry: WorkedEntry) {
+        self.remove(entry.id);
+        let id = entry.id;
+        add_id(&mut self.by_dupe_key, dupe_key(&entry.call, &entry.band, &entry.mode, &entry.qso_date), id);
+        add_id(&mut self.by_call, entry.call.clone(), id);
+        if let Some(dxcc) = entry.dxcc {
+            add_id(&mut self.by_dxcc, dxcc, id);
+            add_id(&mut self.by_dxcc_band, slot_key(dxcc, &entry.band), id);
+            add_id(&mut self.by_dxcc_mode, (dxcc, entry.mode.clone()), id);
+        }
+        self.entries.insert(id, entry);
+    }
+
+    pub fn remove(&mut self, id: i64) {
+        let Some(entry) = self.entries.remove(&id) else {
+            return;
+        };
+        remove_id(&mut self.by_dupe_key, &dupe_key(&entry.call, &entry.band, &entry.mode, &entry.qso_date), id);
+        remove_id(&mut self.by_call, &entry.call, id);
+        if let Some(dxcc) = entry.dxcc {
+            remove_id(&mut self.by_dxcc, &dxcc, id);
+            remove_id(&mut self.by_dxcc_band, &slot_key(dxcc, &entry.band), id);
+            remove_id(&mut self.by_dxcc_mode, &(dxcc, entry.mode), id);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Dupe = same call/band/mode/date; new-DXCC checks by entity, entity+band, entity+mode
+    pub fn status(&self, query: &WorkedQuery, exclude_id: Option<i64>) -> QsoStatus {
+        let WorkedQuery { call, band, mode, dxcc, qso_date } = *query;
+        let is_dupe = count(&self.by_dupe_key, &dupe_key(call, band, mode, qso_date), exclude_id) > 0;
+        let prev_count = count(&self.by_call, &call.to_string(), exclude_id);
+
+        let (is_new_dxcc, is_new_band_dxcc, is_new_mode_dxcc) = match dxcc {
+            Some(d) => (
+                count(&self.by_dxcc, &d, exclude_id) == 0,
+                count(&self.by_dxcc_band, &slot_key(d, band), exclude_id) == 0,
+                count(&self.by_dxcc_mode, &(d, mode.to_string()), exclude_id) == 0,
+            ),
+            None => (false, false, false),
+        };
+
+        QsoStatus {
+            is_dupe,
+            is_new_dxcc,
+            is_new_band_dxcc,
+            is_new_mode_dxcc,
+            has_previous_qso: prev_count > 0,
+            previous_qso_count: prev_count as i32,
+        }
+    }
+
+    /// True when no QSO with this entity on this band is logged yet
+    pub fn is_new_slot(&self, dxcc: i32, band: &str) -> bool {
+        count(&self.by_dxcc_band, &slot_key(dxcc, band), None) == 0
+    }
+
+    /// True when no QSO with this entity is logged yet
+    pub fn is_new_dxcc(&self, dxcc: i32) -> bool {
+        count(&self.by_dxcc, &dxcc, None) == 0
+    }
+}
+
+impl WorkedCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        *self.loaded.read().unwrap()
+    }
+
+    /// Reload the whole index from the database
+    pub async fn rebuild(&self, pool: &Pool<Sqlite>) -> Result<usize, String> {
+        let rows: Vec<WorkedEntry> =
+            sqlx::query_as("SELECT id, call, qso_date, band, mode, dxcc FROM qsos WHERE source IS NOT ?")
+                .bind(SOURCE_SWL)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| format!("Failed to load worked index: {}", e))?;
+
+        let mut index = WorkedIndex::default();
+        for entry in rows {
+            index.insert(entry);
+        }
+        let count = index.len();
+
+        *self.index.write().unwrap() = index;
+        *self.loaded.write().unwrap() = true;
+        log::debug!("Worked-before index rebuilt with {} QSOs", count);
+        Ok(count)
+    }
+
+    /// Re-read a single QSO after insert or edit (drops it if it no longer exists
+    /// or is an SWL report)
+    pub async fn refresh_qso(&self, pool: &Pool<Sqlite>, id: i64) -> Result<(), String> {
+        let entry: Option<WorkedEntry> =
+            sqlx::query_as("SELECT id, call, qso_date, band, mode, dxcc FROM qsos WHERE id = ? AND source IS NOT ?")
+                .bind(id)
+                .bind(SOURCE_SWL)
+                .fetch_optional(pool)
+                .await
+                .map_err(|e| format!("Failed to refresh worked index: {}", e))?;
+
+        let mut index = self.index.write().unwrap();
+        match entry {
+            Some(entry) => index.insert(entry),
+            None => index.remove(id),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&self, id: i64) {
+        self.index.write().unwrap().remove(id);
+    }
+
+    /// Rebuild after a bulk change, logging rather than failing the caller
+    pub async fn rebuild_or_log(&self, pool: &Pool<Sqlite>) {
+        if let Err(e) = self.rebuild(pool).await {
+            log::warn!("{}", e);
+        }
+    }
+
+    /// Dupe / new-DXCC status, loading the index on first use
+    pub async fn status(
+        &self,
+        pool: &Pool<Sqlite>,
+        query: &WorkedQuery<'_>,
+        exclude_id: Option<i64>,
+    ) -> Result<QsoStatus, String> {
+        if !self.is_loaded() {
+            self.rebuild(pool).await?;
+        }
+        Ok(self.index.read().unwrap().status(query, exclude_id))
+    }
+
+    /// Id of the QSO recently logged in this slot. Only trusted while that QSO
+    /// is still indexed with the same call/date/band/mode, so deletes, clears and
+    /// edits fall through to the database check.
+    pub fn recent_slot(&self, key: &str) -> Option<i64> {
+        let id = self.recent.read().unwrap().get(key, Instant::now())?.id?;
+        self.still_indexed(key, id).then_some(id)
+    }
+
+    fn still_indexed(&self, key: &str, id: i64) -> bool {
+        let index = self.index.read().unwrap();
+        let Some(entry) = index.entries.get(&id) else {
+            return false;
+        };
+        let hhmm = key.split('|').nth(2).unwrap_or("");
+        recent_slot_key(&entry.call, &entry.qso_date, hhmm, &entry.band, &entry.mode) == key
+    }
+
+    /// Claim a slot WSJT-X just logged before handling it. False when it was
+    /// already claimed within the TTL, i.e. this QsoLogged is a repeat; a slot whose
+    /// QSO has since been deleted or edited can be claimed again.
+    pub fn claim_slot(&self, key: &str) -> bool {
+        self.claim_slot_at(key, Instant::now())
+    }
+
+    fn claim_slot_at(&self, key: &str, now: Instant) -> bool {
+        let mut recent = self.recent.write().unwrap();
+        recent.prune(now);
+        if let Some(slot) = recent.get(key, now) {
+            if slot.id.is_none_or(|id| self.still_indexed(key, id)) {
+                return false;
+            }
+        }
+        if recent.slots.insert(key.to_string(), RecentSlot { id: None, seen_at: now }).is_none() {
+            recent.order.push_back(key.to_string());
+        }
+        true
+    }
+
+    /// Give up a claim whose insert failed, so WSJT-X's re-send isn't blocked.
+    /// Refused or held QSOs keep their claim: the re-send must not log them.
+    pub fn release_slot(&self, key: &str) {
+        let mut recent = self.recent.write().unwrap();
+        if recent.slots.get(key).is_some_and(|slot| slot.id.is_none()) {
+            recent.slots.remove(key);
+            recent.order.retain(|k| k != key);
+        }
+    }
+
+    /// Remember the QSO a slot was logged as
+    pub fn remember_slot(&self, key: String, id: i64) {
+        let now = Instant::now();
+        let mut recent = self.recent.write().unwrap();
+        recent.prune(now);
+        match recent.slots.get_mut(&key) {
+            Some(slot) => slot.id = Some(id),
+            None => {
+                recent.slots.insert(key.clone(), RecentSlot { id: Some(id), seen_at: now });
+                recent.order.push_back(key);
+            }
+        }
+    }
+
+    /// New (entity, band) slot check for live decodes, loading the index on first use
+    pub async fn is_new_slot(&self, pool: &Pool<Sqlite>, dxcc: i32, band: &str) -> Result<bool, String> {
+        if !self.is_loaded() {
+            self.rebuild(pool).await?;
+        }
+        Ok(self.index.read().unwrap().is_new_slot(dxcc, band))
+    }
+
+    /// New-entity check for live decodes, loading the index on first use
+    pub async fn is_new_dxcc(&self, pool: &Pool<Sqlite>, dxcc: i32) -> Result<bool, String> {
+        if !self.is_loaded() {
+            self.rebuild(pool).await?;
+        }
+        Ok(self.index.read().unwrap().is_new_dxcc(dxcc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{insert_qso, test_pool};
+
+    fn query<'a>(call: &'a str, band: &'a str, dxcc: i32) -> WorkedQuery<'a> {
+        WorkedQuery { call, band, mode: "FT8", dxcc: Some(dxcc), qso_date: "20260101" }
+    }
+
+    async fn insert(pool: &Pool<Sqlite>, call: &str, band: &str, dxcc: i32) -> i64 {
+        insert_qso(call).band(band).dxcc(dxcc).insert(pool).await
+    }
+
+    #[tokio::test]
+    async fn test_cache_tracks_inserts() {
+        let pool = test_pool().await;
+        let cache = WorkedCache::new();
+        cache.rebuild(&pool).await.unwrap();
+
+        let status = cache.status(&pool, &query("JA1ABC", "20m", 339), None).await.unwrap();
+        assert!(!status.is_dupe);
+        assert!(status.is_new_dxcc);
+
+        let id = insert(&pool, "JA1ABC", "20m", 339).await;
+        cache.refresh_qso(&pool, id).await.unwrap();
+
+        let status = cache.status(&pool, &query("JA1ABC", "20m", 339), None).await.unwrap();
+        assert!(status.is_dupe);
+        assert!(!status.is_new_dxcc);
+        assert_eq!(status.previous_qso_count, 1);
+
+        // Another JA on 40m: not a dupe, not a new entity, but a new band slot
+        let status = cache.status(&pool, &query("JA2XYZ", "40m", 339), None).await.unwrap();
+        assert!(!status.is_dupe);
+        assert!(!status.is_new_dxcc);
+        assert!(status.is_new_band_dxcc);
+        assert!(!status.is_new_mode_dxcc);
+
+        // Editing the QSO itself excludes it from the check
+        let status = cache.status(&pool, &query("JA1ABC", "20m", 339), Some(id)).await.unwrap();
+        assert!(!status.is_dupe);
+        assert!(status.is_new_dxcc);
+    }
+
+    #[tokio::test]
+    async fn test_cache_consistent_after_deletes_and_edits() {
+        let pool = test_pool().await;
+        let a = insert(&pool, "JA1ABC", "20m", 339).await;
+        let b = insert(&pool, "JA2XYZ", "20m", 339).await;
+        let cache = WorkedCache::new();
+        cache.rebuild(&pool).await.unwrap();
+
+        // Delete one: entity still worked via the other
+        sqlx::query("DELETE FROM qsos WHERE id = ?").bind(a).execute(&pool).await.unwrap();
+        cache.remove(a);
+        let status = cache.status(&pool, &query("JA1ABC", "20m", 339), None).await.unwrap();
+        assert!(!status.is_dupe);
+        assert!(!status.is_new_dxcc);
+
+        // Edit the other to a different band: 20m is a new slot again
+        sqlx::query("UPDATE qsos SET band = '40m' WHERE id = ?").bind(b).execute(&pool).await.unwrap();
+        cache.refresh_qso(&pool, b).await.unwrap();
+        let status = cache.status(&pool, &query("JA3DEF", "20m", 339), None).await.unwrap();
+        assert!(status.is_new_band_dxcc);
+
+        // Deleted out from under the cache: refresh drops it
+        sqlx::query("DELETE FROM qsos").execute(&pool).await.unwrap();
+        cache.refresh_qso(&pool, b).await.unwrap();
+        let status = cache.status(&pool, &query("JA2XYZ", "40m", 339), None).await.unwrap();
+        assert!(status.is_new_dxcc);
+        assert!(!status.has_previous_qso);
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_matches_database() {
+        let pool = test_pool().await;
+        insert(&pool, "W1AW", "20m", 291).await;
+        insert(&pool, "K1ABC", "40m", 291).await;
+
+        let cache = WorkedCache::new();
+        assert!(!cache.is_loaded());
+        // First status call loads the index
+        let status = cache.status(&pool, &query("W1AW", "20m", 291), None).await.unwrap();
+        assert!(cache.is_loaded());
+        assert!(status.is_dupe);
+
+        sqlx::query("DELETE FROM qsos").execute(&pool).await.unwrap();
+        assert_eq!(cache.rebuild(&pool).await.unwrap(), 0);
+        let status = cache.status(&pool, &query("W1AW", "20m", 291), None).await.unwrap();
+        assert!(!status.is_dupe);
+        assert!(status.is_new_dxcc);
+    }
+
+    #[tokio::test]
+    async fn test_new_slot_for_worked_entity_on_new_band() {
+        let pool = test_pool().await;
+        insert(&pool, "JA1ABC", "20M", 339).await;
+        let cache = WorkedCache::new();
+
+        assert!(!cache.is_new_slot(&pool, 339, "20m").await.unwrap());
+        assert!(cache.is_new_slot(&pool, 339, "40m").await.unwrap());
+
+        let id = insert(&pool, "JA2XYZ", "40m", 339).await;
+        cache.refresh_qso(&pool, id).await.unwrap();
+        assert!(!cache.is_new_slot(&pool, 339, "40m").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_recent_slot_dropped_with_its_qso() {
+        let pool = test_pool().await;
+        let cache = WorkedCache::new();
+        let id = insert(&pool, "JA1ABC", "20m", 339).await;
+        cache.refresh_qso(&pool, id).await.unwrap();
+
+        let key = recent_slot_key("ja1abc", "20260101", "120015", "20M", "ft8");
+        assert_eq!(key, "JA1ABC|20260101|1200|20m|FT8");
+        assert_eq!(cache.recent_slot(&key), None);
+        cache.remember_slot(key.clone(), id);
+        assert_eq!(cache.recent_slot(&key), Some(id));
+
+        // Deleted QSO: the slot falls back to the database check
+        cache.remove(id);
+        assert_eq!(cache.recent_slot(&key), None);
+    }
+
+    #[tokio::test]
+    async fn test_claimed_slot_expires() {
+        let pool = test_pool().await;
+        let cache = WorkedCache::new();
+        let key = recent_slot_key("JA1ABC", "20260101", "1200", "20m", "FT8");
+        let start = Instant::now();
+
+        assert!(cache.claim_slot_at(&key, start));
+        assert!(!cache.claim_slot_at(&key, start + Duration::from_secs(30)));
+        // Past the TTL the slot is forgotten and only the database check remains
+        assert!(cache.claim_slot_at(&key, start + RECENT_SLOT_TTL));
+
+        // A claimed slot whose QSO was deleted can be claimed again
+        let id = insert(&pool, "JA1ABC", "20m", 339).await;
+        cache.refresh_qso(&pool, id).await.unwrap();
+        cache.remember_slot(key.clone(), id);
+        assert!(!cache.claim_slot(&key));
+        cache.remove(id);
+        assert!(cache.claim_slot(&key));
+    }
+
+    #[test]
+    fn test_released_slot_can_be_claimed() {
+        let cache = WorkedCache::new();
+        let key = recent_slot_key("JA1ABC", "20260101", "1200", "20m", "FT8");
+        assert!(cache.claim_slot(&key));
+        cache.release_slot(&key);
+        assert!(cache.claim_slot(&key));
+
+        // A slot logged as a QSO stays claimed
+        cache.remember_slot(key.clone(), 1);
+        cache.release_slot(&key);
+        assert!(cache.recent.read().unwrap().slots.contains_key(&key));
+    }
+}
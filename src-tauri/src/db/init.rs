@@ -2,7 +2,7 @@
 use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite, Row};
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
-use crate::db::migrations::{MIGRATION_001, MIGRATION_002, MIGRATION_003, MIGRATION_004, MIGRATION_005};
+use crate::db::migrations::{latest_version, Migration, MIGRATIONS};
 use crate::reference::{dxcc, prefixes};
 
 /// Get the database path in the app data directory
@@ -43,8 +43,12 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, String> {
 }
 
 /// Run all pending migrations
-async fn run_migrations(pool: &Pool<Sqlite>) -> Result<(), String> {
-    // Create migrations table if not exists
+///
+/// Each migration runs in its own transaction and bumps `schema_version` on
+/// commit, so a failed migration leaves the database at the previous version.
+/// Refuses to touch a database written by a newer GoQSO (downgrade protection).
+pub(crate) async fn run_migrations(pool: &Pool<Sqlite>) -> Result<(), String> {
+    // Migration history log (also used to detect pre-schema_version databases)
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS _migrations (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -56,224 +60,124 @@ async fn run_migrations(pool: &Pool<Sqlite>) -> Result<(), String> {
     .await
     .map_err(|e| format!("Failed to create migrations table: {}", e))?;
     
-    // Check if migration 001 has been applied
-    let applied: bool = sqlx::query("SELECT COUNT(*) as count FROM _migrations WHERE name = 'migration_001'")
-        .fetch_one(pool)
-        .await
-        .map(|row| row.get::<i64, _>("count") > 0)
-        .unwrap_or(false);
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            version INTEGER NOT NULL,
+            updated_at TEXT NOT NULL
+        )"
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to create schema_version table: {}", e))?;
     
-    if !applied {
-        log::info!("Applying migration_001...");
-        
-        // Split migration into individual statements (SQLite doesn't support multiple statements)
-        for statement in MIGRATION_001.split(';') {
-            // Strip leading comments and whitespace
-            let mut stmt = statement.trim();
-            while stmt.starts_with("--") {
-                // Skip to next line
-                if let Some(idx) = stmt.find('\n') {
-                    stmt = stmt[idx + 1..].trim();
-                } else {
-                    stmt = "";
-                    break;
-                }
-            }
-            
-            if !stmt.is_empty() {
-                sqlx::query(stmt)
-                    .execute(pool)
-                    .await
-                    .map_err(|e| format!("Migration failed on statement: {}\nError: {}", stmt, e))?;
-            }
-        }
-        
-        // Mark migration as applied
-        sqlx::query("INSERT INTO _migrations (name, applied_at) VALUES ('migration_001', datetime('now'))")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to record migration: {}", e))?;
-        
-        log::info!("Migration 001 applied successfully");
+    let current = get_schema_version(pool).await?;
+    let latest = latest_version();
+    
+    if current > latest {
+        return Err(format!(
+            "Database schema version {} is newer than this version of GoQSO supports ({}). \
+             Please upgrade GoQSO to open this log.",
+            current, latest
+        ));
     }
     
-    // Check if migration 002 has been applied (adds missing columns)
-    let applied_002: bool = sqlx::query("SELECT COUNT(*) as count FROM _migrations WHERE name = 'migration_002'")
-        .fetch_one(pool)
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        apply_migration(pool, migration).await?;
+    }
+    
+    log::info!("Database schema at version {}", latest.max(current));
+    Ok(())
+}
+
+/// Read the current schema version
+///
+/// Databases created before `schema_version` existed only have `_migrations`
+/// rows named `migration_NNN`; the highest one is taken as the version.
+async fn get_schema_version(pool: &Pool<Sqlite>) -> Result<i64, String> {
+    let version: Option<i64> = sqlx::query_scalar("SELECT version FROM schema_version WHERE id = 1")
+        .fetch_optional(pool)
         .await
-        .map(|row| row.get::<i64, _>("count") > 0)
-        .unwrap_or(false);
+        .map_err(|e| format!("Failed to read schema version: {}", e))?;
     
-    if !applied_002 {
-        log::info!("Applying migration_002 (adding missing columns)...");
-        
-        // Split migration into individual statements
-        for statement in MIGRATION_002.split(';') {
-            // Strip leading comments and whitespace
-            let mut stmt = statement.trim();
-            while stmt.starts_with("--") {
-                if let Some(idx) = stmt.find('\n') {
-                    stmt = stmt[idx + 1..].trim();
-                } else {
-                    stmt = "";
-                    break;
-                }
-            }
-            
-            if !stmt.is_empty() {
-                // For ALTER TABLE ADD COLUMN, ignore "duplicate column" errors
-                // This allows the migration to be idempotent
-                let result = sqlx::query(stmt).execute(pool).await;
-                if let Err(e) = result {
-                    let err_str = e.to_string();
-                    // SQLite error for duplicate column: "duplicate column name"
-                    if err_str.contains("duplicate column name") {
-                        log::debug!("Column already exists, skipping: {}", stmt);
-                    } else if err_str.contains("index") && err_str.contains("already exists") {
-                        log::debug!("Index already exists, skipping: {}", stmt);
-                    } else {
-                        return Err(format!("Migration 002 failed on statement: {}\nError: {}", stmt, e));
-                    }
-                }
-            }
-        }
-        
-        // Mark migration as applied
-        sqlx::query("INSERT INTO _migrations (name, applied_at) VALUES ('migration_002', datetime('now'))")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to record migration: {}", e))?;
-        
-        log::info!("Migration 002 applied successfully");
+    if let Some(v) = version {
+        return Ok(v);
     }
     
-    // Check if migration 003 has been applied (adds qso_date_off and operator)
-    let applied_003: bool = sqlx::query("SELECT COUNT(*) as count FROM _migrations WHERE name = 'migration_003'")
-        .fetch_one(pool)
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM _migrations")
+        .fetch_all(pool)
         .await
-        .map(|row| row.get::<i64, _>("count") > 0)
-        .unwrap_or(false);
+        .map_err(|e| format!("Failed to read migration history: {}", e))?;
     
-    if !applied_003 {
-        log::info!("Applying migration_003 (adding qso_date_off and operator)...");
-        
-        for statement in MIGRATION_003.split(';') {
-            let mut stmt = statement.trim();
-            while stmt.starts_with("--") {
-                if let Some(idx) = stmt.find('\n') {
-                    stmt = stmt[idx + 1..].trim();
-                } else {
-                    stmt = "";
-                    break;
-                }
-            }
-            
-            if !stmt.is_empty() {
-                let result = sqlx::query(stmt).execute(pool).await;
-                if let Err(e) = result {
-                    let err_str = e.to_string();
-                    if err_str.contains("duplicate column name") {
-                        log::debug!("Column already exists, skipping: {}", stmt);
-                    } else {
-                        return Err(format!("Migration 003 failed on statement: {}\nError: {}", stmt, e));
-                    }
-                }
-            }
-        }
-        
-        sqlx::query("INSERT INTO _migrations (name, applied_at) VALUES ('migration_003', datetime('now'))")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to record migration: {}", e))?;
-        
-        log::info!("Migration 003 applied successfully");
-    }
+    Ok(names
+        .iter()
+        .filter_map(|n| n.strip_prefix("migration_").and_then(|v| v.parse::<i64>().ok()))
+        .max()
+        .unwrap_or(0))
+}
+
+/// Apply a single migration inside a transaction
+async fn apply_migration(pool: &Pool<Sqlite>, migration: &Migration) -> Result<(), String> {
+    log::info!("Applying migration_{:03} ({})...", migration.version, migration.description);
     
-    // Check if migration 004 has been applied (adds band_activity table)
-    let applied_004: bool = sqlx::query("SELECT COUNT(*) as count FROM _migrations WHERE name = 'migration_004'")
-        .fetch_one(pool)
+    let mut tx = pool.begin()
         .await
-        .map(|row| row.get::<i64, _>("count") > 0)
-        .unwrap_or(false);
+        .map_err(|e| format!("Failed to start migration transaction: {}", e))?;
     
-    if !applied_004 {
-        log::info!("Applying migration_004 (adding band_activity table)...");
-        
-        for statement in MIGRATION_004.split(';') {
-            let mut stmt = statement.trim();
-            while stmt.starts_with("--") {
-                if let Some(idx) = stmt.find('\n') {
-                    stmt = stmt[idx + 1..].trim();
-                } else {
-                    stmt = "";
-                    break;
-                }
-            }
-            
-            if !stmt.is_empty() {
-                let result = sqlx::query(stmt).execute(pool).await;
-                if let Err(e) = result {
-                    let err_str = e.to_string();
-                    if err_str.contains("already exists") {
-                        log::debug!("Table/index already exists, skipping: {}", stmt);
-                    } else {
-                        return Err(format!("Migration 004 failed on statement: {}\nError: {}", stmt, e));
-                    }
-                }
+    // SQLite doesn't support multiple statements per query
+    for stmt in split_statements(migration.sql) {
+        if let Err(e) = sqlx::query(stmt).execute(&mut *tx).await {
+            let err_str = e.to_string();
+            // Older builds applied some columns/tables ad hoc; keep migrations idempotent
+            if err_str.contains("duplicate column name") || err_str.contains("already exists") {
+                log::debug!("Already exists, skipping: {}", stmt);
+            } else {
+                // Dropping the transaction rolls back this migration
+                return Err(format!(
+                    "Migration {:03} failed on statement: {}\nError: {}",
+                    migration.version, stmt, e
+                ));
             }
         }
-        
-        sqlx::query("INSERT INTO _migrations (name, applied_at) VALUES ('migration_004', datetime('now'))")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to record migration: {}", e))?;
-        
-        log::info!("Migration 004 applied successfully");
     }
     
-    // Check if migration 005 has been applied (adds FCC database tables)
-    let applied_005: bool = sqlx::query("SELECT COUNT(*) as count FROM _migrations WHERE name = 'migration_005'")
-        .fetch_one(pool)
+    sqlx::query("INSERT OR IGNORE INTO _migrations (name, applied_at) VALUES (?, datetime('now'))")
+        .bind(format!("migration_{:03}", migration.version))
+        .execute(&mut *tx)
         .await
-        .map(|row| row.get::<i64, _>("count") > 0)
-        .unwrap_or(false);
+        .map_err(|e| format!("Failed to record migration: {}", e))?;
     
-    if !applied_005 {
-        log::info!("Applying migration_005 (adding FCC database tables)...");
-        
-        for statement in MIGRATION_005.split(';') {
+    sqlx::query(
+        r#"INSERT INTO schema_version (id, version, updated_at) VALUES (1, ?, datetime('now'))
+           ON CONFLICT(id) DO UPDATE SET version = excluded.version, updated_at = excluded.updated_at"#
+    )
+    .bind(migration.version)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to update schema version: {}", e))?;
+    
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit migration {:03}: {}", migration.version, e))?;
+    
+    log::info!("Migration {:03} applied successfully", migration.version);
+    Ok(())
+}
+
+/// Split a migration into statements, dropping leading `--` comment lines
+fn split_statements(sql: &str) -> Vec<&str> {
+    sql.split(';')
+        .filter_map(|statement| {
             let mut stmt = statement.trim();
             while stmt.starts_with("--") {
-                if let Some(idx) = stmt.find('\n') {
-                    stmt = stmt[idx + 1..].trim();
-                } else {
-                    stmt = "";
-                    break;
+                match stmt.find('\n') {
+                    Some(idx) => stmt = stmt[idx + 1..].trim(),
+                    None => stmt = "",
                 }
             }
-            
-            if !stmt.is_empty() {
-                let result = sqlx::query(stmt).execute(pool).await;
-                if let Err(e) = result {
-                    let err_str = e.to_string();
-                    if err_str.contains("already exists") {
-                        log::debug!("Table/index already exists, skipping: {}", stmt);
-                    } else {
-                        return Err(format!("Migration 005 failed on statement: {}\nError: {}", stmt, e));
-                    }
-                }
-            }
-        }
-        
-        sqlx::query("INSERT INTO _migrations (name, applied_at) VALUES ('migration_005', datetime('now'))")
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to record migration: {}", e))?;
-        
-        log::info!("Migration 005 applied successfully");
-    }
-    
-    Ok(())
+            (!stmt.is_empty()).then_some(stmt)
+        })
+        .collect()
 }
 
 /// Populate DXCC entities and prefixes from our reference data
@@ -387,3 +291,93 @@ pub struct DbStats {
     pub entity_count: i64,
     pub prefix_count: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+    
+    async fn memory_pool() -> Pool<Sqlite> {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create in-memory database")
+    }
+    
+    async fn schema_version(pool: &Pool<Sqlite>) -> i64 {
+        sqlx::query_scalar("SELECT version FROM schema_version WHERE id = 1")
+            .fetch_one(pool)
+            .await
+            .expect("schema_version row missing")
+    }
+    
+    /// Test: Fresh database migrates to the latest version
+    #[tokio::test]
+    async fn test_migrations_fresh_db() {
+        let pool = memory_pool().await;
+        
+        run_migrations(&pool).await.expect("Migrations failed");
+        
+        assert_eq!(schema_version(&pool).await, latest_version());
+        let applied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _migrations")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(applied, MIGRATIONS.len() as i64);
+        
+        // Running again is a no-op
+        run_migrations(&pool).await.expect("Re-running migrations failed");
+        assert_eq!(schema_version(&pool).await, latest_version());
+    }
+    
+    /// Test: Refuses to run against a database from a newer build
+    #[tokio::test]
+    async fn test_migrations_refuse_newer_db() {
+        let pool = memory_pool().await;
+        run_migrations(&pool).await.expect("Migrations failed");
+        
+        sqlx::query("UPDATE schema_version SET version = ? WHERE id = 1")
+            .bind(latest_version() + 1)
+            .execute(&pool)
+            .await
+            .unwrap();
+        
+        let result = run_migrations(&pool).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("newer"));
+    }
+    
+    /// Test: Pre-schema_version databases are detected from _migrations
+    #[tokio::test]
+    async fn test_legacy_migration_history() {
+        let pool = memory_pool().await;
+        sqlx::query(
+            "CREATE TABLE _migrations (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, applied_at TEXT NOT NULL)"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO _migrations (name, applied_at) VALUES ('migration_001', datetime('now')), ('migration_002', datetime('now'))")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "CREATE TABLE schema_version (id INTEGER PRIMARY KEY CHECK (id = 1), version INTEGER NOT NULL, updated_at TEXT NOT NULL)"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        
+        assert_eq!(get_schema_version(&pool).await.unwrap(), 2);
+    }
+    
+    #[test]
+    fn test_split_statements_strips_comments() {
+        let sql = "-- comment\nCREATE TABLE a (x INTEGER);\n\n-- only a comment;\nINSERT INTO a VALUES (1);";
+        assert_eq!(
+            split_statements(sql),
+            vec!["CREATE TABLE a (x INTEGER)", "INSERT INTO a VALUES (1)"]
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! SQL migrations for database schema
 //!
-//! NOTE: get_migrations() helper exists for future dynamic migration system.
-//! Currently using hardcoded migration constants in init.rs.
+//! Migrations are applied in order by `db::init::run_migrations`, which tracks
+//! the current version in the `schema_version` table. To change the schema,
+//! add a new `MIGRATION_NNN` constant and append it to `MIGRATIONS` with the
+//! next version number. Never edit a migration that has already shipped.
 //!
 #![allow(dead_code)]
 
@@ -417,7 +419,189 @@ CREATE TABLE IF NOT EXISTS fcc_sync_status (
 INSERT OR IGNORE INTO fcc_sync_status (id, updated_at) VALUES (1, datetime('now'));
 "#;
 
-/// Run all migrations
-pub fn get_migrations() -> Vec<&'static str> {
-    vec![MIGRATION_001, MIGRATION_002, MIGRATION_003, MIGRATION_004, MIGRATION_005]
+/// Migration 006: Decode confidence for band activity
+///
+/// WSJT-X flags a priori / deep-search decodes as low confidence. Storing the
+/// flag lets band activity stats report a low-confidence decode rate as a
+/// signal quality indicator.
+pub const MIGRATION_006: &str = r#"
+ALTER TABLE band_activity ADD COLUMN low_confidence INTEGER;
+"#;
+
+/// Migration 007: Subdivision reference table
+///
+/// Primary administrative subdivisions for sub-entity awards (JA prefectures,
+/// DOKs, oblasts). Codes match the ADIF STATE field. Seeded with the 47 JA
+/// prefectures.
+pub const MIGRATION_007: &str = r#"
+CREATE TABLE IF NOT EXISTS subdivisions (
+    dxcc            INTEGER NOT NULL,
+    code            TEXT NOT NULL,
+    name            TEXT NOT NULL,
+    PRIMARY KEY (dxcc, code)
+);
+
+INSERT OR IGNORE INTO subdivisions (dxcc, code, name) VALUES
+    (339, '01', 'Hokkaido'), (339, '02', 'Aomori'), (339, '03', 'Iwate'), (339, '04', 'Akita'),
+    (339, '05', 'Yamagata'), (339, '06', 'Miyagi'), (339, '07', 'Fukushima'), (339, '08', 'Niigata'),
+    (339, '09', 'Nagano'), (339, '10', 'Tokyo'), (339, '11', 'Kanagawa'), (339, '12', 'Chiba'),
+    (339, '13', 'Saitama'), (339, '14', 'Ibaraki'), (339, '15', 'Tochigi'), (339, '16', 'Gunma'),
+    (339, '17', 'Yamanashi'), (339, '18', 'Shizuoka'), (339, '19', 'Gifu'), (339, '20', 'Aichi'),
+    (339, '21', 'Mie'), (339, '22', 'Kyoto'), (339, '23', 'Shiga'), (339, '24', 'Nara'),
+    (339, '25', 'Osaka'), (339, '26', 'Wakayama'), (339, '27', 'Hyogo'), (339, '28', 'Toyama'),
+    (339, '29', 'Fukui'), (339, '30', 'Ishikawa'), (339, '31', 'Okayama'), (339, '32', 'Shimane'),
+    (339, '33', 'Yamaguchi'), (339, '34', 'Tottori'), (339, '35', 'Hiroshima'), (339, '36', 'Kagawa'),
+    (339, '37', 'Tokushima'), (339, '38', 'Ehime'), (339, '39', 'Kochi'), (339, '40', 'Fukuoka'),
+    (339, '41', 'Saga'), (339, '42', 'Nagasaki'), (339, '43', 'Kumamoto'), (339, '44', 'Oita'),
+    (339, '45', 'Miyazaki'), (339, '46', 'Kagoshima'), (339, '47', 'Okinawa');
+"#;
+
+/// Migration 008: User callsign exceptions
+///
+/// Full callsigns that resolve to a DXCC entity regardless of prefix rules
+/// (DXpeditions, special event stations). Consulted before prefix matching,
+/// alongside the built-in list in reference::exceptions.
+pub const MIGRATION_008: &str = r#"
+CREATE TABLE IF NOT EXISTS callsign_exceptions (
+    call            TEXT PRIMARY KEY,
+    dxcc            INTEGER NOT NULL,
+    note            TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+"#;
+
+/// Migration 009: QSOs held for review
+///
+/// WSJT-X QsoLogged messages whose tracked exchange never completed, kept
+/// out of the log until the operator confirms or discards them. `payload` is
+/// the logged message as JSON.
+pub const MIGRATION_009: &str = r#"
+CREATE TABLE IF NOT EXISTS pending_review_qsos (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    call            TEXT NOT NULL,
+    datetime_on     TEXT,
+    freq_hz         INTEGER,
+    mode            TEXT,
+    reason          TEXT NOT NULL,
+    payload         TEXT NOT NULL,
+    received_at     TEXT NOT NULL DEFAULT (datetime('now'))
+);
+"#;
+
+/// Migration 010: DXCC entity on band activity
+///
+/// The entity of the sending station, resolved when the decode is stored, so
+/// activity can be summarized by entity without re-running prefix lookups.
+pub const MIGRATION_010: &str = r#"
+ALTER TABLE band_activity ADD COLUMN dxcc INTEGER;
+CREATE INDEX IF NOT EXISTS idx_band_activity_dxcc ON band_activity(dxcc);
+"#;
+
+/// Migration 011: Grid provenance
+///
+/// Where a QSO's gridsquare came from (station, prior_qso, fcc, lotw, adif,
+/// manual) so award and map features can prefer grids the station sent or
+/// LoTW confirmed over inferred ones. Existing inferred grids carry the
+/// source in adif_fields; other WSJT-X grids were sent by the station.
+pub const MIGRATION_011: &str = r#"
+ALTER TABLE qsos ADD COLUMN grid_source TEXT;
+UPDATE qsos SET grid_source = json_extract(adif_fields, '$.app_goqso_grid_source')
+    WHERE gridsquare IS NOT NULL AND json_valid(adif_fields);
+UPDATE qsos SET grid_source = 'station'
+    WHERE grid_source IS NULL AND gridsquare IS NOT NULL AND source = 'WSJT-X';
+"#;
+
+/// Migration 012: DXpedition callsign patterns
+///
+/// User-maintained list of active DXpedition and special-event calls,
+/// flagged on decodes and QSOs. Entries are time-limited: `end_date`
+/// (YYYYMMDD, inclusive) retires a pattern without deleting it.
+pub const MIGRATION_012: &str = r#"
+CREATE TABLE IF NOT EXISTS dxpeditions (
+    pattern         TEXT PRIMARY KEY,       -- Callsign, '*' matches any run of characters
+    note            TEXT,
+    end_date        TEXT,                   -- YYYYMMDD, NULL = open-ended
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+"#;
+
+/// Migration 013: Confirmations needing re-verification
+///
+/// Set when the QSO's callsign is corrected after it was confirmed: the
+/// confirmation was for the old call. Cleared when the confirmation is
+/// received again.
+pub const MIGRATION_013: &str = r#"
+ALTER TABLE confirmations ADD COLUMN needs_reverify INTEGER NOT NULL DEFAULT 0;
+"#;
+
+/// Migration 014: Frequency in Hz
+///
+/// `freq` (MHz, REAL) stays for display; `freq_hz` holds the exact integer
+/// frequency for band computation, exact matching and export. Backfilled from
+/// `freq` rounded to the nearest Hz.
+pub const MIGRATION_014: &str = r#"
+ALTER TABLE qsos ADD COLUMN freq_hz INTEGER;
+UPDATE qsos SET freq_hz = CAST(ROUND(freq * 1000000) AS INTEGER) WHERE freq > 0;
+"#;
+
+/// Migration 015: Award snapshots
+///
+/// Worked/confirmed counts per award at a point in time, recorded on demand
+/// or after a LoTW sync, to chart progress over time.
+pub const MIGRATION_015: &str = r#"
+CREATE TABLE IF NOT EXISTS award_snapshots (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    award           TEXT NOT NULL,          -- DXCC, WAS, WAC, WAZ, VUCC
+    worked          INTEGER NOT NULL,
+    confirmed       INTEGER NOT NULL,
+    taken_at        TEXT NOT NULL           -- UTC 'YYYY-MM-DD HH:MM:SS'
+);
+CREATE INDEX IF NOT EXISTS idx_award_snapshots_award ON award_snapshots(award, taken_at);
+"#;
+
+/// Migration 016: Promoted fields out of adif_fields
+///
+/// WSJT-X QSOs also kept prop_mode and app_goqso_grid_source in adif_fields,
+/// so export wrote them twice. The columns now hold them alone.
+pub const MIGRATION_016: &str = r#"
+UPDATE qsos SET prop_mode = json_extract(adif_fields, '$.prop_mode')
+    WHERE prop_mode IS NULL AND json_valid(adif_fields);
+UPDATE qsos SET adif_fields = json_remove(adif_fields, '$.prop_mode', '$.app_goqso_grid_source')
+    WHERE json_valid(adif_fields);
+"#;
+
+/// A versioned schema migration
+#[derive(Debug)]
+pub struct Migration {
+    /// Schema version after this migration is applied
+    pub version: i64,
+    /// Short description for logging
+    pub description: &'static str,
+    /// Up SQL (semicolon-separated statements)
+    pub sql: &'static str,
+}
+
+/// All migrations, in the order they must be applied
+pub const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, description: "initial schema", sql: MIGRATION_001 },
+    Migration { version: 2, description: "adding missing columns", sql: MIGRATION_002 },
+    Migration { version: 3, description: "adding qso_date_off and operator", sql: MIGRATION_003 },
+    Migration { version: 4, description: "adding band_activity table", sql: MIGRATION_004 },
+    Migration { version: 5, description: "adding FCC database tables", sql: MIGRATION_005 },
+    Migration { version: 6, description: "adding band_activity.low_confidence", sql: MIGRATION_006 },
+    Migration { version: 7, description: "adding subdivisions table", sql: MIGRATION_007 },
+    Migration { version: 8, description: "adding callsign_exceptions table", sql: MIGRATION_008 },
+    Migration { version: 9, description: "adding pending_review_qsos table", sql: MIGRATION_009 },
+    Migration { version: 10, description: "adding band_activity.dxcc", sql: MIGRATION_010 },
+    Migration { version: 11, description: "adding qsos.grid_source", sql: MIGRATION_011 },
+    Migration { version: 12, description: "adding dxpeditions table", sql: MIGRATION_012 },
+    Migration { version: 13, description: "adding confirmations.needs_reverify", sql: MIGRATION_013 },
+    Migration { version: 14, description: "adding qsos.freq_hz", sql: MIGRATION_014 },
+    Migration { version: 15, description: "adding award_snapshots table", sql: MIGRATION_015 },
+    Migration { version: 16, description: "moving promoted fields out of adif_fields", sql: MIGRATION_016 },
+];
+
+/// Highest schema version this build knows how to apply
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
 }
@@ -3,5 +3,7 @@ pub mod qso;
 pub mod awards;
 pub mod migrations;
 pub mod init;
+#[cfg(test)]
+pub mod test_support;
 
 pub use init::{init_db, get_db_stats, DbStats};
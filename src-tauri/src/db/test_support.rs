@@ -0,0 +1,145 @@
+// Shared fixtures for database tests
+
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::{Pool, Sqlite};
+
+/// A fresh in-memory database with the full schema
+pub async fn test_pool() -> Pool<Sqlite> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+    crate::db::init::run_migrations(&pool).await.expect("Migrations failed");
+    pool
+}
+
+/// A column value for `TestQso::set`
+#[derive(Debug, Clone)]
+pub enum Value {
+    Int(i64),
+    Real(f64),
+    Text(String),
+    Null,
+}
+
+impl From<i32> for Value {
+    fn from(v: i32) -> Self {
+        Value::Int(v.into())
+    }
+}
+
+impl From<i64> for Value {
+    fn from(v: i64) -> Self {
+        Value::Int(v)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        Value::Real(v)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::Text(v.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::Text(v)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Value::Null, Into::into)
+    }
+}
+
+/// A `qsos` row to insert: 20m FT8 on 2026-01-01 at 120000 unless told
+/// otherwise. Any other column is set with `set`.
+#[derive(Debug, Clone)]
+pub struct TestQso {
+    columns: Vec<(&'static str, Value)>,
+}
+
+/// Start a `qsos` row for `call`; finish with `.insert(&pool)`
+pub fn insert_qso(call: &str) -> TestQso {
+    TestQso {
+        columns: vec![
+            ("uuid", uuid::Uuid::new_v4().to_string().into()),
+            ("call", call.into()),
+            ("qso_date", "20260101".into()),
+            ("time_on", "120000".into()),
+            ("band", "20m".into()),
+            ("mode", "FT8".into()),
+        ],
+    }
+}
+
+impl TestQso {
+    /// Set a column, replacing any earlier value
+    pub fn set(mut self, column: &'static str, value: impl Into<Value>) -> Self {
+        let value = value.into();
+        match self.columns.iter_mut().find(|(name, _)| *name == column) {
+            Some(existing) => existing.1 = value,
+            None => self.columns.push((column, value)),
+        }
+        self
+    }
+
+    pub fn id(self, id: impl Into<Value>) -> Self {
+        self.set("id", id)
+    }
+
+    pub fn date(self, qso_date: &str) -> Self {
+        self.set("qso_date", qso_date)
+    }
+
+    pub fn time(self, time_on: &str) -> Self {
+        self.set("time_on", time_on)
+    }
+
+    pub fn band(self, band: &str) -> Self {
+        self.set("band", band)
+    }
+
+    pub fn mode(self, mode: &str) -> Self {
+        self.set("mode", mode)
+    }
+
+    pub fn dxcc(self, dxcc: impl Into<Value>) -> Self {
+        self.set("dxcc", dxcc)
+    }
+
+    pub fn source(self, source: &str) -> Self {
+        self.set("source", source)
+    }
+
+    /// Insert the row, returning its id
+    pub async fn insert(self, pool: &Pool<Sqlite>) -> i64 {
+        let names: Vec<&str> = self.columns.iter().map(|(name, _)| *name).collect();
+        let sql = format!(
+            "INSERT INTO qsos ({}, created_at, updated_at) VALUES ({}, datetime('now'), datetime('now'))",
+            names.join(", "),
+            vec!["?"; names.len()].join(", ")
+        );
+        let mut query = sqlx::query(&sql);
+        for (_, value) in self.columns {
+            query = match value {
+                Value::Int(v) => query.bind(v),
+                Value::Real(v) => query.bind(v),
+                Value::Text(v) => query.bind(v),
+                Value::Null => query.bind(None::<String>),
+            };
+        }
+        query
+            .execute(pool)
+            .await
+            .expect("Failed to insert test QSO")
+            .last_insert_rowid()
+    }
+}
@@ -0,0 +1,177 @@
+// eQSL.cc API Client
+// Uploads ADIF to eQSL's ImportADIF endpoint and parses the plain-text result.
+// See: https://www.eqsl.cc/qslcard/ADIFContentSpecs.cfm
+//
+// eQSL answers with an HTML page whose body is a handful of plain-text lines:
+//   Result: 2 out of 3 records added<BR>
+//   Warning: Y=2026 M=01 D=15 K5ABC 20M FT8 Bad record: Duplicate<BR>
+//   Error: No match on eQSL_User/eQSL_Pswd for date 20260115<BR>
+
+use reqwest::Client;
+
+/// eQSL ADIF upload endpoint
+const EQSL_UPLOAD_URL: &str = "https://www.eqsl.cc/qslcard/ImportADIF.cfm";
+
+/// Parsed result of an eQSL ADIF upload
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EqslUploadResponse {
+    /// Records eQSL added to the outbox
+    pub accepted: usize,
+    /// Records eQSL refused (duplicates, bad dates, etc.)
+    pub rejected: usize,
+    /// "Error:" lines (authentication or fatal problems)
+    pub errors: Vec<String>,
+    /// "Warning:" lines (per-record problems)
+    pub warnings: Vec<String>,
+    /// eQSL's "Result: N out of M records added" line was found
+    pub has_result: bool,
+}
+
+impl EqslUploadResponse {
+    /// True when eQSL reported a result and no fatal error. Anything else
+    /// (a maintenance page, an empty body) is not a confirmed upload.
+    pub fn is_success(&self) -> bool {
+        self.has_result && self.errors.is_empty()
+    }
+}
+
+/// eQSL API client for uploading QSOs
+pub struct EqslClient {
+    http: Client,
+    username: String,
+    password: String,
+}
+
+impl EqslClient {
+    /// Create a new eQSL client with credentials
+    pub fn new(username: String, password: String) -> Self {
+        Self {
+            http: Client::builder()
+                .user_agent("GoQSO/0.1.0")
+                .timeout(std::time::Duration::from_secs(60))
+                .build()
+                .expect("Failed to create HTTP client"),
+            username,
+            password,
+        }
+    }
+
+    /// Upload ADIF content to the eQSL outbox
+    pub async fn upload_adif(&self, adif_content: &str) -> Result<EqslUploadResponse, String> {
+        let params = [
+            ("EQSL_USER", self.username.as_str()),
+            ("EQSL_PSWD", self.password.as_str()),
+            ("ADIFData", adif_content),
+        ];
+
+        let response = self
+            .http
+            .post(EQSL_UPLOAD_URL)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to contact eQSL: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("eQSL upload failed with status: {}", response.status()));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read eQSL response: {}", e))?;
+
+        Ok(parse_upload_response(&body))
+    }
+}
+
+/// Parse eQSL's plain-text upload result
+pub fn parse_upload_response(body: &str) -> EqslUploadResponse {
+    let mut result = EqslUploadResponse::default();
+
+    // Lines are separated by <BR> tags inside an HTML body
+    let text = body.replace("<BR>", "\n").replace("<br>", "\n");
+
+    for line in text.lines() {
+        let line = strip_tags(line);
+        let line = line.trim();
+
+        if let Some(rest) = line.strip_prefix("Result:") {
+            // "Result: 2 out of 3 records added"
+            let numbers: Vec<usize> = rest
+                .split_whitespace()
+                .filter_map(|w| w.parse().ok())
+                .collect();
+            if let [added, total, ..] = numbers[..] {
+                result.accepted = added;
+                result.rejected = total.saturating_sub(added);
+                result.has_result = true;
+            }
+        } else if let Some(rest) = line.strip_prefix("Error:") {
+            result.errors.push(rest.trim().to_string());
+        } else if let Some(rest) = line.strip_prefix("Warning:") {
+            result.warnings.push(rest.trim().to_string());
+        }
+    }
+
+    result
+}
+
+/// Remove any HTML tags from a line
+fn strip_tags(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_tag = false;
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_success_response() {
+        let body = "<HTML><HEAD></HEAD><BODY>\r\n\
+            Result: 2 out of 3 records added<BR>\r\n\
+            Warning: Y=2026 M=01 D=15 K5ABC 20M FT8 Bad record: Duplicate<BR>\r\n\
+            </BODY></HTML>";
+        let result = parse_upload_response(body);
+
+        assert!(result.is_success());
+        assert_eq!(result.accepted, 2);
+        assert_eq!(result.rejected, 1);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("Duplicate"));
+    }
+
+    #[test]
+    fn test_parse_error_response() {
+        let body = "<HTML><BODY>\r\n\
+            Error: No match on eQSL_User/eQSL_Pswd for date 20260115<BR>\r\n\
+            Result: 0 out of 1 records added<BR>\r\n\
+            </BODY></HTML>";
+        let result = parse_upload_response(body);
+
+        assert!(!result.is_success());
+        assert_eq!(result.accepted, 0);
+        assert_eq!(result.rejected, 1);
+        assert_eq!(result.errors, vec!["No match on eQSL_User/eQSL_Pswd for date 20260115"]);
+    }
+
+    #[test]
+    fn test_parse_unrecognized_response() {
+        let maintenance = "<HTML><BODY><H1>eQSL is down for maintenance</H1></BODY></HTML>";
+        for body in [maintenance, "", "OK"] {
+            let result = parse_upload_response(body);
+            assert!(!result.is_success(), "{:?}", body);
+            assert_eq!(result.accepted, 0);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod client;  // eQSL.cc API client for uploading QSOs
+
+// Re-export commonly used types
+pub use client::EqslClient;
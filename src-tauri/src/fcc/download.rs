@@ -2,8 +2,14 @@
 //
 // Downloads the FCC ULS amateur license database (l_amat.zip)
 // and extracts it to a temporary location for parsing.
+//
+// An interrupted download leaves a truncated zip. The body length is checked
+// against Content-Length and every entry's CRC is verified on extraction; a
+// bad archive is discarded along with anything extracted from it and the
+// download is retried once.
 
-use std::path::PathBuf;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::io::{Read, Cursor};
 use reqwest::Client;
 use zip::ZipArchive;
@@ -12,6 +18,9 @@ use tokio::fs;
 /// FCC Amateur License Database URL
 const FCC_AMAT_URL: &str = "https://data.fcc.gov/download/pub/uls/complete/l_amat.zip";
 
+/// A corrupt archive gets one retry before the sync gives up
+const MAX_DOWNLOAD_ATTEMPTS: usize = 2;
+
 /// Download the FCC amateur license database
 /// 
 /// Returns the path to the extracted EN.dat file (contains license entity data)
@@ -29,6 +38,11 @@ pub async fn download_fcc_database(data_dir: &PathBuf) -> Result<PathBuf, String
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
     
+    download_with_retry(|| fetch_archive(&client), &fcc_dir).await
+}
+
+/// Download l_amat.zip, returning the body and the advertised Content-Length
+async fn fetch_archive(client: &Client) -> Result<(Vec<u8>, Option<u64>), String> {
     log::info!("Downloading FCC database (~25MB)...");
     let response = client.get(FCC_AMAT_URL)
         .send()
@@ -39,26 +53,64 @@ pub async fn download_fcc_database(data_dir: &PathBuf) -> Result<PathBuf, String
         return Err(format!("FCC download failed with status: {}", response.status()));
     }
     
+    let expected_len = response.content_length();
     let bytes = response.bytes()
         .await
         .map_err(|e| format!("Failed to read FCC database response: {}", e))?;
     
-    log::info!("Downloaded {} bytes, extracting...", bytes.len());
-    
-    // Extract synchronously using spawn_blocking since zip types aren't Send
-    let fcc_dir_clone = fcc_dir.clone();
-    let bytes_vec = bytes.to_vec();
-    
-    let extraction_result = tokio::task::spawn_blocking(move || {
-        extract_fcc_files(&bytes_vec, &fcc_dir_clone)
-    })
-    .await
-    .map_err(|e| format!("Failed to spawn blocking task: {}", e))?;
-    
-    extraction_result?;
-    
-    let en_path = fcc_dir.join("EN.dat");
-    Ok(en_path)
+    log::info!("Downloaded {} bytes (expected {:?})", bytes.len(), expected_len);
+    Ok((bytes.to_vec(), expected_len))
+}
+
+/// Fetch and extract the archive, retrying once if it turns out to be corrupt.
+/// Network errors are returned straight away.
+async fn download_with_retry<F, Fut>(mut fetch: F, fcc_dir: &Path) -> Result<PathBuf, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(Vec<u8>, Option<u64>), String>>,
+{
+    let mut last_error = String::new();
+    for attempt in 1..=MAX_DOWNLOAD_ATTEMPTS {
+        let (bytes, expected_len) = fetch().await?;
+        
+        // Extract synchronously using spawn_blocking since zip types aren't Send
+        let dir = fcc_dir.to_path_buf();
+        let extraction_result = tokio::task::spawn_blocking(move || {
+            verify_archive_size(&bytes, expected_len)?;
+            extract_fcc_files(&bytes, &dir)
+        })
+        .await
+        .map_err(|e| format!("Failed to spawn blocking task: {}", e))?;
+        
+        match extraction_result {
+            Ok(()) => return Ok(fcc_dir.join("EN.dat")),
+            Err(e) => {
+                log::warn!("FCC archive unusable (attempt {} of {}): {}", attempt, MAX_DOWNLOAD_ATTEMPTS, e);
+                remove_extracted_files(fcc_dir);
+                last_error = e;
+            }
+        }
+    }
+    Err(format!("FCC download corrupt after {} attempts: {}", MAX_DOWNLOAD_ATTEMPTS, last_error))
+}
+
+/// A body shorter (or longer) than Content-Length is an interrupted download
+fn verify_archive_size(bytes: &[u8], expected_len: Option<u64>) -> Result<(), String> {
+    match expected_len {
+        Some(expected) if expected != bytes.len() as u64 => Err(format!(
+            "FCC archive is {} bytes, expected {}",
+            bytes.len(),
+            expected
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Delete whatever a failed extraction left behind so it can't be imported
+fn remove_extracted_files(fcc_dir: &Path) {
+    for name in ["EN.dat", "HD.dat"] {
+        let _ = std::fs::remove_file(fcc_dir.join(name));
+    }
 }
 
 /// Synchronous extraction of FCC files from zip archive
@@ -110,15 +162,76 @@ fn extract_fcc_files(bytes: &[u8], fcc_dir: &PathBuf) -> Result<(), String> {
     if !found_en {
         return Err("EN.dat not found in FCC archive".to_string());
     }
+    if std::fs::metadata(&en_path).map(|m| m.len()).unwrap_or(0) == 0 {
+        return Err("EN.dat in FCC archive is empty".to_string());
+    }
     
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use std::io::Write;
+
     #[test]
     fn test_url_format() {
         assert!(super::FCC_AMAT_URL.starts_with("https://"));
         assert!(super::FCC_AMAT_URL.ends_with(".zip"));
     }
+
+    fn fcc_zip(en_dat: &str) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        writer.start_file("EN.dat", options).unwrap();
+        writer.write_all(en_dat.as_bytes()).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("goqso_fcc_{}_{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_truncated_zip_retried_once() {
+        let good = fcc_zip("EN|123|||W1AW|\n");
+        let truncated = good[..good.len() / 2].to_vec();
+        let dir = test_dir("retry");
+
+        // Interrupted download: short body, Content-Length says otherwise
+        let mut responses = vec![(good.clone(), Some(good.len() as u64)), (truncated.clone(), Some(good.len() as u64))];
+        let en_path = download_with_retry(|| std::future::ready(Ok(responses.pop().unwrap())), &dir)
+            .await
+            .unwrap();
+        assert!(responses.is_empty());
+        assert!(std::fs::read_to_string(&en_path).unwrap().contains("W1AW"));
+
+        // Truncated both times (no Content-Length): give up, leaving nothing to import
+        remove_extracted_files(&dir);
+        let mut attempts = 0;
+        let err = download_with_retry(
+            || {
+                attempts += 1;
+                std::future::ready(Ok((truncated.clone(), None)))
+            },
+            &dir,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(attempts, MAX_DOWNLOAD_ATTEMPTS);
+        assert!(err.contains("corrupt"), "{}", err);
+        assert!(!dir.join("EN.dat").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_empty_en_dat_rejected() {
+        let dir = test_dir("empty");
+        assert!(extract_fcc_files(&fcc_zip(""), &dir).unwrap_err().contains("empty"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
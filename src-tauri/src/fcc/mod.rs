@@ -16,6 +16,8 @@ use sqlx::SqlitePool;
 use serde::Serialize;
 use tauri::Manager;
 
+use crate::commands::jobs::JOB_FCC_SYNC;
+
 /// Check if FCC sync is needed and run it silently in the background
 /// Syncs if: never synced, or last sync > 7 days ago
 pub async fn sync_fcc_if_needed(app: &tauri::AppHandle) {
@@ -72,21 +74,28 @@ pub async fn sync_fcc_if_needed(app: &tauri::AppHandle) {
     drop(db_guard);
     
     log::info!("Starting background FCC database sync...");
+    let job = state.jobs.start(JOB_FCC_SYNC, "FCC database sync (automatic)").await;
     
     // Get app data directory
     let data_dir = match app.path().app_data_dir() {
         Ok(d) => d,
         Err(e) => {
             log::error!("FCC sync: failed to get app data dir: {}", e);
+            job.finish(&Err::<(), _>(e.to_string()));
             return;
         }
     };
     
     // Download the database
+    job.progress(Some(0.1), "Downloading FCC database (~25MB)...");
     let en_path = match download_fcc_database(&data_dir).await {
         Ok(path) => path,
         Err(e) => {
             log::error!("FCC sync: download failed: {}", e);
+            if let Some(pool) = state.db.lock().await.as_ref() {
+                record_sync_error(pool, &e).await;
+            }
+            job.finish(&Err::<(), _>(e));
             return;
         }
     };
@@ -97,15 +106,19 @@ pub async fn sync_fcc_if_needed(app: &tauri::AppHandle) {
         Some(p) => p,
         None => {
             log::error!("FCC sync: database disappeared during download");
+            job.finish(&Err::<(), _>("Database not initialized".to_string()));
             return;
         }
     };
     
     // Parse and import
+    job.progress(Some(0.5), "Importing FCC records into database...");
     let record_count = match parse_fcc_database(&en_path, pool).await {
         Ok(count) => count,
         Err(e) => {
             log::error!("FCC sync: import failed: {}", e);
+            record_sync_error(pool, &e).await;
+            job.finish(&Err::<(), _>(e));
             return;
         }
     };
@@ -124,6 +137,8 @@ pub async fn sync_fcc_if_needed(app: &tauri::AppHandle) {
     .await;
     
     log::info!("FCC background sync complete: {} records imported", record_count);
+    job.progress(None, format!("Imported {} FCC records", record_count));
+    job.finish(&Ok::<_, String>(record_count));
 }
 
 /// FCC sync status
@@ -136,6 +151,14 @@ pub struct FccSyncStatus {
     pub error_message: Option<String>,
 }
 
+/// End a failed sync, keeping the reason in `error_message` for the UI
+pub async fn record_sync_error(pool: &SqlitePool, error: &str) {
+    let _ = sqlx::query("UPDATE fcc_sync_status SET sync_in_progress = 0, error_message = ? WHERE id = 1")
+        .bind(error)
+        .execute(pool)
+        .await;
+}
+
 /// Get current FCC sync status
 pub async fn get_sync_status(pool: &SqlitePool) -> Result<FccSyncStatus, String> {
     let row: (Option<String>, i64, Option<String>, i64, Option<String>) = sqlx::query_as(
@@ -156,26 +179,32 @@ pub async fn get_sync_status(pool: &SqlitePool) -> Result<FccSyncStatus, String>
 }
 
 /// Lookup a callsign in the FCC database
+///
+/// `call` is the table's key; which of several records for a call survives is
+/// decided at import.
 pub async fn lookup_callsign(pool: &SqlitePool, call: &str) -> Option<FccLicenseInfo> {
     let call_upper = call.to_uppercase();
     
-    let result: Result<(String, Option<String>, Option<String>, Option<String>, Option<String>), _> = 
+    let result: Result<Option<(String, Option<String>, Option<String>, Option<String>, Option<String>)>, _> = 
         sqlx::query_as(
             "SELECT call, name, state, city, grid FROM fcc_licenses WHERE call = ?"
         )
         .bind(&call_upper)
-        .fetch_one(pool)
+        .fetch_optional(pool)
         .await;
     
     match result {
-        Ok((call, name, state, city, grid)) => Some(FccLicenseInfo {
+        Ok(row) => row.map(|(call, name, state, city, grid)| FccLicenseInfo {
             call,
             name,
             state,
             city,
             grid,
         }),
-        Err(_) => None,
+        Err(e) => {
+            log::warn!("FCC lookup of {} failed: {}", call_upper, e);
+            None
+        }
     }
 }
 
@@ -216,6 +245,55 @@ pub async fn lookup_callsigns(pool: &SqlitePool, calls: &[String]) -> Vec<FccLic
     }
 }
 
+/// Outcome of looking up the operator's own callsign
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StationLookupStatus {
+    Found,
+    NotFound,
+    /// The FCC database hasn't been imported yet
+    NotReady,
+}
+
+/// FCC details for the operator's own callsign, shaped for profile prefill
+#[derive(Debug, Serialize, Clone)]
+pub struct StationPrefill {
+    pub status: StationLookupStatus,
+    pub call: String,
+    pub name: Option<String>,
+    pub grid: Option<String>,
+    pub state: Option<String>,
+    pub city: Option<String>,
+}
+
+/// Look up the operator's callsign (portable suffixes dropped) for first-run prefill
+pub async fn lookup_station(pool: &SqlitePool, call: &str) -> StationPrefill {
+    let call = crate::reference::base_callsign(call.trim());
+    let mut prefill = StationPrefill {
+        status: StationLookupStatus::NotFound,
+        call: call.clone(),
+        name: None,
+        grid: None,
+        state: None,
+        city: None,
+    };
+
+    let ready = get_sync_status(pool).await.is_ok_and(|s| s.record_count > 0);
+    if !ready {
+        prefill.status = StationLookupStatus::NotReady;
+        return prefill;
+    }
+
+    if let Some(info) = lookup_callsign(pool, &call).await {
+        prefill.status = StationLookupStatus::Found;
+        prefill.name = info.name;
+        prefill.grid = info.grid.filter(|g| !g.is_empty() && crate::udp::wsjtx::is_valid_grid(g));
+        prefill.state = info.state;
+        prefill.city = info.city;
+    }
+    prefill
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,6 +407,34 @@ mod tests {
         assert_eq!(results.len(), 3);
     }
     
+    /// Test: Station prefill waits for the FCC import, then fills from the license
+    #[tokio::test]
+    async fn test_lookup_station_prefill() {
+        let pool = setup_test_db_with_callsigns(&[("W1AW", "CT")]).await;
+        sqlx::query("UPDATE fcc_licenses SET grid = 'FN31' WHERE call = 'W1AW'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let prefill = lookup_station(&pool, "W1AW").await;
+        assert_eq!(prefill.status, StationLookupStatus::NotReady);
+        assert_eq!(prefill.state, None);
+
+        sqlx::query("UPDATE fcc_sync_status SET record_count = 1 WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let prefill = lookup_station(&pool, "w1aw/p").await;
+        assert_eq!(prefill.status, StationLookupStatus::Found);
+        assert_eq!(prefill.call, "W1AW");
+        assert_eq!(prefill.grid.as_deref(), Some("FN31"));
+        assert_eq!(prefill.state.as_deref(), Some("CT"));
+        assert_eq!(prefill.city.as_deref(), Some("Test City"));
+
+        assert_eq!(lookup_station(&pool, "K9ZZZ").await.status, StationLookupStatus::NotFound);
+    }
+    
     /// Test: Get sync status
     #[tokio::test]
     async fn test_get_sync_status() {
@@ -36,8 +36,19 @@
 // 24: Applicant Type Code Other
 // 25: Status Code
 // 26: Status Date
+//
+// HD.dat (license header) is joined on the Unique System Identifier for
+// license status and dates. A call can appear on several licenses (an
+// expired one next to its renewal, a vanity change); only the current
+// license is imported, since `call` is the table's primary key.
+// HD.dat columns used:
+// 1:  Unique System Identifier
+// 5:  License Status (A=Active, E=Expired, C=Cancelled, T=Terminated, ...)
+// 7:  Grant Date (MM/DD/YYYY)
+// 8:  Expired Date (MM/DD/YYYY)
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::io::{BufRead, BufReader};
 use std::fs::File;
 use sqlx::SqlitePool;
@@ -45,6 +56,7 @@ use sqlx::SqlitePool;
 /// Represents an FCC amateur license record
 #[derive(Debug, Clone)]
 pub struct FccLicense {
+    pub unique_id: String,
     pub call: String,
     pub entity_type: String,
     pub entity_name: Option<String>,
@@ -54,6 +66,20 @@ pub struct FccLicense {
     pub state: Option<String>,
     pub zip: Option<String>,
     pub frn: Option<String>,
+    /// From HD.dat; None when the license has no header record
+    pub license_status: Option<String>,
+    /// YYYY-MM-DD
+    pub grant_date: Option<String>,
+    /// YYYY-MM-DD
+    pub expire_date: Option<String>,
+}
+
+/// Status and dates of one license, from HD.dat
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LicenseHeader {
+    pub status: Option<String>,
+    pub grant_date: Option<String>,
+    pub expire_date: Option<String>,
 }
 
 impl FccLicense {
@@ -66,6 +92,74 @@ impl FccLicense {
         }
         self.entity_name.clone()
     }
+
+    /// Active per HD.dat, or (without a header) not yet expired on `today` (YYYY-MM-DD)
+    pub fn is_active(&self, today: &str) -> bool {
+        match self.license_status.as_deref() {
+            Some(status) => status == "A",
+            None => self.expire_date.as_deref().is_some_and(|e| e >= today),
+        }
+    }
+
+    /// Whether this license should replace `other` for the same call: active
+    /// first, then the latest grant, then the latest expiry. On a tie the
+    /// record read later wins, as it always has.
+    pub fn supersedes(&self, other: &FccLicense, today: &str) -> bool {
+        let rank = |l: &FccLicense| (l.is_active(today), l.grant_date.clone(), l.expire_date.clone());
+        rank(self) >= rank(other)
+    }
+}
+
+/// FCC MM/DD/YYYY date as YYYY-MM-DD, None if it isn't one
+fn parse_fcc_date(date: &str) -> Option<String> {
+    chrono::NaiveDate::parse_from_str(date.trim(), "%m/%d/%Y")
+        .ok()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+/// License headers from HD.dat keyed by Unique System Identifier.
+/// A missing or unreadable file gives no headers rather than failing the import.
+pub fn read_license_headers(hd_path: &Path) -> HashMap<String, LicenseHeader> {
+    let Ok(file) = File::open(hd_path) else {
+        log::warn!("FCC HD.dat not found at {:?}; license status unknown", hd_path);
+        return HashMap::new();
+    };
+    let mut headers = HashMap::new();
+    for line in read_lines_lenient(BufReader::new(file)).map_while(Result::ok) {
+        let fields: Vec<&str> = line.split('|').collect();
+        if fields.len() < 9 || fields[0] != "HD" {
+            continue;
+        }
+        headers.insert(
+            fields[1].trim().to_string(),
+            LicenseHeader {
+                status: non_empty_string(fields.get(5)).map(|s| s.to_uppercase()),
+                grant_date: parse_fcc_date(fields[7]),
+                expire_date: parse_fcc_date(fields[8]),
+            },
+        );
+    }
+    headers
+}
+
+/// One license per call: the current one when a call has several
+pub fn dedup_licenses(records: Vec<FccLicense>, today: &str) -> Vec<FccLicense> {
+    let mut by_call: HashMap<String, usize> = HashMap::new();
+    let mut kept: Vec<FccLicense> = Vec::new();
+    for record in records {
+        match by_call.get(&record.call) {
+            Some(&i) => {
+                if record.supersedes(&kept[i], today) {
+                    kept[i] = record;
+                }
+            }
+            None => {
+                by_call.insert(record.call.clone(), kept.len());
+                kept.push(record);
+            }
+        }
+    }
+    kept
 }
 
 /// Parse EN.dat and import records into the database
@@ -77,12 +171,13 @@ pub async fn parse_fcc_database(en_path: &PathBuf, pool: &SqlitePool) -> Result<
     let file = File::open(en_path)
         .map_err(|e| format!("Failed to open EN.dat: {}", e))?;
     
+    let headers = read_license_headers(&en_path.with_file_name("HD.dat"));
     let reader = BufReader::new(file);
     let mut records: Vec<FccLicense> = Vec::new();
     let mut line_count = 0;
     let mut skipped = 0;
     
-    for line in reader.lines() {
+    for line in read_lines_lenient(reader) {
         line_count += 1;
         
         let line = match line {
@@ -140,7 +235,10 @@ pub async fn parse_fcc_database(en_path: &PathBuf, pool: &SqlitePool) -> Result<
             continue;
         }
         
+        let unique_id = fields[1].trim().to_string();
+        let header = headers.get(&unique_id).cloned().unwrap_or_default();
         records.push(FccLicense {
+            unique_id,
             call,
             entity_type,
             entity_name,
@@ -150,6 +248,9 @@ pub async fn parse_fcc_database(en_path: &PathBuf, pool: &SqlitePool) -> Result<
             state,
             zip,
             frn,
+            license_status: header.status,
+            grant_date: header.grant_date,
+            expire_date: header.expire_date,
         });
         
         // Log progress every 100k records
@@ -161,6 +262,14 @@ pub async fn parse_fcc_database(en_path: &PathBuf, pool: &SqlitePool) -> Result<
     log::info!("Parsed {} total lines, {} valid records, {} skipped", 
                line_count, records.len(), skipped);
     
+    // Several licenses for one call: keep the current one
+    let parsed = records.len();
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let records = dedup_licenses(records, &today);
+    if records.len() < parsed {
+        log::info!("Kept the current license for {} calls with several records", parsed - records.len());
+    }
+    
     // Import into database in batches
     let batch_size = 1000;
     let total = records.len();
@@ -190,28 +299,23 @@ pub async fn parse_fcc_database(en_path: &PathBuf, pool: &SqlitePool) -> Result<
 async fn import_batch(pool: &SqlitePool, records: &[FccLicense]) -> Result<(), String> {
     // Build a multi-value INSERT
     let mut query = String::from(
-        "INSERT OR REPLACE INTO fcc_licenses (call, name, city, state, zip, frn, updated_at) VALUES "
+        "INSERT OR REPLACE INTO fcc_licenses (call, name, city, state, zip, frn, grant_date, expire_date, updated_at) VALUES "
     );
     
+    let quote = |v: Option<&String>| v
+        .map(|v| format!("'{}'", v.replace('\'', "''")))
+        .unwrap_or_else(|| "NULL".to_string());
     let values: Vec<String> = records.iter().map(|r| {
-        let name = r.name()
-            .map(|n| format!("'{}'", n.replace('\'', "''")))
-            .unwrap_or_else(|| "NULL".to_string());
-        let city = r.city.as_ref()
-            .map(|c| format!("'{}'", c.replace('\'', "''")))
-            .unwrap_or_else(|| "NULL".to_string());
-        let state = r.state.as_ref()
-            .map(|s| format!("'{}'", s.replace('\'', "''")))
-            .unwrap_or_else(|| "NULL".to_string());
-        let zip = r.zip.as_ref()
-            .map(|z| format!("'{}'", z.replace('\'', "''")))
-            .unwrap_or_else(|| "NULL".to_string());
-        let frn = r.frn.as_ref()
-            .map(|f| format!("'{}'", f.replace('\'', "''")))
-            .unwrap_or_else(|| "NULL".to_string());
+        let name = quote(r.name().as_ref());
+        let city = quote(r.city.as_ref());
+        let state = quote(r.state.as_ref());
+        let zip = quote(r.zip.as_ref());
+        let frn = quote(r.frn.as_ref());
+        let grant_date = quote(r.grant_date.as_ref());
+        let expire_date = quote(r.expire_date.as_ref());
         
-        format!("('{}', {}, {}, {}, {}, {}, datetime('now'))", 
-                r.call.replace('\'', "''"), name, city, state, zip, frn)
+        format!("('{}', {}, {}, {}, {}, {}, {}, {}, datetime('now'))", 
+                r.call.replace('\'', "''"), name, city, state, zip, frn, grant_date, expire_date)
     }).collect();
     
     query.push_str(&values.join(", "));
@@ -224,6 +328,17 @@ async fn import_batch(pool: &SqlitePool, records: &[FccLicense]) -> Result<(), S
     Ok(())
 }
 
+/// Lines of EN.dat decoded as UTF-8 or latin-1, so accented names aren't
+/// dropped as invalid UTF-8 (which `BufRead::lines` would do)
+fn read_lines_lenient<R: BufRead>(reader: R) -> impl Iterator<Item = std::io::Result<String>> {
+    reader.split(b'\n').map(|line| {
+        line.map(|bytes| {
+            let bytes = bytes.strip_suffix(b"\r").unwrap_or(&bytes);
+            crate::adif::encoding::decode_text(bytes)
+        })
+    })
+}
+
 /// Helper to convert empty strings to None
 fn non_empty_string(s: Option<&&str>) -> Option<String> {
     s.and_then(|v| {
@@ -239,6 +354,7 @@ fn non_empty_string(s: Option<&&str>) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::test_support::test_pool;
     
     #[test]
     fn test_non_empty_string() {
@@ -251,6 +367,7 @@ mod tests {
     #[test]
     fn test_license_name() {
         let license = FccLicense {
+            unique_id: "1".to_string(),
             call: "W1AW".to_string(),
             entity_type: "L".to_string(),
             entity_name: None,
@@ -260,8 +377,63 @@ mod tests {
             state: Some("CT".to_string()),
             zip: Some("06111".to_string()),
             frn: Some("1234567890".to_string()),
+            license_status: None,
+            grant_date: None,
+            expire_date: None,
         };
         
         assert_eq!(license.name(), Some("John Smith".to_string()));
     }
+
+    #[test]
+    fn test_read_latin1_line() {
+        // First and last name (fields 8 and 10) written as latin-1
+        let data = b"EN|1|||W1ABC|L|||Jos\xe9||Garc\xeda|\r\nEN|2|||K1XYZ|L|\r\n";
+        let lines: Vec<String> = read_lines_lenient(&data[..])
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(lines.len(), 2);
+        let fields: Vec<&str> = lines[0].split('|').collect();
+        assert_eq!(fields[4], "W1ABC");
+        assert_eq!(fields[8], "José");
+        assert_eq!(fields[10], "García");
+        assert_eq!(lines[1], "EN|2|||K1XYZ|L|");
+    }
+
+    /// Test: Two licenses for one call (one expired) import as the active one
+    #[tokio::test]
+    async fn test_duplicate_call_keeps_active_license() {
+        let dir = std::env::temp_dir().join(format!("goqso_fcc_dup_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // The expired license comes last, where INSERT OR REPLACE used to let it win
+        std::fs::write(
+            dir.join("EN.dat"),
+            "EN|200|||W1XYZ|L|||Pat||Jones||||||Austin|TX|78701|||||\n\
+             EN|300|||K1ABC|L|||Sam||Lee||||||Hartford|CT|06101|||||\n\
+             EN|100|||W1XYZ|L|||Pat||Jones||||||Fresno|CA|93650|||||\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("HD.dat"),
+            "HD|100|||W1XYZ|E|HA|03/01/2010|03/01/2020|\n\
+             HD|200|||W1XYZ|A|HV|06/15/2021|06/15/2031|\n",
+        )
+        .unwrap();
+
+        let pool = test_pool().await;
+
+        assert_eq!(parse_fcc_database(&dir.join("EN.dat"), &pool).await.unwrap(), 2);
+        let info = crate::fcc::lookup_callsign(&pool, "W1XYZ").await.unwrap();
+        assert_eq!(info.state.as_deref(), Some("TX"));
+        let dates: (Option<String>, Option<String>) =
+            sqlx::query_as("SELECT grant_date, expire_date FROM fcc_licenses WHERE call = 'W1XYZ'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(dates, (Some("2021-06-15".to_string()), Some("2031-06-15".to_string())));
+        // No header: still imported
+        assert_eq!(crate::fcc::lookup_callsign(&pool, "K1ABC").await.unwrap().state.as_deref(), Some("CT"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
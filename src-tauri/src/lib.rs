@@ -6,6 +6,7 @@ pub mod commands;
 pub mod db;
 pub mod udp;
 pub mod lotw;
+pub mod eqsl;      // eQSL.cc upload client
 pub mod reference;  // Authoritative DXCC/prefix data (replaces cty module)
 pub mod awards;
 pub mod qso_tracker; // State machine for auto-logging FT8/FT4 QSOs
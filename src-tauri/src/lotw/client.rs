@@ -99,6 +99,7 @@ impl std::error::Error for LotwError {}
 pub struct LotwClient {
     http: Client,
     credentials: LotwCredentials,
+    report_url: String,
 }
 
 impl LotwClient {
@@ -111,9 +112,34 @@ impl LotwClient {
                 .build()
                 .expect("Failed to create HTTP client"),
             credentials: LotwCredentials { username, password },
+            report_url: LOTW_REPORT_URL.to_string(),
         }
     }
 
+    /// Send report queries to another server (a local mock in tests)
+    #[cfg(test)]
+    fn with_report_url(mut self, url: String) -> Self {
+        self.report_url = url;
+        self
+    }
+
+    /// Check the credentials without downloading the full report
+    ///
+    /// Asks for confirmations of QSOs made today and received since today,
+    /// which is at most a few records. LoTW answers bad credentials with an
+    /// HTML page instead of ADIF, reported as `AuthError`.
+    pub async fn verify_credentials(&self) -> Result<(), LotwError> {
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let options = LotwQueryOptions {
+            qso_qsl: Some("yes".to_string()),
+            qso_qslsince: Some(today.clone()),
+            qso_startdate: Some(today.clone()),
+            qso_enddate: Some(today),
+            ..Default::default()
+        };
+        self.query_report(&options).await.map(|_| ())
+    }
+
     /// Download QSL confirmations from LoTW
     /// 
     /// This fetches records where QSL_RCVD="Y" - confirmed QSOs.
@@ -213,7 +239,7 @@ impl LotwClient {
         );
         
         let response = self.http
-            .get(LOTW_REPORT_URL)
+            .get(&self.report_url)
             .query(&params)
             .send()
             .await
@@ -432,4 +458,55 @@ mod tests {
             None
         );
     }
+
+    /// Serve one HTTP request with `body`, returning the report URL and the request line
+    async fn mock_report_server(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/lotwuser/lotwreport.adi", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).lines().next().unwrap_or_default().to_string()
+        });
+        (url, server)
+    }
+
+    /// Test: ADIF back means the login worked, LoTW's HTML login page means it didn't
+    #[tokio::test]
+    async fn test_verify_credentials() {
+        let (url, server) = mock_report_server("<PROGRAMID:4>LoTW\n<APP_LoTW_NUMREC:1>0\n<EOH>\n<APP_LoTW_EOF>\n").await;
+        let client = LotwClient::new("W1AW".to_string(), "secret".to_string()).with_report_url(url);
+        assert!(client.verify_credentials().await.is_ok());
+        let request = server.await.unwrap();
+        assert!(request.contains("login=W1AW"), "{}", request);
+        assert!(request.contains("qso_qslsince="), "{}", request);
+
+        let (url, _server) =
+            mock_report_server("<html><body>Username/password incorrect. Please log in again.</body></html>").await;
+        let client = LotwClient::new("W1AW".to_string(), "wrong".to_string()).with_report_url(url);
+        assert!(matches!(client.verify_credentials().await, Err(LotwError::AuthError(_))));
+
+        // Nothing listening
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/lotwuser/lotwreport.adi", listener.local_addr().unwrap());
+        drop(listener);
+        let client = LotwClient::new("W1AW".to_string(), "secret".to_string()).with_report_url(url);
+        assert!(matches!(client.verify_credentials().await, Err(LotwError::NetworkError(_))));
+    }
 }
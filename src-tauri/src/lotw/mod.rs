@@ -5,3 +5,70 @@ pub mod adif;
 
 // Re-export commonly used types
 pub use client::{LotwClient, LotwQueryOptions};
+
+use tauri::{Emitter, Manager};
+
+use crate::commands::jobs::JOB_LOTW;
+use crate::commands::lotw::{download_lotw_confirmations, LOTW_LAST_SYNC_AT_SETTING};
+use crate::commands::settings::{read_bool_setting, read_setting};
+
+/// Settings key: download LoTW confirmations automatically on startup
+pub const LOTW_AUTO_DOWNLOAD_SETTING: &str = "lotw_auto_download";
+
+/// Download LoTW confirmations in the background if auto-download is on,
+/// credentials are saved, and the last download is old enough.
+/// Emits lotw-auto-download-started, then -complete or -failed.
+pub async fn auto_download_if_needed(app: &tauri::AppHandle) {
+    let state = app.state::<crate::commands::AppState>();
+    let db_guard = state.db.lock().await;
+    let pool = match db_guard.as_ref() {
+        Some(p) => p,
+        None => {
+            log::warn!("LoTW auto-download: database not ready");
+            return;
+        }
+    };
+
+    if !read_bool_setting(pool, LOTW_AUTO_DOWNLOAD_SETTING, false).await {
+        return;
+    }
+
+    let username = read_setting(pool, "lotw_username").await.filter(|s| !s.is_empty());
+    let password = read_setting(pool, "lotw_password").await.filter(|s| !s.is_empty());
+    let (Some(username), Some(password)) = (username, password) else {
+        log::info!("LoTW auto-download enabled but no credentials saved");
+        return;
+    };
+
+    let last_sync_at = read_setting(pool, LOTW_LAST_SYNC_AT_SETTING)
+        .await
+        .and_then(|s| chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S").ok());
+    if !sync::should_auto_download(last_sync_at, chrono::Utc::now().naive_utc()) {
+        log::debug!("LoTW auto-download: last download at {:?}, not due", last_sync_at);
+        return;
+    }
+
+    let since_date = read_setting(pool, "lotw_last_download").await;
+
+    // Release the lock before the long-running download
+    drop(db_guard);
+
+    log::info!("Starting automatic LoTW download, since_date={:?}", since_date);
+    let _ = app.emit("lotw-auto-download-started", ());
+
+    let download = download_lotw_confirmations(&state, username, password, since_date);
+    match state.jobs.run(JOB_LOTW, "LoTW download (automatic)", download).await {
+        Ok(result) => {
+            log::info!(
+                "Automatic LoTW download complete: {} matched, {} unmatched",
+                result.matched,
+                result.unmatched
+            );
+            let _ = app.emit("lotw-auto-download-complete", result);
+        }
+        Err(e) => {
+            log::error!("Automatic LoTW download failed: {}", e);
+            let _ = app.emit("lotw-auto-download-failed", e);
+        }
+    }
+}
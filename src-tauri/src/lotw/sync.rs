@@ -1 +1,104 @@
 // LoTW sync operations
+//
+// Tracks how far a confirmation download actually got so the stored
+// `lotw_last_download` only advances when every record was processed,
+// and decides when the startup auto-download is due.
+
+/// Minimum time between automatic downloads, so restarts don't hammer LoTW
+pub const AUTO_DOWNLOAD_MIN_INTERVAL_HOURS: i64 = 6;
+
+/// Whether the startup auto-download should run, given when the last
+/// download completed (UTC). Never-synced logs are always due.
+pub fn should_auto_download(last_sync_at: Option<chrono::NaiveDateTime>, now: chrono::NaiveDateTime) -> bool {
+    match last_sync_at {
+        Some(last) => now - last >= chrono::Duration::hours(AUTO_DOWNLOAD_MIN_INTERVAL_HOURS),
+        None => true,
+    }
+}
+
+/// Tracks the newest QSLRDATE processed during a LoTW download batch
+#[derive(Debug, Default)]
+pub struct SinceTracker {
+    max_qslrdate: Option<String>,
+    failed: bool,
+}
+
+impl SinceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a successfully processed record (matched or unmatched)
+    pub fn record_processed(&mut self, qslrdate: Option<&str>) {
+        let Some(date) = qslrdate.map(str::trim).filter(|d| d.len() == 8 && d.chars().all(|c| c.is_ascii_digit())) else {
+            return;
+        };
+        // YYYYMMDD compares correctly as a string
+        if self.max_qslrdate.as_deref().is_none_or(|max| date > max) {
+            self.max_qslrdate = Some(date.to_string());
+        }
+    }
+
+    /// Record a record that failed to process
+    pub fn record_failure(&mut self) {
+        self.failed = true;
+    }
+
+    /// Value to store as `lotw_last_download` (LoTW's YYYY-MM-DD format).
+    /// Returns None on partial failure or when nothing was processed, in
+    /// which case the prior value must be kept so the next sync re-fetches.
+    pub fn next_since_date(&self) -> Option<String> {
+        if self.failed {
+            return None;
+        }
+        self.max_qslrdate
+            .as_ref()
+            .map(|d| format!("{}-{}-{}", &d[0..4], &d[4..6], &d[6..8]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advances_to_max_qslrdate() {
+        let mut tracker = SinceTracker::new();
+        tracker.record_processed(Some("20260110"));
+        tracker.record_processed(Some("20260115"));
+        tracker.record_processed(Some("20260112"));
+        tracker.record_processed(None);
+        assert_eq!(tracker.next_since_date(), Some("2026-01-15".to_string()));
+    }
+
+    #[test]
+    fn test_mid_batch_error_keeps_prior_value() {
+        let mut tracker = SinceTracker::new();
+        tracker.record_processed(Some("20260110"));
+        tracker.record_failure();
+        tracker.record_processed(Some("20260115"));
+        assert_eq!(tracker.next_since_date(), None);
+    }
+
+    #[test]
+    fn test_empty_batch_keeps_prior_value() {
+        let mut tracker = SinceTracker::new();
+        tracker.record_processed(Some("bogus"));
+        assert_eq!(tracker.next_since_date(), None);
+    }
+
+    fn at(s: &str) -> chrono::NaiveDateTime {
+        chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_auto_download_respects_min_interval() {
+        let now = at("2026-03-01 12:00:00");
+        assert!(should_auto_download(None, now));
+        assert!(!should_auto_download(Some(at("2026-03-01 10:00:00")), now));
+        assert!(should_auto_download(Some(at("2026-03-01 06:00:00")), now));
+        assert!(should_auto_download(Some(at("2026-02-20 12:00:00")), now));
+        // Clock moved backwards: treat as recent rather than re-downloading
+        assert!(!should_auto_download(Some(at("2026-03-02 12:00:00")), now));
+    }
+}
@@ -5,6 +5,7 @@ mod adif;
 mod awards;
 mod commands;
 mod db;
+mod eqsl;
 mod fcc;
 mod lotw;
 mod qso_tracker;
@@ -15,7 +16,10 @@ use std::sync::Arc;
 use tauri::{Manager, Emitter};
 use tokio::sync::Mutex;
 use commands::AppState;
-use udp::UdpListenerState;
+use commands::jobs::JobQueue;
+use commands::worked_cache::WorkedCache;
+use qso_tracker::QsoTrackers;
+use udp::UdpInstances;
 
 fn main() {
     // Initialize logging - default to info level for our crate
@@ -30,7 +34,11 @@ fn main() {
         .plugin(tauri_plugin_fs::init())
         .manage(AppState {
             db: Arc::new(Mutex::new(None)),
-            udp_state: Arc::new(UdpListenerState::new()),
+            udp_state: Arc::new(UdpInstances::new()),
+            udp_task: std::sync::Mutex::new(None),
+            trackers: Arc::new(QsoTrackers::new()),
+            worked: Arc::new(WorkedCache::new()),
+            jobs: Arc::new(JobQueue::new()),
         })
         .setup(|app| {
             let app_handle = app.handle().clone();
@@ -50,6 +58,13 @@ fn main() {
                         
                         // Store pool in app state
                         let state = app_handle.state::<AppState>();
+                        state.worked.rebuild_or_log(&pool).await;
+                        if let Err(e) = commands::callsign_exceptions::load_callsign_exceptions(&pool).await {
+                            log::warn!("{}", e);
+                        }
+                        if let Err(e) = commands::dxpeditions::load_dxpeditions(&pool).await {
+                            log::warn!("{}", e);
+                        }
                         let mut db_guard = state.db.lock().await;
                         *db_guard = Some(pool);
                         drop(db_guard);
@@ -65,6 +80,12 @@ fn main() {
                         tauri::async_runtime::spawn(async move {
                             fcc::sync_fcc_if_needed(&app_handle_fcc).await;
                         });
+
+                        // Download new LoTW confirmations if enabled
+                        let app_handle_lotw = app_handle.clone();
+                        tauri::async_runtime::spawn(async move {
+                            lotw::auto_download_if_needed(&app_handle_lotw).await;
+                        });
                     }
                     Err(e) => {
                         log::error!("Failed to initialize database: {}", e);
@@ -88,34 +109,88 @@ fn main() {
             commands::udp::start_udp_listener,
             commands::udp::stop_udp_listener,
             commands::udp::call_station,
+            commands::udp::log_from_decode,
             commands::udp::get_udp_status,
+            commands::udp::get_pending_review,
+            commands::udp::confirm_pending,
+            commands::udp::discard_pending,
             // QSO Operations
             commands::qso::get_qsos,
             commands::qso::add_qso,
+            commands::qso::get_qso_defaults,
+            commands::qso::set_qso_defaults,
             commands::qso::update_qso,
+            commands::qso::correct_callsign,
+            commands::qso::relabel_source,
             commands::qso::delete_qso,
             commands::qso::remove_duplicate_qsos,
             commands::qso::clear_all_qsos,
             commands::qso::add_test_qsos,
             // ADIF Import/Export
             commands::adif::import_adif,
+            commands::adif::import_adif_multi,
+            commands::adif::import_cabrillo,
             commands::adif::export_adif,
+            commands::adif::export_adif_with_manifest,
+            commands::adif::export_adif_for_lotw,
             // Callsign History & Status
             commands::qso::get_callsign_history,
             commands::qso::check_qso_status,
+            commands::qso::get_qso_path,
+            commands::qso::get_qsos_by_distance,
+            commands::qso::get_p2p_qsos,
+            commands::qso::get_s2s_qsos,
+            commands::qso::get_swl_reports,
             // QSO Data Repair
             commands::qso::repair_qso_data,
+            // Log Validation
+            commands::validation::validate_log,
+            commands::validation::find_suspect_callsigns,
             // LoTW Integration
             commands::adif::import_lotw_confirmations,
             commands::lotw::get_sync_status,
             commands::lotw::sync_lotw_download,
+            commands::lotw::resync_lotw_range,
+            commands::lotw::verify_lotw_credentials,
+            commands::lotw::import_lotw_file,
             commands::lotw::detect_tqsl_path,
             commands::lotw::upload_to_lotw,
+            // eQSL Integration
+            commands::eqsl::upload_to_eqsl,
+            // Paper QSL Cards
+            commands::qsl_card::mark_card_sent,
+            commands::qsl_card::mark_card_confirmed,
+            commands::qsl_card::get_card_qsl_queue,
             // Awards Progress
             commands::awards::get_dxcc_progress,
+            commands::awards::get_dxcc_by_mode_group,
             commands::awards::get_was_progress,
+            commands::awards::get_wwff_progress,
+            commands::awards::get_vucc_progress,
+            commands::awards::get_all_award_progress,
+            commands::awards::take_award_snapshot,
+            commands::awards::get_award_history,
+            commands::awards::get_next_milestones,
+            commands::awards::get_entity_band_mode_matrix,
+            commands::awards::get_grid_map_data,
+            commands::awards::get_subdivision_progress,
+            commands::awards::get_dxcc_application,
+            commands::awards::get_was_application,
+            commands::awards::export_adif_for_award,
+            commands::awards::get_granted_credits,
+            commands::awards::get_confirmation_reconciliation,
+            commands::awards::get_last_contact_per_entity,
+            commands::awards::get_last_contact_for_entity,
             // CTY Lookup
             commands::settings::lookup_callsign,
+            commands::callsign_exceptions::get_callsign_exceptions,
+            commands::callsign_exceptions::add_callsign_exception,
+            commands::callsign_exceptions::remove_callsign_exception,
+            commands::callsign_exceptions::get_prefix_discrepancies,
+            commands::dxpeditions::get_dxpeditions,
+            commands::dxpeditions::add_dxpedition,
+            commands::dxpeditions::remove_dxpedition,
+            commands::webhook::test_webhook,
             // Settings
             commands::settings::get_setting,
             commands::settings::set_setting,
@@ -125,14 +200,32 @@ fn main() {
             // Band Activity
             commands::band_activity::get_recent_activity,
             commands::band_activity::prune_band_activity,
+            commands::band_activity::export_band_activity,
+            commands::band_activity::get_band_activity_stats,
+            commands::band_activity::get_recent_entities,
+            // Statistics
+            commands::stats::get_rate_stats,
+            commands::stats::get_yearly_stats,
+            commands::stats::get_monthly_stats,
+            commands::stats::get_entity_rarity,
+            commands::stats::get_activity_by_hour,
             // FCC Database
             commands::fcc::get_fcc_sync_status,
+            commands::jobs::get_jobs,
             commands::fcc::sync_fcc_database,
             commands::fcc::lookup_fcc_callsign,
             commands::fcc::lookup_fcc_callsigns,
+            commands::fcc::lookup_my_station,
             // Diagnostics
             commands::diagnostics::get_qso_diagnostics,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running GoQSO");
+        .build(tauri::generate_context!())
+        .expect("error while building GoQSO")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Log QSOs still in progress before the runtime goes away
+                let state = app_handle.state::<AppState>();
+                tauri::async_runtime::block_on(commands::udp::flush_on_shutdown(&state));
+            }
+        });
 }
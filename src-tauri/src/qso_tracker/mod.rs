@@ -2,8 +2,10 @@
 // QSO Tracker - State Machine for Auto-Logging FT8/FT4 QSOs
 // =============================================================================
 //
-// NOTE: This module is intentionally not used yet - planned for future auto-logging
-// improvements where we track QSO state machine to log only complete exchanges.
+// NOTE: The UDP listener feeds decodes/status into the tracker so logged QSOs can
+// be checked against the observed exchange (e.g. low-confidence gating). The
+// Status `transmitting` flag marks real TX end, which stamps `time_off` once our
+// final RR73/RRR/73 has gone out. Full auto-logging from the state machine is planned but not enabled yet.
 //
 #![allow(dead_code)]
 
@@ -66,6 +68,7 @@ mod state;
 mod tracker;
 
 pub use state::{QsoPhase, QsoRole};
+pub use tracker::QsoTrackers;
 
 /// A message we observed (either transmitted or received)
 #[derive(Debug, Clone)]
@@ -80,6 +83,8 @@ pub struct ObservedMessage {
     pub snr: Option<i32>,
     /// Frequency offset (Hz)
     pub freq_offset: Option<u32>,
+    /// WSJT-X flagged this decode as low confidence (a priori / deep search)
+    pub low_confidence: bool,
 }
 
 /// Represents a QSO in progress
@@ -111,6 +116,8 @@ pub struct QsoInProgress {
     pub freq_hz: u64,
     /// Mode (FT8, FT4, etc.)
     pub mode: String,
+    /// UTC time our final transmission (RR73/RRR/73) ended, from WSJT-X Status
+    pub time_off: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl QsoInProgress {
@@ -134,6 +141,40 @@ impl QsoInProgress {
         // Must have reached at least the Confirmed phase
         matches!(self.phase, QsoPhase::Confirmed | QsoPhase::Complete)
     }
+    
+    /// Fraction of received messages that were low-confidence decodes (0.0 if none received)
+    pub fn low_confidence_ratio(&self) -> f64 {
+        let rx: Vec<&ObservedMessage> = self.messages.iter().filter(|m| !m.is_tx).collect();
+        if rx.is_empty() {
+            return 0.0;
+        }
+        rx.iter().filter(|m| m.low_confidence).count() as f64 / rx.len() as f64
+    }
+    
+    /// True when most of what we heard from them came from low-confidence decodes
+    pub fn is_mostly_low_confidence(&self) -> bool {
+        self.low_confidence_ratio() > 0.5
+    }
+}
+
+/// Decide whether a QSO logged by WSJT-X should be auto-logged.
+///
+/// With `reject_low_confidence` on, QSOs whose tracked exchange was built
+/// mostly from low-confidence decodes are refused. Untracked QSOs are allowed.
+pub fn should_autolog(reject_low_confidence: bool, tracked: Option<&QsoInProgress>) -> bool {
+    if !reject_low_confidence {
+        return true;
+    }
+    !tracked.is_some_and(|qso| qso.is_mostly_low_confidence())
+}
+
+/// Decide whether a QSO logged by WSJT-X should be held for review.
+///
+/// With `require_complete` on, QSOs whose tracked exchange never reached a
+/// loggable state (e.g. Log clicked before RRR/RR73) are held. Untracked QSOs
+/// are not held, matching `should_autolog`.
+pub fn needs_review(require_complete: bool, tracked: Option<&QsoInProgress>) -> bool {
+    require_complete && tracked.is_some_and(|qso| !qso.is_valid_for_logging())
 }
 
 /// Result of processing a message
@@ -145,6 +186,8 @@ pub enum QsoEvent {
     Progressed { their_call: String, phase: QsoPhase },
     /// QSO is complete and ready to log
     Complete(QsoInProgress),
+    /// Our final transmission ended; time_off is stamped
+    Finished(QsoInProgress),
     /// QSO was abandoned (timed out or they QSYed)
     Abandoned { their_call: String },
     /// No change
@@ -154,9 +197,80 @@ pub enum QsoEvent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::tracker::QsoTracker;
     
     #[test]
     fn test_basic_qso_flow() {
         // TODO: Add tests for QSO state machine
     }
+    
+    fn tracked_qso(low_confidence: &[bool]) -> QsoInProgress {
+        let mut tracker = QsoTracker::new();
+        tracker.update_status("K1ABC", "FN42", 14074000, "FT8", "");
+        let messages = ["K1ABC G0XYZ IO91", "K1ABC G0XYZ R-22", "K1ABC G0XYZ RR73"];
+        for (msg, low) in messages.iter().zip(low_confidence) {
+            tracker.process_rx_decode(msg, -20, 1500, *low);
+        }
+        tracker.get_qso("G0XYZ").unwrap().clone()
+    }
+    
+    #[test]
+    fn test_low_confidence_ratio() {
+        assert_eq!(tracked_qso(&[false, false, false]).low_confidence_ratio(), 0.0);
+        assert!(tracked_qso(&[true, true, false]).is_mostly_low_confidence());
+        assert!(!tracked_qso(&[true, false, false]).is_mostly_low_confidence());
+    }
+    
+    #[test]
+    fn test_should_autolog_setting_off() {
+        let qso = tracked_qso(&[true, true, true]);
+        assert!(should_autolog(false, Some(&qso)));
+        assert!(should_autolog(false, None));
+    }
+    
+    #[test]
+    fn test_should_autolog_setting_on() {
+        let low = tracked_qso(&[true, true, false]);
+        let good = tracked_qso(&[false, true, false]);
+        assert!(!should_autolog(true, Some(&low)));
+        assert!(should_autolog(true, Some(&good)));
+        // Nothing tracked (e.g. my call unknown) - don't block
+        assert!(should_autolog(true, None));
+    }
+
+    /// We call CQ as K1ABC and G0XYZ answers; `steps` are played in order
+    fn exchange(steps: &[(bool, &str)]) -> QsoInProgress {
+        let mut tracker = QsoTracker::new();
+        tracker.update_status("K1ABC", "FN42", 14074000, "FT8", "");
+        for (is_tx, msg) in steps {
+            if *is_tx {
+                tracker.process_tx(msg, None);
+            } else {
+                tracker.process_rx_decode(msg, -15, 1500, false);
+            }
+        }
+        tracker.get_qso("G0XYZ").unwrap().clone()
+    }
+
+    #[test]
+    fn test_needs_review_complete_exchange() {
+        let qso = exchange(&[
+            (false, "K1ABC G0XYZ IO91"),
+            (true, "G0XYZ K1ABC -10"),
+            (false, "K1ABC G0XYZ R-15"),
+            (true, "G0XYZ K1ABC RR73"),
+        ]);
+        assert!(qso.is_valid_for_logging());
+        assert!(!needs_review(true, Some(&qso)));
+    }
+
+    #[test]
+    fn test_needs_review_incomplete_exchange() {
+        // Log clicked after sending the report, before their roger
+        let qso = exchange(&[(false, "K1ABC G0XYZ IO91"), (true, "G0XYZ K1ABC -10")]);
+        assert!(needs_review(true, Some(&qso)));
+        // Setting off, or nothing tracked: never held
+        assert!(!needs_review(false, Some(&qso)));
+        assert!(!needs_review(true, None));
+    }
 }
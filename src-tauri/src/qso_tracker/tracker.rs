@@ -26,6 +26,10 @@ pub struct QsoTracker {
     current_mode: String,
     /// Currently selected DX call in WSJT-X
     current_dx_call: String,
+    /// Transmitting flag from the last WSJT-X Status
+    transmitting: bool,
+    /// Callsign our most recent transmission was addressed to
+    last_tx_call: Option<String>,
 }
 
 impl Default for QsoTracker {
@@ -43,6 +47,8 @@ impl QsoTracker {
             current_freq: 0,
             current_mode: String::new(),
             current_dx_call: String::new(),
+            transmitting: false,
+            last_tx_call: None,
         }
     }
     
@@ -96,6 +102,7 @@ impl QsoTracker {
             if sender != self.my_call {
                 return QsoEvent::None;
             }
+            self.last_tx_call = Some(their_call.clone());
             
             // Get or create QSO entry
             let qso = self.qsos.entry(their_call.clone()).or_insert_with(|| {
@@ -114,6 +121,7 @@ impl QsoTracker {
                     completed: false,
                     freq_hz: self.current_freq,
                     mode: self.current_mode.clone(),
+                    time_off: None,
                 }
             });
             
@@ -124,6 +132,7 @@ impl QsoTracker {
                 is_tx: true,
                 snr,
                 freq_offset: None,
+                low_confidence: false,
             });
             qso.last_activity = Instant::now();
             
@@ -159,8 +168,58 @@ impl QsoTracker {
         QsoEvent::None
     }
     
+    /// Consume the WSJT-X Status `transmitting` flag.
+    ///
+    /// When transmitting goes false after our final message (RR73/RRR/73) in a
+    /// loggable QSO, the real TX end is stamped as `time_off` and the QSO is
+    /// finalized.
+    pub fn update_transmitting(&mut self, transmitting: bool) -> QsoEvent {
+        let was_transmitting = self.transmitting;
+        self.transmitting = transmitting;
+        if !was_transmitting || transmitting {
+            return QsoEvent::None;
+        }
+
+        let Some(call) = self.last_tx_call.clone() else {
+            return QsoEvent::None;
+        };
+        let Some(qso) = self.qsos.get_mut(&call) else {
+            return QsoEvent::None;
+        };
+
+        // TX ended - that's real activity on this QSO
+        qso.last_activity = Instant::now();
+
+        let sent_final = qso
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.is_tx)
+            .is_some_and(|m| is_final_message(&m.message));
+        if !sent_final || !qso.phase.is_loggable() || qso.time_off.is_some() {
+            return QsoEvent::None;
+        }
+
+        qso.time_off = Some(chrono::Utc::now());
+        qso.phase = QsoPhase::Complete;
+        qso.completed = true;
+        log::info!("QSO with {} finished (TX ended after final message)", call);
+        QsoEvent::Finished(qso.clone())
+    }
+    
     /// Process a received (decoded) message from the waterfall
     pub fn process_rx(&mut self, message: &str, snr: i32, freq_offset: u32) -> QsoEvent {
+        self.process_rx_decode(message, snr, freq_offset, false)
+    }
+    
+    /// Process a received decode, recording WSJT-X's low-confidence flag
+    pub fn process_rx_decode(
+        &mut self,
+        message: &str,
+        snr: i32,
+        freq_offset: u32,
+        low_confidence: bool,
+    ) -> QsoEvent {
         let message = message.trim().to_uppercase();
         log::trace!("QsoTracker RX: {} (SNR: {})", message, snr);
         
@@ -203,6 +262,7 @@ impl QsoTracker {
                     completed: false,
                     freq_hz: self.current_freq,
                     mode: self.current_mode.clone(),
+                    time_off: None,
                 }
             });
             
@@ -213,6 +273,7 @@ impl QsoTracker {
                 is_tx: false,
                 snr: Some(snr),
                 freq_offset: Some(freq_offset),
+                low_confidence,
             });
             qso.last_activity = Instant::now();
             
@@ -278,6 +339,12 @@ impl QsoTracker {
         self.qsos.get(&call.to_uppercase())
     }
     
+    /// Remove a QSO WSJT-X has reported as logged, so the exit flush
+    /// doesn't log it a second time
+    pub fn take_qso(&mut self, call: &str) -> Option<QsoInProgress> {
+        self.qsos.remove(&call.to_uppercase())
+    }
+    
     /// Get all QSOs currently in progress
     pub fn active_qsos(&self) -> Vec<&QsoInProgress> {
         self.qsos.values().filter(|q| !q.completed).collect()
@@ -287,6 +354,41 @@ impl QsoTracker {
     pub fn active_count(&self) -> usize {
         self.qsos.values().filter(|q| !q.completed).count()
     }
+    
+    /// QSOs that meet the minimum requirements for logging
+    pub fn loggable_qsos(&self) -> Vec<&QsoInProgress> {
+        self.qsos.values().filter(|q| q.is_valid_for_logging()).collect()
+    }
+}
+
+/// One tracker per WSJT-X instance (keyed by UDP port), shared with the app
+/// so QSOs still in progress can be flushed when it exits
+#[derive(Debug, Default)]
+pub struct QsoTrackers {
+    trackers: std::sync::Mutex<HashMap<u16, QsoTracker>>,
+}
+
+impl QsoTrackers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Run `f` on the tracker for `instance`, creating it if needed
+    pub fn with<R>(&self, instance: u16, f: impl FnOnce(&mut QsoTracker) -> R) -> R {
+        let mut trackers = self.trackers.lock().unwrap();
+        f(trackers.entry(instance).or_default())
+    }
+    
+    /// Drop every tracker (a new listener session starts fresh)
+    pub fn clear(&self) {
+        self.trackers.lock().unwrap().clear();
+    }
+    
+    /// Copies of the loggable QSOs across all instances
+    pub fn loggable_qsos(&self) -> Vec<QsoInProgress> {
+        let trackers = self.trackers.lock().unwrap();
+        trackers.values().flat_map(|t| t.loggable_qsos()).cloned().collect()
+    }
 }
 
 /// Build a QsoState by replaying a slice of messages
@@ -306,6 +408,11 @@ fn build_state_from_messages(messages: &[ObservedMessage], role: QsoRole) -> Qso
     state
 }
 
+/// True if the message closes the exchange (ends with RR73, RRR or 73)
+fn is_final_message(message: &str) -> bool {
+    matches!(message.split_whitespace().last(), Some("RR73" | "RRR" | "73"))
+}
+
 /// Clean angle brackets and other decorations from callsigns
 fn clean_callsign(s: &str) -> String {
     let s = s.trim();
@@ -377,4 +484,37 @@ mod tests {
         assert!(!qso.completed);
         assert!(!qso.phase.is_loggable());
     }
+    
+    #[test]
+    fn test_tx_end_after_final_message_stamps_time_off() {
+        let mut tracker = setup_tracker();
+        tracker.process_rx("K1ABC G0XYZ IO91", -15, 1500);
+        tracker.process_tx("G0XYZ K1ABC -19", None);
+        tracker.process_rx("K1ABC G0XYZ R-22", -14, 1500);
+        
+        // Status: we start transmitting RR73, then TX ends
+        assert!(matches!(tracker.update_transmitting(true), QsoEvent::None));
+        tracker.process_tx("G0XYZ K1ABC RR73", None);
+        let event = tracker.update_transmitting(false);
+        let QsoEvent::Finished(qso) = event else {
+            panic!("expected Finished, got {:?}", event);
+        };
+        assert_eq!(qso.their_call, "G0XYZ");
+        assert!(qso.time_off.is_some());
+        assert_eq!(qso.phase, QsoPhase::Complete);
+        
+        // Repeated Status with transmitting=false doesn't finalize again
+        assert!(matches!(tracker.update_transmitting(false), QsoEvent::None));
+    }
+    
+    #[test]
+    fn test_tx_end_mid_exchange_does_not_finalize() {
+        let mut tracker = setup_tracker();
+        tracker.process_rx("K1ABC G0XYZ IO91", -15, 1500);
+        
+        tracker.update_transmitting(true);
+        tracker.process_tx("G0XYZ K1ABC -19", None);
+        assert!(matches!(tracker.update_transmitting(false), QsoEvent::None));
+        assert!(tracker.get_qso("G0XYZ").unwrap().time_off.is_none());
+    }
 }
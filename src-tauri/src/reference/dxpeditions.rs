@@ -0,0 +1,86 @@
+// DXpedition and special-event callsign patterns
+//
+// Chasers want to know when a decode is a DXpedition (or a special prefix,
+// or a /MM on a rare entity) so they can prioritize it. DXpeditions are
+// time-limited, so the list is user-maintained in the `dxpeditions` table
+// and loaded into memory at startup; each pattern can carry an end date.
+//
+// Patterns are callsigns with `*` matching any run of characters:
+// "3Y0K" (exact), "TX7*" (any TX7 call), "*/MM".
+
+use std::sync::{OnceLock, RwLock};
+
+/// One pattern, active through `end_date` (YYYYMMDD) if set
+#[derive(Debug, Clone, PartialEq)]
+pub struct DxpeditionPattern {
+    pub pattern: String,
+    pub end_date: Option<String>,
+}
+
+static DXPEDITIONS: OnceLock<RwLock<Vec<DxpeditionPattern>>> = OnceLock::new();
+
+fn dxpeditions() -> &'static RwLock<Vec<DxpeditionPattern>> {
+    DXPEDITIONS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Replace the loaded patterns
+pub fn set_dxpeditions(patterns: Vec<DxpeditionPattern>) {
+    let normalized = patterns
+        .into_iter()
+        .map(|p| DxpeditionPattern {
+            pattern: p.pattern.trim().to_uppercase(),
+            end_date: p.end_date.filter(|d| !d.trim().is_empty()),
+        })
+        .collect();
+    if let Ok(mut list) = dxpeditions().write() {
+        *list = normalized;
+    }
+}
+
+/// Case-insensitive match with `*` as a wildcard
+pub fn matches_pattern(pattern: &str, call: &str) -> bool {
+    let pattern = pattern.to_uppercase();
+    let call = call.trim().to_uppercase();
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == call;
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !call.starts_with(first) || call.len() < first.len() + last.len() || !call.ends_with(last) {
+        return false;
+    }
+    let mut rest = &call[first.len()..call.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// True if `call` matches a pattern still active on `date` (YYYYMMDD)
+pub fn is_dxpedition(call: &str, date: &str) -> bool {
+    dxpeditions().read().is_ok_and(|list| {
+        list.iter().any(|p| {
+            p.end_date.as_deref().is_none_or(|end| date <= end) && matches_pattern(&p.pattern, call)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matching() {
+        assert!(matches_pattern("3Y0K", "3y0k"));
+        assert!(!matches_pattern("3Y0K", "3Y0KA"));
+        assert!(matches_pattern("TX7*", "TX7G"));
+        assert!(matches_pattern("*/MM", "K1ABC/MM"));
+        assert!(matches_pattern("VP8*/P", "VP8ABC/P"));
+        assert!(!matches_pattern("*/MM", "K1ABC/M"));
+        assert!(!matches_pattern("TX7*", "TX5S"));
+    }
+}
@@ -0,0 +1,62 @@
+// Exact-callsign DXCC exceptions
+//
+// Some callsigns don't follow their prefix rules: DXpeditions on shared
+// prefixes (3Y covers Bouvet and Peter 1, TX covers several French Pacific
+// entities) and special event calls. These are matched on the full callsign
+// before any prefix rule.
+//
+// The built-in list is extended by user entries from the `callsign_exceptions`
+// table, loaded into memory at startup; user entries win over built-ins.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Built-in exceptions: (callsign, ARRL entity ID)
+pub const EXACT_CALL_EXCEPTIONS: &[(&str, &str)] = &[
+    ("3Y0J", "024"),   // Bouvet 2023 (3Y prefix defaults to Peter 1)
+    ("TX5K", "036"),   // Clipperton 2013 (TX prefix defaults to Chesterfield)
+    ("VK0EK", "111"),  // Heard I. 2016 (no VK0H in the call)
+    ("K1N", "182"),    // Navassa I. 2015
+    ("VP8STI", "240"), // South Sandwich Is. 2016 (VP8S is South Shetland)
+    ("VP8SGI", "235"), // South Georgia I. 2016
+];
+
+static USER_EXCEPTIONS: OnceLock<RwLock<HashMap<String, String>>> = OnceLock::new();
+
+fn user_exceptions() -> &'static RwLock<HashMap<String, String>> {
+    USER_EXCEPTIONS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Replace the user-defined exceptions (callsign -> ARRL entity ID)
+pub fn set_user_exceptions(exceptions: HashMap<String, String>) {
+    let normalized = exceptions
+        .into_iter()
+        .map(|(call, entity_id)| (call.trim().to_uppercase(), entity_id))
+        .collect();
+    if let Ok(mut map) = user_exceptions().write() {
+        *map = normalized;
+    }
+}
+
+/// Entity ID for an exact callsign match, user entries first
+pub fn exception_for(call: &str) -> Option<String> {
+    let call = call.trim().to_uppercase();
+    if let Some(entity_id) = user_exceptions().read().ok().and_then(|m| m.get(&call).cloned()) {
+        return Some(entity_id);
+    }
+    EXACT_CALL_EXCEPTIONS
+        .iter()
+        .find(|(c, _)| *c == call)
+        .map(|(_, entity_id)| entity_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_exception() {
+        assert_eq!(exception_for("3y0j").as_deref(), Some("024"));
+        assert_eq!(exception_for("3Y0JA"), None);
+    }
+}
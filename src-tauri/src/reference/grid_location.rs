@@ -68,13 +68,13 @@ impl GridLocation {
     }
 }
 
-/// Convert a 4 or 6 character grid square to lat/lon coordinates
+/// Convert a 4, 6 or 8 character grid square to lat/lon coordinates
 /// Returns (latitude, longitude) of the grid square center
 pub fn grid_to_latlon(grid: &str) -> Option<(f64, f64)> {
     let grid = grid.to_uppercase();
     let len = grid.len();
     
-    if len < 4 || len % 2 != 0 {
+    if !matches!(len, 4 | 6 | 8) {
         return None;
     }
     
@@ -94,24 +94,93 @@ pub fn grid_to_latlon(grid: &str) -> Option<(f64, f64)> {
     // Maidenhead origin is at -180°, -90°
     // Each field is 20° longitude x 10° latitude
     // Each square is 2° longitude x 1° latitude
-    let mut lon = -180.0 + lon_field * 20.0 + lon_square * 2.0 + 1.0; // +1 for center
-    let mut lat = -90.0 + lat_field * 10.0 + lat_square * 1.0 + 0.5;  // +0.5 for center
+    // (lon, lat) is the south-west corner of the cell; its size shrinks with each pair
+    let mut lon = -180.0 + lon_field * 20.0 + lon_square * 2.0;
+    let mut lat = -90.0 + lat_field * 10.0 + lat_square * 1.0;
+    let (mut lon_size, mut lat_size) = (2.0, 1.0);
     
     // 6-character grid adds subsquare precision
     if len >= 6 {
         if bytes[4] < b'A' || bytes[4] > b'X' { return None; }
         if bytes[5] < b'A' || bytes[5] > b'X' { return None; }
         
-        let lon_subsq = (bytes[4].to_ascii_lowercase() - b'a') as f64;
-        let lat_subsq = (bytes[5].to_ascii_lowercase() - b'a') as f64;
-        
         // Each subsquare is 5' longitude x 2.5' latitude
         // = 1/12 degree x 1/24 degree
-        lon = -180.0 + lon_field * 20.0 + lon_square * 2.0 + lon_subsq * (2.0/24.0) + (1.0/24.0);
-        lat = -90.0 + lat_field * 10.0 + lat_square * 1.0 + lat_subsq * (1.0/24.0) + (0.5/24.0);
+        lon_size /= 24.0;
+        lat_size /= 24.0;
+        lon += (bytes[4] - b'A') as f64 * lon_size;
+        lat += (bytes[5] - b'A') as f64 * lat_size;
+    }
+    
+    // 8-character grid adds the extended square (a tenth of a subsquare each way)
+    if len == 8 {
+        if !bytes[6].is_ascii_digit() || !bytes[7].is_ascii_digit() { return None; }
+        
+        lon_size /= 10.0;
+        lat_size /= 10.0;
+        lon += (bytes[6] - b'0') as f64 * lon_size;
+        lat += (bytes[7] - b'0') as f64 * lat_size;
     }
     
-    Some((lat, lon))
+    Some((lat + lat_size / 2.0, lon + lon_size / 2.0))
+}
+
+/// Earth's equatorial circumference; the long path is this minus the short path
+pub const EARTH_CIRCUMFERENCE_KM: f64 = 40075.0;
+
+/// Great-circle distance in km between two (lat, lon) points
+pub fn distance_km(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let radius = EARTH_CIRCUMFERENCE_KM / (2.0 * std::f64::consts::PI);
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+    let a = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * radius * a.sqrt().min(1.0).asin()
+}
+
+/// Initial short-path bearing in degrees (0-360, 0 = north) from one (lat, lon) point to another
+pub fn bearing_deg(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lat2) = (from.0.to_radians(), to.0.to_radians());
+    let dlon = (to.1 - from.1).to_radians();
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Long-path bearing: the short-path heading turned around
+pub fn long_path_bearing(from: (f64, f64), to: (f64, f64)) -> f64 {
+    (bearing_deg(from, to) + 180.0).rem_euclid(360.0)
+}
+
+/// Short-path distance in km between the centers of two grid squares
+pub fn grid_distance_km(from: &str, to: &str) -> Option<f64> {
+    Some(distance_km(grid_to_latlon(from)?, grid_to_latlon(to)?))
+}
+
+/// Short-path bearing in degrees between the centers of two grid squares
+pub fn grid_bearing_deg(from: &str, to: &str) -> Option<f64> {
+    Some(bearing_deg(grid_to_latlon(from)?, grid_to_latlon(to)?))
+}
+
+/// Beam headings and distances both ways around from one grid to another
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct GridPath {
+    pub short_path_bearing: f64,
+    pub long_path_bearing: f64,
+    pub short_path_km: f64,
+    pub long_path_km: f64,
+}
+
+/// Short and long path between the centers of two grid squares
+pub fn grid_path(from: &str, to: &str) -> Option<GridPath> {
+    let (from, to) = (grid_to_latlon(from)?, grid_to_latlon(to)?);
+    let short_path_km = distance_km(from, to);
+    Some(GridPath {
+        short_path_bearing: bearing_deg(from, to),
+        long_path_bearing: long_path_bearing(from, to),
+        short_path_km,
+        long_path_km: EARTH_CIRCUMFERENCE_KM - short_path_km,
+    })
 }
 
 /// Look up DXCC entity from Maidenhead grid square
@@ -412,6 +481,44 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_grid_to_latlon_8char() {
+        // FN31pr spans 5' x 2.5'; FN31pr42 is a tenth of that each way, inside it
+        let (lat6, lon6) = grid_to_latlon("FN31pr").unwrap();
+        let (lat8, lon8) = grid_to_latlon("FN31pr42").unwrap();
+        assert!((lon8 - lon6).abs() < 1.0 / 24.0 && (lat8 - lat6).abs() < 0.5 / 24.0);
+        // Extended square 4,2 of 0-9 sits west and south of the subsquare center
+        assert!((lon8 - (lon6 - 0.5 / 120.0)).abs() < 1e-9, "lon {}", lon8);
+        assert!((lat8 - (lat6 - 2.5 / 240.0)).abs() < 1e-9, "lat {}", lat8);
+
+        // Distances between neighbouring 8-char grids are a few hundred meters
+        let km = grid_distance_km("FN31pr42", "FN31pr52").unwrap();
+        assert!(km > 0.5 && km < 0.8, "distance {}", km);
+
+        assert!(grid_to_latlon("FN31prAB").is_none());
+        assert!(grid_to_latlon("FN31pr4").is_none());
+        assert!(grid_to_latlon("FN31pr4200").is_none());
+    }
+
+    #[test]
+    fn test_short_and_long_path() {
+        // FN31 (Connecticut) to PM95 (Tokyo): short path is roughly north-northwest
+        let short = grid_bearing_deg("FN31", "PM95").unwrap();
+        assert!(short > 320.0 && short < 340.0, "short path {}", short);
+        let (from, to) = (grid_to_latlon("FN31").unwrap(), grid_to_latlon("PM95").unwrap());
+        assert!((long_path_bearing(from, to) - (short + 180.0) % 360.0).abs() < 1e-9);
+
+        let km = grid_distance_km("FN31", "PM95").unwrap();
+        assert!(km > 10500.0 && km < 11000.0, "distance {}", km);
+        let path = grid_path("FN31", "PM95").unwrap();
+        assert_eq!(path.short_path_bearing, short);
+        assert!((path.long_path_km - (40075.0 - km)).abs() < 1.0);
+
+        // Heading due east along the equator turns around to due west
+        assert!((long_path_bearing((0.0, 0.0), (0.0, 10.0)) - 270.0).abs() < 1e-9);
+        assert_eq!(grid_path("FN31", "XX"), None);
+    }
+
     #[test]
     fn test_guantanamo_vs_alabama() {
         // FK29 is Guantanamo Bay
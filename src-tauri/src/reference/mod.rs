@@ -19,8 +19,20 @@
 // - KG4 + Guantanamo grids (FK19-FK39, FL19-FL30) → Guantanamo Bay (105)
 // - KG4 + any other grid OR no grid → USA (291)
 //   Rationale: FCC database shows ALL 16,499 KG4 callsigns are US hams
+//
+// Special Cases (Enclaves, Prefix Override):
+// Small entities inside or beside a larger one share its grid squares, and the
+// grid lookup always answers with the larger country. In these grids a call
+// whose prefix belongs to the enclave keeps the enclave:
+// - JN61 Vatican (295) / Italy
+// - JN63 San Marino (278) / Italy
+// - JN47 Liechtenstein (251) / Switzerland, Austria
+// - JN33 Monaco (260) / France
+// - JN02 Andorra (203) / Spain, France
 
 pub mod dxcc;
+pub mod dxpeditions;
+pub mod exceptions;
 pub mod grid_location;
 pub mod prefixes;
 pub mod states;
@@ -51,6 +63,24 @@ fn is_guantanamo_grid(grid: &str) -> bool {
     false
 }
 
+/// Grid squares shared by an enclave entity and its neighbours: (grid, enclave entity)
+const ENCLAVE_GRIDS: &[(&str, &str)] = &[
+    ("JN61", "295"), // Vatican
+    ("JN63", "278"), // San Marino
+    ("JN47", "251"), // Liechtenstein
+    ("JN33", "260"), // Monaco
+    ("JN02", "203"), // Andorra
+];
+
+/// The enclave entity sharing this grid square, if any
+fn enclave_for_grid(grid: &str) -> Option<&'static str> {
+    let grid_4 = grid.get(..4)?.to_uppercase();
+    ENCLAVE_GRIDS
+        .iter()
+        .find(|(g, _)| *g == grid_4)
+        .map(|(_, entity_id)| *entity_id)
+}
+
 /// Lazily-initialized HashMap for O(1) DXCC entity lookup by entity_id
 /// Uses ARRL 3-digit string format (e.g., "001" for Canada)
 static DXCC_MAP: OnceLock<HashMap<&'static str, &'static DxccEntity>> = OnceLock::new();
@@ -98,6 +128,8 @@ impl CallsignLookup {
 /// Special case handling:
 /// - KG4 + Guantanamo grid (FK/FL area) → Guantanamo Bay (105)
 /// - KG4 + other grid or no grid → USA (291)
+/// - Enclave grid (see ENCLAVE_GRIDS) + enclave prefix → the enclave, not
+///   the surrounding country
 /// 
 /// Fallback to prefix when:
 /// - Grid is empty or invalid
@@ -131,6 +163,15 @@ pub fn lookup_location(call: &str, grid: &str) -> CallsignLookup {
         // Fall through to normal prefix lookup (KG4 rule now returns 291)
     }
     
+    // Enclave grids are ambiguous: an enclave prefix wins over the grid
+    if let Some(enclave) = enclave_for_grid(grid) {
+        let prefix_result = lookup_call_full(call);
+        if prefix_result.dxcc.as_deref() == Some(enclave) {
+            log::debug!("{} in shared grid {} → enclave {}", call, grid, enclave);
+            return prefix_result;
+        }
+    }
+
     // Try grid-based lookup first (PRIMARY)
     if !grid.is_empty() && grid.len() >= 4 {
         let grid_result = lookup_grid(grid);
@@ -169,16 +210,46 @@ pub fn lookup_location(call: &str, grid: &str) -> CallsignLookup {
 /// - "W1AW/KH6" -> KH6 suffix (Hawaii)  
 /// - "W1AW/P" or "W1AW/M" -> base call W1AW (portable/mobile markers ignored)
 /// 
+/// Exact-callsign exceptions (DXpeditions, special event calls) are
+/// checked before any prefix rule.
+/// 
 /// Uses O(1) HashMap lookup for DXCC entity after prefix match.
 pub fn lookup_call_full(call: &str) -> CallsignLookup {
     let call_upper = call.to_uppercase();
     let dxcc_map = get_dxcc_map();
     
+    if let Some(entity_id) = exceptions::exception_for(&call_upper) {
+        if let Some(entity) = dxcc_map.get(entity_id.as_str()) {
+            return entity_lookup(entity);
+        }
+    }
+    
+    // If we found a prefix match, look up the full DXCC entity via HashMap (O(1))
+    if let Some(rule) = prefix_rule_for(&call_upper) {
+        if let Some(entity) = dxcc_map.get(rule.entity_id) {
+            return entity_lookup(entity);
+        }
+        // Entity ID found but no entity data (shouldn't happen)
+        return CallsignLookup {
+            dxcc: Some(rule.entity_id.to_string()),
+            ..Default::default()
+        };
+    }
+    
+    CallsignLookup::default()
+}
+
+/// The prefix rule that decides a callsign's DXCC, ignoring exact-callsign
+/// exceptions. Compound calls are reduced to their DXCC-determining part;
+/// the longest matching prefix wins, then the higher priority.
+pub fn prefix_rule_for(call: &str) -> Option<&'static prefixes::PrefixRule> {
+    let call_upper = call.to_uppercase();
+    
     // Handle compound callsigns with /
     let lookup_call = if call_upper.contains('/') {
         extract_dxcc_portion(&call_upper)
     } else {
-        call_upper.clone()
+        call_upper
     };
     
     // Find the best matching prefix rule
@@ -189,8 +260,7 @@ pub fn lookup_call_full(call: &str) -> CallsignLookup {
     for rule in PREFIX_RULES {
         if rule.exact {
             if lookup_call == rule.prefix {
-                best_match = Some(rule);
-                break;
+                return Some(rule);
             }
         } else if lookup_call.starts_with(rule.prefix) {
             let len = rule.prefix.len();
@@ -201,50 +271,56 @@ pub fn lookup_call_full(call: &str) -> CallsignLookup {
             }
         }
     }
-    
-    // If we found a prefix match, look up the full DXCC entity via HashMap (O(1))
-    if let Some(rule) = best_match {
-        if let Some(entity) = dxcc_map.get(rule.entity_id) {
-            // Use first zone from arrays
-            let cqz = entity.cq_zones.first().copied().unwrap_or(0) as i32;
-            let ituz = entity.itu_zones.first().copied().unwrap_or(0) as i32;
-            return CallsignLookup {
-                dxcc: Some(entity.entity_id.to_string()),
-                country: Some(entity.name.to_uppercase()),
-                continent: Some(entity.continent.to_string()),
-                cqz: Some(cqz),
-                ituz: Some(ituz),
-            };
-        }
-        // Entity ID found but no entity data (shouldn't happen)
-        return CallsignLookup {
-            dxcc: Some(rule.entity_id.to_string()),
-            ..Default::default()
-        };
+    best_match
+}
+
+/// Full lookup result for a matched entity (first CQ/ITU zone)
+fn entity_lookup(entity: &DxccEntity) -> CallsignLookup {
+    let cqz = entity.cq_zones.first().copied().unwrap_or(0) as i32;
+    let ituz = entity.itu_zones.first().copied().unwrap_or(0) as i32;
+    CallsignLookup {
+        dxcc: Some(entity.entity_id.to_string()),
+        country: Some(entity.name.to_uppercase()),
+        continent: Some(entity.continent.to_string()),
+        cqz: Some(cqz),
+        ituz: Some(ituz),
     }
-    
-    CallsignLookup::default()
+}
+
+/// Special operation suffixes of any length that never indicate a DXCC entity:
+/// low power, lighthouse, beacon, aeronautical/maritime mobile
+const SPECIAL_SUFFIXES: &[&str] = &["QRP", "QRPP", "LH", "LGT", "BCN", "BEACON", "AM", "MM", "PM"];
+
+/// True when a suffix is an operating modifier rather than a DXCC indicator:
+/// one of SPECIAL_SUFFIXES, or any 1-2 letter suffix (/P, /M, /A, ...)
+fn is_modifier_suffix(part: &str) -> bool {
+    SPECIAL_SUFFIXES.contains(&part) || (part.len() <= 2 && part.chars().all(|c| c.is_ascii_alphabetic()))
 }
 
 /// Extract the DXCC-determining portion of a compound callsign
 /// Rules:
-/// - Single char suffix like /P, /M, /A are ignored (portable, mobile, etc)
+/// - Modifier suffixes like /P, /M, /QRP, /LH, /BEACON are ignored
 /// - Prefix/Call like HK0/DF3TJ -> use HK0 (prefix determines DXCC)
 /// - Call/Suffix like W1AW/KH6 -> use KH6 (suffix determines DXCC)
 fn extract_dxcc_portion(call: &str) -> String {
     let parts: Vec<&str> = call.split('/').collect();
     
+    if parts.len() > 2 {
+        // More than one slash: drop trailing modifiers, use the rest as-is
+        let modifiers = parts.iter().skip(1).rev().take_while(|p| is_modifier_suffix(p)).count();
+        return parts[..parts.len() - modifiers].join("/");
+    }
     if parts.len() != 2 {
-        // More than one slash or no slash, just use as-is
+        // No slash, just use as-is
         return call.to_string();
     }
     
     let part0 = parts[0];
     let part1 = parts[1];
     
-    // Single char suffixes are modifiers, not DXCC indicators
-    // /P = portable, /M = mobile, /A = alternative, /MM = maritime mobile, etc.
-    if part1.len() <= 2 && part1.chars().all(|c| c.is_ascii_alphabetic()) {
+    // Modifier suffixes are not DXCC indicators
+    // /P = portable, /M = mobile, /A = alternative, /MM = maritime mobile, /QRP, etc.
+    if is_modifier_suffix(part1) {
         return part0.to_string();
     }
     
@@ -268,6 +344,34 @@ fn extract_dxcc_portion(call: &str) -> String {
     }
 }
 
+/// Strip prefix and suffix modifiers from a compound callsign, leaving the
+/// operator's home call: HK0/DF3TJ -> DF3TJ, W1AW/KH6 -> W1AW, W1AW/P -> W1AW.
+/// Unlike extract_dxcc_portion this never returns the DXCC-determining part;
+/// it is for display only, the full call is what gets stored and looked up.
+pub fn base_callsign(call: &str) -> String {
+    let call = call.trim().to_uppercase();
+    let parts: Vec<&str> = call.split('/').filter(|p| !p.is_empty()).collect();
+    if parts.len() <= 1 {
+        return parts.first().map(|p| p.to_string()).unwrap_or(call);
+    }
+
+    // A home call ends in letters after its digit (DF3TJ, W1AW); bare prefixes
+    // (KH6, HK0) and modifiers (P, MM, QRP) don't. Short-suffix prefixes like
+    // VP2E lose to the part with the longer suffix.
+    let suffix_len = |p: &str| match p.rfind(|c: char| c.is_ascii_digit()) {
+        Some(i) => p.len() - i - 1,
+        None => 0,
+    };
+
+    parts
+        .iter()
+        .filter(|p| p.len() >= 3 && suffix_len(p) > 0)
+        .max_by_key(|p| (suffix_len(p), p.len()))
+        .or_else(|| parts.iter().max_by_key(|p| p.len()))
+        .map(|p| p.to_string())
+        .unwrap_or(call)
+}
+
 // These accessor functions are for future features (award matrix, data export)
 #[allow(dead_code)]
 /// Get all DXCC entities
@@ -293,6 +397,51 @@ pub fn get_all_states() -> &'static [states::UsState] {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_exact_call_exception_overrides_prefix() {
+        // 3Y prefix defaults to Peter 1; 3Y0J was the Bouvet DXpedition
+        assert_eq!(lookup_call_full("3Y0A").dxcc.as_deref(), Some("199"));
+        let result = lookup_call_full("3Y0J");
+        assert_eq!(result.dxcc.as_deref(), Some("024"));
+        assert_eq!(result.country.as_deref(), Some("BOUVET"));
+
+        // VP8S is South Shetland, but VP8STI was South Sandwich
+        assert_eq!(lookup_call_full("VP8STI").dxcc.as_deref(), Some("240"));
+    }
+
+    /// Clears the process-wide user exceptions when dropped, even if the test panics
+    struct ClearUserExceptions;
+
+    impl Drop for ClearUserExceptions {
+        fn drop(&mut self) {
+            exceptions::set_user_exceptions(HashMap::new());
+        }
+    }
+
+    #[test]
+    fn test_user_call_exception() {
+        assert_eq!(lookup_call_full("Q9XYZ").dxcc, None);
+        let guard = ClearUserExceptions;
+        exceptions::set_user_exceptions(HashMap::from([("q9xyz".to_string(), "024".to_string())]));
+        assert_eq!(lookup_call_full("Q9XYZ").dxcc.as_deref(), Some("024"));
+        drop(guard);
+        assert_eq!(lookup_call_full("Q9XYZ").dxcc, None);
+    }
+
+    #[test]
+    fn test_enclave_grid_defers_to_prefix() {
+        // San Marino call in JN63, which the grid lookup calls Italy
+        assert_eq!(lookup_grid("JN63gw").dxcc.as_deref(), Some("248"));
+        let result = lookup_location("T70A", "JN63gw");
+        assert_eq!(result.dxcc.as_deref(), Some("278"));
+        assert_eq!(result.country.as_deref(), Some("SAN MARINO"));
+
+        // An Italian call in the same grid stays Italy
+        assert_eq!(lookup_location("I4ABC", "JN63").dxcc.as_deref(), Some("248"));
+        assert_eq!(lookup_location("HV0A", "JN61fv").dxcc.as_deref(), Some("295"));
+        assert_eq!(lookup_location("HB0A", "JN47sd").dxcc.as_deref(), Some("251"));
+    }
+
     #[test]
     fn test_compound_callsign_hk0() {
         // HK0/DF3TJ should resolve to San Andres & Providencia (entity 216)
@@ -314,6 +463,37 @@ mod tests {
         assert_eq!(extract_dxcc_portion("W1AW/MM"), "W1AW");
     }
 
+    #[test]
+    fn test_special_suffixes_ignored() {
+        for suffix in SPECIAL_SUFFIXES {
+            assert_eq!(extract_dxcc_portion(&format!("W1AW/{}", suffix)), "W1AW", "/{}", suffix);
+            assert_eq!(extract_dxcc_portion(&format!("DF3TJ/{}", suffix)), "DF3TJ", "/{}", suffix);
+            assert_eq!(
+                extract_dxcc_portion(&format!("HK0/DF3TJ/{}", suffix)),
+                "HK0/DF3TJ",
+                "/{}",
+                suffix
+            );
+        }
+        // Longer than the call itself, so it used to win as "the base call"
+        assert_eq!(lookup_call_full("DF3TJ/BEACON").dxcc.as_deref(), Some("230"));
+        assert_eq!(lookup_call_full("W1AW/QRP").dxcc.as_deref(), Some("291"));
+        assert_eq!(lookup_call_full("VP2E/W1AW/LH").dxcc, lookup_call_full("VP2E/W1AW/P").dxcc);
+    }
+
+    #[test]
+    fn test_base_callsign() {
+        // Display keeps the operator's call where DXCC lookup keeps the prefix
+        assert_eq!(base_callsign("HK0/DF3TJ"), "DF3TJ");
+        assert_eq!(extract_dxcc_portion("HK0/DF3TJ"), "HK0");
+        assert_eq!(base_callsign("W1AW/KH6"), "W1AW");
+        assert_eq!(base_callsign("w1aw/p"), "W1AW");
+        assert_eq!(base_callsign("VP2E/W1AW/P"), "W1AW");
+        assert_eq!(base_callsign("W1AW/VP2E"), "W1AW");
+        assert_eq!(base_callsign("G4ABC/QRP"), "G4ABC");
+        assert_eq!(base_callsign("W1AW"), "W1AW");
+    }
+
     /// Regression test for BUG-001: DXCC must convert to integer for database
     /// The root cause was binding Option<String> "291" to INTEGER column.
     /// This test ensures dxcc_as_i32() correctly converts the ARRL 3-digit
@@ -1,13 +1,14 @@
 // UDP Listener for WSJT-X
 // Listens on configurable port (default 2237) and parses WSJT-X messages
 
+use std::collections::BTreeMap;
 use std::net::{UdpSocket, SocketAddr};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
-use super::wsjtx::{parse_message, parse_qso_logged, parse_logged_adif, parse_decode, WsjtxMessageType, QsoLoggedMessage, DecodeMessage, read_qt_string, ReplyMessage, is_valid_grid, normalize_rst};
+use super::wsjtx::{parse_message, parse_qso_logged, parse_logged_adif, parse_decode, WsjtxMessageType, QsoLoggedMessage, DecodeMessage, read_qt_string, ReplyMessage, is_valid_grid, normalize_rst, strip_hash_brackets};
 
 /// Parse an ADIF record string into a QsoLoggedMessage
 /// ADIF format: <TAG:LENGTH>VALUE or <TAG:LENGTH:TYPE>VALUE
@@ -96,6 +97,13 @@ fn parse_adif_to_qso(adif: &str) -> Option<QsoLoggedMessage> {
     let my_call = fields.get("STATION_CALLSIGN").or(fields.get("OPERATOR")).cloned().unwrap_or_default();
     let my_grid = fields.get("MY_GRIDSQUARE").cloned().unwrap_or_default();
     
+    // Carry QSO_DATE along so a QSO logged just before 0000 UTC keeps its own date
+    let time_on = fields.get("TIME_ON").cloned().unwrap_or_default();
+    let datetime_on = match fields.get("QSO_DATE").filter(|d| d.len() == 8 && d.chars().all(|c| c.is_ascii_digit())) {
+        Some(d) if !time_on.is_empty() => format!("{}-{}-{} {}", &d[..4], &d[4..6], &d[6..], time_on),
+        _ => time_on,
+    };
+    
     log::info!("Parsed ADIF QSO: call={} grid={} freq={} mode={}", call, grid, freq_hz, mode);
     
     Some(QsoLoggedMessage {
@@ -110,7 +118,7 @@ fn parse_adif_to_qso(adif: &str) -> Option<QsoLoggedMessage> {
         tx_power: fields.get("TX_PWR").cloned().unwrap_or_default(),
         comments: fields.get("COMMENT").cloned().unwrap_or_default(),
         name: fields.get("NAME").cloned().unwrap_or_default(),
-        datetime_on: fields.get("TIME_ON").cloned().unwrap_or_default(),
+        datetime_on,
         operator_call: fields.get("OPERATOR").cloned().unwrap_or_default(),
         my_call,
         my_grid,
@@ -120,21 +128,53 @@ fn parse_adif_to_qso(adif: &str) -> Option<QsoLoggedMessage> {
     })
 }
 
+/// WSJT-X's default UDP server port
+pub const DEFAULT_UDP_PORT: u16 = 2237;
+
+/// WSJT-X sends a heartbeat every ~15s; no heartbeat for this long means it's gone
+pub const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// WSJT-X has no explicit Reply acknowledgment (type 13 is HighlightCallsign,
+/// which only flows the other way). An accepted Reply shows up as a Status
+/// whose DX call is the replied-to station; none within this window means
+/// WSJT-X ignored it.
+pub const REPLY_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What became of a Reply sent to WSJT-X, with the station it targeted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplyOutcome {
+    Accepted(String),
+    Ignored(String),
+}
+
 /// Listener state that can be shared across threads
 pub struct UdpListenerState {
     running: AtomicBool,
     port: std::sync::Mutex<u16>,
     wsjtx_addr: std::sync::Mutex<Option<SocketAddr>>,
     wsjtx_id: std::sync::Mutex<Option<String>>,
+    wsjtx_version: std::sync::Mutex<Option<String>>,
+    last_heartbeat: std::sync::Mutex<Option<Instant>>,
+    /// Connection state last reported to the app (edge detection for events)
+    connected: AtomicBool,
+    /// WSJT-X announced it was exiting (Close); cleared by the next heartbeat
+    closed: AtomicBool,
+    /// Station of the last Reply sent, until WSJT-X acts on it or REPLY_ACK_TIMEOUT passes
+    pending_reply: std::sync::Mutex<Option<(String, Instant)>>,
 }
 
 impl UdpListenerState {
     pub fn new() -> Self {
         Self {
             running: AtomicBool::new(false),
-            port: std::sync::Mutex::new(2237),
+            port: std::sync::Mutex::new(DEFAULT_UDP_PORT),
             wsjtx_addr: std::sync::Mutex::new(None),
             wsjtx_id: std::sync::Mutex::new(None),
+            wsjtx_version: std::sync::Mutex::new(None),
+            last_heartbeat: std::sync::Mutex::new(None),
+            connected: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+            pending_reply: std::sync::Mutex::new(None),
         }
     }
 
@@ -166,6 +206,90 @@ impl UdpListenerState {
     pub fn get_wsjtx_id(&self) -> Option<String> {
         self.wsjtx_id.lock().unwrap().clone()
     }
+    
+    pub fn get_wsjtx_version(&self) -> Option<String> {
+        self.wsjtx_version.lock().unwrap().clone()
+    }
+    
+    /// Record a heartbeat received at `now`.
+    /// Returns true if this heartbeat (re)established the connection.
+    pub fn record_heartbeat_at(&self, now: Instant, version: &str) -> bool {
+        *self.last_heartbeat.lock().unwrap() = Some(now);
+        *self.wsjtx_version.lock().unwrap() = Some(version.to_string());
+        self.closed.store(false, Ordering::SeqCst);
+        !self.connected.swap(true, Ordering::SeqCst)
+    }
+    
+    /// True if a heartbeat arrived within HEARTBEAT_TIMEOUT of `now`
+    pub fn is_connected_at(&self, now: Instant) -> bool {
+        self.last_heartbeat
+            .lock()
+            .unwrap()
+            .is_some_and(|last| now.saturating_duration_since(last) <= HEARTBEAT_TIMEOUT)
+    }
+    
+    pub fn is_connected(&self) -> bool {
+        self.is_connected_at(Instant::now())
+    }
+    
+    /// Check for a heartbeat timeout at `now`.
+    /// Returns true exactly once when a connected WSJT-X stops sending heartbeats.
+    pub fn check_heartbeat_timeout_at(&self, now: Instant) -> bool {
+        if self.is_connected_at(now) {
+            return false;
+        }
+        self.connected.swap(false, Ordering::SeqCst)
+    }
+    
+    /// Forget the WSJT-X connection (listener stopped)
+    pub fn reset_connection(&self) {
+        *self.last_heartbeat.lock().unwrap() = None;
+        self.connected.store(false, Ordering::SeqCst);
+        *self.pending_reply.lock().unwrap() = None;
+    }
+
+    /// WSJT-X sent Close: forget its address and id so nothing is sent to a
+    /// dead socket. Returns true if it was connected.
+    pub fn record_close(&self) -> bool {
+        *self.wsjtx_addr.lock().unwrap() = None;
+        *self.wsjtx_id.lock().unwrap() = None;
+        *self.last_heartbeat.lock().unwrap() = None;
+        *self.pending_reply.lock().unwrap() = None;
+        self.closed.store(true, Ordering::SeqCst);
+        self.connected.swap(false, Ordering::SeqCst)
+    }
+
+    /// True after a Close until WSJT-X sends another heartbeat
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Start waiting for WSJT-X to act on a Reply to `call` (replaces any earlier one)
+    pub fn expect_reply_at(&self, call: &str, now: Instant) {
+        *self.pending_reply.lock().unwrap() = Some((strip_hash_brackets(call).to_uppercase(), now));
+    }
+
+    /// Check a Status at `now` against the pending Reply: accepted when the DX
+    /// call matches, ignored once the window has passed
+    pub fn check_reply_status_at(&self, dx_call: &str, now: Instant) -> Option<ReplyOutcome> {
+        let mut pending = self.pending_reply.lock().unwrap();
+        let (call, _) = pending.as_ref()?;
+        if strip_hash_brackets(dx_call).eq_ignore_ascii_case(call) {
+            return pending.take().map(|(call, _)| ReplyOutcome::Accepted(call));
+        }
+        drop(pending);
+        self.check_reply_timeout_at(now)
+    }
+
+    /// Returns the ignored Reply once, when no matching Status arrived in time
+    pub fn check_reply_timeout_at(&self, now: Instant) -> Option<ReplyOutcome> {
+        let mut pending = self.pending_reply.lock().unwrap();
+        let (_, sent_at) = pending.as_ref()?;
+        if now.saturating_duration_since(*sent_at) <= REPLY_ACK_TIMEOUT {
+            return None;
+        }
+        pending.take().map(|(call, _)| ReplyOutcome::Ignored(call))
+    }
 }
 
 impl Default for UdpListenerState {
@@ -174,7 +298,76 @@ impl Default for UdpListenerState {
     }
 }
 
-/// Message types sent from the listener to the main thread
+/// One listener per UDP port. Multi-radio operators run a WSJT-X per rig,
+/// each reporting to its own port; the port identifies the instance, and each
+/// keeps its own WSJT-X address, id and heartbeat.
+pub struct UdpInstances {
+    instances: std::sync::Mutex<BTreeMap<u16, Arc<UdpListenerState>>>,
+}
+
+impl UdpInstances {
+    pub fn new() -> Self {
+        Self {
+            instances: std::sync::Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Replace the instances with fresh state for each of `ports`
+    pub fn reset(&self, ports: &[u16]) -> Vec<Arc<UdpListenerState>> {
+        let mut instances = self.instances.lock().unwrap();
+        instances.clear();
+        for &port in ports {
+            let state = Arc::new(UdpListenerState::new());
+            state.set_port(port);
+            instances.insert(port, state);
+        }
+        instances.values().cloned().collect()
+    }
+
+    pub fn get(&self, port: u16) -> Option<Arc<UdpListenerState>> {
+        self.instances.lock().unwrap().get(&port).cloned()
+    }
+
+    /// All instances, lowest port first
+    pub fn all(&self) -> Vec<Arc<UdpListenerState>> {
+        self.instances.lock().unwrap().values().cloned().collect()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.all().iter().any(|state| state.is_running())
+    }
+
+    pub fn stop_all(&self) {
+        for state in self.all() {
+            state.set_running(false);
+        }
+    }
+
+    /// The instance a Reply should go to: `port` if given, otherwise the
+    /// lowest-port instance that has heard from WSJT-X
+    pub fn target(&self, port: Option<u16>) -> Result<Arc<UdpListenerState>, String> {
+        match port {
+            Some(port) => self.get(port).ok_or_else(|| format!("No WSJT-X listener on port {}", port)),
+            None => {
+                let all = self.all();
+                all.iter()
+                    .find(|state| state.get_wsjtx_id().is_some())
+                    .or_else(|| all.first())
+                    .cloned()
+                    .ok_or_else(|| "UDP listener not running".to_string())
+            }
+        }
+    }
+}
+
+impl Default for UdpInstances {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Message types sent from the listener to the main thread, paired with the
+/// port (instance) they arrived on
 /// NOTE: Some variant fields (max_schema, revision, tx_mode, decoding) are parsed
 /// from the WSJT-X protocol for completeness but not currently used in the UI.
 #[derive(Debug, Clone)]
@@ -185,15 +378,125 @@ pub enum UdpMessage {
     Clear { id: String, window: u8 },
     Heartbeat { id: String, max_schema: u32, version: String, revision: String },
     Status { id: String, dial_freq: u64, mode: String, dx_call: String, de_call: String, report: String, tx_mode: String, tx_enabled: bool, transmitting: bool, decoding: bool, tx_message: String },
+    Reply(ReplyOutcome),
     Connected,
     Disconnected,
     Error(String),
 }
 
-/// Start the UDP listener in a background thread
+/// Handle one packet from WSJT-X on `state`'s port, reporting what it carried
+/// through `send`
+fn handle_packet(state: &UdpListenerState, data: &[u8], src: SocketAddr, send: &impl Fn(UdpMessage)) {
+    if let Some(msg_type) = parse_message(data) {
+        log::debug!("UDP message type: {:?} ({} bytes)", msg_type, data.len());
+        match msg_type {
+            WsjtxMessageType::Decode => {
+                if let Some(decode) = parse_decode(data) {
+                    if decode.is_new && !decode.off_air {
+                        log::debug!("Decode: {} dB: {}", decode.snr, decode.message);
+                        send(UdpMessage::Decode(decode));
+                    }
+                }
+            }
+            WsjtxMessageType::QsoLogged => {
+                log::warn!("[QSO-SOURCE] QsoLogged (type 5) received from WSJT-X ({} bytes)", data.len());
+                log::debug!("QsoLogged raw bytes: {:02x?}", &data[..data.len().min(200)]);
+                if let Some(mut qso) = parse_qso_logged(data) {
+                    log::warn!("[QSO-SOURCE] Type5: call={} mode={} freq={} datetime_on={} grid={}", 
+                        qso.call, qso.mode, qso.freq_hz, qso.datetime_on, qso.grid);
+                    // Tag source for debugging
+                    qso.id = "TYPE5".to_string();
+                    send(UdpMessage::QsoLogged(qso));
+                } else {
+                    log::error!("Failed to parse QsoLogged message");
+                }
+            }
+            WsjtxMessageType::Heartbeat => {
+                if let Some(hb) = parse_heartbeat(data) {
+                    log::debug!("Heartbeat from WSJT-X: {} at {}", hb.id, src);
+                    // Store the WSJT-X address for sending replies
+                    state.set_wsjtx_addr(src, hb.id.clone());
+                    if state.record_heartbeat_at(Instant::now(), &hb.version) {
+                        log::info!("WSJT-X connected: {} {}", hb.id, hb.version);
+                        send(UdpMessage::Connected);
+                    }
+                    send(UdpMessage::Heartbeat {
+                        id: hb.id,
+                        max_schema: hb.max_schema,
+                        version: hb.version,
+                        revision: hb.revision,
+                    });
+                }
+            }
+            WsjtxMessageType::Status => {
+                if let Some(status) = parse_status(data) {
+                    log::debug!("Status: {} de_call={} mode={} freq={} tx_msg='{}'", 
+                        status.id, status.de_call, status.mode, status.dial_freq, status.tx_message);
+                    if let Some(outcome) = state.check_reply_status_at(&status.dx_call, Instant::now()) {
+                        send(UdpMessage::Reply(outcome));
+                    }
+                    send(UdpMessage::Status {
+                        id: status.id,
+                        dial_freq: status.dial_freq,
+                        mode: status.mode,
+                        dx_call: status.dx_call,
+                        de_call: status.de_call,
+                        report: status.report,
+                        tx_mode: status.tx_mode,
+                        tx_enabled: status.tx_enabled,
+                        transmitting: status.transmitting,
+                        decoding: status.decoding,
+                        tx_message: status.tx_message,
+                    });
+                }
+            }
+            WsjtxMessageType::LoggedADIF => {
+                log::warn!("[QSO-SOURCE] LoggedADIF (type 12) received from WSJT-X ({} bytes)", data.len());
+                if let Some(adif_msg) = parse_logged_adif(data) {
+                    log::warn!("[QSO-SOURCE] Type12 ADIF: {}", adif_msg.adif);
+                    // Convert ADIF string to QsoLoggedMessage
+                    if let Some(mut qso) = parse_adif_to_qso(&adif_msg.adif) {
+                        log::warn!("[QSO-SOURCE] Type12: call={} mode={} freq={} datetime_on={} grid={}", 
+                            qso.call, qso.mode, qso.freq_hz, qso.datetime_on, qso.grid);
+                        // Tag source for debugging
+                        qso.id = "TYPE12".to_string();
+                        send(UdpMessage::QsoLogged(qso));
+                    } else {
+                        log::error!("Failed to parse ADIF content: {}", adif_msg.adif);
+                    }
+                } else {
+                    log::error!("Failed to parse LoggedADIF message");
+                }
+            }
+            WsjtxMessageType::Clear => {
+                // Clear message sent at start of new decode period
+                // Window: 0 = Band Activity, 1 = Rx Frequency
+                if let Some(clear) = parse_clear(data) {
+                    log::debug!("Clear window {} from {}", clear.window, clear.id);
+                    send(UdpMessage::Clear {
+                        id: clear.id,
+                        window: clear.window,
+                    });
+                }
+            }
+            WsjtxMessageType::Close => {
+                if let Some(close) = parse_close(data) {
+                    log::info!("WSJT-X closed: {}", close.id);
+                    state.record_close();
+                    send(UdpMessage::Disconnected);
+                }
+            }
+            _ => {
+                log::trace!("Received {:?} message", msg_type);
+            }
+        }
+    }
+}
+
+/// Start the UDP listener for one instance in a background thread
 pub fn start_listener(
     state: Arc<UdpListenerState>,
-    sender: mpsc::UnboundedSender<UdpMessage>,
+    sender: mpsc::UnboundedSender<(u16, UdpMessage)>,
 ) -> Result<(), String> {
     if state.is_running() {
         return Err("UDP listener already running".to_string());
@@ -203,17 +506,20 @@ pub fn start_listener(
     state.set_running(true);
 
     std::thread::spawn(move || {
+        let send = |message: UdpMessage| {
+            let _ = sender.send((port, message));
+        };
         let addr = format!("0.0.0.0:{}", port);
         
         let socket = match UdpSocket::bind(&addr) {
             Ok(s) => {
+                // Not "connected" until WSJT-X sends a heartbeat
                 log::info!("UDP listener bound to {}", addr);
-                let _ = sender.send(UdpMessage::Connected);
                 s
             }
             Err(e) => {
                 log::error!("Failed to bind UDP socket: {}", e);
-                let _ = sender.send(UdpMessage::Error(format!("Failed to bind: {}", e)));
+                send(UdpMessage::Error(format!("Failed to bind: {}", e)));
                 state.set_running(false);
                 return;
             }
@@ -225,100 +531,19 @@ pub fn start_listener(
         let mut buf = [0u8; 2048];
         
         while state.is_running() {
+            if state.check_heartbeat_timeout_at(Instant::now()) {
+                log::warn!("No WSJT-X heartbeat for {:?}, marking disconnected", HEARTBEAT_TIMEOUT);
+                send(UdpMessage::Disconnected);
+            }
+            if let Some(outcome) = state.check_reply_timeout_at(Instant::now()) {
+                send(UdpMessage::Reply(outcome));
+            }
+            
             match socket.recv_from(&mut buf) {
                 Ok((len, src)) => {
                     log::trace!("Received {} bytes from {}", len, src);
                     
-                    if let Some(msg_type) = parse_message(&buf[..len]) {
-                        log::debug!("UDP message type: {:?} ({} bytes)", msg_type, len);
-                        match msg_type {
-                            WsjtxMessageType::Decode => {
-                                if let Some(decode) = parse_decode(&buf[..len]) {
-                                    if decode.is_new && !decode.off_air {
-                                        log::debug!("Decode: {} dB: {}", decode.snr, decode.message);
-                                        let _ = sender.send(UdpMessage::Decode(decode));
-                                    }
-                                }
-                            }
-                            WsjtxMessageType::QsoLogged => {
-                                log::warn!("[QSO-SOURCE] QsoLogged (type 5) received from WSJT-X ({} bytes)", len);
-                                log::debug!("QsoLogged raw bytes: {:02x?}", &buf[..len.min(200)]);
-                                if let Some(mut qso) = parse_qso_logged(&buf[..len]) {
-                                    log::warn!("[QSO-SOURCE] Type5: call={} mode={} freq={} datetime_on={} grid={}", 
-                                        qso.call, qso.mode, qso.freq_hz, qso.datetime_on, qso.grid);
-                                    // Tag source for debugging
-                                    qso.id = "TYPE5".to_string();
-                                    let _ = sender.send(UdpMessage::QsoLogged(qso));
-                                } else {
-                                    log::error!("Failed to parse QsoLogged message");
-                                }
-                            }
-                            WsjtxMessageType::Heartbeat => {
-                                if let Some(hb) = parse_heartbeat(&buf[..len]) {
-                                    log::debug!("Heartbeat from WSJT-X: {} at {}", hb.id, src);
-                                    // Store the WSJT-X address for sending replies
-                                    state.set_wsjtx_addr(src, hb.id.clone());
-                                    let _ = sender.send(UdpMessage::Heartbeat {
-                                        id: hb.id,
-                                        max_schema: hb.max_schema,
-                                        version: hb.version,
-                                        revision: hb.revision,
-                                    });
-                                }
-                            }
-                            WsjtxMessageType::Status => {
-                                if let Some(status) = parse_status(&buf[..len]) {
-                                    log::debug!("Status: {} de_call={} mode={} freq={} tx_msg='{}'", 
-                                        status.id, status.de_call, status.mode, status.dial_freq, status.tx_message);
-                                    let _ = sender.send(UdpMessage::Status {
-                                        id: status.id,
-                                        dial_freq: status.dial_freq,
-                                        mode: status.mode,
-                                        dx_call: status.dx_call,
-                                        de_call: status.de_call,
-                                        report: status.report,
-                                        tx_mode: status.tx_mode,
-                                        tx_enabled: status.tx_enabled,
-                                        transmitting: status.transmitting,
-                                        decoding: status.decoding,
-                                        tx_message: status.tx_message,
-                                    });
-                                }
-                            }
-                            WsjtxMessageType::LoggedADIF => {
-                                log::warn!("[QSO-SOURCE] LoggedADIF (type 12) received from WSJT-X ({} bytes)", len);
-                                if let Some(adif_msg) = parse_logged_adif(&buf[..len]) {
-                                    log::warn!("[QSO-SOURCE] Type12 ADIF: {}", adif_msg.adif);
-                                    // Convert ADIF string to QsoLoggedMessage
-                                    if let Some(mut qso) = parse_adif_to_qso(&adif_msg.adif) {
-                                        log::warn!("[QSO-SOURCE] Type12: call={} mode={} freq={} datetime_on={} grid={}", 
-                                            qso.call, qso.mode, qso.freq_hz, qso.datetime_on, qso.grid);
-                                        // Tag source for debugging
-                                        qso.id = "TYPE12".to_string();
-                                        let _ = sender.send(UdpMessage::QsoLogged(qso));
-                                    } else {
-                                        log::error!("Failed to parse ADIF content: {}", adif_msg.adif);
-                                    }
-                                } else {
-                                    log::error!("Failed to parse LoggedADIF message");
-                                }
-                            }
-                            WsjtxMessageType::Clear => {
-                                // Clear message sent at start of new decode period
-                                // Window: 0 = Band Activity, 1 = Rx Frequency
-                                if let Some(clear) = parse_clear(&buf[..len]) {
-                                    log::debug!("Clear window {} from {}", clear.window, clear.id);
-                                    let _ = sender.send(UdpMessage::Clear {
-                                        id: clear.id,
-                                        window: clear.window,
-                                    });
-                                }
-                            }
-                            _ => {
-                                log::trace!("Received {:?} message", msg_type);
-                            }
-                        }
-                    }
+                    handle_packet(&state, &buf[..len], src, &send);
                 }
                 Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
                     continue;
@@ -333,13 +558,14 @@ pub fn start_listener(
                 }
                 Err(e) => {
                     log::error!("UDP receive error: {}", e);
-                    let _ = sender.send(UdpMessage::Error(format!("Receive error: {}", e)));
+                    send(UdpMessage::Error(format!("Receive error: {}", e)));
                 }
             }
         }
 
         log::info!("UDP listener stopped");
-        let _ = sender.send(UdpMessage::Disconnected);
+        state.reset_connection();
+        send(UdpMessage::Disconnected);
         state.set_running(false);
     });
 
@@ -495,6 +721,18 @@ fn parse_clear(data: &[u8]) -> Option<ClearMessage> {
     Some(ClearMessage { id, window })
 }
 
+#[derive(Debug)]
+struct CloseMessage {
+    id: String,
+}
+
+/// Close (type 6): WSJT-X is exiting; the payload is just its id
+fn parse_close(data: &[u8]) -> Option<CloseMessage> {
+    let mut offset = 12; // Skip magic, schema, type
+    let id = read_qt_string(data, &mut offset)?;
+    Some(CloseMessage { id })
+}
+
 /// Send a Reply message to WSJT-X to initiate a QSO
 pub fn send_reply(
     state: &Arc<UdpListenerState>,
@@ -517,4 +755,183 @@ pub fn send_reply(
         .map_err(|e| format!("Failed to send Reply: {}", e))?;
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_connected_before_heartbeat() {
+        let state = UdpListenerState::new();
+        let now = Instant::now();
+        assert!(!state.is_connected_at(now));
+        assert!(!state.check_heartbeat_timeout_at(now + HEARTBEAT_TIMEOUT * 2));
+    }
+
+    #[test]
+    fn test_heartbeat_timeout_boundary() {
+        let state = UdpListenerState::new();
+        let t0 = Instant::now();
+
+        // First heartbeat connects, later ones don't re-announce
+        assert!(state.record_heartbeat_at(t0, "2.7.0"));
+        assert!(!state.record_heartbeat_at(t0, "2.7.0"));
+        assert_eq!(state.get_wsjtx_version().as_deref(), Some("2.7.0"));
+
+        // Exactly at the timeout we're still connected
+        assert!(state.is_connected_at(t0 + HEARTBEAT_TIMEOUT));
+        assert!(!state.check_heartbeat_timeout_at(t0 + HEARTBEAT_TIMEOUT));
+
+        // Just past it we're disconnected, reported once
+        let late = t0 + HEARTBEAT_TIMEOUT + Duration::from_millis(1);
+        assert!(!state.is_connected_at(late));
+        assert!(state.check_heartbeat_timeout_at(late));
+        assert!(!state.check_heartbeat_timeout_at(late + Duration::from_secs(5)));
+
+        // Next heartbeat reconnects
+        let t1 = late + Duration::from_secs(10);
+        assert!(state.record_heartbeat_at(t1, "2.7.0"));
+        assert!(state.is_connected_at(t1 + Duration::from_secs(15)));
+    }
+
+    #[test]
+    fn test_reset_connection() {
+        let state = UdpListenerState::new();
+        let t0 = Instant::now();
+        state.record_heartbeat_at(t0, "2.7.0");
+        state.reset_connection();
+        assert!(!state.is_connected_at(t0));
+        assert!(state.record_heartbeat_at(t0, "2.7.0"));
+    }
+
+    #[test]
+    fn test_close_clears_wsjtx_id() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&super::super::wsjtx::WSJTX_MAGIC.to_be_bytes());
+        data.extend_from_slice(&2u32.to_be_bytes());
+        data.extend_from_slice(&6u32.to_be_bytes());
+        data.extend_from_slice(&6u32.to_be_bytes());
+        data.extend_from_slice(b"WSJT-X");
+        assert_eq!(parse_message(&data), Some(WsjtxMessageType::Close));
+        assert_eq!(parse_close(&data).unwrap().id, "WSJT-X");
+
+        let state = UdpListenerState::new();
+        let t0 = Instant::now();
+        state.set_wsjtx_addr("127.0.0.1:2237".parse().unwrap(), "WSJT-X".to_string());
+        state.record_heartbeat_at(t0, "2.7.0");
+
+        assert!(state.record_close());
+        assert!(state.is_closed());
+        assert_eq!(state.get_wsjtx_id(), None);
+        assert_eq!(state.get_wsjtx_addr(), None);
+        assert!(!state.is_connected_at(t0));
+        // Not reported again by the heartbeat timeout
+        assert!(!state.check_heartbeat_timeout_at(t0 + HEARTBEAT_TIMEOUT * 2));
+
+        // WSJT-X restarted
+        assert!(state.record_heartbeat_at(t0 + HEARTBEAT_TIMEOUT, "2.7.0"));
+        assert!(!state.is_closed());
+    }
+
+    fn qt_string(data: &mut Vec<u8>, s: &str) {
+        data.extend_from_slice(&(s.len() as u32).to_be_bytes());
+        data.extend_from_slice(s.as_bytes());
+    }
+
+    fn heartbeat_packet(id: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&super::super::wsjtx::WSJTX_MAGIC.to_be_bytes());
+        data.extend_from_slice(&2u32.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        qt_string(&mut data, id);
+        data.extend_from_slice(&3u32.to_be_bytes());
+        qt_string(&mut data, "2.7.0");
+        qt_string(&mut data, "");
+        data
+    }
+
+    #[test]
+    fn test_two_instances_tracked_separately() {
+        let instances = UdpInstances::new();
+        instances.reset(&[2238, 2237]);
+        // Nothing heard yet: the default target is the lowest port
+        assert_eq!(instances.target(None).unwrap().get_port(), 2237);
+        assert!(instances.target(Some(1)).is_err());
+
+        let radio_a: SocketAddr = "192.168.1.10:50001".parse().unwrap();
+        let radio_b: SocketAddr = "192.168.1.11:50002".parse().unwrap();
+        let heartbeats = std::cell::RefCell::new(Vec::new());
+        for (port, id, src) in [(2237, "WSJT-X - IC7300", radio_a), (2238, "WSJT-X - K3", radio_b)] {
+            let state = instances.get(port).unwrap();
+            handle_packet(&state, &heartbeat_packet(id), src, &|message| {
+                if let UdpMessage::Heartbeat { id, .. } = message {
+                    heartbeats.borrow_mut().push((port, id));
+                }
+            });
+        }
+        assert_eq!(
+            heartbeats.into_inner(),
+            vec![(2237, "WSJT-X - IC7300".to_string()), (2238, "WSJT-X - K3".to_string())]
+        );
+
+        // Each instance keeps its own id and address
+        let a = instances.target(Some(2237)).unwrap();
+        let b = instances.target(Some(2238)).unwrap();
+        assert_eq!(a.get_wsjtx_id().as_deref(), Some("WSJT-X - IC7300"));
+        assert_eq!(b.get_wsjtx_id().as_deref(), Some("WSJT-X - K3"));
+        assert_eq!(a.get_wsjtx_addr(), Some(radio_a));
+        assert_eq!(b.get_wsjtx_addr(), Some(radio_b));
+
+        // Closing one radio leaves the other as the default target
+        a.record_close();
+        assert_eq!(instances.target(None).unwrap().get_port(), 2238);
+    }
+
+    #[test]
+    fn test_reply_accepted_by_matching_status() {
+        let state = UdpListenerState::new();
+        let t0 = Instant::now();
+        state.expect_reply_at("<k1abc>", t0);
+
+        // Status for the previous QSO doesn't settle it
+        assert_eq!(state.check_reply_status_at("W5XYZ", t0 + Duration::from_millis(200)), None);
+        assert_eq!(
+            state.check_reply_status_at("K1ABC", t0 + Duration::from_secs(1)),
+            Some(ReplyOutcome::Accepted("K1ABC".to_string()))
+        );
+        // Reported once
+        assert_eq!(state.check_reply_status_at("K1ABC", t0 + Duration::from_secs(2)), None);
+        assert_eq!(state.check_reply_timeout_at(t0 + REPLY_ACK_TIMEOUT * 2), None);
+    }
+
+    #[test]
+    fn test_reply_ignored_after_timeout() {
+        let state = UdpListenerState::new();
+        let t0 = Instant::now();
+        state.expect_reply_at("K1ABC", t0);
+        assert_eq!(state.check_reply_timeout_at(t0 + REPLY_ACK_TIMEOUT), None);
+
+        let late = t0 + REPLY_ACK_TIMEOUT + Duration::from_millis(1);
+        assert_eq!(state.check_reply_status_at("", late), Some(ReplyOutcome::Ignored("K1ABC".to_string())));
+        assert_eq!(state.check_reply_timeout_at(late), None);
+
+        // A newer Reply replaces the pending one
+        state.expect_reply_at("K1ABC", late);
+        state.expect_reply_at("JA1XYZ", late);
+        assert_eq!(
+            state.check_reply_status_at("JA1XYZ", late),
+            Some(ReplyOutcome::Accepted("JA1XYZ".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_logged_adif_keeps_qso_date() {
+        let adif = "<call:6>JA1ABC <gridsquare:4>PM95 <mode:3>FT8 <qso_date:8>20260301 \
+                    <time_on:6>235945 <freq:9>14.075000 <eor>";
+        let qso = parse_adif_to_qso(adif).unwrap();
+        assert_eq!(qso.datetime_on, "2026-03-01 235945");
+        let (date, time) = crate::commands::time_utils::resolve_qso_datetime(&qso.datetime_on, chrono::Utc::now());
+        assert_eq!((date.as_str(), time.as_str()), ("20260301", "235945"));
+    }
+}
@@ -1,4 +1,4 @@
 pub mod listener;
 pub mod wsjtx;
-pub use listener::{UdpListenerState, UdpMessage, start_listener};
+pub use listener::{UdpInstances, UdpMessage, start_listener};
 pub use wsjtx::{QsoLoggedMessage, parse_ft8_message};
@@ -27,7 +27,7 @@ pub enum WsjtxMessageType {
 }
 
 /// Parsed QSO Logged message from WSJT-X
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct QsoLoggedMessage {
     pub id: String,
     pub datetime_off: String,
@@ -376,7 +376,7 @@ fn is_ft8_message_fragment(s: &str) -> bool {
 /// 
 /// This performs comprehensive validation:
 /// 1. Empty strings are valid (represents "unknown grid")
-/// 2. Must be 2, 4, or 6 characters
+/// 2. Must be 2, 4, 6 or 8 characters
 /// 3. Must match Maidenhead format (letters/digits in correct positions)
 /// 4. Must not be a known FT8 message fragment
 /// 5. Must be geographically plausible (not in the middle of an ocean)
@@ -399,9 +399,9 @@ pub fn is_valid_grid(s: &str) -> bool {
         return false;
     }
     
-    // Validate length: must be 2, 4, or 6 characters
+    // Validate length: must be 2, 4, 6 or 8 characters
     let len = s.len();
-    if len != 2 && len != 4 && len != 6 {
+    if !matches!(len, 2 | 4 | 6 | 8) {
         log::debug!("Grid '{}' rejected: invalid length {}", s, len);
         return false;
     }
@@ -434,6 +434,12 @@ pub fn is_valid_grid(s: &str) -> bool {
         }
     }
     
+    // Extended square (if present): 0-9
+    if len == 8 && (!bytes[6].is_ascii_digit() || !bytes[7].is_ascii_digit()) {
+        log::debug!("Grid '{}' rejected: invalid extended square digits", s);
+        return false;
+    }
+    
     // Geographic plausibility check (for 4+ char grids)
     if len >= 4 && !is_geographically_plausible(s) {
         return false;
@@ -500,6 +506,28 @@ pub fn normalize_rst(rst: &str) -> String {
     cleaned // Return cleaned version if can't normalize
 }
 
+/// Exchange acknowledgments that sometimes land in an RST field instead of the report
+const RST_ACK_TOKENS: &[&str] = &["73", "RR73", "RRR", "R73"];
+
+/// Normalize an RST, dropping acknowledgments ("RR73", "73", "RRR") logged in
+/// place of a report. A dropped report becomes the SNR on digital modes when
+/// it is known, otherwise None. Blank values are None.
+pub fn sanitize_rst(value: &str, mode: &str, snr: Option<i32>) -> Option<String> {
+    use crate::adif::modes::{get_mode_group, ModeGroup};
+
+    let rst = normalize_rst(value);
+    if rst.is_empty() {
+        return None;
+    }
+    if !RST_ACK_TOKENS.contains(&rst.to_uppercase().as_str()) {
+        return Some(rst);
+    }
+    match get_mode_group(mode) {
+        ModeGroup::Data => snr.map(|n| format!("{:+03}", n)),
+        _ => None,
+    }
+}
+
 // ============================================================================
 // LoggedADIF Message (Type 12) - ADIF record when QSO is logged
 // ============================================================================
@@ -629,6 +657,22 @@ pub fn parse_decode(data: &[u8]) -> Option<DecodeMessage> {
     })
 }
 
+/// ADIF mode for the one-character mode symbol WSJT-X puts in a Decode
+/// message ("~" FT8, "+" FT4, ...). None for an unknown symbol.
+pub fn decode_mode_to_adif(symbol: &str) -> Option<&'static str> {
+    match symbol.trim() {
+        "~" => Some("FT8"),
+        "+" => Some("FT4"),
+        "#" => Some("JT65"),
+        "@" => Some("JT9"),
+        "$" => Some("JT4"),
+        "&" => Some("MSK144"),
+        ":" => Some("Q65"),
+        "`" => Some("FST4"),
+        _ => None,
+    }
+}
+
 /// Extract callsign and grid from a decoded FT8 message
 /// FT8 messages have formats like:
 /// - "CQ W5ABC EM10"
@@ -652,9 +696,13 @@ pub fn parse_ft8_message(message: &str) -> Option<(String, Option<String>, Optio
     
     // CQ message: "CQ W5ABC EM10" or "CQ DX W5ABC EM10" or "CQ POTA W5ABC EM10"
     if parts[0] == "CQ" {
-        // Find the first part after CQ that looks like a valid callsign
+        // Find the first part after CQ that looks like a valid callsign,
+        // skipping modifiers ("DX", "POTA", "NA", "290")
         for i in 1..parts.len() {
-            let candidate = parts[i];
+            if is_cq_modifier(parts[i]) {
+                continue;
+            }
+            let candidate = strip_hash_brackets(parts[i]);
             if is_valid_callsign(candidate) {
                 let call = candidate.to_string();
                 let grid = if parts.len() > i + 1 && is_grid(parts[i + 1]) {
@@ -674,13 +722,7 @@ pub fn parse_ft8_message(message: &str) -> Option<(String, Option<String>, Optio
     }
     
     // Handle compound callsigns with angle brackets: "<W7UUU> W4/ZS2GK"
-    let first = parts[0];
-    let dx_call = if first.starts_with('<') && first.ends_with('>') {
-        // Strip angle brackets
-        first[1..first.len()-1].to_string()
-    } else {
-        first.to_string()
-    };
+    let dx_call = strip_hash_brackets(parts[0]).to_string();
     
     // Validate the extracted callsign
     if !is_valid_callsign(&dx_call) {
@@ -690,13 +732,8 @@ pub fn parse_ft8_message(message: &str) -> Option<(String, Option<String>, Optio
     // Standard exchange: "DX_CALL DE_CALL REPORT/GRID"
     // Example: "N5JKK W9MDM EN61" means W9MDM is calling N5JKK
     if parts.len() >= 2 {
-        let de_call_str = parts[1];
         // Handle compound callsigns
-        let de_call = if de_call_str.starts_with('<') && de_call_str.ends_with('>') {
-            de_call_str[1..de_call_str.len()-1].to_string()
-        } else {
-            de_call_str.to_string()
-        };
+        let de_call = strip_hash_brackets(parts[1]).to_string();
         
         if !is_valid_callsign(&de_call) {
             return None;
@@ -742,6 +779,23 @@ pub enum MessageType {
     Other,
 }
 
+/// Token WSJT-X allows between CQ and the caller: up to four letters
+/// ("DX", "POTA", "TEST", "NA") or three digits (a reply frequency, "CQ 290")
+pub fn is_cq_modifier(token: &str) -> bool {
+    let letters = (1..=4).contains(&token.len()) && token.chars().all(|c| c.is_ascii_uppercase());
+    let digits = token.len() == 3 && token.chars().all(|c| c.is_ascii_digit());
+    letters || digits
+}
+
+/// Visible callsign of a hashed-call token: "<W1AW>" -> "W1AW".
+/// An unresolved hash ("<...>") comes back as "..." and fails callsign validation.
+pub fn strip_hash_brackets(token: &str) -> &str {
+    token
+        .strip_prefix('<')
+        .and_then(|t| t.strip_suffix('>'))
+        .unwrap_or(token)
+}
+
 fn is_grid(s: &str) -> bool {
     if s.len() != 4 {
         return false;
@@ -888,6 +942,15 @@ mod tests {
         assert!(is_valid_grid("fn42fv")); // lowercase OK
     }
 
+    #[test]
+    fn test_is_valid_grid_8char() {
+        // 8-char extended square (subsquare + 00-99)
+        assert!(is_valid_grid("FN31pr42"));
+        assert!(is_valid_grid("EM20KE09"));
+        assert!(!is_valid_grid("FN31prAB"));
+        assert!(!is_valid_grid("FN3142pr"));
+    }
+
     #[test]
     fn test_is_valid_grid_invalid_ft8_messages() {
         // These FT8 message fragments should NOT be valid grids
@@ -1053,4 +1116,70 @@ mod tests {
         assert_eq!(normalize_rst("-573"), "-05");
         assert_eq!(normalize_rst("-1473"), "-14");
     }
+
+    #[test]
+    fn test_sanitize_rst_replaces_acknowledgments() {
+        assert_eq!(sanitize_rst("RR73", "FT8", Some(-7)).as_deref(), Some("-07"));
+        assert_eq!(sanitize_rst("73", "FT8", None), None);
+        assert_eq!(sanitize_rst("rrr", "FT4", None), None);
+        // No SNR stands in for a phone or CW report
+        assert_eq!(sanitize_rst("73", "CW", Some(-7)), None);
+    }
+
+    #[test]
+    fn test_sanitize_rst_keeps_valid_reports() {
+        assert_eq!(sanitize_rst("-5", "FT8", Some(-20)).as_deref(), Some("-05"));
+        assert_eq!(sanitize_rst("-1473", "FT8", None).as_deref(), Some("-14"));
+        assert_eq!(sanitize_rst("599", "CW", None).as_deref(), Some("599"));
+        assert_eq!(sanitize_rst("  ", "FT8", Some(-3)), None);
+    }
+
+    #[test]
+    fn test_parse_cq_with_modifiers() {
+        for (message, call, grid) in [
+            ("CQ W1AW FN31", "W1AW", Some("FN31")),
+            ("CQ POTA W1AW FN31", "W1AW", Some("FN31")),
+            ("CQ DX JA1ABC PM95", "JA1ABC", Some("PM95")),
+            ("CQ TEST K1ABC", "K1ABC", None),
+            ("CQ NA W5ABC EM10", "W5ABC", Some("EM10")),
+            ("CQ 290 W5ABC EM10", "W5ABC", Some("EM10")),
+        ] {
+            let (de, dx, g, msg_type) = parse_ft8_message(message).unwrap();
+            assert_eq!(de, call, "{}", message);
+            assert_eq!(dx, None);
+            assert_eq!(g.as_deref(), grid, "{}", message);
+            assert_eq!(msg_type, MessageType::Cq);
+        }
+        assert!(parse_ft8_message("CQ DX").is_none());
+    }
+
+    #[test]
+    fn test_parse_hashed_calls() {
+        let (de, dx, _, _) = parse_ft8_message("<W1AW> G0XYZ").unwrap();
+        assert_eq!((de.as_str(), dx.as_deref()), ("G0XYZ", Some("W1AW")));
+
+        let (de, dx, _, msg_type) = parse_ft8_message("G0XYZ <W1AW> RR73").unwrap();
+        assert_eq!((de.as_str(), dx.as_deref()), ("W1AW", Some("G0XYZ")));
+        assert_eq!(msg_type, MessageType::End);
+
+        let (de, _, _, _) = parse_ft8_message("CQ <PJ4/K1ABC>").unwrap();
+        assert_eq!(de, "PJ4/K1ABC");
+
+        // Unresolved hash has no visible call
+        assert!(parse_ft8_message("<...> G0XYZ").is_none());
+    }
+
+    #[test]
+    fn test_cq_modifier() {
+        assert!(is_cq_modifier("DX") && is_cq_modifier("POTA") && is_cq_modifier("290"));
+        assert!(!is_cq_modifier("W1AW") && !is_cq_modifier("WWFFX") && !is_cq_modifier("29"));
+    }
+
+    #[test]
+    fn test_decode_mode_to_adif() {
+        assert_eq!(decode_mode_to_adif("~"), Some("FT8"));
+        assert_eq!(decode_mode_to_adif("+"), Some("FT4"));
+        assert_eq!(decode_mode_to_adif("FT8"), None);
+        assert_eq!(decode_mode_to_adif(""), None);
+    }
 }
@@ -49,6 +49,7 @@ interface DecodeEvent {
   cqz: number | null;
   ituz: number | null;
   low_confidence: boolean;
+  new_slot: boolean;
 }
 
 type ActivityMode = "idle" | "calling_cq" | "in_qso" | "qso_complete";
@@ -227,7 +228,6 @@ export default function ActiveQso() {
           gridsquare: qsoState.dxGrid,
           rst_sent: qsoState.rstSent,
           rst_rcvd: qsoState.rstRcvd,
-          source: "WSJT-X",
         },
       });
       console.log(`✓ QSO logged: ${qsoState.dxCall}`);
@@ -13,6 +13,7 @@ interface ImportResult {
   total_records: number;
   imported: number;
   skipped: number;
+  updated: number;
   errors: number;
   error_messages: string[];
 }
@@ -26,6 +27,7 @@ export function AdifImport({ onClose, onImportComplete }: AdifImportProps) {
   const [isImporting, setIsImporting] = useState(false);
   const [result, setResult] = useState<ImportResult | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [duplicatePolicy, setDuplicatePolicy] = useState<"skip" | "replace" | "keep_both">("skip");
 
   const selectFile = useCallback(async () => {
     console.log("[AdifImport] selectFile called");
@@ -84,11 +86,12 @@ export function AdifImport({ onClose, onImportComplete }: AdifImportProps) {
       const importResult = await invoke<ImportResult>("import_adif", {
         content: fileContent,
         skipDuplicates: true,
+        duplicatePolicy,
       });
 
       setResult(importResult);
 
-      if (importResult.imported > 0) {
+      if (importResult.imported > 0 || importResult.updated > 0) {
         onImportComplete(importResult.imported);
       }
     } catch (err) {
@@ -96,7 +99,7 @@ export function AdifImport({ onClose, onImportComplete }: AdifImportProps) {
     } finally {
       setIsImporting(false);
     }
-  }, [fileContent, onImportComplete]);
+  }, [fileContent, duplicatePolicy, onImportComplete]);
 
   return (
     <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50">
@@ -150,6 +153,18 @@ export function AdifImport({ onClose, onImportComplete }: AdifImportProps) {
                 <span className="text-sm text-zinc-400">QSO Records:</span>
                 <span className="text-lg font-bold text-sky-400">{previewCount}</span>
               </div>
+              <div className="flex items-center justify-between">
+                <span className="text-sm text-zinc-400">Duplicates:</span>
+                <select
+                  value={duplicatePolicy}
+                  onChange={(e) => setDuplicatePolicy(e.target.value as "skip" | "replace" | "keep_both")}
+                  className="bg-zinc-900 border border-zinc-700 rounded px-2 py-1 text-sm"
+                >
+                  <option value="skip">Skip</option>
+                  <option value="replace">Update existing</option>
+                  <option value="keep_both">Keep both</option>
+                </select>
+              </div>
             </div>
           )}
 
@@ -181,6 +196,10 @@ export function AdifImport({ onClose, onImportComplete }: AdifImportProps) {
                   <span className="text-zinc-400">Skipped (dupes):</span>
                   <span className="text-yellow-400">{result.skipped}</span>
                 </div>
+                <div className="flex justify-between">
+                  <span className="text-zinc-400">Updated:</span>
+                  <span className="text-sky-400">{result.updated}</span>
+                </div>
                 <div className="flex justify-between">
                   <span className="text-zinc-400">Errors:</span>
                   <span className={result.errors > 0 ? "text-red-400" : ""}>
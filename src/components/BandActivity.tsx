@@ -12,10 +12,13 @@ interface Decode {
   snr: number;
   call: string;
   grid: string | null;
+  gridHint: string | null; // Full grid from prior QSOs when the decode's is partial
   message: string;
   msgType: string;
   mode: string;          // For Reply message
   lowConfidence: boolean; // For Reply message
+  instance: number;      // For Reply message: the WSJT-X that heard it
+  dxpedition: boolean;
   dxcc: number | null;
   country: string | null;
   continent: string | null;
@@ -42,6 +45,7 @@ interface DecodeEvent {
   dx_call: string | null; // The station being called (null for CQ)
   call: string;           // backwards compat - same as de_call
   grid: string | null;
+  grid_hint: string | null;
   msg_type: string;
   dxcc: number | null;
   country: string | null;
@@ -49,6 +53,9 @@ interface DecodeEvent {
   cqz: number | null;
   ituz: number | null;
   low_confidence: boolean;
+  new_slot: boolean;      // Entity not yet worked on the current band
+  instance: number;       // Port of the WSJT-X instance that decoded it
+  dxpedition: boolean;    // Matches an active DXpedition pattern
 }
 
 // US DXCC entity code - hardcoded for now, will be user setting later
@@ -223,8 +230,11 @@ export function BandActivity() {
       if (!isHomeCountry && !isDxccWorked && d.dxcc) {
         needReason = d.country ? `NEW DXCC: ${d.country}` : "NEW DXCC";
         isNeeded = true;
+      } else if (!isHomeCountry && d.new_slot && d.dxcc) {
+        needReason = d.country ? `NEW BAND: ${d.country}` : "NEW BAND";
+        isNeeded = true;
       }
-      
+
       // For US stations, check if STATE is needed
       if (isHomeCountry && isStateNeeded && state) {
         needReason = `NEW STATE: ${stateName || state}`;
@@ -232,17 +242,20 @@ export function BandActivity() {
       }
       
       const decode: Decode = {
-        id: `${d.time_ms}-${call}-${d.delta_freq}`,
+        id: `${d.instance}-${d.time_ms}-${call}-${d.delta_freq}`,
         time: msToTime(d.time_ms),
         timeMs: d.time_ms,
         deltaTime: d.delta_time,
         snr: d.snr,
         call: call,
         grid: d.grid,
+        gridHint: d.grid_hint,
         message: d.message,
         msgType: d.msg_type,
         mode: d.mode,
         lowConfidence: d.low_confidence,
+        instance: d.instance,
+        dxpedition: d.dxpedition,
         dxcc: d.dxcc,
         country: d.country,
         continent: d.continent,
@@ -300,6 +313,22 @@ export function BandActivity() {
 
   const priorityDecodes = decodes.filter((d) => d.isNeeded && d.msgType === "Cq");
 
+  // WSJT-X's response to the last call_station, read from its next Status
+  const [replyFeedback, setReplyFeedback] = useState<{ call: string; accepted: boolean } | null>(null);
+
+  useEffect(() => {
+    const unlistenAccepted = listen<{ call: string }>("reply-accepted", (event) => {
+      setReplyFeedback({ call: event.payload.call, accepted: true });
+    });
+    const unlistenIgnored = listen<{ call: string }>("reply-ignored", (event) => {
+      setReplyFeedback({ call: event.payload.call, accepted: false });
+    });
+    return () => {
+      unlistenAccepted.then((f) => f());
+      unlistenIgnored.then((f) => f());
+    };
+  }, []);
+
   // Double-click to call a station via WSJT-X
   const handleCallStation = useCallback(async (decode: Decode) => {
     try {
@@ -311,6 +340,7 @@ export function BandActivity() {
         mode: decode.mode,
         message: decode.message,
         lowConfidence: decode.lowConfidence,
+        instance: decode.instance,
       });
     } catch (err) {
       console.error("Failed to call station:", err);
@@ -319,6 +349,13 @@ export function BandActivity() {
 
   return (
     <div className="flex-1 flex flex-col min-h-0 gap-4 overflow-hidden">
+      {replyFeedback && (
+        <div className={`text-xs shrink-0 ${replyFeedback.accepted ? "text-green-400" : "text-amber-400"}`}>
+          {replyFeedback.accepted
+            ? `WSJT-X is calling ${replyFeedback.call}`
+            : `WSJT-X did not act on the reply to ${replyFeedback.call}`}
+        </div>
+      )}
       {/* Priority Queue - Stations You Need */}
       {priorityDecodes.length > 0 && (
         <section className="bg-card rounded-lg border border-border p-4 shrink-0">
@@ -346,7 +383,9 @@ export function BandActivity() {
                       </>
                     )}
                   </span>
-                  {d.grid && <span className="font-mono text-xs text-muted-foreground">{d.grid}</span>}
+                  {d.gridHint ? (
+                    <span className="font-mono text-xs text-muted-foreground italic" title={`Sent ${d.grid}, completed from prior QSOs`}>{d.gridHint}</span>
+                  ) : d.grid && <span className="font-mono text-xs text-muted-foreground">{d.grid}</span>}
                 </div>
                 <div className="flex items-center gap-3">
                   <span className="text-sm">{d.snr > 0 ? "+" : ""}{d.snr} dB</span>
@@ -431,7 +470,9 @@ export function BandActivity() {
                       {d.call}
                     </span>
                   </td>
-                  <td className="p-2 font-mono text-xs">{d.grid || "—"}</td>
+                  <td className={`p-2 font-mono text-xs ${d.gridHint ? "italic" : ""}`} title={d.gridHint ? `Sent ${d.grid}, completed from prior QSOs` : undefined}>
+                    {d.gridHint || d.grid || "—"}
+                  </td>
                   <td className="p-2 text-xs truncate max-w-[120px]" title={d.country || undefined}>
                     {d.country || "—"}
                   </td>
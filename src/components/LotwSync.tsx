@@ -26,12 +26,14 @@ interface LotwDownloadResult {
   unmatched_qsos: UnmatchedQso[];
   errors: string[];
   last_qsl: string | null;
+  next_since_date: string | null;
 }
 
 interface LotwUploadResult {
   qsos_exported: number;
   success: boolean;
   message: string;
+  excluded: { qso_id: number; call: string; field: string; message: string }[];
 }
 
 interface TqslInfo {
@@ -57,6 +59,7 @@ export function LotwSync({ onClose, onSyncComplete }: LotwSyncProps) {
   const [username, setUsername] = useState("");
   const [password, setPassword] = useState("");
   const [saveCredentials, setSaveCredentials] = useState(true);
+  const [autoDownload, setAutoDownload] = useState(false);
   
   // TQSL info
   const [tqslInfo, setTqslInfo] = useState<TqslInfo>({
@@ -94,6 +97,8 @@ export function LotwSync({ onClose, onSyncComplete }: LotwSyncProps) {
         const savedUsername = await invoke<string | null>("get_setting", { key: "lotw_username" });
         const savedPassword = await invoke<string | null>("get_setting", { key: "lotw_password" });
         const lastSync = await invoke<string | null>("get_setting", { key: "lotw_last_download" });
+        const savedAutoDownload = await invoke<string | null>("get_setting", { key: "lotw_auto_download" });
+        setAutoDownload(savedAutoDownload === "true");
         
         if (savedUsername) setUsername(savedUsername);
         if (savedPassword) setPassword(savedPassword);
@@ -172,34 +177,17 @@ export function LotwSync({ onClose, onSyncComplete }: LotwSyncProps) {
       
       setSyncResult(result);
       
-      // Save the last sync date and update state for next sync
-      // IMPORTANT: Add 1 second to the timestamp because LoTW's qso_qslsince is INCLUSIVE
-      // (returns records "on or after" the date). Without this, the same record keeps returning.
+      // The backend advances lotw_last_download only when every record was
+      // processed; mirror that value locally for the next "Sync Again"
       if (result.last_qsl) {
-        // Parse the datetime, add 1 second, format back to LoTW format
-        let nextSinceDate = result.last_qsl;
         try {
-          const dt = new Date(result.last_qsl.replace(' ', 'T'));
-          dt.setSeconds(dt.getSeconds() + 1);
-          // Format back to "YYYY-MM-DD HH:MM:SS" format that LoTW expects
-          const year = dt.getFullYear();
-          const month = String(dt.getMonth() + 1).padStart(2, '0');
-          const day = String(dt.getDate()).padStart(2, '0');
-          const hours = String(dt.getHours()).padStart(2, '0');
-          const minutes = String(dt.getMinutes()).padStart(2, '0');
-          const seconds = String(dt.getSeconds()).padStart(2, '0');
-          nextSinceDate = `${year}-${month}-${day} ${hours}:${minutes}:${seconds}`;
-          setLastSyncDisplay(dt.toLocaleString());
+          setLastSyncDisplay(new Date(result.last_qsl.replace(' ', 'T')).toLocaleString());
         } catch {
           setLastSyncDisplay(result.last_qsl);
         }
-        
-        await invoke("set_setting", { 
-          key: "lotw_last_download", 
-          value: nextSinceDate 
-        });
-        // Update local state so next "Sync Again" uses new date
-        setSinceDate(nextSinceDate);
+      }
+      if (result.next_since_date) {
+        setSinceDate(result.next_since_date);
         setIsFirstSync(false);
       }
       
@@ -402,6 +390,15 @@ export function LotwSync({ onClose, onSyncComplete }: LotwSyncProps) {
                     {uploadResult.qsos_exported} QSO(s) processed
                   </div>
                 )}
+                {uploadResult.excluded.length > 0 && (
+                  <div className="text-xs text-amber-400 mt-1 ml-6 space-y-0.5">
+                    {uploadResult.excluded.map((issue, i) => (
+                      <div key={`${issue.qso_id}-${i}`}>
+                        Held back {issue.call}: {issue.message}
+                      </div>
+                    ))}
+                  </div>
+                )}
               </div>
             )}
           </div>
@@ -442,6 +439,20 @@ export function LotwSync({ onClose, onSyncComplete }: LotwSyncProps) {
               />
               Save credentials
             </label>
+            <label className="flex items-center gap-2 text-sm text-zinc-400">
+              <input
+                type="checkbox"
+                checked={autoDownload}
+                onChange={(e) => {
+                  setAutoDownload(e.target.checked);
+                  invoke("set_setting", { key: "lotw_auto_download", value: String(e.target.checked) })
+                    .catch((err) => console.error("Failed to save auto-download setting:", err));
+                }}
+                className="rounded"
+                disabled={isSyncing || !saveCredentials}
+              />
+              Download new confirmations on startup
+            </label>
           </div>
 
           {/* Sync Options */}
@@ -649,8 +649,8 @@ export function QsoLog() {
                         case "call":
                           return (
                             <td key={key} className="px-4 py-3 text-sm font-medium text-sky-400">
-                              <span className="flex items-center gap-1">
-                                {qso.call}
+                              <span className="flex items-center gap-1" title={qso.call}>
+                                {qso.display_call || qso.call}
                                 {status?.has_previous_qso && (
                                   <span title={`Worked ${status.previous_qso_count + 1} times`}>
                                     <Star className="h-3 w-3 text-amber-500 fill-amber-500" />
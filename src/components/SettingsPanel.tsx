@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { lookupMyStation, type StationPrefill } from "@/lib/tauri";
 import { X, Folder, Trash2, Bug, Copy } from "lucide-react";
 
 interface SettingsPanelProps {
@@ -32,6 +33,7 @@ export function SettingsPanel({ onClose }: SettingsPanelProps) {
   const [tqslAutoDetected, setTqslAutoDetected] = useState(false);
   const [myCallsign, setMyCallsign] = useState("");
   const [myGrid, setMyGrid] = useState("");
+  const [stationLookupStatus, setStationLookupStatus] = useState<StationPrefill["status"] | null>(null);
   const [autoSync, setAutoSync] = useState(true);
 
   useEffect(() => {
@@ -161,9 +163,20 @@ export function SettingsPanel({ onClose }: SettingsPanelProps) {
                   type="text"
                   value={myCallsign}
                   onChange={(e) => setMyCallsign(e.target.value.toUpperCase())}
+                  onBlur={async () => {
+                    if (!myCallsign.trim()) return;
+                    const prefill = await lookupMyStation(myCallsign);
+                    setStationLookupStatus(prefill.status);
+                    if (prefill.grid && !myGrid) setMyGrid(prefill.grid);
+                  }}
                   className="w-full mt-1 px-3 py-2 bg-background rounded border border-border"
                   placeholder="N0CALL"
                 />
+                {stationLookupStatus === "not_ready" && (
+                  <p className="text-xs text-muted-foreground mt-1">
+                    FCC database still syncing - grid lookup not available yet
+                  </p>
+                )}
               </div>
               <div>
                 <label className="text-sm text-muted-foreground">My Grid</label>
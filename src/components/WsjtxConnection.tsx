@@ -9,6 +9,13 @@ interface UdpStatus {
   port: number;
   connected: boolean;
   wsjtx_version: string | null;
+  instances: {
+    port: number;
+    running: boolean;
+    connected: boolean;
+    wsjtx_id: string | null;
+    wsjtx_version: string | null;
+  }[];
 }
 
 interface WsjtxStatus {
@@ -8,26 +8,28 @@
  * Band sort order - lower numbers sort first (lower frequency first)
  */
 export const BAND_ORDER: Record<string, number> = {
-  "160m": 1,
-  "80m": 2,
-  "60m": 3,
-  "40m": 4,
-  "30m": 5,
-  "20m": 6,
-  "17m": 7,
-  "15m": 8,
-  "12m": 9,
-  "10m": 10,
-  "6m": 11,
-  "2m": 12,
-  "70cm": 13,
+  "2200m": 1,
+  "630m": 2,
+  "160m": 3,
+  "80m": 4,
+  "60m": 5,
+  "40m": 6,
+  "30m": 7,
+  "20m": 8,
+  "17m": 9,
+  "15m": 10,
+  "12m": 11,
+  "10m": 12,
+  "6m": 13,
+  "2m": 14,
+  "70cm": 15,
 };
 
 /**
  * Available band options for filtering
  */
 export const BAND_OPTIONS = [
-  "160m", "80m", "60m", "40m", "30m", "20m", "17m", "15m", "12m", "10m", "6m", "2m", "70cm"
+  "2200m", "630m", "160m", "80m", "60m", "40m", "30m", "20m", "17m", "15m", "12m", "10m", "6m", "2m", "70cm"
 ] as const;
 
 /**
@@ -10,29 +10,209 @@ export async function addQso(qso: Omit<Qso, "id" | "uuid" | "created_at">): Prom
   return invoke("add_qso", { qso });
 }
 
+/** Values filled into manually entered QSOs when left blank */
+export interface QsoDefaults {
+  tx_pwr: number | null;
+  my_gridsquare: string | null;
+  station_callsign: string | null;
+  mode: string | null;
+  band: string | null;
+}
+
+export async function getQsoDefaults(): Promise<QsoDefaults> {
+  return invoke("get_qso_defaults");
+}
+
+export async function setQsoDefaults(defaults: QsoDefaults): Promise<void> {
+  return invoke("set_qso_defaults", { defaults });
+}
+
 export async function deleteQso(id: number): Promise<void> {
   return invoke("delete_qso", { id });
 }
 
+/** The fields of a `wsjtx-decode` event that log_from_decode uses */
+export interface DecodePayload {
+  time_ms: number;
+  snr: number;
+  delta_freq: number;
+  mode: string;
+  message: string;
+  instance?: number;
+}
+
+/** Log a QSO from a decode; resolves to null if it was already logged */
+export async function logFromDecode(
+  decode: DecodePayload,
+  dialFreq: number,
+  rstSent?: string,
+  rstRcvd?: string
+): Promise<number | null> {
+  return invoke("log_from_decode", { decode, dialFreq, rstSent, rstRcvd });
+}
+
 export async function removeDuplicateQsos(): Promise<number> {
   return invoke("remove_duplicate_qsos");
 }
 
+/** Beam headings (degrees) and distances (km) from my grid, both ways around */
+export interface QsoPath {
+  my_grid: string;
+  their_grid: string;
+  short_path_bearing: number;
+  long_path_bearing: number;
+  short_path_km: number;
+  long_path_km: number;
+}
+
+export async function getQsoPath(id: number): Promise<QsoPath> {
+  return invoke("get_qso_path", { id });
+}
+
+/** A QSO and its distance (km) from the home grid */
+export interface DxRecord {
+  id: number;
+  call: string;
+  qso_date: string;
+  band: string;
+  mode: string;
+  gridsquare: string;
+  country: string | null;
+  distance_km: number;
+}
+
+/** A contact with both stations activating (park-to-park or summit-to-summit) */
+export interface ActivationQso {
+  id: number;
+  call: string;
+  qso_date: string;
+  time_on: string;
+  band: string;
+  mode: string;
+  their_ref: string;
+  my_ref: string;
+}
+
+export async function getP2pQsos(): Promise<ActivationQso[]> {
+  return invoke("get_p2p_qsos");
+}
+
+export async function getS2sQsos(): Promise<ActivationQso[]> {
+  return invoke("get_s2s_qsos");
+}
+
+/** A shortwave listener report (ADIF SWL=Y), kept apart from two-way QSOs */
+export interface SwlReport {
+  id: number;
+  call: string;
+  qso_date: string;
+  time_on: string;
+  band: string;
+  mode: string;
+  country: string | null;
+  rst_rcvd: string | null;
+  comment: string | null;
+}
+
+export async function getSwlReports(): Promise<SwlReport[]> {
+  return invoke("get_swl_reports");
+}
+
+/** Personal DX records, farthest from the home grid first */
+export async function getQsosByDistance(limit: number): Promise<DxRecord[]> {
+  return invoke("get_qsos_by_distance", { limit });
+}
+
+/** Result of correcting a QSO's callsign */
+export interface CallsignCorrection {
+  qso_id: number;
+  old_call: string;
+  new_call: string;
+  dxcc: number | null;
+  country: string | null;
+  confirmations_flagged: number;
+}
+
+/** Fix a QSO's callsign: re-derives DXCC/zones and flags its confirmations for re-verification */
+export async function correctCallsign(qsoId: number, newCall: string): Promise<CallsignCorrection> {
+  return invoke("correct_callsign", { qsoId, newCall });
+}
+
+/** Set the source label of several QSOs (e.g. "N3FJP import 2019"); resolves to how many changed */
+export async function relabelSource(qsoIds: number[], newSource: string): Promise<number> {
+  return invoke("relabel_source", { qsoIds, newSource });
+}
+
 // ADIF Import/Export
 export interface ImportResult {
   total_records: number;
   imported: number;
   skipped: number;
+  updated: number;
   errors: number;
   error_messages: string[];
 }
 
-export async function importAdif(content: string, skipDuplicates: boolean = true): Promise<ImportResult> {
-  return invoke("import_adif", { content, skipDuplicates });
+/** What an import does with a record matching a logged QSO */
+export type DuplicatePolicy = "skip" | "replace" | "keep_both";
+
+export async function importAdif(
+  content: string,
+  skipDuplicates: boolean = true,
+  duplicatePolicy?: DuplicatePolicy,
+): Promise<ImportResult> {
+  return invoke("import_adif", { content, skipDuplicates, duplicatePolicy });
+}
+
+export async function importAdifMulti(
+  contents: string[],
+  duplicatePolicy?: DuplicatePolicy,
+): Promise<ImportResult> {
+  return invoke("import_adif_multi", { contents, duplicatePolicy });
+}
+
+export async function importCabrillo(
+  content: string,
+  skipDuplicates: boolean = true,
+  duplicatePolicy?: DuplicatePolicy,
+): Promise<ImportResult> {
+  return invoke("import_cabrillo", { content, skipDuplicates, duplicatePolicy });
+}
+
+export type ExportSort = "date_desc" | "date_asc";
+export type ExportGroupBy = "band" | "dxcc";
+
+export async function exportAdif(
+  path: string,
+  qsoIds?: number[],
+  sort?: ExportSort,
+  groupBy?: ExportGroupBy,
+  unconfirmedOnly?: boolean,
+  confirmationSource?: string
+): Promise<number> {
+  return invoke("export_adif", { path, qsoIds, sort, groupBy, unconfirmedOnly, confirmationSource });
+}
+
+export interface AdifExportWithManifest {
+  adif: string;
+  /** JSON: record_count, first/last_qso_date, bands, modes, dxcc_count, state_count, sha256 */
+  manifest: string;
+}
+
+/** ADIF export plus a manifest to save alongside it */
+export async function exportAdifWithManifest(
+  qsoIds?: number[],
+  sort?: ExportSort,
+  groupBy?: ExportGroupBy,
+  unconfirmedOnly?: boolean,
+  confirmationSource?: string
+): Promise<AdifExportWithManifest> {
+  return invoke("export_adif_with_manifest", { qsoIds, sort, groupBy, unconfirmedOnly, confirmationSource });
 }
 
-export async function exportAdif(path: string, qsoIds?: number[]): Promise<number> {
-  return invoke("export_adif", { path, qsoIds });
+/** ADIF with only LoTW fields, for QSOs pending LoTW upload */
+export async function exportAdifForLotw(qsoIds?: number[]): Promise<string> {
+  return invoke("export_adif_for_lotw", { qsoIds });
 }
 
 // LoTW Sync
@@ -50,6 +230,19 @@ export interface LotwDownloadResult {
   unmatched: number;
   errors: string[];
   last_qsl: string | null;
+  next_since_date: string | null;
+  new_dxcc_confirmed: string[];
+  new_states_confirmed: string[];
+  new_grids_confirmed: string[];
+  new_zones_confirmed: string[];
+  /** Award levels this sync crossed, e.g. DXCC 100 */
+  milestones_reached: AwardMilestone[];
+}
+
+export interface AwardMilestone {
+  award: string;
+  threshold: number;
+  confirmed: number;
 }
 
 export async function syncLotwUpload(): Promise<number> {
@@ -60,6 +253,34 @@ export async function syncLotwDownload(): Promise<number> {
   return invoke("sync_lotw_download");
 }
 
+/** Re-match confirmations for QSOs made from fromDate through toDate (YYYYMMDD) */
+export async function resyncLotwRange(
+  username: string,
+  password: string,
+  fromDate: string,
+  toDate: string,
+): Promise<LotwDownloadResult> {
+  return invoke("resync_lotw_range", { username, password, fromDate, toDate });
+}
+
+export interface LotwCredentialCheck {
+  status: "valid" | "invalid_credentials" | "network_error" | "service_error";
+  message: string;
+}
+
+/** Check a LoTW login with a minimal query, without downloading the report */
+export async function verifyLotwCredentials(
+  username: string,
+  password: string,
+): Promise<LotwCredentialCheck> {
+  return invoke("verify_lotw_credentials", { username, password });
+}
+
+/** Apply a lotwreport.adi saved from the LoTW website, like a download */
+export async function importLotwFile(path: string): Promise<LotwDownloadResult> {
+  return invoke("import_lotw_file", { path });
+}
+
 export async function getSyncStatus(): Promise<SyncStatus> {
   return invoke("get_sync_status");
 }
@@ -72,12 +293,105 @@ export interface LotwUploadResult {
   qsos_exported: number;
   success: boolean;
   message: string;
+  excluded: ValidationIssue[];
 }
 
 export async function uploadToLotw(tqslPath: string): Promise<LotwUploadResult> {
   return invoke("upload_to_lotw", { tqslPath });
 }
 
+// Station profile
+export interface StationPrefill {
+  status: "found" | "not_found" | "not_ready";
+  call: string;
+  name: string | null;
+  grid: string | null;
+  state: string | null;
+  city: string | null;
+}
+
+export async function lookupMyStation(callsign: string): Promise<StationPrefill> {
+  return invoke("lookup_my_station", { callsign });
+}
+
+// eQSL Sync
+export interface EqslUploadResult {
+  qsos_exported: number;
+  accepted: number;
+  rejected: number;
+  success: boolean;
+  message: string;
+  warnings: string[];
+}
+
+export async function uploadToEqsl(
+  username: string,
+  password: string,
+  qthNickname?: string
+): Promise<EqslUploadResult> {
+  return invoke("upload_to_eqsl", { username, password, qthNickname });
+}
+
+// Paper QSL Cards
+export interface CardQueueEntry {
+  qso_id: number;
+  call: string;
+  qso_date: string;
+  time_on: string;
+  band: string;
+  mode: string;
+  dxcc: number | null;
+  country: string | null;
+}
+
+export async function markCardSent(qsoId: number, sentDate: string): Promise<void> {
+  return invoke("mark_card_sent", { qsoId, sentDate });
+}
+
+export async function markCardConfirmed(qsoId: number, rcvdDate: string): Promise<void> {
+  return invoke("mark_card_confirmed", { qsoId, rcvdDate });
+}
+
+export async function getCardQslQueue(limit?: number): Promise<CardQueueEntry[]> {
+  return invoke("get_card_qsl_queue", { limit });
+}
+
+// Log Validation
+export interface ValidationIssue {
+  qso_id: number;
+  call: string;
+  field: string;
+  severity: "error" | "warning";
+  message: string;
+}
+
+export interface ValidationReport {
+  qsos_checked: number;
+  qsos_with_issues: number;
+  errors: number;
+  warnings: number;
+  issues: ValidationIssue[];
+}
+
+export async function validateLog(): Promise<ValidationReport> {
+  return invoke("validate_log");
+}
+
+export interface SuspectCallsign {
+  qso_id: number;
+  call: string;
+  likely_qso_id: number;
+  likely_call: string;
+  band: string;
+  qso_date: string;
+  minutes_apart: number;
+}
+
+/** QSOs whose call is one character off another logged nearby on the same band */
+export async function findSuspectCallsigns(): Promise<SuspectCallsign[]> {
+  return invoke("find_suspect_callsigns");
+}
+
 // Awards Progress
 export interface DxccProgress {
   worked: number;
@@ -104,14 +418,296 @@ export async function getDxccProgress(): Promise<DxccProgress> {
   return invoke("get_dxcc_progress");
 }
 
+export interface DxccModeGroupProgress {
+  mode_group: "PHONE" | "CW" | "DIGITAL";
+  worked: number;
+  confirmed: number;
+  total: number;
+}
+
+/** DXCC Phone, CW and Digital, in that order */
+export async function getDxccByModeGroup(): Promise<DxccModeGroupProgress[]> {
+  return invoke("get_dxcc_by_mode_group");
+}
+
 export async function getWasProgress(): Promise<WasProgress> {
   return invoke("get_was_progress");
 }
 
+export interface WwffPrefixGroup {
+  prefix: string;
+  references: string[];
+}
+
+export interface WwffProgress {
+  worked: number;
+  activated: number;
+  worked_by_prefix: WwffPrefixGroup[];
+  activated_by_prefix: WwffPrefixGroup[];
+}
+
+export async function getWwffProgress(): Promise<WwffProgress> {
+  return invoke("get_wwff_progress");
+}
+
 export async function getVuccProgress(band?: string): Promise<VuccProgress> {
   return invoke("get_vucc_progress", { band });
 }
 
+/** WAC continents or WAZ zones worked/confirmed */
+export interface CountProgress {
+  worked: number;
+  confirmed: number;
+  total: number;
+}
+
+export type AwardProgress = DxccProgress | WasProgress | CountProgress | VuccProgress | WwffProgress;
+
+/** Progress for every award in the `enabled_awards` setting, keyed by award name (e.g. "DXCC", "WAZ") */
+export async function getAllAwardProgress(): Promise<Record<string, AwardProgress>> {
+  return invoke("get_all_award_progress");
+}
+
+export interface AwardSnapshot {
+  award: string;
+  worked: number;
+  confirmed: number;
+  /** UTC "YYYY-MM-DD HH:MM:SS" */
+  taken_at: string;
+}
+
+export async function takeAwardSnapshot(): Promise<AwardSnapshot[]> {
+  return invoke("take_award_snapshot");
+}
+
+export async function getAwardHistory(award: string): Promise<AwardSnapshot[]> {
+  return invoke("get_award_history", { award });
+}
+
+/** The next level of an award, e.g. 7 more states for WAS */
+export interface NextMilestone {
+  award: string;
+  /** e.g. "DXCC 150", "Honor Roll" */
+  level: string;
+  confirmed: number;
+  threshold: number;
+  remaining: number;
+}
+
+export async function getNextMilestones(): Promise<NextMilestone[]> {
+  return invoke("get_next_milestones");
+}
+
+export interface GridMapCell {
+  grid: string;
+  row: number;
+  col: number;
+  worked: boolean;
+  confirmed: boolean;
+}
+
+export interface GridMapData {
+  center: string;
+  precision: number;
+  radius: number;
+  rows: number;
+  cols: number;
+  worked: number;
+  confirmed: number;
+  cells: GridMapCell[];
+}
+
+export async function getGridMapData(
+  centerGrid: string,
+  radiusFields: number,
+  precision: 4 | 6 = 4
+): Promise<GridMapData> {
+  return invoke("get_grid_map_data", { centerGrid, radiusFields, precision });
+}
+
+export interface SubdivisionStatus {
+  code: string;
+  name: string;
+  worked: boolean;
+  confirmed: boolean;
+}
+
+export interface SubdivisionProgress {
+  dxcc: number;
+  entity_name: string | null;
+  total: number;
+  worked: number;
+  confirmed: number;
+  subdivisions: SubdivisionStatus[];
+}
+
+export async function getSubdivisionProgress(dxcc: number): Promise<SubdivisionProgress> {
+  return invoke("get_subdivision_progress", { dxcc });
+}
+
+/** The QSO submitted as proof for one award slot */
+export interface AwardProofQso {
+  slot: string;
+  slot_name: string;
+  qso_id: number;
+  call: string;
+  qso_date: string;
+  time_on: string;
+  band: string;
+  mode: string;
+  confirmation_source: string;
+  confirmation_date: string | null;
+}
+
+/** Earliest confirmed QSO per DXCC entity, for the application record sheet */
+export async function getDxccApplication(): Promise<AwardProofQso[]> {
+  return invoke("get_dxcc_application");
+}
+
+/** Earliest confirmed QSO per state, for the WAS application */
+export async function getWasApplication(): Promise<AwardProofQso[]> {
+  return invoke("get_was_application");
+}
+
+/** ADIF of an award application's proof QSOs, one per confirmed slot */
+export async function exportAdifForAward(award: "dxcc" | "was"): Promise<string> {
+  return invoke("export_adif_for_award", { award });
+}
+
+/** LoTW's officially granted credit for one award vs our own confirmed count */
+export interface GrantedCredit {
+  award: string;
+  granted_qsos: number;
+  granted_slots: number;
+  confirmed_slots: number | null;
+}
+
+/** Awards LoTW has granted credit for (APP_LOTW_CREDIT_GRANTED), for reconciling with ARRL */
+export async function getGrantedCredits(): Promise<GrantedCredit[]> {
+  return invoke("get_granted_credits");
+}
+
+export interface ReconciledEntity {
+  dxcc: number;
+  name: string;
+}
+
+/** Our confirmed DXCC entities vs LoTW's granted DXCC credit */
+export interface ConfirmationReconciliation {
+  confirmed: number;
+  granted: number;
+  confirmed_not_granted: ReconciledEntity[];
+  granted_not_confirmed: ReconciledEntity[];
+}
+
+export async function getConfirmationReconciliation(): Promise<ConfirmationReconciliation> {
+  return invoke("get_confirmation_reconciliation");
+}
+
+/** Most recent QSO with a DXCC entity */
+export interface EntityLastContact {
+  dxcc: number;
+  entity_name: string;
+  qso_id: number;
+  call: string;
+  qso_date: string;
+  time_on: string;
+  band: string;
+  mode: string;
+}
+
+export async function getLastContactPerEntity(): Promise<EntityLastContact[]> {
+  return invoke("get_last_contact_per_entity");
+}
+
+export async function getLastContactForEntity(dxcc: number): Promise<EntityLastContact | null> {
+  return invoke("get_last_contact_for_entity", { dxcc });
+}
+
+// Band Activity
+/** A DXCC entity heard in recent decodes */
+export interface RecentEntity {
+  dxcc: number;
+  name: string | null;
+  first_seen: string;
+  last_seen: string;
+  first_call: string;
+  decode_count: number;
+  needed: boolean;
+}
+
+/** Distinct entities decoded in the last `minutes` (default 15), freshest first */
+export async function getRecentEntities(minutes?: number): Promise<RecentEntity[]> {
+  return invoke("get_recent_entities", { minutes });
+}
+
+/** Stored decodes between `from` and `to` (UTC dates or datetimes) as CSV or JSON text */
+export async function exportBandActivity(from: string, to: string, format?: "csv" | "json"): Promise<string> {
+  return invoke("export_band_activity", { from, to, format });
+}
+
+// Statistics
+export interface RateStats {
+  window_minutes: number;
+  qsos_in_window: number;
+  last_10_minutes: number;
+  last_60_minutes: number;
+  rate_10: number;
+  rate_60: number;
+  projected_hourly_rate: number;
+}
+
+export async function getRateStats(windowMinutes?: number): Promise<RateStats> {
+  return invoke("get_rate_stats", { windowMinutes });
+}
+
+/** Worked and confirmed totals for one year, or one month of it */
+export interface PeriodStats {
+  year: number;
+  month: number | null;
+  qsos: number;
+  dxcc: number;
+  states: number;
+  confirmed_qsos: number;
+  confirmed_dxcc: number;
+}
+
+export async function getYearlyStats(): Promise<PeriodStats[]> {
+  return invoke("get_yearly_stats");
+}
+
+export async function getMonthlyStats(year: number): Promise<PeriodStats[]> {
+  return invoke("get_monthly_stats", { year });
+}
+
+/** A worked DXCC entity and how many QSOs the log has with it */
+export interface EntityRarity {
+  dxcc: number;
+  entity: string;
+  qsos: number;
+  bands: number;
+  confirmed_qsos: number;
+  unconfirmed: boolean;
+  last_qso_date: string;
+}
+
+/** Worked entities, fewest QSOs first */
+export async function getEntityRarity(): Promise<EntityRarity[]> {
+  return invoke("get_entity_rarity");
+}
+
+/** QSOs on one band per UTC hour; hours[0] is 00z */
+export interface BandHourActivity {
+  band: string;
+  hours: number[];
+  total: number;
+}
+
+/** Heatmap rows of QSOs by UTC hour, one per band in frequency order */
+export async function getActivityByHour(): Promise<BandHourActivity[]> {
+  return invoke("get_activity_by_hour");
+}
+
 // Callsign Lookup
 export interface CallsignInfo {
   call: string;
@@ -128,9 +724,68 @@ export async function lookupCallsign(call: string): Promise<CallsignInfo> {
   return invoke("lookup_callsign", { call });
 }
 
+/** Exact-callsign DXCC override, checked before prefix rules */
+export interface CallsignException {
+  call: string;
+  dxcc: number;
+  note: string | null;
+}
+
+export async function getCallsignExceptions(): Promise<CallsignException[]> {
+  return invoke("get_callsign_exceptions");
+}
+
+export async function addCallsignException(call: string, dxcc: number, note?: string): Promise<void> {
+  return invoke("add_callsign_exception", { call, dxcc, note });
+}
+
+export async function removeCallsignException(call: string): Promise<void> {
+  return invoke("remove_callsign_exception", { call });
+}
+
+/** A prefix rule LoTW confirmations keep contradicting */
+export interface PrefixDiscrepancy {
+  prefix: string;
+  our_dxcc: number;
+  our_country: string;
+  lotw_dxcc: number;
+  lotw_country: string;
+  disagreeing_calls: string[];
+  confirmed_calls: number;
+}
+
+export async function getPrefixDiscrepancies(): Promise<PrefixDiscrepancy[]> {
+  return invoke("get_prefix_discrepancies");
+}
+
+/** DXpedition / special-event call pattern (`*` wildcard), active through end_date */
+export interface Dxpedition {
+  pattern: string;
+  note: string | null;
+  end_date: string | null;
+}
+
+export async function getDxpeditions(): Promise<Dxpedition[]> {
+  return invoke("get_dxpeditions");
+}
+
+export async function addDxpedition(pattern: string, note?: string, endDate?: string): Promise<void> {
+  return invoke("add_dxpedition", { pattern, note, endDate });
+}
+
+export async function removeDxpedition(pattern: string): Promise<void> {
+  return invoke("remove_dxpedition", { pattern });
+}
+
+/** POST a test event to the `webhook_url` setting; rejects with the HTTP error */
+export async function testWebhook(): Promise<void> {
+  return invoke("test_webhook");
+}
+
 // UDP Listener
-export async function startUdpListener(port: number): Promise<void> {
-  return invoke("start_udp_listener", { port });
+/** Listen on `port`, plus `additionalPorts` for further WSJT-X instances */
+export async function startUdpListener(port: number, additionalPorts?: number[]): Promise<void> {
+  return invoke("start_udp_listener", { port, additionalPorts });
 }
 
 export async function stopUdpListener(): Promise<void> {
@@ -141,6 +796,53 @@ export async function getUdpStatus(): Promise<boolean> {
   return invoke("get_udp_status");
 }
 
+/** A WSJT-X logged QSO held because its exchange never completed */
+export interface PendingReviewQso {
+  id: number;
+  call: string;
+  datetime_on: string | null;
+  freq_hz: number | null;
+  mode: string | null;
+  reason: string;
+  received_at: string;
+}
+
+export async function getPendingReview(): Promise<PendingReviewQso[]> {
+  return invoke("get_pending_review");
+}
+
+/** Log a held QSO; resolves to the new QSO id (null if it was a duplicate) */
+export async function confirmPending(id: number): Promise<number | null> {
+  return invoke("confirm_pending", { id });
+}
+
+export async function discardPending(id: number): Promise<void> {
+  return invoke("discard_pending", { id });
+}
+
+// Background Jobs
+export type JobStatus = "queued" | "running" | "completed" | "failed";
+
+/** A long-running task (FCC sync, LoTW download/upload, log import) */
+export interface Job {
+  id: number;
+  kind: "fcc_sync" | "lotw" | "import";
+  label: string;
+  status: JobStatus;
+  progress: number | null;
+  message: string | null;
+  result: unknown;
+  error: string | null;
+  queued_at: string;
+  started_at: string | null;
+  finished_at: string | null;
+}
+
+/** Queued, running and recently finished jobs, newest first */
+export async function getJobs(): Promise<Job[]> {
+  return invoke("get_jobs");
+}
+
 // Settings
 export async function getSetting(key: string): Promise<string | null> {
   return invoke("get_setting", { key });
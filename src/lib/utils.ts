@@ -12,6 +12,10 @@ export function cn(...inputs: ClassValue[]) {
 export function freqToBand(freqHz: number): string {
   const mhz = freqHz / 1_000_000;
   
+  // LF/MF Bands
+  if (mhz >= 0.1357 && mhz <= 0.1378) return "2200m";
+  if (mhz >= 0.472 && mhz <= 0.479) return "630m";
+
   // HF Bands
   if (mhz >= 1.8 && mhz <= 2.0) return "160m";
   if (mhz >= 3.5 && mhz <= 4.0) return "80m";
@@ -45,8 +45,20 @@ export interface Qso {
   // Confirmation status (from confirmations table via JOIN)
   lotw_rcvd?: string;   // "Y" if confirmed via LoTW
   eqsl_rcvd?: string;   // "Y" if confirmed via eQSL
+  display_call?: string; // Call as shown in the log (base call when that display is selected)
+  dxpedition?: boolean;  // Matched an active DXpedition pattern on the QSO date
+  p2p?: boolean;  // Park-to-park: POTA_REF and MY_POTA_REF both set
+  s2s?: boolean;  // Summit-to-summit: SOTA_REF and MY_SOTA_REF both set
+  needs_reverify?: boolean;  // Confirmed under an earlier callsign; re-check the QSL
+  confirmation_state?: ConfirmationState;
 }
 
+/** Worked, sent and awaiting confirmation, or confirmed by one or more sources */
+export type ConfirmationState =
+  | { status: "worked" }
+  | { status: "sent_pending_confirmation" }
+  | { status: "confirmed"; sources: string[] };
+
 // Parsed ADIF fields from the JSON blob
 export interface AdifFields {
   name?: string;
@@ -14,6 +14,19 @@ export interface LotwDownloadResult {
   unmatched: number;
   errors: string[];
   last_qsl: string | null;
+  next_since_date: string | null;
+  new_dxcc_confirmed: string[];
+  new_states_confirmed: string[];
+  new_grids_confirmed: string[];
+  new_zones_confirmed: string[];
+  /** Award levels this sync crossed, e.g. DXCC 100 */
+  milestones_reached: AwardMilestone[];
+}
+
+export interface AwardMilestone {
+  award: string;
+  threshold: number;
+  confirmed: number;
 }
 
 export interface SyncQueueEntry {
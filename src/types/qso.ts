@@ -14,6 +14,8 @@ export interface Qso {
   country?: string;
   state?: string;
   gridsquare?: string;
+  /** Where the grid came from: station, lotw, manual, adif, fcc, prior_qso */
+  grid_source?: string;
   cqz?: number;
   ituz?: number;
   rst_sent?: string;
@@ -37,6 +39,11 @@ export interface NewQso {
   rst_sent?: string;
   rst_rcvd?: string;
   source?: string;
+  snr_sent?: number;
+  snr_rcvd?: number;
+  station_callsign?: string;
+  my_gridsquare?: string;
+  tx_pwr?: number;
 }
 
 export interface Confirmation {